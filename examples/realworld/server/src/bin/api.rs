@@ -1,12 +1,11 @@
 use anyhow::Context;
 use pavex::config::ConfigLoader;
-use pavex::server::{Server, ServerHandle, ShutdownMode};
+use pavex::server::Server;
 use server::{
     configuration::Profile,
     telemetry::{get_subscriber, init_telemetry},
 };
 use server_sdk::{ApplicationConfig, ApplicationState, run};
-use std::time::Duration;
 use tracing_log_error::log_error;
 
 #[tokio::main]
@@ -37,27 +36,17 @@ async fn _main() -> anyhow::Result<()> {
     let address = tcp_listener
         .local_addr()
         .context("The server TCP listener doesn't have a local socket address")?;
-    let server_builder = Server::new().listen(tcp_listener);
-    let shutdown_timeout = config.server.graceful_shutdown_timeout;
+    // The server shuts down gracefully on SIGTERM/SIGINT (Ctrl+C).
+    // A second signal forces it to shut down immediately.
+    let server_builder = Server::new()
+        .shutdown_on_signals(config.server.graceful_shutdown_timeout)
+        .listen(tcp_listener);
 
     let application_state = ApplicationState::new(config)
         .await
         .context("Failed to build the application state")?;
 
     tracing::info!("Starting to listen for incoming requests at {}", address);
-    let server_handle = run(server_builder, application_state);
-    graceful_shutdown(server_handle.clone(), shutdown_timeout).await;
-    server_handle.await;
+    run(server_builder, application_state).await;
     Ok(())
 }
-
-async fn graceful_shutdown(server_handle: ServerHandle, timeout: Duration) {
-    tokio::spawn(async move {
-        tokio::signal::ctrl_c()
-            .await
-            .expect("Failed to listen for the Ctrl+C signal");
-        server_handle
-            .shutdown(ShutdownMode::Graceful { timeout })
-            .await;
-    });
-}
@@ -0,0 +1,110 @@
+//! Compare the connection dispatch strategies supported by `pavex::server`.
+//!
+//! Each strategy serves a mix of long-lived connections, sending requests
+//! that keep a worker busy, and short-lived connections, sending cheap requests.
+//! We report the overall throughput and the latency of the cheap requests.
+//!
+//! Run it with `cargo bench -p pavex --bench dispatch`.
+use std::time::{Duration, Instant};
+
+use http::Request;
+use hyper::body::Incoming;
+use pavex::connection::ConnectionInfo;
+use pavex::response::Response;
+use pavex::server::{DispatchStrategy, IncomingStream, Server, ServerConfiguration, ShutdownMode};
+
+const N_WORKERS: usize = 4;
+const N_SLOW_CLIENTS: usize = 4;
+const N_FAST_CLIENTS: usize = 32;
+const RUN_FOR: Duration = Duration::from_secs(3);
+
+async fn handler(
+    request: Request<Incoming>,
+    _connection_info: Option<ConnectionInfo>,
+    _state: (),
+) -> Response {
+    if request.uri().path() == "/slow" {
+        // Simulate CPU-bound work, hogging the worker thread.
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    Response::ok()
+}
+
+/// Send requests to `url` over a dedicated connection until the deadline,
+/// returning the latency of each request.
+async fn client(url: String, deadline: Instant) -> Vec<Duration> {
+    let client = reqwest::Client::new();
+    let mut latencies = Vec::new();
+    while Instant::now() < deadline {
+        let start = Instant::now();
+        client
+            .get(&url)
+            .send()
+            .await
+            .unwrap()
+            .error_for_status()
+            .unwrap();
+        latencies.push(start.elapsed());
+    }
+    latencies
+}
+
+async fn bench(strategy: DispatchStrategy) {
+    #[cfg(unix)]
+    let incoming = IncomingStream::bind_reuse_port("127.0.0.1:0".parse().unwrap())
+        .await
+        .unwrap();
+    #[cfg(not(unix))]
+    let incoming = IncomingStream::bind("127.0.0.1:0".parse().unwrap())
+        .await
+        .unwrap();
+    let port = incoming.local_addr().unwrap().port();
+    let config = ServerConfiguration::new()
+        .set_n_workers(N_WORKERS)
+        .set_dispatch_strategy(strategy);
+    let server_handle = Server::new()
+        .set_config(config)
+        .listen(incoming)
+        .serve(handler, ());
+
+    let deadline = Instant::now() + RUN_FOR;
+    let slow: Vec<_> = (0..N_SLOW_CLIENTS)
+        .map(|_| tokio::spawn(client(format!("http://localhost:{port}/slow"), deadline)))
+        .collect();
+    let fast: Vec<_> = (0..N_FAST_CLIENTS)
+        .map(|_| tokio::spawn(client(format!("http://localhost:{port}/fast"), deadline)))
+        .collect();
+
+    let mut n_requests = 0;
+    for handle in slow {
+        n_requests += handle.await.unwrap().len();
+    }
+    let mut fast_latencies = Vec::new();
+    for handle in fast {
+        fast_latencies.extend(handle.await.unwrap());
+    }
+    n_requests += fast_latencies.len();
+    fast_latencies.sort();
+    let p99 = fast_latencies[fast_latencies.len() * 99 / 100];
+
+    println!(
+        "{:<26} {:>10.0} req/s {:>12.2?} p99 (fast requests)",
+        format!("{strategy:?}"),
+        n_requests as f64 / RUN_FOR.as_secs_f64(),
+        p99
+    );
+
+    server_handle
+        .shutdown(ShutdownMode::Graceful {
+            timeout: Duration::from_secs(1),
+        })
+        .await;
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    bench(DispatchStrategy::RoundRobin).await;
+    bench(DispatchStrategy::LeastActiveConnections).await;
+    #[cfg(unix)]
+    bench(DispatchStrategy::ReusePort).await;
+}
@@ -202,6 +202,7 @@ impl Blueprint {
             method_guard: method_guard2method_guard(method_guard),
             request_handler: raw_identifiers2callable(callable),
             error_handler: None,
+            body_size_limit: None,
         };
         let component_id = self.push_component(registered_route);
         RegisteredRoute {
@@ -216,6 +217,7 @@ impl Blueprint {
             method_guard: method_guard2method_guard(r.method_guard),
             error_handler: r.error_handler,
             request_handler: r.callable,
+            body_size_limit: r.body_size_limit,
         };
         let component_id = self.push_component(r);
         RegisteredRoute {
@@ -681,6 +683,7 @@ impl Blueprint {
             blueprint: blueprint.schema,
             path_prefix: None,
             domain: None,
+            body_size_limit: None,
             nested_at: Location::caller(),
         });
     }
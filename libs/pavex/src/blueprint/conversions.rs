@@ -3,6 +3,7 @@ use super::reflection::{CreatedAt, Sources, WithLocation};
 use crate::blueprint::constructor::{CloningStrategy, Lifecycle};
 use crate::blueprint::linter::Lint;
 use crate::blueprint::reflection::RawIdentifiers;
+use crate::request::body::BodySizeLimit;
 use crate::router::AllowedMethods;
 use pavex_bp_schema::{Callable, Location, Type};
 use pavex_reflection::CreatedBy;
@@ -86,6 +87,20 @@ pub(super) fn method_guard2method_guard(
     }
 }
 
+#[track_caller]
+pub(super) fn body_size_limit2body_size_limit(
+    limit: BodySizeLimit,
+) -> pavex_bp_schema::BodySizeLimit {
+    let max_size = match limit {
+        BodySizeLimit::Enabled { max_size } => Some(max_size.as_u64()),
+        BodySizeLimit::Disabled => None,
+    };
+    pavex_bp_schema::BodySizeLimit {
+        max_size,
+        registered_at: Location::caller(),
+    }
+}
+
 pub(super) fn lint2lint(lint: Lint) -> pavex_bp_schema::Lint {
     match lint {
         Lint::Unused => pavex_bp_schema::Lint::Unused,
@@ -1,10 +1,13 @@
 //! Customize how nested routes should behave.
 
 use pavex_bp_schema::{
-    Blueprint as BlueprintSchema, Domain, Location, NestedBlueprint, PathPrefix,
+    Blueprint as BlueprintSchema, BodySizeLimit as BodySizeLimitSchema, Domain, Location,
+    NestedBlueprint, PathPrefix,
 };
 
 use super::Blueprint;
+use super::conversions::body_size_limit2body_size_limit;
+use crate::request::body::BodySizeLimit;
 
 /// The type returned by [`Blueprint::prefix`] and [`Blueprint::domain`].
 ///
@@ -12,11 +15,12 @@ use super::Blueprint;
 ///
 /// [`Blueprint::prefix`]: crate::blueprint::Blueprint::prefix
 /// [`Blueprint::domain`]: crate::blueprint::Blueprint::domain
-#[must_use = "`prefix` and `domain` do nothing unless you invoke `nest` to register some routes under them"]
+#[must_use = "`prefix`, `domain` and `body_size_limit` do nothing unless you invoke `nest` to register some routes under them"]
 pub struct NestingConditions<'a> {
     pub(super) blueprint: &'a mut BlueprintSchema,
     pub(super) path_prefix: Option<PathPrefix>,
     pub(super) domain: Option<Domain>,
+    pub(super) body_size_limit: Option<BodySizeLimitSchema>,
 }
 
 impl<'a> NestingConditions<'a> {
@@ -25,6 +29,7 @@ impl<'a> NestingConditions<'a> {
             blueprint,
             path_prefix: None,
             domain: None,
+            body_size_limit: None,
         }
     }
 
@@ -56,6 +61,44 @@ impl<'a> NestingConditions<'a> {
         self
     }
 
+    /// Set an upper limit on the size of incoming request bodies for all routes nested
+    /// under this condition.
+    ///
+    /// It takes precedence over the [`BodySizeLimit`] that would otherwise be injected by
+    /// the parent blueprint—e.g. the default 2 MBs limit.
+    /// Routes can still override it via
+    /// [`RegisteredRoute::body_size_limit`](crate::blueprint::router::RegisteredRoute::body_size_limit).
+    ///
+    /// The nested blueprint can't register its own constructor for [`BodySizeLimit`]:
+    /// Pavex will report an error, since it wouldn't be clear which limit should apply.
+    /// Blueprints nested further down, instead, can: their constructor takes precedence
+    /// over this limit for their own routes, just like any other constructor would.
+    ///
+    /// If a limit has already been set, it will be overridden.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::blueprint::Blueprint;
+    /// use pavex::request::body::BodySizeLimit;
+    /// use pavex::unit::ToByteUnit;
+    /// # fn uploads_bp() -> Blueprint { Blueprint::new() }
+    ///
+    /// # fn main() {
+    /// let mut bp = Blueprint::new();
+    /// bp.prefix("/uploads")
+    ///     .body_size_limit(BodySizeLimit::Enabled {
+    ///         max_size: 1.gigabytes(),
+    ///     })
+    ///     .nest(uploads_bp());
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn body_size_limit(mut self, limit: BodySizeLimit) -> Self {
+        self.body_size_limit = Some(body_size_limit2body_size_limit(limit));
+        self
+    }
+
     #[track_caller]
     #[doc(alias("scope"))]
     /// Nest a [`Blueprint`], optionally applying a [common prefix](`Self::prefix`) and a [domain restriction](`Self::domain`) to all its routes.
@@ -185,6 +228,7 @@ impl<'a> NestingConditions<'a> {
                 path_prefix: self.path_prefix,
                 nested_at: Location::caller(),
                 domain: self.domain,
+                body_size_limit: self.body_size_limit,
             }
             .into(),
         );
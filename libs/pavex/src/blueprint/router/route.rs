@@ -1,7 +1,8 @@
-use crate::blueprint::conversions::raw_identifiers2callable;
+use crate::blueprint::conversions::{body_size_limit2body_size_limit, raw_identifiers2callable};
 use crate::blueprint::reflection::RawIdentifiers;
 use crate::blueprint::router::MethodGuard;
 use crate::blueprint::{Blueprint, reflection::WithLocation};
+use crate::request::body::BodySizeLimit;
 use pavex_bp_schema::{Blueprint as BlueprintSchema, Callable, Component};
 
 /// The type returned by [`Blueprint::route`].
@@ -61,6 +62,41 @@ impl RegisteredRoute<'_> {
         self
     }
 
+    #[track_caller]
+    /// Set an upper limit on the size of incoming request bodies for this route.
+    ///
+    /// It takes precedence over the [`BodySizeLimit`] that would otherwise be injected
+    /// into the request pipeline of this route—e.g. the default 2 MBs limit,
+    /// or the limit set on the [`NestingConditions`] this route was nested under.
+    /// That includes constructors for [`BodySizeLimit`] registered by the blueprint
+    /// this route belongs to: the route-level limit wins.
+    ///
+    /// If a limit has already been set for this route, it will be overwritten.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::f;
+    /// use pavex::blueprint::{Blueprint, router::POST};
+    /// use pavex::request::body::BodySizeLimit;
+    /// use pavex::unit::ToByteUnit;
+    /// # fn upload() {}
+    ///
+    /// # fn main() {
+    /// let mut bp = Blueprint::new();
+    /// bp.route(POST, "/upload", f!(crate::upload))
+    ///     .body_size_limit(BodySizeLimit::Enabled {
+    ///         max_size: 100.megabytes(),
+    ///     });
+    /// # }
+    /// ```
+    ///
+    /// [`NestingConditions`]: crate::blueprint::nesting::NestingConditions
+    pub fn body_size_limit(mut self, limit: BodySizeLimit) -> Self {
+        self.route().body_size_limit = Some(body_size_limit2body_size_limit(limit));
+        self
+    }
+
     fn route(&mut self) -> &mut pavex_bp_schema::Route {
         let component = &mut self.blueprint.components[self.component_id];
         let Component::Route(c) = component else {
@@ -82,6 +118,7 @@ pub struct Route {
     pub(in crate::blueprint) path: String,
     pub(in crate::blueprint) callable: Callable,
     pub(in crate::blueprint) error_handler: Option<Callable>,
+    pub(in crate::blueprint) body_size_limit: Option<pavex_bp_schema::BodySizeLimit>,
 }
 
 impl Route {
@@ -98,6 +135,7 @@ impl Route {
         Self {
             callable: raw_identifiers2callable(callable),
             error_handler: None,
+            body_size_limit: None,
             method_guard,
             path: path.to_owned(),
         }
@@ -112,6 +150,15 @@ impl Route {
         self
     }
 
+    /// Set an upper limit on the size of incoming request bodies for this route.
+    ///
+    /// Check out the documentation of [`RegisteredRoute::body_size_limit`] for more details.
+    #[track_caller]
+    pub fn body_size_limit(mut self, limit: BodySizeLimit) -> Self {
+        self.body_size_limit = Some(body_size_limit2body_size_limit(limit));
+        self
+    }
+
     /// Register this route with a [`Blueprint`].
     ///
     /// Check out the documentation of [`Blueprint::route`] for more details.
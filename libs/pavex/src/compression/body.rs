@@ -0,0 +1,129 @@
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use bytes::Bytes;
+use http_body::Frame;
+
+use super::encoder::Encoder;
+use crate::response::ResponseBody;
+use crate::response::body::raw::RawBody;
+
+/// A response body that's compressed on the fly, as it's being streamed.
+pub(super) struct CompressedBody {
+    inner: ResponseBody,
+    /// `None` once the compressed stream has been terminated.
+    encoder: Option<Box<dyn Encoder>>,
+    /// The trailers of the inner body, to be sent after the last compressed chunk.
+    trailers: Option<Frame<Bytes>>,
+}
+
+impl CompressedBody {
+    pub(super) fn new(inner: ResponseBody, encoder: Box<dyn Encoder>) -> Self {
+        Self {
+            inner,
+            encoder: Some(encoder),
+            trailers: None,
+        }
+    }
+}
+
+impl RawBody for CompressedBody {
+    type Data = Bytes;
+    type Error = crate::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        loop {
+            let Some(encoder) = this.encoder.as_mut() else {
+                return Poll::Ready(this.trailers.take().map(Ok));
+            };
+            let output = match ready!(Pin::new(&mut this.inner).poll_frame(cx)) {
+                Some(Ok(frame)) => match frame.into_data() {
+                    Ok(data) if data.is_empty() => continue,
+                    Ok(data) => encoder.encode(&data),
+                    // Trailers come last: the compressed stream must be terminated first.
+                    Err(trailers) => {
+                        this.trailers = Some(trailers);
+                        finish(&mut this.encoder)
+                    }
+                },
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => finish(&mut this.encoder),
+            };
+            match output {
+                Ok(output) if output.is_empty() => continue,
+                Ok(output) => return Poll::Ready(Some(Ok(Frame::data(output.into())))),
+                Err(e) => {
+                    this.encoder = None;
+                    this.trailers = None;
+                    return Poll::Ready(Some(Err(crate::Error::new(e))));
+                }
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.encoder.is_none() && self.trailers.is_none()
+    }
+}
+
+/// Terminate the compressed stream.
+fn finish(encoder: &mut Option<Box<dyn Encoder>>) -> std::io::Result<Vec<u8>> {
+    encoder
+        .take()
+        .expect("The compressed stream has already been terminated")
+        .finish()
+}
+
+#[cfg(all(test, feature = "compression_gzip"))]
+mod tests {
+    use std::io::Read;
+
+    use http::HeaderMap;
+    use http_body_util::{BodyExt, StreamBody};
+
+    use super::*;
+    use crate::compression::CompressionLevel;
+    use crate::compression::encoder::Encoding;
+
+    #[tokio::test]
+    async fn chunks_are_compressed_as_they_are_streamed() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("x-checksum", "abc".parse().unwrap());
+        let frames = vec![
+            Ok::<_, std::convert::Infallible>(Frame::data(Bytes::from("Hello, "))),
+            Ok(Frame::data(Bytes::new())),
+            Ok(Frame::data(Bytes::from("world!"))),
+            Ok(Frame::trailers(trailers)),
+        ];
+        let inner = ResponseBody::new(StreamBody::new(futures_util::stream::iter(frames)));
+        let mut body =
+            CompressedBody::new(inner, Encoding::Gzip.encoder(CompressionLevel::Default));
+
+        let mut compressed = Vec::new();
+        let mut n_data_frames = 0;
+        let mut received_trailers = None;
+        while let Some(frame) = body.frame().await {
+            match frame.unwrap().into_data() {
+                Ok(data) => {
+                    n_data_frames += 1;
+                    compressed.extend_from_slice(&data);
+                }
+                Err(frame) => received_trailers = frame.into_trailers().ok(),
+            }
+        }
+        assert!(body.is_end_stream());
+        // One frame for each non-empty chunk, plus the end of the compressed stream.
+        assert_eq!(n_data_frames, 3);
+        assert_eq!(received_trailers.unwrap()["x-checksum"], "abc");
+
+        let mut decompressed = String::new();
+        flate2::read::GzDecoder::new(&compressed[..])
+            .read_to_string(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, "Hello, world!");
+    }
+}
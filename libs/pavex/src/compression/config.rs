@@ -0,0 +1,52 @@
+use pavex_macros::config;
+use ubyte::{ByteUnit, ToByteUnit};
+
+#[config(key = "compression", default_if_missing)]
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+/// Configure how [`compress_response`](super::compress_response) compresses response bodies.
+///
+/// # Example
+///
+/// ```yaml
+/// compression:
+///   min_size: "2 KiB"
+///   level: fastest
+/// ```
+pub struct CompressionConfig {
+    /// Responses with a body smaller than this are sent uncompressed,
+    /// since the savings wouldn't be worth the overhead.
+    ///
+    /// It only applies to bodies whose size is known upfront: streaming bodies are
+    /// always compressed.
+    /// It defaults to 1 KiB.
+    pub min_size: ByteUnit,
+    /// How hard the encoder should try to shrink the body.
+    ///
+    /// It defaults to [`CompressionLevel::Default`].
+    pub level: CompressionLevel,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            min_size: 1.kibibytes(),
+            level: CompressionLevel::Default,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+/// The trade-off between compression speed and compression ratio.
+///
+/// Each level is mapped to an encoder-specific setting.
+pub enum CompressionLevel {
+    /// Favour speed over compression ratio.
+    Fastest,
+    /// A balanced setting, suitable for dynamically generated responses.
+    #[default]
+    Default,
+    /// Favour compression ratio over speed.
+    Best,
+}
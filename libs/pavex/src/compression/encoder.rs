@@ -0,0 +1,157 @@
+//! The encoders for the supported content codings.
+//!
+//! Each encoding is gated behind its own cargo feature.
+use std::io::{self, Write};
+
+use super::CompressionLevel;
+
+/// A content coding we can compress response bodies with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Encoding {
+    #[cfg(feature = "compression_br")]
+    Brotli,
+    #[cfg(feature = "compression_zstd")]
+    Zstd,
+    #[cfg(feature = "compression_gzip")]
+    Gzip,
+    #[cfg(feature = "compression_deflate")]
+    Deflate,
+}
+
+impl Encoding {
+    /// The enabled encodings, in order of preference for when the client has none.
+    pub(super) const ALL: &[Encoding] = &[
+        #[cfg(feature = "compression_br")]
+        Encoding::Brotli,
+        #[cfg(feature = "compression_zstd")]
+        Encoding::Zstd,
+        #[cfg(feature = "compression_gzip")]
+        Encoding::Gzip,
+        #[cfg(feature = "compression_deflate")]
+        Encoding::Deflate,
+    ];
+
+    /// The name of the encoding, as used in the `Accept-Encoding` and `Content-Encoding` headers.
+    pub(super) fn as_str(&self) -> &'static str {
+        match *self {
+            #[cfg(feature = "compression_br")]
+            Encoding::Brotli => "br",
+            #[cfg(feature = "compression_zstd")]
+            Encoding::Zstd => "zstd",
+            #[cfg(feature = "compression_gzip")]
+            Encoding::Gzip => "gzip",
+            #[cfg(feature = "compression_deflate")]
+            Encoding::Deflate => "deflate",
+        }
+    }
+
+    /// Build a new encoder for this encoding.
+    #[allow(unused_variables)]
+    pub(super) fn encoder(&self, level: CompressionLevel) -> Box<dyn Encoder> {
+        match *self {
+            #[cfg(feature = "compression_br")]
+            Encoding::Brotli => {
+                let quality = match level {
+                    CompressionLevel::Fastest => 1,
+                    CompressionLevel::Default => 4,
+                    CompressionLevel::Best => 11,
+                };
+                Box::new(brotli::CompressorWriter::new(Vec::new(), 4096, quality, 22))
+            }
+            #[cfg(feature = "compression_zstd")]
+            Encoding::Zstd => {
+                let level = match level {
+                    CompressionLevel::Fastest => 1,
+                    CompressionLevel::Default => zstd::DEFAULT_COMPRESSION_LEVEL,
+                    CompressionLevel::Best => 19,
+                };
+                Box::new(
+                    zstd::stream::write::Encoder::new(Vec::new(), level)
+                        .expect("Failed to initialize the zstd encoder"),
+                )
+            }
+            #[cfg(feature = "compression_gzip")]
+            Encoding::Gzip => Box::new(flate2::write::GzEncoder::new(
+                Vec::new(),
+                flate2_level(level),
+            )),
+            #[cfg(feature = "compression_deflate")]
+            Encoding::Deflate => Box::new(flate2::write::ZlibEncoder::new(
+                Vec::new(),
+                flate2_level(level),
+            )),
+        }
+    }
+}
+
+#[cfg(any(feature = "compression_gzip", feature = "compression_deflate"))]
+fn flate2_level(level: CompressionLevel) -> flate2::Compression {
+    match level {
+        CompressionLevel::Fastest => flate2::Compression::fast(),
+        CompressionLevel::Default => flate2::Compression::default(),
+        CompressionLevel::Best => flate2::Compression::best(),
+    }
+}
+
+/// Compress a response body, one chunk at a time.
+pub(super) trait Encoder: Send {
+    /// Compress `chunk` and return the compressed bytes.
+    ///
+    /// The encoder is flushed, so that the client can decode everything it has
+    /// received so far.
+    fn encode(&mut self, chunk: &[u8]) -> io::Result<Vec<u8>>;
+    /// Terminate the compressed stream and return its last bytes.
+    fn finish(self: Box<Self>) -> io::Result<Vec<u8>>;
+}
+
+#[cfg(feature = "compression_br")]
+impl Encoder for brotli::CompressorWriter<Vec<u8>> {
+    fn encode(&mut self, chunk: &[u8]) -> io::Result<Vec<u8>> {
+        self.write_all(chunk)?;
+        self.flush()?;
+        Ok(std::mem::take(self.get_mut()))
+    }
+
+    fn finish(self: Box<Self>) -> io::Result<Vec<u8>> {
+        Ok(self.into_inner())
+    }
+}
+
+#[cfg(feature = "compression_zstd")]
+impl Encoder for zstd::stream::write::Encoder<'static, Vec<u8>> {
+    fn encode(&mut self, chunk: &[u8]) -> io::Result<Vec<u8>> {
+        self.write_all(chunk)?;
+        self.flush()?;
+        Ok(std::mem::take(self.get_mut()))
+    }
+
+    fn finish(self: Box<Self>) -> io::Result<Vec<u8>> {
+        (*self).finish()
+    }
+}
+
+#[cfg(feature = "compression_gzip")]
+impl Encoder for flate2::write::GzEncoder<Vec<u8>> {
+    fn encode(&mut self, chunk: &[u8]) -> io::Result<Vec<u8>> {
+        self.write_all(chunk)?;
+        self.flush()?;
+        Ok(std::mem::take(self.get_mut()))
+    }
+
+    fn finish(self: Box<Self>) -> io::Result<Vec<u8>> {
+        (*self).finish()
+    }
+}
+
+#[cfg(feature = "compression_deflate")]
+impl Encoder for flate2::write::ZlibEncoder<Vec<u8>> {
+    fn encode(&mut self, chunk: &[u8]) -> io::Result<Vec<u8>> {
+        self.write_all(chunk)?;
+        self.flush()?;
+        Ok(std::mem::take(self.get_mut()))
+    }
+
+    fn finish(self: Box<Self>) -> io::Result<Vec<u8>> {
+        (*self).finish()
+    }
+}
@@ -0,0 +1,80 @@
+use crate::blueprint::Blueprint;
+use crate::blueprint::constructor::Constructor;
+use crate::blueprint::linter::Lint;
+use crate::blueprint::middleware::PostProcessingMiddleware;
+use crate::f;
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+/// A collection of components required to compress response bodies.
+///
+/// # Configuration
+///
+/// [`CompressionConfig`] is a configuration type, under the `compression` key.
+/// It's registered when you import Pavex's components—i.e. `bp.import(from![pavex])`.
+/// All its fields are optional.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::blueprint::{Blueprint, from};
+/// use pavex::compression::CompressionKit;
+///
+/// let mut bp = Blueprint::new();
+/// bp.import(from![pavex]);
+/// let kit = CompressionKit::new().register(&mut bp);
+/// ```
+///
+/// [`CompressionConfig`]: super::CompressionConfig
+pub struct CompressionKit {
+    /// The constructor for [`ResponseCompression`].
+    ///
+    /// By default, it uses [`ResponseCompression::new`].
+    ///
+    /// [`ResponseCompression::new`]: super::ResponseCompression::new
+    /// [`ResponseCompression`]: super::ResponseCompression
+    pub response_compression: Option<Constructor>,
+    /// A post-processing middleware to compress the body of the outgoing response.
+    ///
+    /// By default, it's set to [`compress_response`].
+    ///
+    /// [`compress_response`]: super::compress_response
+    pub compressor: Option<PostProcessingMiddleware>,
+}
+
+impl Default for CompressionKit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CompressionKit {
+    /// Create a new [`CompressionKit`] with all the bundled constructors and middlewares.
+    pub fn new() -> Self {
+        let response_compression =
+            Constructor::request_scoped(f!(super::ResponseCompression::new)).ignore(Lint::Unused);
+        let compressor = PostProcessingMiddleware::new(f!(super::compress_response));
+        Self {
+            response_compression: Some(response_compression),
+            compressor: Some(compressor),
+        }
+    }
+
+    /// Register all the bundled constructors and middlewares with a [`Blueprint`].
+    ///
+    /// If a component is set to `None` it will not be registered.
+    pub fn register(self, bp: &mut Blueprint) -> RegisteredCompressionKit {
+        if let Some(response_compression) = self.response_compression {
+            response_compression.register(bp);
+        }
+        if let Some(compressor) = self.compressor {
+            compressor.register(bp);
+        }
+        RegisteredCompressionKit {}
+    }
+}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+/// The type returned by [`CompressionKit::register`].
+pub struct RegisteredCompressionKit {}
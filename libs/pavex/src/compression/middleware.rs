@@ -0,0 +1,350 @@
+use http::header::{
+    ACCEPT_RANGES, CACHE_CONTROL, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE,
+    ETAG, VARY,
+};
+use http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
+
+use super::CompressionConfig;
+use super::body::CompressedBody;
+use super::encoder::Encoding;
+use crate::request::RequestHead;
+use crate::request::headers::{AcceptEncoding, EntityTag, TypedHeader};
+use crate::response::body::raw::RawBody;
+use crate::response::{Response, ResponseBody};
+
+/// Control the compression of the response to the current request.
+///
+/// Responses are compressed by default.
+/// Inject `&mut ResponseCompression` in a request handler to opt out:
+///
+/// ```rust
+/// use pavex::compression::ResponseCompression;
+/// use pavex::response::Response;
+///
+/// pub fn download(compression: &mut ResponseCompression) -> Response {
+///     // This response is already compressed.
+///     compression.disable();
+///     // [...]
+///     # Response::ok()
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResponseCompression {
+    enabled: bool,
+}
+
+impl Default for ResponseCompression {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResponseCompression {
+    /// Create a new [`ResponseCompression`], with compression enabled.
+    pub fn new() -> Self {
+        Self { enabled: true }
+    }
+
+    /// Send the response uncompressed.
+    pub fn disable(&mut self) {
+        self.enabled = false;
+    }
+
+    /// Returns `true` if the response may be compressed.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+/// Compress the response body using the encoding preferred by the client,
+/// according to its `Accept-Encoding` header.
+///
+/// The body is compressed as it's being streamed, with `Content-Encoding` set accordingly.
+/// The response is left untouched if:
+///
+/// - compression has been disabled via [`ResponseCompression::disable`]
+/// - it's the response to a `HEAD` request
+/// - it has no body (e.g. `204 No Content` or `304 Not Modified`) or it's a partial
+///   response (`206 Partial Content`)
+/// - it already has a `Content-Encoding`, or `Cache-Control` includes `no-transform`
+/// - its `Content-Type` is already compressed (e.g. images, audio, video or archives)
+///   or it's an event stream
+/// - its body is smaller than [`CompressionConfig::min_size`]
+/// - the client doesn't accept any of the enabled encodings
+///
+/// Compressible responses get `Vary: Accept-Encoding`, whether they end up being
+/// compressed or not.
+/// Strong `ETag`s are turned into weak ones when compressing, since the bytes
+/// sent to the client no longer match the original representation.
+pub fn compress_response(
+    response: Response,
+    request_head: &RequestHead,
+    compression: &ResponseCompression,
+    config: &CompressionConfig,
+) -> Response {
+    if !compression.is_enabled() || !is_compressible(&response, request_head) {
+        return response;
+    }
+    let mut response = add_vary(response);
+    if response
+        .body()
+        .size_hint()
+        .exact()
+        .is_some_and(|size| size < config.min_size)
+    {
+        return response;
+    }
+    let Some(encoding) = negotiate(&request_head.headers) else {
+        return response;
+    };
+
+    let headers = response.headers_mut();
+    headers.insert(
+        CONTENT_ENCODING,
+        HeaderValue::from_static(encoding.as_str()),
+    );
+    headers.remove(CONTENT_LENGTH);
+    headers.remove(ACCEPT_RANGES);
+    let weak_etag = headers
+        .get(ETAG)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<EntityTag>().ok())
+        .filter(|etag| !etag.weak)
+        .map(|etag| EntityTag::weak(etag.tag()));
+    if let Some(etag) = weak_etag {
+        headers.insert(ETAG, etag.into());
+    }
+
+    let (head, body) = response.into_parts();
+    let body = CompressedBody::new(body, encoding.encoder(config.level));
+    Response::from_parts(head, ResponseBody::new(body))
+}
+
+/// Check the properties of the response that don't depend on the client preferences.
+fn is_compressible(response: &Response, request_head: &RequestHead) -> bool {
+    let status = response.status();
+    if request_head.method == Method::HEAD
+        || status.is_informational()
+        || status == StatusCode::NO_CONTENT
+        || status == StatusCode::NOT_MODIFIED
+        || status == StatusCode::PARTIAL_CONTENT
+    {
+        return false;
+    }
+    let headers = response.headers();
+    if headers.contains_key(CONTENT_ENCODING) || headers.contains_key(CONTENT_RANGE) {
+        return false;
+    }
+    if tokens(headers, &CACHE_CONTROL).any(|t| t.eq_ignore_ascii_case("no-transform")) {
+        return false;
+    }
+    match headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok()) {
+        Some(content_type) => is_compressible_content_type(content_type),
+        None => true,
+    }
+}
+
+/// Returns `false` for media types that are already compressed, as well as event streams:
+/// compressing them would waste CPU cycles, or delay the delivery of events.
+fn is_compressible_content_type(content_type: &str) -> bool {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let Some((type_, subtype)) = essence.split_once('/') else {
+        return true;
+    };
+    match type_ {
+        "image" => subtype == "svg+xml",
+        "audio" | "video" => false,
+        "font" => !matches!(subtype, "woff" | "woff2"),
+        "text" => subtype != "event-stream",
+        "application" => !matches!(
+            subtype,
+            "zip"
+                | "gzip"
+                | "x-gzip"
+                | "zstd"
+                | "x-bzip2"
+                | "x-xz"
+                | "x-7z-compressed"
+                | "x-rar-compressed"
+                | "vnd.rar"
+                | "grpc"
+        ),
+        _ => true,
+    }
+}
+
+/// Append `Accept-Encoding` to the `Vary` header, unless it's already there.
+fn add_vary(response: Response) -> Response {
+    let headers = response.headers();
+    if tokens(headers, &VARY).any(|t| t == "*" || t.eq_ignore_ascii_case("accept-encoding")) {
+        return response;
+    }
+    response.append_header(VARY, HeaderValue::from_static("accept-encoding"))
+}
+
+/// Pick the enabled encoding with the highest weight in the client's `Accept-Encoding` header.
+///
+/// Ties are broken according to the order of [`Encoding::ALL`].
+fn negotiate(headers: &HeaderMap) -> Option<Encoding> {
+    if !headers.contains_key(AcceptEncoding::NAME) {
+        return None;
+    }
+    let accept_encoding = AcceptEncoding::decode(headers.get_all(AcceptEncoding::NAME)).ok()?;
+    let mut best: Option<(Encoding, f32)> = None;
+    for &encoding in Encoding::ALL {
+        let quality = accept_encoding.quality(encoding.as_str());
+        if quality > 0.0 && best.is_none_or(|(_, q)| quality > q) {
+            best = Some((encoding, quality));
+        }
+    }
+    best.map(|(encoding, _)| encoding)
+}
+
+/// The comma-separated tokens in all the values of the given header.
+fn tokens<'a>(headers: &'a HeaderMap, name: &HeaderName) -> impl Iterator<Item = &'a str> {
+    headers
+        .get_all(name)
+        .into_iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Version;
+
+    use super::*;
+
+    fn request(accept_encoding: Option<&str>) -> RequestHead {
+        let mut headers = HeaderMap::new();
+        if let Some(accept_encoding) = accept_encoding {
+            headers.insert(AcceptEncoding::NAME, accept_encoding.parse().unwrap());
+        }
+        RequestHead {
+            method: Method::GET,
+            target: "/".parse().unwrap(),
+            version: Version::HTTP_11,
+            headers,
+        }
+    }
+
+    fn compress(response: Response, request_head: &RequestHead) -> Response {
+        compress_response(
+            response,
+            request_head,
+            &ResponseCompression::new(),
+            &CompressionConfig::default(),
+        )
+    }
+
+    fn large_body() -> String {
+        "Hello world! ".repeat(200)
+    }
+
+    #[cfg(feature = "compression_gzip")]
+    #[tokio::test]
+    async fn bodies_are_compressed() {
+        use std::io::Read;
+
+        use http_body_util::BodyExt;
+
+        let response = Response::ok()
+            .set_typed_body(large_body())
+            .insert_header(ETAG, HeaderValue::from_static(r#""v1""#));
+        let response = compress(response, &request(Some("gzip")));
+        let headers = response.headers();
+        assert_eq!(headers[CONTENT_ENCODING], "gzip");
+        assert_eq!(headers[VARY], "accept-encoding");
+        assert_eq!(headers[ETAG], r#"W/"v1""#);
+        assert!(!headers.contains_key(CONTENT_LENGTH));
+
+        let (_, body) = response.into_parts();
+        let compressed = body.collect().await.unwrap().to_bytes();
+        assert!(compressed.len() < large_body().len());
+        let mut decompressed = String::new();
+        flate2::read::GzDecoder::new(&compressed[..])
+            .read_to_string(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, large_body());
+    }
+
+    #[cfg(all(
+        feature = "compression_br",
+        feature = "compression_zstd",
+        feature = "compression_gzip",
+        feature = "compression_deflate"
+    ))]
+    #[test]
+    fn the_preferred_encoding_is_negotiated() {
+        let negotiated = |accept_encoding: &str| {
+            negotiate(&request(Some(accept_encoding)).headers).map(|e| e.as_str())
+        };
+        assert_eq!(negotiated("gzip, deflate"), Some("gzip"));
+        assert_eq!(negotiated("gzip, br, zstd"), Some("br"));
+        assert_eq!(negotiated("br;q=0.5, zstd;q=0.8"), Some("zstd"));
+        assert_eq!(negotiated("*"), Some("br"));
+        assert_eq!(negotiated("*;q=0.5, br;q=0, zstd;q=0"), Some("gzip"));
+        assert_eq!(negotiated("identity"), None);
+        assert_eq!(negotiated("gzip;q=2"), None);
+    }
+
+    #[test]
+    fn some_responses_are_left_untouched() {
+        let request_head = request(Some("*"));
+
+        // Too small.
+        let response = compress(Response::ok().set_typed_body("Hi!"), &request_head);
+        assert!(!response.headers().contains_key(CONTENT_ENCODING));
+        assert_eq!(response.headers()[VARY], "accept-encoding");
+
+        // Already compressed.
+        let response = Response::ok()
+            .set_typed_body(large_body())
+            .insert_header(CONTENT_TYPE, HeaderValue::from_static("image/png"));
+        let response = compress(response, &request_head);
+        assert!(!response.headers().contains_key(CONTENT_ENCODING));
+        assert!(!response.headers().contains_key(VARY));
+
+        let response = Response::ok().set_typed_body(large_body()).insert_header(
+            CACHE_CONTROL,
+            HeaderValue::from_static("public, no-transform"),
+        );
+        let response = compress(response, &request_head);
+        assert!(!response.headers().contains_key(CONTENT_ENCODING));
+
+        // Opted out.
+        let mut compression = ResponseCompression::new();
+        compression.disable();
+        let response = compress_response(
+            Response::ok().set_typed_body(large_body()),
+            &request_head,
+            &compression,
+            &CompressionConfig::default(),
+        );
+        assert!(!response.headers().contains_key(CONTENT_ENCODING));
+
+        // The client doesn't support compression.
+        let response = compress(Response::ok().set_typed_body(large_body()), &request(None));
+        assert!(!response.headers().contains_key(CONTENT_ENCODING));
+        assert_eq!(response.headers()[VARY], "accept-encoding");
+    }
+
+    #[test]
+    fn compressed_content_types_are_detected() {
+        assert!(is_compressible_content_type("text/html; charset=utf-8"));
+        assert!(is_compressible_content_type("application/json"));
+        assert!(is_compressible_content_type("image/svg+xml"));
+        assert!(!is_compressible_content_type("image/webp"));
+        assert!(!is_compressible_content_type("Video/MP4"));
+        assert!(!is_compressible_content_type("application/zip"));
+        assert!(!is_compressible_content_type("font/woff2"));
+        assert!(!is_compressible_content_type("text/event-stream"));
+    }
+}
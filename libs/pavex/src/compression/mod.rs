@@ -0,0 +1,25 @@
+//! Compress response bodies, according to the encodings accepted by the client.
+//!
+//! Register a [`CompressionKit`] with your [`Blueprint`](crate::blueprint::Blueprint):
+//! its post-processing middleware, [`compress_response`], negotiates an encoding via the
+//! `Accept-Encoding` header and compresses the response body as it's being streamed.
+//!
+//! Each supported encoding is gated behind its own cargo feature: `compression_br`,
+//! `compression_zstd`, `compression_gzip` and `compression_deflate`.
+//! The `compression` feature enables all of them.
+//! When the client's preferences are tied, encodings are picked in that order.
+//!
+//! # Opting out
+//!
+//! Inject `&mut` [`ResponseCompression`] in a request handler and call
+//! [`ResponseCompression::disable`] to send its response uncompressed.
+//! Check out [`compress_response`] for the list of responses that are never compressed.
+mod body;
+mod config;
+mod encoder;
+mod kit;
+mod middleware;
+
+pub use config::{CompressionConfig, CompressionLevel};
+pub use kit::{CompressionKit, RegisteredCompressionKit};
+pub use middleware::{ResponseCompression, compress_response};
@@ -0,0 +1,50 @@
+use pavex_macros::config;
+use ubyte::{ByteUnit, ToByteUnit};
+
+#[config(key = "etag", default_if_missing)]
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+/// Configure how [`conditional_get`](super::conditional_get) generates `ETag`s.
+///
+/// # Example
+///
+/// ```yaml
+/// etag:
+///   kind: weak
+///   max_body_size: "512 KiB"
+/// ```
+pub struct ETagConfig {
+    /// Whether generated `ETag`s are strong or weak validators.
+    ///
+    /// It defaults to [`ETagKind::Strong`].
+    pub kind: ETagKind,
+    /// Bodies larger than this aren't buffered to compute their `ETag`.
+    ///
+    /// Attach a [`ResponseETag`](super::ResponseETag) to large responses
+    /// if you want them to be revalidated.
+    /// It defaults to 1 MiB.
+    pub max_body_size: ByteUnit,
+}
+
+impl Default for ETagConfig {
+    fn default() -> Self {
+        Self {
+            kind: ETagKind::Strong,
+            max_body_size: 1.mebibytes(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+/// The kind of `ETag`s generated from response bodies.
+///
+/// Check out [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#name-weak-versus-strong)
+/// for the differences between the two.
+pub enum ETagKind {
+    /// The `ETag` changes whenever the body changes, byte for byte.
+    #[default]
+    Strong,
+    /// The `ETag` is prefixed with `W/`.
+    Weak,
+}
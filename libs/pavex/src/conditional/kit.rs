@@ -0,0 +1,66 @@
+use crate::blueprint::Blueprint;
+use crate::blueprint::middleware::PostProcessingMiddleware;
+use crate::f;
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+/// A collection of components required to handle conditional `GET` requests.
+///
+/// # Configuration
+///
+/// [`ETagConfig`] is a configuration type, under the `etag` key.
+/// It's registered when you import Pavex's components—i.e. `bp.import(from![pavex])`.
+/// All its fields are optional.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::blueprint::{Blueprint, from};
+/// use pavex::conditional::ConditionalGetKit;
+///
+/// let mut bp = Blueprint::new();
+/// bp.import(from![pavex]);
+/// let kit = ConditionalGetKit::new().register(&mut bp);
+/// ```
+///
+/// [`ETagConfig`]: super::ETagConfig
+pub struct ConditionalGetKit {
+    /// A post-processing middleware to attach an `ETag` to the outgoing response
+    /// and evaluate the preconditions of the request against it.
+    ///
+    /// By default, it's set to [`conditional_get`].
+    ///
+    /// [`conditional_get`]: super::conditional_get
+    pub conditional_get: Option<PostProcessingMiddleware>,
+}
+
+impl Default for ConditionalGetKit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConditionalGetKit {
+    /// Create a new [`ConditionalGetKit`] with all the bundled middlewares.
+    pub fn new() -> Self {
+        let conditional_get = PostProcessingMiddleware::new(f!(super::conditional_get));
+        Self {
+            conditional_get: Some(conditional_get),
+        }
+    }
+
+    /// Register all the bundled middlewares with a [`Blueprint`].
+    ///
+    /// If a component is set to `None` it will not be registered.
+    pub fn register(self, bp: &mut Blueprint) -> RegisteredConditionalGetKit {
+        if let Some(conditional_get) = self.conditional_get {
+            conditional_get.register(bp);
+        }
+        RegisteredConditionalGetKit {}
+    }
+}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+/// The type returned by [`ConditionalGetKit::register`].
+pub struct RegisteredConditionalGetKit {}
@@ -0,0 +1,334 @@
+use bytes::Bytes;
+use http::header::{CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, ETAG, LAST_MODIFIED};
+use http::{Method, StatusCode};
+use http_body::Frame;
+use http_body_util::{BodyExt, Empty, Full, StreamBody};
+use tracing_log_error::log_error;
+
+use super::{ETagConfig, ETagKind};
+use crate::request::RequestHead;
+use crate::request::headers::{
+    EntityTag, IfMatch, IfModifiedSince, IfNoneMatch, TypedHeader, decode_if_valid,
+};
+use crate::response::body::raw::RawBody;
+use crate::response::{Response, ResponseBody};
+
+/// An entity tag chosen by the request handler, attached to the response as an extension.
+///
+/// [`conditional_get`] uses it as the `ETag` of the response, without buffering and
+/// hashing the response body.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::conditional::ResponseETag;
+/// use pavex::request::headers::EntityTag;
+/// use pavex::response::Response;
+///
+/// pub fn article() -> Response {
+///     // E.g. the revision number of the article, retrieved from the database.
+///     let revision = 42;
+///     let mut response = Response::ok().set_typed_body("...");
+///     response
+///         .extensions_mut()
+///         .insert(ResponseETag(EntityTag::strong(format!("r{revision}"))));
+///     response
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseETag(pub EntityTag);
+
+/// Attach an `ETag` to successful responses to `GET` and `HEAD` requests, then evaluate
+/// the preconditions of the request against it.
+///
+/// The `ETag` is, in order of precedence:
+///
+/// - the [`ResponseETag`] attached to the response, if any
+/// - the `ETag` header set by the request handler, if any
+/// - the hash of the response body, if its size is known upfront and it doesn't exceed
+///   [`ETagConfig::max_body_size`]. The body is buffered in memory to compute it
+///
+/// The preconditions are evaluated as specified in
+/// [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#name-evaluation):
+///
+/// - if `If-Match` doesn't match the `ETag`, the response is replaced by `412 Precondition Failed`
+/// - if `If-None-Match` matches the `ETag`, the response is turned into `304 Not Modified`
+/// - if `If-None-Match` is missing, `If-Modified-Since` is compared with the `Last-Modified`
+///   header set by the request handler, if any. The response is turned into
+///   `304 Not Modified` if the resource hasn't been modified since
+///
+/// `304 Not Modified` responses keep the headers of the original response,
+/// except for the ones describing its body (e.g. `Content-Type`).
+///
+/// # Unsafe methods
+///
+/// Requests with other methods (e.g. `PUT` or `DELETE`) are left untouched:
+/// this middleware runs *after* the request handler, too late to prevent its side effects.
+/// Evaluate `If-Match` in your request handler for those requests.
+pub async fn conditional_get(
+    response: Response,
+    request_head: &RequestHead,
+    config: &ETagConfig,
+) -> Response {
+    if !matches!(request_head.method, Method::GET | Method::HEAD)
+        || response.status() != StatusCode::OK
+    {
+        return response;
+    }
+    let (response, etag) = match response.extensions().get::<ResponseETag>().cloned() {
+        Some(ResponseETag(etag)) => (
+            response.insert_header(ETAG, etag.clone().into()),
+            Some(etag),
+        ),
+        None => match response.headers().get(ETAG) {
+            Some(etag) => {
+                let etag = etag.to_str().ok().and_then(|v| v.parse().ok());
+                (response, etag)
+            }
+            None => hash_body(response, config).await,
+        },
+    };
+
+    let headers = &request_head.headers;
+    if let Some(condition) = decode_if_valid::<IfMatch>(headers) {
+        let is_match = match condition {
+            IfMatch::Any => true,
+            IfMatch::Tags(_) => etag.as_ref().is_some_and(|etag| condition.matches(etag)),
+        };
+        if !is_match {
+            return Response::precondition_failed();
+        }
+    }
+    let is_fresh = if headers.contains_key(IfNoneMatch::NAME) {
+        decode_if_valid::<IfNoneMatch>(headers).is_some_and(|condition| match condition {
+            IfNoneMatch::Any => true,
+            IfNoneMatch::Tags(_) => etag.as_ref().is_some_and(|etag| condition.matches(etag)),
+        })
+    } else {
+        let last_modified = response
+            .headers()
+            .get(LAST_MODIFIED)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| {
+                jiff::fmt::rfc2822::DateTimeParser::new()
+                    .parse_timestamp(v)
+                    .ok()
+            });
+        decode_if_valid::<IfModifiedSince>(headers)
+            .zip(last_modified)
+            .is_some_and(|(condition, last_modified)| !condition.is_modified(last_modified))
+    };
+    if is_fresh {
+        return not_modified(response);
+    }
+    response
+}
+
+/// Buffer the response body to compute its `ETag`, if it's not too large.
+async fn hash_body(response: Response, config: &ETagConfig) -> (Response, Option<EntityTag>) {
+    let is_buffered = response
+        .body()
+        .size_hint()
+        .exact()
+        .is_some_and(|size| size <= config.max_body_size);
+    if !is_buffered {
+        return (response, None);
+    }
+    let (head, body) = response.into_parts();
+    let collected = match body.collect().await {
+        Ok(collected) => collected,
+        Err(e) => {
+            log_error!(e, "Failed to buffer the response body to compute its ETag");
+            return (Response::internal_server_error(), None);
+        }
+    };
+    let trailers = collected.trailers().cloned();
+    let data = collected.to_bytes();
+    let hash = format!("{:032x}", xxhash_rust::xxh3::xxh3_128(&data));
+    let etag = match config.kind {
+        ETagKind::Strong => EntityTag::strong(hash),
+        ETagKind::Weak => EntityTag::weak(hash),
+    };
+    let body = match trailers {
+        None => ResponseBody::new(Full::new(data)),
+        Some(trailers) => ResponseBody::new(StreamBody::new(futures_util::stream::iter([
+            Ok::<_, std::convert::Infallible>(Frame::data(data)),
+            Ok(Frame::trailers(trailers)),
+        ]))),
+    };
+    let response = Response::from_parts(head, body).insert_header(ETAG, etag.clone().into());
+    (response, Some(etag))
+}
+
+/// Turn a successful response into a `304 Not Modified`.
+fn not_modified(response: Response) -> Response {
+    let mut response = response
+        .set_status(StatusCode::NOT_MODIFIED)
+        .set_raw_body(Empty::<Bytes>::new());
+    let headers = response.headers_mut();
+    for name in [CONTENT_TYPE, CONTENT_LENGTH, CONTENT_ENCODING] {
+        headers.remove(name);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use http::header::{IF_MATCH, IF_MODIFIED_SINCE, IF_NONE_MATCH};
+    use http::{HeaderMap, Version};
+
+    use super::*;
+
+    fn request(method: Method, headers: &[(http::HeaderName, &str)]) -> RequestHead {
+        let mut map = HeaderMap::new();
+        for (name, value) in headers {
+            map.insert(name.clone(), value.parse().unwrap());
+        }
+        RequestHead {
+            method,
+            target: "/".parse().unwrap(),
+            version: Version::HTTP_11,
+            headers: map,
+        }
+    }
+
+    async fn etag_of(response: Response, request_head: &RequestHead) -> Response {
+        conditional_get(response, request_head, &ETagConfig::default()).await
+    }
+
+    async fn body_of(response: Response) -> Bytes {
+        let (_, body) = response.into_parts();
+        body.collect().await.unwrap().to_bytes()
+    }
+
+    #[tokio::test]
+    async fn etags_are_computed_from_the_body() {
+        let request_head = request(Method::GET, &[]);
+        let response = etag_of(Response::ok().set_typed_body("Hello"), &request_head).await;
+        let etag = response.headers()[ETAG].to_str().unwrap().to_owned();
+        assert!(etag.starts_with('"'));
+        assert_eq!(body_of(response).await, "Hello");
+
+        // Same body, same ETag.
+        let response = etag_of(Response::ok().set_typed_body("Hello"), &request_head).await;
+        assert_eq!(response.headers()[ETAG], etag);
+
+        let config = ETagConfig {
+            kind: ETagKind::Weak,
+            ..Default::default()
+        };
+        let response = conditional_get(
+            Response::ok().set_typed_body("Hello"),
+            &request_head,
+            &config,
+        )
+        .await;
+        assert_eq!(response.headers()[ETAG], format!("W/{etag}"));
+    }
+
+    #[tokio::test]
+    async fn matching_if_none_match_returns_304() {
+        let response = etag_of(
+            Response::ok().set_typed_body("Hello"),
+            &request(Method::GET, &[]),
+        )
+        .await;
+        let etag = response.headers()[ETAG].to_str().unwrap().to_owned();
+
+        let request_head = request(
+            Method::GET,
+            &[(IF_NONE_MATCH, &format!(r#""other", W/{etag}"#))],
+        );
+        let response = Response::ok()
+            .set_typed_body("Hello")
+            .insert_header(http::header::CACHE_CONTROL, "max-age=60".parse().unwrap());
+        let response = etag_of(response, &request_head).await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[ETAG], etag);
+        assert_eq!(
+            response.headers()[http::header::CACHE_CONTROL],
+            "max-age=60"
+        );
+        assert!(!response.headers().contains_key(CONTENT_TYPE));
+        assert!(body_of(response).await.is_empty());
+
+        let request_head = request(Method::GET, &[(IF_NONE_MATCH, r#""other""#)]);
+        let response = etag_of(Response::ok().set_typed_body("Hello"), &request_head).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn if_match_mismatches_return_412() {
+        let request_head = request(Method::GET, &[(IF_MATCH, r#""other""#)]);
+        let response = etag_of(Response::ok().set_typed_body("Hello"), &request_head).await;
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+
+        let request_head = request(Method::GET, &[(IF_MATCH, "*")]);
+        let response = etag_of(Response::ok().set_typed_body("Hello"), &request_head).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn if_modified_since_is_compared_with_last_modified() {
+        let last_modified = "Wed, 21 Oct 2015 07:28:00 GMT";
+        let response = || {
+            Response::ok()
+                .set_typed_body("Hello")
+                .insert_header(LAST_MODIFIED, last_modified.parse().unwrap())
+        };
+
+        let request_head = request(Method::GET, &[(IF_MODIFIED_SINCE, last_modified)]);
+        let response_ = etag_of(response(), &request_head).await;
+        assert_eq!(response_.status(), StatusCode::NOT_MODIFIED);
+
+        let request_head = request(
+            Method::GET,
+            &[(IF_MODIFIED_SINCE, "Tue, 20 Oct 2015 07:28:00 GMT")],
+        );
+        let response_ = etag_of(response(), &request_head).await;
+        assert_eq!(response_.status(), StatusCode::OK);
+
+        // `If-None-Match` takes precedence over `If-Modified-Since`.
+        let request_head = request(
+            Method::GET,
+            &[
+                (IF_MODIFIED_SINCE, last_modified),
+                (IF_NONE_MATCH, r#""other""#),
+            ],
+        );
+        let response_ = etag_of(response(), &request_head).await;
+        assert_eq!(response_.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn response_etags_are_used_without_buffering_the_body() {
+        let body = StreamBody::new(futures_util::stream::iter([Ok::<
+            _,
+            std::convert::Infallible,
+        >(Frame::data(
+            Bytes::from_static(b"Hello"),
+        ))]));
+        let mut response = Response::ok().set_raw_body(body);
+        response
+            .extensions_mut()
+            .insert(ResponseETag(EntityTag::strong("r42")));
+
+        let request_head = request(Method::GET, &[(IF_NONE_MATCH, r#""r42""#)]);
+        let response = etag_of(response, &request_head).await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[ETAG], r#""r42""#);
+    }
+
+    #[tokio::test]
+    async fn other_methods_and_statuses_are_left_untouched() {
+        let request_head = request(Method::POST, &[(IF_MATCH, r#""other""#)]);
+        let response = etag_of(Response::ok().set_typed_body("Hello"), &request_head).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!response.headers().contains_key(ETAG));
+
+        let request_head = request(Method::GET, &[(IF_NONE_MATCH, "*")]);
+        let response = etag_of(Response::not_found().set_typed_body("Hello"), &request_head).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert!(!response.headers().contains_key(ETAG));
+    }
+}
@@ -0,0 +1,30 @@
+//! Let clients revalidate their cached responses, via `ETag`s and conditional `GET` requests.
+//!
+//! Register a [`ConditionalGetKit`] with your [`Blueprint`](crate::blueprint::Blueprint):
+//! its post-processing middleware, [`conditional_get`], attaches an `ETag` to successful
+//! responses and replies with `304 Not Modified` if the client's copy is still fresh,
+//! sparing the bandwidth required to send the body again.
+//!
+//! It's only available if the `time` feature is enabled.
+//!
+//! # Supplying your own `ETag`
+//!
+//! By default, the `ETag` is computed by hashing the response body, which must be buffered
+//! in memory.
+//! If you can cheaply tell which version of the resource you're serving (e.g. a revision
+//! number stored in your database), attach a [`ResponseETag`] to the response instead.
+//!
+//! # Middleware ordering
+//!
+//! Post-processing middlewares are invoked in the order they were registered.
+//! Register [`ConditionalGetKit`] before middlewares that transform the response body,
+//! such as `CompressionKit`:
+//! `ETag`s are computed against the original representation and `304 Not Modified`
+//! responses skip the transformation altogether.
+mod config;
+mod kit;
+mod middleware;
+
+pub use config::{ETagConfig, ETagKind};
+pub use kit::{ConditionalGetKit, RegisteredConditionalGetKit};
+pub use middleware::{ResponseETag, conditional_get};
@@ -1,9 +1,17 @@
 //! Extract data concerning the HTTP connection.
+pub use client_info::{ClientInfo, TrustedProxiesConfig};
+pub use ipnet::IpNet;
+
 use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+mod client_info;
 
 /// Information relating to the current underlying HTTP connection.
 ///
-/// It includes the [peer address](SocketAddr).
+/// It includes the [peer address](PeerAddr) and, for connections
+/// terminated using TLS, the outcome of the TLS handshake.
 ///
 /// # Guide
 ///
@@ -11,7 +19,10 @@ use std::net::SocketAddr;
 /// for more details on `ConnectionInfo`.
 #[derive(Clone, Debug)]
 pub struct ConnectionInfo {
-    pub(crate) peer_addr: SocketAddr,
+    pub(crate) peer_addr: PeerAddr,
+    pub(crate) alpn_protocol: Option<Arc<[u8]>>,
+    pub(crate) server_name: Option<Arc<str>>,
+    pub(crate) is_tls: bool,
 }
 
 impl ConnectionInfo {
@@ -32,7 +43,94 @@ impl ConnectionInfo {
     ///     Response::ok().set_typed_body(body)
     /// }
     /// ```
-    pub fn peer_addr(&self) -> SocketAddr {
-        self.peer_addr
+    pub fn peer_addr(&self) -> &PeerAddr {
+        &self.peer_addr
+    }
+
+    /// Returns `true` if the connection is encrypted using TLS.
+    ///
+    /// It refers to the connection between the peer and Pavex: if you're behind a TLS-terminating
+    /// proxy, use [`ClientInfo::scheme`] to determine the scheme used by the client.
+    pub fn is_tls(&self) -> bool {
+        self.is_tls
+    }
+
+    /// Returns the application protocol negotiated via ALPN during the TLS handshake—e.g. `h2`
+    /// or `http/1.1`.
+    ///
+    /// It returns `None` if the connection isn't using TLS or if the client didn't
+    /// take part in ALPN negotiation.
+    pub fn alpn_protocol(&self) -> Option<&[u8]> {
+        self.alpn_protocol.as_deref()
+    }
+
+    /// Returns the server name requested by the client via
+    /// [SNI](https://en.wikipedia.org/wiki/Server_Name_Indication) during the TLS handshake.
+    ///
+    /// It returns `None` if the connection isn't using TLS or if the client didn't
+    /// send the SNI extension.
+    pub fn server_name(&self) -> Option<&str> {
+        self.server_name.as_deref()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+/// The address of the peer on the other side of a connection.
+///
+/// Check out [`ConnectionInfo::peer_addr`] for more details.
+pub enum PeerAddr {
+    /// The peer is connected over TCP/IP.
+    Ip(SocketAddr),
+    /// The peer is connected over a Unix domain socket.
+    ///
+    /// The path is set to `None` if the peer socket is unnamed—the most common
+    /// scenario for client sockets.
+    Unix(Option<PathBuf>),
+}
+
+impl PeerAddr {
+    /// Returns the IP address (and port) of the peer, if it's connected over TCP/IP.
+    /// `None` otherwise.
+    pub fn as_ip(&self) -> Option<SocketAddr> {
+        match self {
+            PeerAddr::Ip(addr) => Some(*addr),
+            PeerAddr::Unix(_) => None,
+        }
+    }
+
+    /// Returns the path of the peer socket, if it's connected over a named Unix domain socket.
+    /// `None` otherwise.
+    pub fn as_unix_path(&self) -> Option<&Path> {
+        match self {
+            PeerAddr::Ip(_) => None,
+            PeerAddr::Unix(path) => path.as_deref(),
+        }
+    }
+
+    /// Returns `true` if the peer is connected over TCP/IP.
+    pub fn is_ip(&self) -> bool {
+        matches!(self, PeerAddr::Ip(_))
+    }
+
+    /// Returns `true` if the peer is connected over a Unix domain socket.
+    pub fn is_unix(&self) -> bool {
+        matches!(self, PeerAddr::Unix(_))
+    }
+}
+
+impl From<SocketAddr> for PeerAddr {
+    fn from(addr: SocketAddr) -> Self {
+        PeerAddr::Ip(addr)
+    }
+}
+
+impl std::fmt::Display for PeerAddr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PeerAddr::Ip(addr) => write!(f, "{addr}"),
+            PeerAddr::Unix(Some(path)) => write!(f, "unix:{}", path.display()),
+            PeerAddr::Unix(None) => write!(f, "unix:(unnamed)"),
+        }
     }
 }
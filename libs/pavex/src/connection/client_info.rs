@@ -0,0 +1,315 @@
+use std::net::IpAddr;
+
+use http::header::HOST;
+use http::uri::Scheme;
+use http::{HeaderMap, HeaderName};
+use ipnet::IpNet;
+use pavex_macros::{config, request_scoped};
+
+use super::{ConnectionInfo, PeerAddr};
+use crate::request::RequestHead;
+use crate::request::headers::{Forwarded, ForwardedNode, TypedHeader, XForwardedFor};
+
+const X_FORWARDED_PROTO: HeaderName = HeaderName::from_static("x-forwarded-proto");
+const X_FORWARDED_HOST: HeaderName = HeaderName::from_static("x-forwarded-host");
+
+/// Information about the client that sent the request, taking proxies into account.
+///
+/// When your application sits behind a load balancer or a reverse proxy,
+/// [`ConnectionInfo::peer_addr`] returns the address of the proxy, rather than the
+/// address of the client.
+/// Proxies report the details of the original request using the
+/// [`Forwarded`](https://datatracker.ietf.org/doc/html/rfc7239) header or the
+/// `X-Forwarded-For`, `X-Forwarded-Proto` and `X-Forwarded-Host` headers.
+///
+/// Those headers can be set by anyone, though: `ClientInfo` only takes them into account
+/// if they were set by one of the proxies listed in [`TrustedProxiesConfig`].
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::connection::ClientInfo;
+///
+/// pub fn whoami(client: &ClientInfo) -> String {
+///     match client.ip() {
+///         Some(ip) => format!("Your IP is {ip}"),
+///         None => "I don't know your IP".into(),
+///     }
+/// }
+/// ```
+///
+/// # Resolution
+///
+/// If the peer is a trusted proxy, the list of forwarding hops is walked from the most recent
+/// to the oldest, until an untrusted address is found: that's the client.
+/// The `Forwarded` header takes precedence over `X-Forwarded-*` headers if both are present.
+///
+/// If the peer isn't trusted, the forwarding headers are ignored: the client is the peer,
+/// the scheme is determined by the use of TLS and the host by the `Host` header
+/// (or the authority of the request target, for HTTP/2 requests).
+#[derive(Debug, Clone)]
+pub struct ClientInfo {
+    ip: Option<IpAddr>,
+    scheme: Scheme,
+    host: Option<String>,
+}
+
+impl ClientInfo {
+    /// The default constructor for [`ClientInfo`].
+    #[request_scoped]
+    pub fn extract(
+        request_head: &RequestHead,
+        connection_info: &ConnectionInfo,
+        config: &TrustedProxiesConfig,
+    ) -> Self {
+        let mut client = Self::direct(request_head, connection_info);
+        let peer_is_trusted = match connection_info.peer_addr() {
+            PeerAddr::Ip(addr) => config.is_trusted(addr.ip()),
+            PeerAddr::Unix(_) => config.trust_unix_sockets,
+        };
+        if peer_is_trusted {
+            client.resolve_forwarded(&request_head.headers, config);
+        }
+        client
+    }
+
+    /// The IP address of the client.
+    ///
+    /// It's `None` if the peer is connected over a Unix domain socket or if a trusted
+    /// proxy didn't disclose the address of the client (e.g. `Forwarded: for=unknown`).
+    pub fn ip(&self) -> Option<IpAddr> {
+        self.ip
+    }
+
+    /// The scheme used by the client to send the request—e.g. `https`.
+    pub fn scheme(&self) -> &Scheme {
+        &self.scheme
+    }
+
+    /// The host targeted by the client, including the port if specified—e.g. `example.com:8080`.
+    ///
+    /// It's `None` if the request didn't specify a host.
+    pub fn host(&self) -> Option<&str> {
+        self.host.as_deref()
+    }
+
+    /// Client information as seen on the connection with the peer.
+    fn direct(request_head: &RequestHead, connection_info: &ConnectionInfo) -> Self {
+        let host = request_head
+            .headers
+            .get(HOST)
+            .and_then(|h| h.to_str().ok())
+            .or_else(|| request_head.target.authority().map(|a| a.as_str()))
+            .map(ToOwned::to_owned);
+        Self {
+            ip: connection_info.peer_addr().as_ip().map(|addr| addr.ip()),
+            scheme: if connection_info.is_tls() {
+                Scheme::HTTPS
+            } else {
+                Scheme::HTTP
+            },
+            host,
+        }
+    }
+
+    /// Walk the forwarding headers, from the most recent hop to the oldest one.
+    ///
+    /// Malformed headers are ignored.
+    fn resolve_forwarded(&mut self, headers: &HeaderMap, config: &TrustedProxiesConfig) {
+        if headers.contains_key(Forwarded::NAME) {
+            let Ok(Forwarded(elements)) = Forwarded::decode(headers.get_all(Forwarded::NAME))
+            else {
+                return;
+            };
+            for element in elements.iter().rev() {
+                if let Some(proto) = element.proto.as_deref().and_then(parse_scheme) {
+                    self.scheme = proto;
+                }
+                if let Some(host) = &element.host {
+                    self.host = Some(host.to_owned());
+                }
+                if !self.step(element.for_.as_ref(), config) {
+                    break;
+                }
+            }
+        } else if headers.contains_key(XForwardedFor::NAME) {
+            let Ok(XForwardedFor(nodes)) =
+                XForwardedFor::decode(headers.get_all(XForwardedFor::NAME))
+            else {
+                return;
+            };
+            // `X-Forwarded-Proto` and `X-Forwarded-Host` can't be matched with the hops
+            // in `X-Forwarded-For`: we trust the value set by the closest proxy.
+            if let Some(proto) = last_value(headers, &X_FORWARDED_PROTO).and_then(parse_scheme) {
+                self.scheme = proto;
+            }
+            if let Some(host) = last_value(headers, &X_FORWARDED_HOST) {
+                self.host = Some(host.to_owned());
+            }
+            for node in nodes.iter().rev() {
+                if !self.step(Some(node), config) {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Move one hop closer to the client.
+    ///
+    /// It returns `false` if the walk should stop, i.e. if the new hop isn't a trusted proxy.
+    fn step(&mut self, node: Option<&ForwardedNode>, config: &TrustedProxiesConfig) -> bool {
+        self.ip = node.and_then(ForwardedNode::ip);
+        self.ip.is_some_and(|ip| config.is_trusted(ip))
+    }
+}
+
+/// The last element of a comma-separated header, if any.
+fn last_value<'a>(headers: &'a HeaderMap, name: &HeaderName) -> Option<&'a str> {
+    let value = headers.get_all(name).iter().next_back()?.to_str().ok()?;
+    let last = value.rsplit(',').next()?.trim();
+    (!last.is_empty()).then_some(last)
+}
+
+fn parse_scheme(proto: &str) -> Option<Scheme> {
+    if proto.eq_ignore_ascii_case("https") {
+        Some(Scheme::HTTPS)
+    } else if proto.eq_ignore_ascii_case("http") {
+        Some(Scheme::HTTP)
+    } else {
+        None
+    }
+}
+
+#[config(key = "trusted_proxies", default_if_missing)]
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+/// The proxies that [`ClientInfo`] trusts to report the details of the original request.
+///
+/// No proxy is trusted by default: forwarding headers are ignored.
+///
+/// # Example
+///
+/// ```yaml
+/// trusted_proxies:
+///   cidrs:
+///     - "10.0.0.0/8"
+///     - "2001:db8::/32"
+/// ```
+pub struct TrustedProxiesConfig {
+    /// The IP ranges of the trusted proxies, in CIDR notation—e.g. `10.0.0.0/8`.
+    ///
+    /// Use a `/32` (IPv4) or `/128` (IPv6) range to trust a single address.
+    pub cidrs: Vec<IpNet>,
+    /// Trust peers connected over a Unix domain socket.
+    ///
+    /// It defaults to `false`.
+    pub trust_unix_sockets: bool,
+}
+
+impl TrustedProxiesConfig {
+    /// Returns `true` if `ip` belongs to one of the trusted ranges.
+    pub fn is_trusted(&self, ip: IpAddr) -> bool {
+        // IPv4 addresses may be reported by dual-stack sockets as IPv4-mapped IPv6 addresses.
+        let ip = match ip {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip),
+            IpAddr::V4(_) => ip,
+        };
+        self.cidrs.iter().any(|cidr| cidr.contains(&ip))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use super::*;
+
+    fn config(cidrs: &[&str]) -> TrustedProxiesConfig {
+        TrustedProxiesConfig {
+            cidrs: cidrs.iter().map(|c| c.parse().unwrap()).collect(),
+            trust_unix_sockets: false,
+        }
+    }
+
+    fn client(peer: &str, headers: &[(&str, &str)], config: &TrustedProxiesConfig) -> ClientInfo {
+        let mut header_map = HeaderMap::new();
+        header_map.insert(HOST, "internal:8080".parse().unwrap());
+        for (name, value) in headers {
+            header_map.append(
+                HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                value.parse().unwrap(),
+            );
+        }
+        let request_head = RequestHead {
+            method: http::Method::GET,
+            target: "/".parse().unwrap(),
+            version: http::Version::HTTP_11,
+            headers: header_map,
+        };
+        let connection_info = ConnectionInfo {
+            peer_addr: PeerAddr::Ip(peer.parse::<SocketAddr>().unwrap()),
+            alpn_protocol: None,
+            server_name: None,
+            is_tls: false,
+        };
+        ClientInfo::extract(&request_head, &connection_info, config)
+    }
+
+    #[test]
+    fn untrusted_peers_cant_spoof_the_client() {
+        let info = client(
+            "203.0.113.7:5000",
+            &[
+                ("x-forwarded-for", "1.2.3.4"),
+                ("x-forwarded-proto", "https"),
+            ],
+            &config(&["10.0.0.0/8"]),
+        );
+        assert_eq!(info.ip(), Some("203.0.113.7".parse().unwrap()));
+        assert_eq!(info.scheme(), &Scheme::HTTP);
+        assert_eq!(info.host(), Some("internal:8080"));
+    }
+
+    #[test]
+    fn x_forwarded_headers_from_trusted_proxies() {
+        // The left-most address is spoofed by the client: we stop at the first
+        // untrusted address, walking from the right.
+        let info = client(
+            "10.0.0.2:5000",
+            &[
+                ("x-forwarded-for", "6.6.6.6, 198.51.100.1, 10.0.0.1"),
+                ("x-forwarded-proto", "https"),
+                ("x-forwarded-host", "example.com"),
+            ],
+            &config(&["10.0.0.0/8"]),
+        );
+        assert_eq!(info.ip(), Some("198.51.100.1".parse().unwrap()));
+        assert_eq!(info.scheme(), &Scheme::HTTPS);
+        assert_eq!(info.host(), Some("example.com"));
+    }
+
+    #[test]
+    fn forwarded_header_from_trusted_proxies() {
+        let info = client(
+            "[::ffff:10.0.0.2]:5000",
+            &[
+                ("x-forwarded-for", "6.6.6.6"),
+                (
+                    "forwarded",
+                    r#"for=198.51.100.1;proto=https;host=example.com, for="[2001:db8::1]:4711""#,
+                ),
+            ],
+            &config(&["10.0.0.0/8", "2001:db8::/32"]),
+        );
+        assert_eq!(info.ip(), Some("198.51.100.1".parse().unwrap()));
+        assert_eq!(info.scheme(), &Scheme::HTTPS);
+        assert_eq!(info.host(), Some("example.com"));
+
+        let info = client(
+            "10.0.0.2:5000",
+            &[("forwarded", "for=unknown")],
+            &config(&["10.0.0.0/8"]),
+        );
+        assert_eq!(info.ip(), None);
+    }
+}
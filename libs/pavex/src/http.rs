@@ -10,6 +10,7 @@ pub use ::http::uri;
 pub use ::http::version;
 
 // Re-export commonly used types at the top-level for convenience.
+pub use ::http::Extensions;
 pub use header::HeaderMap;
 pub use header::HeaderName;
 pub use header::HeaderValue;
@@ -12,6 +12,15 @@
 pub use error::error_::Error;
 
 pub mod blueprint;
+#[cfg(any(
+    feature = "compression_gzip",
+    feature = "compression_deflate",
+    feature = "compression_br",
+    feature = "compression_zstd"
+))]
+pub mod compression;
+#[cfg(feature = "time")]
+pub mod conditional;
 #[cfg(feature = "config")]
 pub mod config;
 pub mod connection;
@@ -20,14 +29,25 @@ pub mod cookie;
 pub mod error;
 pub mod http;
 pub mod middleware;
+pub mod mime {
+    //! Utilities to work with media types (a.k.a. MIME types).
+    //!
+    //! It's a re-export of the [`mime@0.3`](https://docs.rs/mime/0.3) crate.
+    pub use mime::*;
+}
 pub mod request;
 pub mod response;
 pub mod router;
 pub mod serialization;
 #[cfg(feature = "server")]
 pub mod server;
+#[cfg(feature = "static_files")]
+pub mod static_files;
 pub mod telemetry;
+pub mod testing;
 pub mod unit;
+#[cfg(feature = "websocket")]
+pub mod websocket;
 #[cfg(feature = "time")]
 pub mod time {
     //! Utilities to work with dates, timestamps and datetimes.
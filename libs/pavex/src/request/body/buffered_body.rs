@@ -1,15 +1,13 @@
-use bytes::Bytes;
-use http::header::CONTENT_LENGTH;
-use http_body_util::{BodyExt, Limited};
+use bytes::{Bytes, BytesMut};
+use futures_util::StreamExt;
+use http_body_util::BodyExt;
+use http_body_util::combinators::UnsyncBoxBody;
 use pavex_macros::request_scoped;
-use ubyte::ByteUnit;
 
-use crate::{request::RequestHead, request::body::errors::SizeLimitExceeded};
+use crate::request::RequestHead;
 
-use super::{
-    BodySizeLimit, RawIncomingBody,
-    errors::{ExtractBufferedBodyError, UnexpectedBufferError},
-};
+use super::streaming::BoxError;
+use super::{BodySizeLimit, RawIncomingBody, StreamingBody, errors::ExtractBufferedBodyError};
 
 #[derive(Debug, Clone)]
 #[non_exhaustive]
@@ -31,6 +29,12 @@ use super::{
 /// Check out [the guide](https://pavex.dev/docs/guide/request_data/body/byte_wrappers/#body-size-limit)
 /// for examples on how to configure it.
 ///
+/// # Decompression
+///
+/// Bodies with a `Content-Encoding` are decompressed transparently, if the corresponding
+/// cargo feature is enabled. The size limit applies to the decompressed body.
+/// Check out [`StreamingBody`] for more details.
+///
 /// # Example
 ///
 /// ```rust
@@ -65,72 +69,38 @@ impl BufferedBody {
         body: RawIncomingBody,
         body_size_limit: BodySizeLimit,
     ) -> Result<Self, ExtractBufferedBodyError> {
-        match body_size_limit {
-            BodySizeLimit::Enabled { max_size } => {
-                Self::_extract_with_limit(request_head, body, max_size).await
-            }
-            BodySizeLimit::Disabled => match body.collect().await {
-                Ok(collected) => Ok(Self {
-                    bytes: collected.to_bytes(),
-                }),
-                Err(e) => Err(UnexpectedBufferError { source: e.into() }.into()),
-            },
-        }
+        Self::_extract(
+            request_head,
+            body.map_err(Into::into).boxed_unsync(),
+            body_size_limit,
+        )
+        .await
     }
 
-    async fn _extract_with_limit<B>(
+    async fn _extract(
         request_head: &RequestHead,
-        body: B,
-        max_size: ByteUnit,
-    ) -> Result<Self, ExtractBufferedBodyError>
-    where
-        B: hyper::body::Body,
-        B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
-    {
-        let content_length = request_head
-            .headers
-            .get(CONTENT_LENGTH)
-            .and_then(|value| value.to_str().ok()?.parse::<usize>().ok());
-
-        // Little shortcut to create a `SizeLimitExceeded` error.
-        let limit_error = || SizeLimitExceeded {
-            max_size,
-            content_length,
+        body: UnsyncBoxBody<Bytes, BoxError>,
+        body_size_limit: BodySizeLimit,
+    ) -> Result<Self, ExtractBufferedBodyError> {
+        // `StreamingBody` takes care of enforcing the size limit and decompressing the body.
+        let mut body = StreamingBody::from_parts(request_head, body, body_size_limit)?;
+        let Some(first) = body.next().await.transpose()? else {
+            return Ok(Self {
+                bytes: Bytes::new(),
+            });
         };
-
-        // We first check the `Content-Length` header, if it exists, to see if the
-        // "expected" size of the body is larger than the maximum size limit.
-        // If it is, we return an error immediately.
-        // This is a performance optimization: it allows us to short-circuit the
-        // body reading process entirely rather than reading the body incrementally
-        // until the limit is reached.
-        if let Some(len) = content_length {
-            if len > max_size {
-                return Err(limit_error().into());
-            }
-        }
-
-        // We saturate to `usize::MAX` if we happen to be on a platform where
-        // `usize` is smaller than `u64` (e.g. 32-bit platforms).
-        let max_n_bytes = max_size.as_u64().try_into().unwrap_or(usize::MAX);
-        // If the `Content-Length` header is missing, or if the expected size of the body
-        // is smaller than the maximum size limit, we start buffering the body while keeping
-        // track of the size limit.
-        let limited_body = Limited::new(body, max_n_bytes);
-        match limited_body.collect().await {
-            Ok(collected) => Ok(Self {
-                bytes: collected.to_bytes(),
-            }),
-            Err(e) => {
-                if e.downcast_ref::<http_body_util::LengthLimitError>()
-                    .is_some()
-                {
-                    Err(limit_error().into())
-                } else {
-                    Err(UnexpectedBufferError { source: e }.into())
-                }
-            }
+        // Avoid a copy if the whole body arrives in a single chunk.
+        let Some(second) = body.next().await.transpose()? else {
+            return Ok(Self { bytes: first });
+        };
+        let mut buffer = BytesMut::from(first);
+        buffer.extend_from_slice(&second);
+        while let Some(chunk) = body.next().await.transpose()? {
+            buffer.extend_from_slice(&chunk);
         }
+        Ok(Self {
+            bytes: buffer.freeze(),
+        })
     }
 }
 
@@ -143,11 +113,23 @@ impl From<BufferedBody> for Bytes {
 #[cfg(test)]
 mod tests {
     use http::HeaderMap;
-    use ubyte::ToByteUnit;
+    use http_body_util::BodyExt;
+    use http_body_util::combinators::UnsyncBoxBody;
+    use ubyte::{ByteUnit, ToByteUnit};
 
     use crate::request::RequestHead;
+    use crate::request::body::BodySizeLimit;
+    use crate::response::body::raw::Full;
+
+    use super::{BoxError, BufferedBody, Bytes};
+
+    fn boxed(body: Full<Bytes>) -> UnsyncBoxBody<Bytes, BoxError> {
+        body.map_err(Into::into).boxed_unsync()
+    }
 
-    use super::{BufferedBody, Bytes};
+    fn limit(max_size: ByteUnit) -> BodySizeLimit {
+        BodySizeLimit::Enabled { max_size }
+    }
 
     // No headers.
     fn dummy_request_head() -> RequestHead {
@@ -167,8 +149,8 @@ mod tests {
         let max_n_bytes = 100.bytes();
         assert!(raw_body.len() > max_n_bytes.as_u64() as usize);
 
-        let body = crate::response::body::raw::Full::new(Bytes::from(raw_body));
-        let err = BufferedBody::_extract_with_limit(&dummy_request_head(), body, max_n_bytes)
+        let body = Full::new(Bytes::from(raw_body));
+        let err = BufferedBody::_extract(&dummy_request_head(), boxed(body), limit(max_n_bytes))
             .await
             .unwrap_err();
         insta::assert_snapshot!(err, @"The request body is larger than the maximum size limit enforced by this server.");
@@ -194,13 +176,13 @@ mod tests {
         // Smaller than the value declared in the `Content-Length` header,
         // even though it's bigger than the actual size of the body.
         let max_size = 100.bytes();
-        let body = crate::response::body::raw::Full::new(Bytes::from(vec![0; 500]));
+        let body = Full::new(Bytes::from(vec![0; 500]));
         request_head
             .headers
             .insert("Content-Length", "1000".parse().unwrap());
 
         // Act
-        let err = BufferedBody::_extract_with_limit(&request_head, body, max_size)
+        let err = BufferedBody::_extract(&request_head, boxed(body), limit(max_size))
             .await
             .unwrap_err();
         insta::assert_snapshot!(err, @"The request body is larger than the maximum size limit enforced by this server.");
@@ -217,4 +199,33 @@ mod tests {
         )
         "###);
     }
+
+    #[cfg(feature = "decompression_gzip")]
+    #[tokio::test]
+    async fn size_limit_applies_to_the_decompressed_body() {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&[0; 1000]).unwrap();
+        let encoded = encoder.finish().unwrap();
+
+        let mut request_head = dummy_request_head();
+        request_head
+            .headers
+            .insert("Content-Encoding", "gzip".parse().unwrap());
+
+        let body = Full::new(Bytes::from(encoded.clone()));
+        let buffered = BufferedBody::_extract(&request_head, boxed(body), limit(1000.bytes()))
+            .await
+            .unwrap();
+        assert_eq!(buffered.bytes, vec![0; 1000]);
+
+        // The compressed body is well below the limit, the decompressed one isn't.
+        assert!(encoded.len() < 100);
+        let body = Full::new(Bytes::from(encoded));
+        let err = BufferedBody::_extract(&request_head, boxed(body), limit(100.bytes()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.into_response().status().as_u16(), 413);
+    }
 }
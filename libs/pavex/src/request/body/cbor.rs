@@ -0,0 +1,229 @@
+use std::marker::PhantomData;
+
+use crate::request::RequestHead;
+use http::HeaderMap;
+use pavex_macros::request_scoped;
+use serde::de::DeserializeOwned;
+
+use super::{
+    buffered_body::BufferedBody,
+    errors::{
+        CborContentTypeMismatch, CborDeserializationError, ExtractCborBodyError,
+        MissingCborContentType,
+    },
+};
+
+#[doc(alias = "Cbor")]
+#[derive(Debug)]
+/// Parse the body of an incoming request as [CBOR](https://cbor.io/).
+///
+/// It's only available if the `cbor` feature is enabled.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::request::body::CborBody;
+///
+/// #[derive(serde::Deserialize)]
+/// pub struct Reading {
+///     sensor_id: u64,
+///     value: f64,
+/// }
+///
+/// pub fn record(body: &CborBody<Reading>) -> String {
+///     format!("Sensor {} measured {}", body.0.sensor_id, body.0.value)
+/// }
+/// ```
+pub struct CborBody<T>(pub T);
+
+impl<T> CborBody<T> {
+    /// The default constructor for [`CborBody`].
+    ///
+    /// The extraction can fail for a number of reasons:
+    ///
+    /// - the `Content-Type` is missing
+    /// - the `Content-Type` header is not set to `application/cbor` or another `application/*+cbor` MIME type
+    /// - the request body is not a valid CBOR document
+    ///
+    /// In all of the above cases, an [`ExtractCborBodyError`] is returned.
+    ///
+    /// Unlike [`JsonBody`](super::JsonBody), `T` can't borrow from the request body.
+    #[request_scoped(
+        error_handler = "crate::request::body::errors::ExtractCborBodyError::into_response"
+    )]
+    pub fn extract(
+        request_head: &RequestHead,
+        buffered_body: &BufferedBody,
+    ) -> Result<Self, ExtractCborBodyError>
+    where
+        T: DeserializeOwned,
+    {
+        check_cbor_content_type(&request_head.headers)?;
+        let body = parse(buffered_body.bytes.as_ref())?;
+        Ok(CborBody(body))
+    }
+}
+
+pub(crate) fn parse<T>(bytes: &[u8]) -> Result<T, CborDeserializationError>
+where
+    T: DeserializeOwned,
+{
+    ciborium::from_reader(bytes).map_err(|e| {
+        // `ciborium` doesn't let us wrap its deserializer, so we can't track the path
+        // to the failing field while deserializing.
+        // We go through the payload a second time, on the error path only, to find it.
+        let path = ciborium::from_reader::<PathProbe<T>, _>(bytes)
+            .map(|probe| probe.path)
+            .unwrap_or_else(|_| serde_path_to_error::Track::new().path());
+        CborDeserializationError {
+            source: serde_path_to_error::Error::new(path, e),
+        }
+    })
+}
+
+/// Deserializes a `T`, recording the path to the field where deserialization failed.
+struct PathProbe<T> {
+    path: serde_path_to_error::Path,
+    _target: PhantomData<T>,
+}
+
+impl<'de, T> serde::Deserialize<'de> for PathProbe<T>
+where
+    T: serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut track = serde_path_to_error::Track::new();
+        let _ = T::deserialize(serde_path_to_error::Deserializer::new(
+            deserializer,
+            &mut track,
+        ));
+        Ok(PathProbe {
+            path: track.path(),
+            _target: PhantomData,
+        })
+    }
+}
+
+/// Returns `true` if the media type is a CBOR one.
+pub(crate) fn is_cbor(mime: &mime::Mime) -> bool {
+    mime.type_() == mime::APPLICATION
+        && (mime.subtype() == "cbor" || mime.suffix().is_some_and(|name| name == "cbor"))
+}
+
+/// Check that the `Content-Type` header is set to `application/cbor`, or another
+/// `application/*+cbor` MIME type.
+///
+/// Return an error otherwise.
+fn check_cbor_content_type(headers: &HeaderMap) -> Result<(), ExtractCborBodyError> {
+    let Some(content_type) = headers.get(http::header::CONTENT_TYPE) else {
+        return Err(MissingCborContentType.into());
+    };
+    let Ok(content_type) = content_type.to_str() else {
+        return Err(MissingCborContentType.into());
+    };
+
+    let is_cbor_content_type = content_type
+        .parse::<mime::Mime>()
+        .is_ok_and(|mime| is_cbor(&mime));
+    if !is_cbor_content_type {
+        return Err(CborContentTypeMismatch {
+            actual: content_type.to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::request::body::{BufferedBody, CborBody};
+
+    fn request_head(content_type: &str) -> crate::request::RequestHead {
+        let mut headers = http::HeaderMap::new();
+        headers.insert(http::header::CONTENT_TYPE, content_type.parse().unwrap());
+        crate::request::RequestHead {
+            headers,
+            method: http::Method::POST,
+            target: "/".parse().unwrap(),
+            version: http::Version::HTTP_11,
+        }
+    }
+
+    fn cbor<T: serde::Serialize>(value: &T) -> BufferedBody {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(value, &mut bytes).unwrap();
+        BufferedBody {
+            bytes: Bytes::from(bytes),
+        }
+    }
+
+    #[test]
+    fn cbor_content_types() {
+        let headers = request_head("application/senml+cbor").headers;
+        assert!(super::check_cbor_content_type(&headers).is_ok());
+
+        let err = super::check_cbor_content_type(&http::HeaderMap::new()).unwrap_err();
+        insta::assert_snapshot!(err, @"The `Content-Type` header is missing. This endpoint expects requests with a `Content-Type` header set to `application/cbor`, or another `application/*+cbor` MIME type");
+        assert_eq!(
+            err.into_response().status(),
+            http::StatusCode::UNSUPPORTED_MEDIA_TYPE
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+        struct Reading {
+            sensor: String,
+            values: Vec<f64>,
+        }
+
+        let reading = Reading {
+            sensor: "thermometer".into(),
+            values: vec![21.5, 22.0],
+        };
+        let extracted =
+            CborBody::<Reading>::extract(&request_head("application/cbor"), &cbor(&reading))
+                .unwrap();
+        assert_eq!(extracted.0, reading);
+    }
+
+    #[test]
+    /// Let's check the error quality when a nested field has the wrong type.
+    fn invalid_nested_field() {
+        #[derive(serde::Serialize)]
+        struct Sent {
+            readings: Vec<(&'static str, &'static str)>,
+        }
+
+        #[derive(serde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Expected {
+            readings: Vec<(String, f64)>,
+        }
+
+        let sent = Sent {
+            readings: vec![("a", "1.0")],
+        };
+        let err = CborBody::<Expected>::extract(&request_head("application/cbor"), &cbor(&sent))
+            .unwrap_err();
+        insta::assert_snapshot!(err, @"Failed to deserialize the body as a CBOR document.
+readings[0][1]: invalid type: string, expected float");
+        assert_eq!(err.into_response().status(), http::StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn truncated_payloads_are_rejected() {
+        let mut body = cbor(&vec![1, 2, 3]);
+        body.bytes.truncate(2);
+        let err =
+            CborBody::<Vec<u8>>::extract(&request_head("application/cbor"), &body).unwrap_err();
+        insta::assert_snapshot!(err, @"Failed to deserialize the body as a CBOR document.
+[1]: Unexpected end of input");
+    }
+}
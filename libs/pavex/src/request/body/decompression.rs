@@ -0,0 +1,414 @@
+//! Transparent decompression of request bodies, based on their `Content-Encoding`.
+//!
+//! Each supported encoding is gated behind its own cargo feature.
+//! Bodies that use an encoding we don't support (or that hasn't been enabled)
+//! are rejected with an [`UnsupportedContentEncoding`] error.
+use std::io::{self, Write};
+
+use bytes::Bytes;
+use http::HeaderMap;
+use http::header::CONTENT_ENCODING;
+use ubyte::ByteUnit;
+
+use super::errors::UnsupportedContentEncoding;
+
+/// The maximum number of encodings that can be applied, one after the other, to a request body.
+const MAX_STACKED_ENCODINGS: usize = 3;
+
+/// Decode a request body, one chunk at a time.
+pub(super) struct Decoder {
+    /// One decoder per encoding, in the order they must be applied—i.e. the reverse
+    /// of the order in which they're listed in the `Content-Encoding` header.
+    layers: Vec<Box<dyn Layer + Send>>,
+}
+
+/// The reasons why decoding may fail.
+pub(super) enum DecodeError {
+    /// The decompressed body is larger than the size limit.
+    SizeLimitExceeded,
+    /// The body isn't a valid payload for its encoding.
+    Invalid(io::Error),
+}
+
+impl From<io::Error> for DecodeError {
+    fn from(e: io::Error) -> Self {
+        if e.get_ref().is_some_and(|inner| inner.is::<LimitReached>()) {
+            DecodeError::SizeLimitExceeded
+        } else {
+            DecodeError::Invalid(e)
+        }
+    }
+}
+
+impl Decoder {
+    /// Build a decoder for the encodings listed in the `Content-Encoding` header.
+    ///
+    /// It returns `None` if the body isn't encoded.
+    /// It fails if more than [`MAX_STACKED_ENCODINGS`] encodings have been applied.
+    /// Every decoding step fails if its output exceeds `max_size`, to defuse
+    /// decompression bombs.
+    pub(super) fn from_headers(
+        headers: &HeaderMap,
+        max_size: Option<ByteUnit>,
+    ) -> Result<Option<Self>, UnsupportedContentEncoding> {
+        let mut encodings = Vec::new();
+        for value in headers.get_all(CONTENT_ENCODING) {
+            let Ok(value) = value.to_str() else {
+                return Err(UnsupportedContentEncoding {
+                    encoding: String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                });
+            };
+            for encoding in value.split(',').map(str::trim) {
+                if encoding.is_empty() || encoding.eq_ignore_ascii_case("identity") {
+                    continue;
+                }
+                encodings.push(encoding);
+                // Each layer allocates its own state and buffers before reading any input:
+                // an unbounded chain of encodings would be a cheap way to exhaust our memory.
+                if encodings.len() > MAX_STACKED_ENCODINGS {
+                    return Err(UnsupportedContentEncoding {
+                        encoding: encodings.join(", "),
+                    });
+                }
+            }
+        }
+        let mut layers = encodings
+            .into_iter()
+            .map(|encoding| layer(encoding, LimitedWriter::new(max_size)))
+            .collect::<Result<Vec<_>, _>>()?;
+        if layers.is_empty() {
+            return Ok(None);
+        }
+        // Encodings are listed in the order they were applied.
+        layers.reverse();
+        Ok(Some(Self { layers }))
+    }
+
+    /// Decode the next chunk of the body.
+    ///
+    /// The output may be empty, if the decoders need more input to make progress.
+    pub(super) fn decode(&mut self, chunk: &[u8]) -> Result<Bytes, DecodeError> {
+        let mut input = chunk.to_vec();
+        for layer in &mut self.layers {
+            layer.write(&input)?;
+            input = layer.take_output();
+        }
+        Ok(input.into())
+    }
+
+    /// Flush whatever is left in the decoders, once the body is over.
+    pub(super) fn finish(&mut self) -> Result<Bytes, DecodeError> {
+        let mut input = Vec::new();
+        for layer in &mut self.layers {
+            if !input.is_empty() {
+                layer.write(&input)?;
+            }
+            layer.finish()?;
+            input = layer.take_output();
+        }
+        Ok(input.into())
+    }
+}
+
+#[allow(unused_variables)]
+fn layer(
+    encoding: &str,
+    output: LimitedWriter,
+) -> Result<Box<dyn Layer + Send>, UnsupportedContentEncoding> {
+    let encoding = encoding.to_ascii_lowercase();
+    match encoding.as_str() {
+        #[cfg(feature = "decompression_gzip")]
+        "gzip" | "x-gzip" => Ok(Box::new(flate2::write::GzDecoder::new(output))),
+        #[cfg(feature = "decompression_deflate")]
+        "deflate" => Ok(Box::new(flate2::write::ZlibDecoder::new(output))),
+        #[cfg(feature = "decompression_br")]
+        "br" => Ok(Box::new(brotli_decompressor::DecompressorWriter::new(
+            output, 4096,
+        ))),
+        #[cfg(feature = "decompression_zstd")]
+        "zstd" => {
+            let decoder =
+                zstd::stream::raw::Decoder::new().map_err(|_| UnsupportedContentEncoding {
+                    encoding: encoding.clone(),
+                })?;
+            // We use the lower-level writer, rather than `zstd::stream::write::Decoder`,
+            // since it's the only one that can tell us if the last frame is incomplete.
+            Ok(Box::new(zstd::stream::zio::Writer::new(output, decoder)))
+        }
+        _ => Err(UnsupportedContentEncoding { encoding }),
+    }
+}
+
+/// A single decoding step.
+trait Layer {
+    fn write(&mut self, input: &[u8]) -> io::Result<()>;
+    fn finish(&mut self) -> io::Result<()>;
+    /// Take the output that has been produced so far.
+    fn take_output(&mut self) -> Vec<u8>;
+}
+
+#[cfg(feature = "decompression_gzip")]
+impl Layer for flate2::write::GzDecoder<LimitedWriter> {
+    fn write(&mut self, input: &[u8]) -> io::Result<()> {
+        self.write_all(input)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.try_finish()
+    }
+
+    fn take_output(&mut self) -> Vec<u8> {
+        self.get_mut().take()
+    }
+}
+
+#[cfg(feature = "decompression_deflate")]
+impl Layer for flate2::write::ZlibDecoder<LimitedWriter> {
+    fn write(&mut self, input: &[u8]) -> io::Result<()> {
+        self.write_all(input)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.try_finish()
+    }
+
+    fn take_output(&mut self) -> Vec<u8> {
+        self.get_mut().take()
+    }
+}
+
+#[cfg(feature = "decompression_br")]
+impl Layer for brotli_decompressor::DecompressorWriter<LimitedWriter> {
+    fn write(&mut self, input: &[u8]) -> io::Result<()> {
+        self.write_all(input)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.close()
+    }
+
+    fn take_output(&mut self) -> Vec<u8> {
+        self.get_mut().take()
+    }
+}
+
+#[cfg(feature = "decompression_zstd")]
+impl Layer for zstd::stream::zio::Writer<LimitedWriter, zstd::stream::raw::Decoder<'static>> {
+    fn write(&mut self, input: &[u8]) -> io::Result<()> {
+        self.write_all(input)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        // It fails if the body ends in the middle of a frame.
+        zstd::stream::zio::Writer::finish(self)
+    }
+
+    fn take_output(&mut self) -> Vec<u8> {
+        self.writer_mut().take()
+    }
+}
+
+/// An in-memory sink that refuses to hold more than `max_size` bytes over its lifetime.
+#[cfg_attr(
+    not(any(
+        feature = "decompression_gzip",
+        feature = "decompression_deflate",
+        feature = "decompression_br",
+        feature = "decompression_zstd"
+    )),
+    allow(dead_code)
+)]
+struct LimitedWriter {
+    buffer: Vec<u8>,
+    n_written: u64,
+    max_size: Option<ByteUnit>,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("The decompressed body is larger than the maximum size limit")]
+struct LimitReached;
+
+#[cfg_attr(
+    not(any(
+        feature = "decompression_gzip",
+        feature = "decompression_deflate",
+        feature = "decompression_br",
+        feature = "decompression_zstd"
+    )),
+    allow(dead_code)
+)]
+impl LimitedWriter {
+    fn new(max_size: Option<ByteUnit>) -> Self {
+        Self {
+            buffer: Vec::new(),
+            n_written: 0,
+            max_size,
+        }
+    }
+
+    fn take(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.buffer)
+    }
+}
+
+impl Write for LimitedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.n_written += buf.len() as u64;
+        if self
+            .max_size
+            .is_some_and(|max_size| self.n_written > max_size)
+        {
+            return Err(io::Error::other(LimitReached));
+        }
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+    use http::header::CONTENT_ENCODING;
+
+    use super::Decoder;
+
+    fn headers(encoding: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_ENCODING, encoding.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn identity_is_a_no_op() {
+        assert!(
+            Decoder::from_headers(&HeaderMap::new(), None)
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            Decoder::from_headers(&headers("identity"), None)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn unknown_encodings_are_rejected() {
+        let err = Decoder::from_headers(&headers("compress"), None)
+            .err()
+            .unwrap();
+        insta::assert_snapshot!(err, @"The request body is encoded using `compress`, which is not supported by this server");
+    }
+
+    #[test]
+    fn too_many_stacked_encodings_are_rejected() {
+        // The check doesn't depend on the encodings being supported.
+        let err = Decoder::from_headers(
+            &headers("compress, identity, compress, compress, compress, compress"),
+            None,
+        )
+        .err()
+        .unwrap();
+        insta::assert_snapshot!(err, @"The request body is encoded using `compress, compress, compress, compress`, which is not supported by this server");
+    }
+
+    #[cfg(feature = "decompression_gzip")]
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[cfg(feature = "decompression_gzip")]
+    #[test]
+    fn gzip_is_decoded_incrementally() {
+        let data = "Hello world! ".repeat(100);
+        let encoded = gzip(data.as_bytes());
+        let mut decoder = Decoder::from_headers(&headers("gzip"), None)
+            .unwrap()
+            .unwrap();
+        let mut decoded = Vec::new();
+        for chunk in encoded.chunks(7) {
+            decoded.extend_from_slice(&decoder.decode(chunk).ok().unwrap());
+        }
+        decoded.extend_from_slice(&decoder.finish().ok().unwrap());
+        assert_eq!(decoded, data.as_bytes());
+    }
+
+    #[cfg(feature = "decompression_gzip")]
+    #[test]
+    fn stacked_encodings_are_decoded_in_reverse_order() {
+        let data = b"Hello world!";
+        let encoded = gzip(&gzip(data));
+        let mut decoder = Decoder::from_headers(&headers("gzip, identity, gzip"), None)
+            .unwrap()
+            .unwrap();
+        let mut decoded = decoder.decode(&encoded).ok().unwrap().to_vec();
+        decoded.extend_from_slice(&decoder.finish().ok().unwrap());
+        assert_eq!(decoded, data);
+    }
+
+    #[cfg(feature = "decompression_gzip")]
+    #[test]
+    fn decompression_bombs_are_defused() {
+        use ubyte::ToByteUnit;
+
+        // 10 MBs of zeroes compress down to ~10 KBs.
+        let encoded = gzip(&vec![0; 10_000_000]);
+        let mut decoder = Decoder::from_headers(&headers("gzip"), Some(1.megabytes()))
+            .unwrap()
+            .unwrap();
+        let outcome = decoder.decode(&encoded).and_then(|_| decoder.finish());
+        assert!(matches!(
+            outcome,
+            Err(super::DecodeError::SizeLimitExceeded)
+        ));
+    }
+
+    #[cfg(feature = "decompression_br")]
+    #[test]
+    fn brotli_is_decoded() {
+        // "Hello" encoded with brotli.
+        let encoded = [0x0b, 0x02, 0x80, 0x48, 0x65, 0x6c, 0x6c, 0x6f, 0x03];
+        let mut decoder = Decoder::from_headers(&headers("br"), None)
+            .unwrap()
+            .unwrap();
+        let mut decoded = decoder.decode(&encoded).ok().unwrap().to_vec();
+        decoded.extend_from_slice(&decoder.finish().ok().unwrap());
+        assert_eq!(decoded, b"Hello");
+    }
+
+    #[cfg(feature = "decompression_zstd")]
+    #[test]
+    fn zstd_is_decoded() {
+        let encoded = zstd::encode_all(&b"Hello"[..], 3).unwrap();
+        let mut decoder = Decoder::from_headers(&headers("zstd"), None)
+            .unwrap()
+            .unwrap();
+        let mut decoded = decoder.decode(&encoded).ok().unwrap().to_vec();
+        decoded.extend_from_slice(&decoder.finish().ok().unwrap());
+        assert_eq!(decoded, b"Hello");
+    }
+
+    #[cfg(feature = "decompression_zstd")]
+    #[test]
+    fn truncated_zstd_bodies_are_rejected() {
+        let encoded = zstd::encode_all("Hello world! ".repeat(100).as_bytes(), 3).unwrap();
+        let mut decoder = Decoder::from_headers(&headers("zstd"), None)
+            .unwrap()
+            .unwrap();
+        let outcome = decoder
+            .decode(&encoded[..encoded.len() - 4])
+            .and_then(|_| decoder.finish());
+        let Err(super::DecodeError::Invalid(e)) = outcome else {
+            panic!("A truncated zstd frame should be rejected");
+        };
+        insta::assert_snapshot!(e, @"incomplete frame");
+    }
+}
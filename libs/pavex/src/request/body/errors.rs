@@ -31,6 +31,99 @@ impl ExtractJsonBodyError {
     }
 }
 
+#[cfg(feature = "msgpack")]
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+/// The error returned by [`MsgPackBody::extract`] when the extraction fails.
+///
+/// [`MsgPackBody::extract`]: crate::request::body::MsgPackBody::extract
+pub enum ExtractMsgPackBodyError {
+    #[error(transparent)]
+    /// See [`MissingMsgPackContentType`] for details.
+    MissingContentType(#[from] MissingMsgPackContentType),
+    #[error(transparent)]
+    /// See [`MsgPackContentTypeMismatch`] for details.
+    ContentTypeMismatch(#[from] MsgPackContentTypeMismatch),
+    #[error(transparent)]
+    /// See [`MsgPackDeserializationError`] for details.
+    DeserializationError(#[from] MsgPackDeserializationError),
+}
+
+#[cfg(feature = "msgpack")]
+impl ExtractMsgPackBodyError {
+    /// Convert an [`ExtractMsgPackBodyError`] into an HTTP response.
+    pub fn into_response(&self) -> Response {
+        match self {
+            ExtractMsgPackBodyError::MissingContentType(_)
+            | ExtractMsgPackBodyError::ContentTypeMismatch(_) => Response::unsupported_media_type(),
+            ExtractMsgPackBodyError::DeserializationError(_) => Response::bad_request(),
+        }
+        .set_typed_body(format!("{}", self))
+    }
+}
+
+#[cfg(feature = "cbor")]
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+/// The error returned by [`CborBody::extract`] when the extraction fails.
+///
+/// [`CborBody::extract`]: crate::request::body::CborBody::extract
+pub enum ExtractCborBodyError {
+    #[error(transparent)]
+    /// See [`MissingCborContentType`] for details.
+    MissingContentType(#[from] MissingCborContentType),
+    #[error(transparent)]
+    /// See [`CborContentTypeMismatch`] for details.
+    ContentTypeMismatch(#[from] CborContentTypeMismatch),
+    #[error(transparent)]
+    /// See [`CborDeserializationError`] for details.
+    DeserializationError(#[from] CborDeserializationError),
+}
+
+#[cfg(feature = "cbor")]
+impl ExtractCborBodyError {
+    /// Convert an [`ExtractCborBodyError`] into an HTTP response.
+    pub fn into_response(&self) -> Response {
+        match self {
+            ExtractCborBodyError::MissingContentType(_)
+            | ExtractCborBodyError::ContentTypeMismatch(_) => Response::unsupported_media_type(),
+            ExtractCborBodyError::DeserializationError(_) => Response::bad_request(),
+        }
+        .set_typed_body(format!("{}", self))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+/// The error returned by [`Negotiated::extract`] when the extraction fails.
+///
+/// [`Negotiated::extract`]: crate::request::body::Negotiated::extract
+pub enum ExtractNegotiatedBodyError {
+    #[error(transparent)]
+    /// See [`MissingNegotiatedContentType`] for details.
+    MissingContentType(#[from] MissingNegotiatedContentType),
+    #[error(transparent)]
+    /// See [`NegotiatedContentTypeMismatch`] for details.
+    ContentTypeMismatch(#[from] NegotiatedContentTypeMismatch),
+    #[error(transparent)]
+    /// See [`NegotiatedDeserializationError`] for details.
+    DeserializationError(#[from] NegotiatedDeserializationError),
+}
+
+impl ExtractNegotiatedBodyError {
+    /// Convert an [`ExtractNegotiatedBodyError`] into an HTTP response.
+    pub fn into_response(&self) -> Response {
+        match self {
+            ExtractNegotiatedBodyError::MissingContentType(_)
+            | ExtractNegotiatedBodyError::ContentTypeMismatch(_) => {
+                Response::unsupported_media_type()
+            }
+            ExtractNegotiatedBodyError::DeserializationError(_) => Response::bad_request(),
+        }
+        .set_typed_body(format!("{}", self))
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
 /// The error returned by [`BufferedBody::extract`] when the extraction fails.
@@ -43,6 +136,31 @@ pub enum ExtractBufferedBodyError {
     #[error(transparent)]
     /// See [`UnexpectedBufferError`] for details.
     UnexpectedBufferError(#[from] UnexpectedBufferError),
+    #[error(transparent)]
+    /// See [`UnsupportedContentEncoding`] for details.
+    UnsupportedContentEncoding(#[from] UnsupportedContentEncoding),
+    #[error(transparent)]
+    /// See [`DecompressionError`] for details.
+    DecompressionError(#[from] DecompressionError),
+}
+
+impl From<ExtractStreamingBodyError> for ExtractBufferedBodyError {
+    fn from(e: ExtractStreamingBodyError) -> Self {
+        match e {
+            ExtractStreamingBodyError::SizeLimitExceeded(e) => e.into(),
+            ExtractStreamingBodyError::UnsupportedContentEncoding(e) => e.into(),
+        }
+    }
+}
+
+impl From<StreamingBodyError> for ExtractBufferedBodyError {
+    fn from(e: StreamingBodyError) -> Self {
+        match e {
+            StreamingBodyError::SizeLimitExceeded(e) => e.into(),
+            StreamingBodyError::UnexpectedBufferError(e) => e.into(),
+            StreamingBodyError::DecompressionError(e) => e.into(),
+        }
+    }
 }
 
 impl ExtractBufferedBodyError {
@@ -51,6 +169,10 @@ impl ExtractBufferedBodyError {
         match self {
             ExtractBufferedBodyError::SizeLimitExceeded(_) => Response::payload_too_large(),
             ExtractBufferedBodyError::UnexpectedBufferError(_) => Response::internal_server_error(),
+            ExtractBufferedBodyError::UnsupportedContentEncoding(_) => {
+                Response::unsupported_media_type()
+            }
+            ExtractBufferedBodyError::DecompressionError(_) => Response::bad_request(),
         }
         .set_typed_body(format!("{}", self))
     }
@@ -87,6 +209,185 @@ impl ExtractUrlEncodedBodyError {
     }
 }
 
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+/// The error returned by [`MultipartBody::extract`] when the extraction fails.
+///
+/// [`MultipartBody::extract`]: crate::request::body::MultipartBody::extract
+pub enum ExtractMultipartBodyError {
+    #[error(transparent)]
+    /// See [`MissingMultipartContentType`] for details.
+    MissingContentType(#[from] MissingMultipartContentType),
+    #[error(transparent)]
+    /// See [`MultipartContentTypeMismatch`] for details.
+    ContentTypeMismatch(#[from] MultipartContentTypeMismatch),
+    #[error(transparent)]
+    /// See [`MissingMultipartBoundary`] for details.
+    MissingBoundary(#[from] MissingMultipartBoundary),
+    #[error(transparent)]
+    /// See [`SizeLimitExceeded`] for details.
+    SizeLimitExceeded(#[from] SizeLimitExceeded),
+    #[error(transparent)]
+    /// See [`UnsupportedContentEncoding`] for details.
+    UnsupportedContentEncoding(#[from] UnsupportedContentEncoding),
+}
+
+impl From<ExtractStreamingBodyError> for ExtractMultipartBodyError {
+    fn from(e: ExtractStreamingBodyError) -> Self {
+        match e {
+            ExtractStreamingBodyError::SizeLimitExceeded(e) => e.into(),
+            ExtractStreamingBodyError::UnsupportedContentEncoding(e) => e.into(),
+        }
+    }
+}
+
+impl ExtractMultipartBodyError {
+    /// Convert an [`ExtractMultipartBodyError`] into an HTTP response.
+    pub fn into_response(&self) -> Response {
+        match self {
+            ExtractMultipartBodyError::MissingContentType(_)
+            | ExtractMultipartBodyError::ContentTypeMismatch(_)
+            | ExtractMultipartBodyError::UnsupportedContentEncoding(_) => {
+                Response::unsupported_media_type()
+            }
+            ExtractMultipartBodyError::MissingBoundary(_) => Response::bad_request(),
+            ExtractMultipartBodyError::SizeLimitExceeded(_) => Response::payload_too_large(),
+        }
+        .set_typed_body(format!("{}", self))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+/// The error returned by [`MultipartBody::next_field`] and its companion methods
+/// when the body can't be processed.
+///
+/// [`MultipartBody::next_field`]: crate::request::body::MultipartBody::next_field
+pub enum MultipartError {
+    #[error(transparent)]
+    /// See [`SizeLimitExceeded`] for details.
+    SizeLimitExceeded(#[from] SizeLimitExceeded),
+    #[error(transparent)]
+    /// See [`FieldSizeLimitExceeded`] for details.
+    FieldSizeLimitExceeded(#[from] FieldSizeLimitExceeded),
+    #[error(transparent)]
+    /// See [`MalformedMultipartBody`] for details.
+    MalformedBody(#[from] MalformedMultipartBody),
+    #[error(transparent)]
+    /// See [`UnexpectedBufferError`] for details.
+    UnexpectedBufferError(#[from] UnexpectedBufferError),
+    #[error(transparent)]
+    /// See [`DecompressionError`] for details.
+    DecompressionError(#[from] DecompressionError),
+}
+
+impl From<StreamingBodyError> for MultipartError {
+    fn from(e: StreamingBodyError) -> Self {
+        match e {
+            StreamingBodyError::SizeLimitExceeded(e) => e.into(),
+            StreamingBodyError::UnexpectedBufferError(e) => e.into(),
+            StreamingBodyError::DecompressionError(e) => e.into(),
+        }
+    }
+}
+
+impl MultipartError {
+    /// Convert a [`MultipartError`] into an HTTP response.
+    pub fn into_response(&self) -> Response {
+        match self {
+            MultipartError::SizeLimitExceeded(_) | MultipartError::FieldSizeLimitExceeded(_) => {
+                Response::payload_too_large()
+            }
+            MultipartError::MalformedBody(_) | MultipartError::DecompressionError(_) => {
+                Response::bad_request()
+            }
+            MultipartError::UnexpectedBufferError(_) => Response::internal_server_error(),
+        }
+        .set_typed_body(format!("{}", self))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+/// The error returned by [`StreamingBody::extract`] when the extraction fails.
+///
+/// [`StreamingBody::extract`]: crate::request::body::StreamingBody::extract
+pub enum ExtractStreamingBodyError {
+    #[error(transparent)]
+    /// See [`SizeLimitExceeded`] for details.
+    SizeLimitExceeded(#[from] SizeLimitExceeded),
+    #[error(transparent)]
+    /// See [`UnsupportedContentEncoding`] for details.
+    UnsupportedContentEncoding(#[from] UnsupportedContentEncoding),
+}
+
+impl ExtractStreamingBodyError {
+    /// Convert an [`ExtractStreamingBodyError`] into an HTTP response.
+    pub fn into_response(&self) -> Response {
+        match self {
+            ExtractStreamingBodyError::SizeLimitExceeded(_) => Response::payload_too_large(),
+            ExtractStreamingBodyError::UnsupportedContentEncoding(_) => {
+                Response::unsupported_media_type()
+            }
+        }
+        .set_typed_body(format!("{}", self))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+/// The error yielded by [`StreamingBody`] when the next chunk can't be read.
+///
+/// [`StreamingBody`]: crate::request::body::StreamingBody
+pub enum StreamingBodyError {
+    #[error(transparent)]
+    /// See [`SizeLimitExceeded`] for details.
+    SizeLimitExceeded(#[from] SizeLimitExceeded),
+    #[error(transparent)]
+    /// See [`UnexpectedBufferError`] for details.
+    UnexpectedBufferError(#[from] UnexpectedBufferError),
+    #[error(transparent)]
+    /// See [`DecompressionError`] for details.
+    DecompressionError(#[from] DecompressionError),
+}
+
+impl StreamingBodyError {
+    /// Convert a [`StreamingBodyError`] into an HTTP response.
+    pub fn into_response(&self) -> Response {
+        match self {
+            StreamingBodyError::SizeLimitExceeded(_) => Response::payload_too_large(),
+            StreamingBodyError::UnexpectedBufferError(_) => Response::internal_server_error(),
+            StreamingBodyError::DecompressionError(_) => Response::bad_request(),
+        }
+        .set_typed_body(format!("{}", self))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+/// The error yielded by [`NdJsonStream`] when the next item can't be produced.
+///
+/// [`NdJsonStream`]: crate::request::body::NdJsonStream
+pub enum NdJsonError {
+    #[error(transparent)]
+    /// The body couldn't be read. See [`StreamingBodyError`] for details.
+    Body(#[from] StreamingBodyError),
+    #[error(transparent)]
+    /// See [`NdJsonDeserializationError`] for details.
+    DeserializationError(#[from] NdJsonDeserializationError),
+}
+
+impl NdJsonError {
+    /// Convert an [`NdJsonError`] into an HTTP response.
+    pub fn into_response(&self) -> Response {
+        match self {
+            NdJsonError::Body(e) => return e.into_response(),
+            NdJsonError::DeserializationError(_) => Response::bad_request(),
+        }
+        .set_typed_body(format!("{}", self))
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 #[error("The request body is larger than the maximum size limit enforced by this server.")]
 #[non_exhaustive]
@@ -141,6 +442,144 @@ pub struct JsonContentTypeMismatch {
     pub actual: String,
 }
 
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "The `Content-Type` header is missing. This endpoint expects requests with a `Content-Type` header set to one of: {}",
+    list_media_types(.supported)
+)]
+#[non_exhaustive]
+/// The `Content-Type` header is missing, while we expected it to be set to one of the media types
+/// supported by the endpoint.
+pub struct MissingNegotiatedContentType {
+    /// The media types supported by the endpoint.
+    pub supported: Vec<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "The `Content-Type` header was set to `{actual}`. This endpoint expects requests with a `Content-Type` header set to one of: {}",
+    list_media_types(.supported)
+)]
+#[non_exhaustive]
+/// The `Content-Type` header doesn't match any of the media types supported by the endpoint.
+pub struct NegotiatedContentTypeMismatch {
+    /// The actual value of the `Content-Type` header for this request.
+    pub actual: String,
+    /// The media types supported by the endpoint.
+    pub supported: Vec<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to deserialize the body as `{media_type}`.\n{source}")]
+#[non_exhaustive]
+/// Something went wrong when deserializing the request body into the specified type.
+pub struct NegotiatedDeserializationError {
+    /// The media type of the format that was used to deserialize the body.
+    pub media_type: String,
+    #[source]
+    pub(super) source: crate::serialization::FormatError,
+}
+
+fn list_media_types(media_types: &[String]) -> String {
+    media_types
+        .iter()
+        .map(|m| format!("`{m}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(feature = "msgpack")]
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "The `Content-Type` header is missing. This endpoint expects requests with a `Content-Type` header set to `application/msgpack`, or another MessagePack MIME type"
+)]
+#[non_exhaustive]
+/// The `Content-Type` header is missing, while we expected it to be set to `application/msgpack`,
+/// or another MessagePack MIME type.
+pub struct MissingMsgPackContentType;
+
+#[cfg(feature = "msgpack")]
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to deserialize the body as a MessagePack document.\n{source}")]
+#[non_exhaustive]
+/// Something went wrong when deserializing the request body into the specified type.
+pub struct MsgPackDeserializationError {
+    #[source]
+    pub(super) source: serde_path_to_error::Error<rmp_serde::decode::Error>,
+}
+
+#[cfg(feature = "msgpack")]
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "The `Content-Type` header was set to `{actual}`. This endpoint expects requests with a `Content-Type` header set to `application/msgpack`, or another MessagePack MIME type"
+)]
+#[non_exhaustive]
+/// The `Content-Type` header not set to `application/msgpack`, `application/vnd.msgpack`,
+/// `application/x-msgpack` or another `application/*+msgpack` MIME type.
+pub struct MsgPackContentTypeMismatch {
+    /// The actual value of the `Content-Type` header for this request.
+    pub actual: String,
+}
+
+#[cfg(feature = "cbor")]
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "The `Content-Type` header is missing. This endpoint expects requests with a `Content-Type` header set to `application/cbor`, or another `application/*+cbor` MIME type"
+)]
+#[non_exhaustive]
+/// The `Content-Type` header is missing, while we expected it to be set to `application/cbor`, or
+/// another `application/*+cbor` MIME type.
+pub struct MissingCborContentType;
+
+#[cfg(feature = "cbor")]
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "Failed to deserialize the body as a CBOR document.\n{}",
+    describe_cbor_error(.source)
+)]
+#[non_exhaustive]
+/// Something went wrong when deserializing the request body into the specified type.
+pub struct CborDeserializationError {
+    #[source]
+    pub(super) source: serde_path_to_error::Error<ciborium::de::Error<std::io::Error>>,
+}
+
+#[cfg(feature = "cbor")]
+// `ciborium`'s errors are rendered using their `Debug` representation, so we format them ourselves.
+fn describe_cbor_error(
+    e: &serde_path_to_error::Error<ciborium::de::Error<std::io::Error>>,
+) -> String {
+    use ciborium::de::Error;
+
+    let inner = match e.inner() {
+        Error::Io(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+            "Unexpected end of input".to_owned()
+        }
+        Error::Io(e) => e.to_string(),
+        Error::Syntax(offset) => format!("Invalid CBOR syntax at byte {offset}"),
+        Error::Semantic(_, message) => message.to_owned(),
+        Error::RecursionLimitExceeded => "The document is nested too deeply".to_owned(),
+    };
+    let path = e.path().to_string();
+    if path == "." {
+        inner
+    } else {
+        format!("{path}: {inner}")
+    }
+}
+
+#[cfg(feature = "cbor")]
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "The `Content-Type` header was set to `{actual}`. This endpoint expects requests with a `Content-Type` header set to `application/cbor`, or another `application/*+cbor` MIME type"
+)]
+#[non_exhaustive]
+/// The `Content-Type` header not set to `application/cbor`, or another `application/*+cbor` MIME type.
+pub struct CborContentTypeMismatch {
+    /// The actual value of the `Content-Type` header for this request.
+    pub actual: String,
+}
+
 #[derive(Debug, thiserror::Error)]
 #[error(
     "The `Content-Type` header is missing. This endpoint expects requests with a `Content-Type` header set to `application/x-www-form-urlencoded`"
@@ -168,3 +607,93 @@ pub struct UrlEncodedBodyDeserializationError {
     #[source]
     pub(super) source: serde_html_form::de::Error,
 }
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "The `Content-Type` header is missing. This endpoint expects requests with a `Content-Type` header set to `multipart/form-data`"
+)]
+#[non_exhaustive]
+/// The `Content-Type` header is missing, while we expected it to be set to `multipart/form-data`.
+pub struct MissingMultipartContentType;
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "The `Content-Type` header was set to `{actual}`. This endpoint expects requests with a `Content-Type` header set to `multipart/form-data`"
+)]
+#[non_exhaustive]
+/// The `Content-Type` header not set to `multipart/form-data`.
+pub struct MultipartContentTypeMismatch {
+    /// The actual value of the `Content-Type` header for this request.
+    pub actual: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "The `Content-Type` header is missing the `boundary` parameter, which is required for `multipart/form-data` requests"
+)]
+#[non_exhaustive]
+/// The `Content-Type` header doesn't specify a (valid) boundary, which is required to
+/// tell the fields of a `multipart/form-data` body apart.
+pub struct MissingMultipartBoundary;
+
+#[derive(Debug, thiserror::Error)]
+#[error("The `{field_name}` field is larger than the maximum size limit enforced by this server.")]
+#[non_exhaustive]
+/// A field in a `multipart/form-data` body is larger than the maximum size limit enforced by this server.
+///
+/// Check out [`MultipartLimits`](crate::request::body::MultipartLimits) for more details.
+pub struct FieldSizeLimitExceeded {
+    /// The name of the field that breached the limit.
+    pub field_name: String,
+    /// The maximum size limit enforced by this server for the field.
+    pub max_size: ByteUnit,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("The request body is not a valid `multipart/form-data` payload: {reason}.")]
+#[non_exhaustive]
+/// The request body doesn't follow the `multipart/form-data` format.
+pub struct MalformedMultipartBody {
+    /// What went wrong.
+    pub reason: &'static str,
+}
+
+impl MalformedMultipartBody {
+    pub(super) fn new(reason: &'static str) -> Self {
+        Self { reason }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to deserialize line {line} as a JSON document.\n{source}")]
+#[non_exhaustive]
+/// A line of a newline-delimited JSON body couldn't be deserialized into the specified type.
+pub struct NdJsonDeserializationError {
+    /// The (1-based) number of the line that failed to deserialize.
+    pub line: usize,
+    #[source]
+    pub(super) source: serde_json::Error,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("The request body is encoded using `{encoding}`, which is not supported by this server")]
+#[non_exhaustive]
+/// The request body is encoded using a `Content-Encoding` that this server can't decode.
+///
+/// Each encoding is supported behind a dedicated cargo feature of `pavex`
+/// (e.g. `decompression_gzip`).
+/// Bodies that have been encoded more than three times, one encoding on top of the
+/// other, are rejected as well.
+pub struct UnsupportedContentEncoding {
+    /// The unsupported encoding, as it appears in the `Content-Encoding` header.
+    pub encoding: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to decompress the request body.")]
+#[non_exhaustive]
+/// The request body isn't a valid payload for the `Content-Encoding` it declares.
+pub struct DecompressionError {
+    #[source]
+    pub(super) source: std::io::Error,
+}
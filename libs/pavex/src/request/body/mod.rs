@@ -3,14 +3,29 @@
 //! Check the [relevant section of the guide](https://pavex.dev/docs/guide/request_data/body/)
 //! for a thorough introduction to Pavex's body extractors.
 pub use buffered_body::BufferedBody;
+#[cfg(feature = "cbor")]
+pub use cbor::CborBody;
 pub use json::JsonBody;
 pub use limit::BodySizeLimit;
+#[cfg(feature = "msgpack")]
+pub use msgpack::MsgPackBody;
+pub use multipart::{MultipartBody, MultipartField, MultipartLimits};
+pub use negotiated::Negotiated;
 pub use raw_body::RawIncomingBody;
+pub use streaming::{NdJsonStream, StreamingBody};
 pub use url_encoded::UrlEncodedBody;
 
 mod buffered_body;
+#[cfg(feature = "cbor")]
+pub(crate) mod cbor;
+mod decompression;
 pub mod errors;
 mod json;
 mod limit;
+#[cfg(feature = "msgpack")]
+pub(crate) mod msgpack;
+mod multipart;
+mod negotiated;
 mod raw_body;
+mod streaming;
 mod url_encoded;
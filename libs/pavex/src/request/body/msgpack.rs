@@ -0,0 +1,190 @@
+use crate::request::RequestHead;
+use http::HeaderMap;
+use pavex_macros::request_scoped;
+use serde::Deserialize;
+
+use super::{
+    buffered_body::BufferedBody,
+    errors::{
+        ExtractMsgPackBodyError, MissingMsgPackContentType, MsgPackContentTypeMismatch,
+        MsgPackDeserializationError,
+    },
+};
+
+#[doc(alias = "MsgPack")]
+#[doc(alias = "MessagePack")]
+#[derive(Debug)]
+/// Parse the body of an incoming request as [MessagePack](https://msgpack.org/).
+///
+/// It's only available if the `msgpack` feature is enabled.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::request::body::MsgPackBody;
+///
+/// #[derive(serde::Deserialize)]
+/// pub struct Reading {
+///     sensor_id: u64,
+///     value: f64,
+/// }
+///
+/// pub fn record(body: &MsgPackBody<Reading>) -> String {
+///     format!("Sensor {} measured {}", body.0.sensor_id, body.0.value)
+/// }
+/// ```
+pub struct MsgPackBody<T>(pub T);
+
+impl<T> MsgPackBody<T> {
+    /// The default constructor for [`MsgPackBody`].
+    ///
+    /// The extraction can fail for a number of reasons:
+    ///
+    /// - the `Content-Type` is missing
+    /// - the `Content-Type` header is not set to `application/msgpack`, `application/vnd.msgpack`,
+    ///   `application/x-msgpack` or another `application/*+msgpack` MIME type
+    /// - the request body is not a valid MessagePack document
+    ///
+    /// In all of the above cases, an [`ExtractMsgPackBodyError`] is returned.
+    // # Implementation notes
+    //
+    // We are using two separate lifetimes here to make it clear to the compiler
+    // that `MsgPackBody` doesn't borrow from `RequestHead`.
+    #[request_scoped(
+        error_handler = "crate::request::body::errors::ExtractMsgPackBodyError::into_response"
+    )]
+    pub fn extract<'head, 'body>(
+        request_head: &'head RequestHead,
+        buffered_body: &'body BufferedBody,
+    ) -> Result<Self, ExtractMsgPackBodyError>
+    where
+        T: Deserialize<'body>,
+    {
+        check_msgpack_content_type(&request_head.headers)?;
+        let body = parse(buffered_body.bytes.as_ref())?;
+        Ok(MsgPackBody(body))
+    }
+}
+
+pub(crate) fn parse<'de, T>(bytes: &'de [u8]) -> Result<T, MsgPackDeserializationError>
+where
+    T: Deserialize<'de>,
+{
+    let mut deserializer = rmp_serde::Deserializer::from_read_ref(bytes);
+    serde_path_to_error::deserialize(&mut deserializer)
+        .map_err(|e| MsgPackDeserializationError { source: e })
+}
+
+/// Returns `true` if the media type is a MessagePack one.
+pub(crate) fn is_msgpack(mime: &mime::Mime) -> bool {
+    mime.type_() == mime::APPLICATION
+        && (matches!(
+            mime.subtype().as_str(),
+            "msgpack" | "vnd.msgpack" | "x-msgpack"
+        ) || mime.suffix().is_some_and(|name| name == "msgpack"))
+}
+
+/// Check that the `Content-Type` header is set to a MessagePack MIME type.
+///
+/// Return an error otherwise.
+fn check_msgpack_content_type(headers: &HeaderMap) -> Result<(), ExtractMsgPackBodyError> {
+    let Some(content_type) = headers.get(http::header::CONTENT_TYPE) else {
+        return Err(MissingMsgPackContentType.into());
+    };
+    let Ok(content_type) = content_type.to_str() else {
+        return Err(MissingMsgPackContentType.into());
+    };
+
+    let is_msgpack_content_type = content_type
+        .parse::<mime::Mime>()
+        .is_ok_and(|mime| is_msgpack(&mime));
+    if !is_msgpack_content_type {
+        return Err(MsgPackContentTypeMismatch {
+            actual: content_type.to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::request::body::{BufferedBody, MsgPackBody};
+
+    fn request_head(content_type: &str) -> crate::request::RequestHead {
+        let mut headers = http::HeaderMap::new();
+        headers.insert(http::header::CONTENT_TYPE, content_type.parse().unwrap());
+        crate::request::RequestHead {
+            headers,
+            method: http::Method::POST,
+            target: "/".parse().unwrap(),
+            version: http::Version::HTTP_11,
+        }
+    }
+
+    #[test]
+    fn msgpack_content_types() {
+        let headers = request_head("application/vnd.msgpack").headers;
+        assert!(super::check_msgpack_content_type(&headers).is_ok());
+        let headers = request_head("application/problem+msgpack").headers;
+        assert!(super::check_msgpack_content_type(&headers).is_ok());
+
+        let headers = request_head("application/json").headers;
+        let err = super::check_msgpack_content_type(&headers).unwrap_err();
+        insta::assert_snapshot!(err, @"The `Content-Type` header was set to `application/json`. This endpoint expects requests with a `Content-Type` header set to `application/msgpack`, or another MessagePack MIME type");
+    }
+
+    #[test]
+    fn round_trip() {
+        #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+        struct Reading<'a> {
+            sensor: &'a str,
+            value: f64,
+        }
+
+        let reading = Reading {
+            sensor: "thermometer",
+            value: 21.5,
+        };
+        let body = BufferedBody {
+            bytes: Bytes::from(rmp_serde::to_vec_named(&reading).unwrap()),
+        };
+        let extracted =
+            MsgPackBody::<Reading>::extract(&request_head("application/msgpack"), &body).unwrap();
+        assert_eq!(extracted.0, reading);
+    }
+
+    #[test]
+    /// Let's check the error quality when the request body is missing
+    /// a required field.
+    fn missing_msgpack_field() {
+        #[derive(serde::Serialize)]
+        struct Sent {
+            name: String,
+            age: u8,
+        }
+
+        #[derive(serde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Expected {
+            name: String,
+            surname: String,
+            age: u8,
+        }
+
+        let sent = Sent {
+            name: "John".into(),
+            age: 42,
+        };
+        let body = BufferedBody {
+            bytes: Bytes::from(rmp_serde::to_vec_named(&sent).unwrap()),
+        };
+        let err = MsgPackBody::<Expected>::extract(&request_head("application/msgpack"), &body)
+            .unwrap_err();
+        insta::assert_snapshot!(err, @"Failed to deserialize the body as a MessagePack document.
+missing field `surname`");
+        assert_eq!(err.into_response().status(), http::StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,715 @@
+use bytes::{Buf, Bytes, BytesMut};
+use futures_util::StreamExt;
+use http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use http::{HeaderMap, HeaderName, HeaderValue};
+use pavex_macros::request_scoped;
+use ubyte::{ByteUnit, ToByteUnit};
+
+use crate::request::RequestHead;
+
+use super::errors::{
+    ExtractMultipartBodyError, FieldSizeLimitExceeded, MalformedMultipartBody,
+    MissingMultipartBoundary, MissingMultipartContentType, MultipartContentTypeMismatch,
+    MultipartError,
+};
+use super::{BodySizeLimit, RawIncomingBody, StreamingBody};
+
+/// The maximum size of the header block of a single field.
+const MAX_FIELD_HEADERS_SIZE: usize = 8 * 1024;
+
+#[doc(alias = "Multipart")]
+#[doc(alias = "FormData")]
+#[doc(alias = "FileUpload")]
+/// Parse a `multipart/form-data` request body, field by field.
+///
+/// # Streaming
+///
+/// `MultipartBody` doesn't buffer the request body in memory.
+/// Fields are yielded one at a time, in the order they were sent by the client,
+/// and the content of each field can be consumed chunk by chunk as it comes
+/// off the network. It's the right tool for file uploads: you can write the
+/// incoming bytes to their destination without holding the whole file in memory.
+///
+/// Since it consumes the underlying byte stream, you must take `MultipartBody`
+/// by value (i.e. `mut body: MultipartBody`) in your request handler.
+///
+/// # Security
+///
+/// `MultipartBody` enforces the [`BodySizeLimit`] on the body as a whole, as well as the
+/// per-field limits specified via [`MultipartLimits`].
+/// Check out [`MultipartLimits`] for more details on the default values and
+/// on how to customize them.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::http::StatusCode;
+/// use pavex::request::body::MultipartBody;
+/// use pavex::request::body::errors::MultipartError;
+///
+/// pub async fn upload(mut body: MultipartBody) -> Result<StatusCode, MultipartError> {
+///     while let Some(mut field) = body.next_field().await? {
+///         if let Some(file_name) = field.file_name() {
+///             let file_name = file_name.to_owned();
+///             let mut size = 0;
+///             // Process the file, one chunk at a time.
+///             while let Some(chunk) = field.chunk().await? {
+///                 size += chunk.len();
+///             }
+///             println!("Received `{file_name}` ({size} bytes)");
+///         } else {
+///             let name = field.name().to_owned();
+///             let value = field.text().await?;
+///             println!("{name}: {value}");
+///         }
+///     }
+///     Ok(StatusCode::OK)
+/// }
+/// ```
+pub struct MultipartBody {
+    body: StreamingBody,
+    /// Bytes that have been received from the client but haven't been processed yet.
+    buffer: BytesMut,
+    /// The delimiter that separates fields, i.e. `\r\n--{boundary}`.
+    delimiter: Bytes,
+    state: State,
+    limits: MultipartLimits,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// We haven't seen the first delimiter yet.
+    Preamble,
+    /// We're right after a delimiter: either a new field starts or the body is over.
+    Delimiter,
+    /// We're inside the content of a field.
+    Content,
+    /// We've seen the closing delimiter.
+    Done,
+}
+
+impl std::fmt::Debug for MultipartBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultipartBody")
+            .field("limits", &self.limits)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MultipartBody {
+    /// The default constructor for [`MultipartBody`].
+    ///
+    /// The extraction can fail for a number of reasons:
+    ///
+    /// - the `Content-Type` is missing
+    /// - the `Content-Type` header is not set to `multipart/form-data`
+    /// - the `Content-Type` header doesn't specify a boundary
+    /// - the `Content-Length` header exceeds the [`BodySizeLimit`]
+    ///
+    /// In all of the above cases, an [`ExtractMultipartBodyError`] is returned.
+    ///
+    /// Errors that can only be detected while reading the body (e.g. a field that's larger
+    /// than the configured limit) are returned by [`MultipartBody::next_field`] and
+    /// [`MultipartField::chunk`].
+    #[request_scoped(
+        error_handler = "crate::request::body::errors::ExtractMultipartBodyError::into_response"
+    )]
+    pub fn extract(
+        request_head: &RequestHead,
+        body: RawIncomingBody,
+        body_size_limit: BodySizeLimit,
+        limits: MultipartLimits,
+    ) -> Result<Self, ExtractMultipartBodyError> {
+        let boundary = parse_boundary(&request_head.headers)?;
+        let body = StreamingBody::extract(request_head, body, body_size_limit)?;
+        Ok(Self::new(body, &boundary, limits))
+    }
+
+    fn new(body: StreamingBody, boundary: &str, limits: MultipartLimits) -> Self {
+        let delimiter = Bytes::from(format!("\r\n--{boundary}"));
+        // The first delimiter isn't preceded by a line break if there is no preamble.
+        // We add one to the buffer, so that all delimiters can be matched in the same way.
+        let buffer = BytesMut::from(&b"\r\n"[..]);
+        Self {
+            body,
+            buffer,
+            delimiter,
+            state: State::Preamble,
+            limits,
+        }
+    }
+
+    /// Move on to the next field.
+    ///
+    /// It returns `Ok(None)` when there are no more fields to process.
+    /// If you didn't consume the content of the previous field, it's skipped.
+    pub async fn next_field(&mut self) -> Result<Option<MultipartField<'_>>, MultipartError> {
+        loop {
+            match self.state {
+                State::Preamble => {
+                    self.skip_preamble().await?;
+                }
+                State::Content => while self.read_chunk().await?.is_some() {},
+                State::Delimiter => {
+                    if !self.parse_delimiter_line().await? {
+                        self.state = State::Done;
+                        return Ok(None);
+                    }
+                    let headers = self.parse_field_headers().await?;
+                    let (name, file_name) = parse_content_disposition(&headers)?;
+                    let max_size = if file_name.is_some() {
+                        self.limits.max_file_size
+                    } else {
+                        self.limits.max_field_size
+                    };
+                    self.state = State::Content;
+                    return Ok(Some(MultipartField {
+                        name,
+                        file_name,
+                        headers,
+                        max_size,
+                        n_read: 0,
+                        body: self,
+                    }));
+                }
+                State::Done => return Ok(None),
+            }
+        }
+    }
+
+    /// Discard everything that comes before the first delimiter.
+    async fn skip_preamble(&mut self) -> Result<(), MultipartError> {
+        loop {
+            if let Some(i) = find(&self.buffer, &self.delimiter) {
+                self.buffer.advance(i + self.delimiter.len());
+                self.state = State::Delimiter;
+                return Ok(());
+            }
+            // Keep the tail of the buffer around: it may be the beginning of the delimiter.
+            let discard = self.buffer.len().saturating_sub(self.delimiter.len() - 1);
+            self.buffer.advance(discard);
+            if !self.fill().await? {
+                return Err(MalformedMultipartBody::new("the opening boundary is missing").into());
+            }
+        }
+    }
+
+    /// Process the rest of the line after a delimiter.
+    ///
+    /// It returns `false` if it was the closing delimiter, `true` otherwise.
+    async fn parse_delimiter_line(&mut self) -> Result<bool, MultipartError> {
+        loop {
+            if self.buffer.starts_with(b"--") {
+                // Closing delimiter. Whatever comes after it (the epilogue) is ignored.
+                self.buffer.clear();
+                return Ok(false);
+            }
+            if let Some(i) = find(&self.buffer, b"\r\n") {
+                // The delimiter can be followed by (linear) whitespace before the line break.
+                if !self.buffer[..i].iter().all(|b| *b == b' ' || *b == b'\t') {
+                    return Err(
+                        MalformedMultipartBody::new("a boundary is followed by garbage").into(),
+                    );
+                }
+                self.buffer.advance(i + 2);
+                return Ok(true);
+            }
+            if self.buffer.len() > MAX_FIELD_HEADERS_SIZE {
+                return Err(
+                    MalformedMultipartBody::new("a boundary is followed by garbage").into(),
+                );
+            }
+            if !self.fill().await? {
+                return Err(MalformedMultipartBody::new("the closing boundary is missing").into());
+            }
+        }
+    }
+
+    async fn parse_field_headers(&mut self) -> Result<HeaderMap, MultipartError> {
+        let header_block = loop {
+            if self.buffer.starts_with(b"\r\n") {
+                self.buffer.advance(2);
+                break Bytes::new();
+            }
+            if let Some(i) = find(&self.buffer, b"\r\n\r\n") {
+                let block = self.buffer.split_to(i).freeze();
+                self.buffer.advance(4);
+                break block;
+            }
+            if self.buffer.len() > MAX_FIELD_HEADERS_SIZE {
+                return Err(
+                    MalformedMultipartBody::new("the headers of a field are too large").into(),
+                );
+            }
+            if !self.fill().await? {
+                return Err(
+                    MalformedMultipartBody::new("the headers of a field are incomplete").into(),
+                );
+            }
+        };
+
+        let mut headers = HeaderMap::new();
+        for line in header_block.split(|b| *b == b'\n') {
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            if line.is_empty() {
+                continue;
+            }
+            let Some(colon) = line.iter().position(|b| *b == b':') else {
+                return Err(MalformedMultipartBody::new("a field header is invalid").into());
+            };
+            let name = HeaderName::from_bytes(line[..colon].trim_ascii());
+            let value = HeaderValue::from_bytes(line[colon + 1..].trim_ascii());
+            let (Ok(name), Ok(value)) = (name, value) else {
+                return Err(MalformedMultipartBody::new("a field header is invalid").into());
+            };
+            headers.append(name, value);
+        }
+        Ok(headers)
+    }
+
+    /// Read the next chunk of the current field.
+    ///
+    /// It returns `None` when the content of the current field has been fully consumed.
+    async fn read_chunk(&mut self) -> Result<Option<Bytes>, MultipartError> {
+        if self.state != State::Content {
+            return Ok(None);
+        }
+        loop {
+            if let Some(i) = find(&self.buffer, &self.delimiter) {
+                if i > 0 {
+                    return Ok(Some(self.buffer.split_to(i).freeze()));
+                }
+                self.buffer.advance(self.delimiter.len());
+                self.state = State::Delimiter;
+                return Ok(None);
+            }
+            // A delimiter can't start before this point, or we'd have found it.
+            let safe = self.buffer.len().saturating_sub(self.delimiter.len() - 1);
+            if safe > 0 {
+                return Ok(Some(self.buffer.split_to(safe).freeze()));
+            }
+            if !self.fill().await? {
+                return Err(MalformedMultipartBody::new("the closing boundary is missing").into());
+            }
+        }
+    }
+
+    /// Pull more data from the underlying body into the buffer.
+    ///
+    /// It returns `false` if the body has been fully consumed.
+    async fn fill(&mut self) -> Result<bool, MultipartError> {
+        match self.body.next().await {
+            Some(chunk) => {
+                self.buffer.extend_from_slice(&chunk?);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+/// A field of a `multipart/form-data` request body.
+///
+/// Check out [`MultipartBody`] for more details.
+pub struct MultipartField<'a> {
+    name: String,
+    file_name: Option<String>,
+    headers: HeaderMap,
+    max_size: ByteUnit,
+    n_read: u64,
+    body: &'a mut MultipartBody,
+}
+
+impl std::fmt::Debug for MultipartField<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultipartField")
+            .field("name", &self.name)
+            .field("file_name", &self.file_name)
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MultipartField<'_> {
+    /// The name of the field, as specified in its `Content-Disposition` header.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The name of the uploaded file, as specified in the `Content-Disposition` header.
+    ///
+    /// It's `None` if the field isn't a file.
+    ///
+    /// # Security
+    ///
+    /// The file name is chosen by the client: don't use it as a path on your filesystem
+    /// without sanitizing it first.
+    pub fn file_name(&self) -> Option<&str> {
+        self.file_name.as_deref()
+    }
+
+    /// The value of the `Content-Type` header for this field, if any.
+    pub fn content_type(&self) -> Option<&str> {
+        self.headers.get(CONTENT_TYPE)?.to_str().ok()
+    }
+
+    /// All the headers attached to this field.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Read the next chunk of bytes from the content of this field.
+    ///
+    /// It returns `Ok(None)` when the whole field has been consumed.
+    /// It fails if the field exceeds the limits specified via [`MultipartLimits`].
+    pub async fn chunk(&mut self) -> Result<Option<Bytes>, MultipartError> {
+        let Some(chunk) = self.body.read_chunk().await? else {
+            return Ok(None);
+        };
+        self.n_read += chunk.len() as u64;
+        if self.n_read > self.max_size {
+            return Err(FieldSizeLimitExceeded {
+                field_name: self.name.clone(),
+                max_size: self.max_size,
+            }
+            .into());
+        }
+        Ok(Some(chunk))
+    }
+
+    /// Buffer the whole content of this field in memory.
+    pub async fn bytes(mut self) -> Result<Bytes, MultipartError> {
+        let mut buffer = BytesMut::new();
+        while let Some(chunk) = self.chunk().await? {
+            buffer.extend_from_slice(&chunk);
+        }
+        Ok(buffer.freeze())
+    }
+
+    /// Buffer the whole content of this field in memory, as UTF-8 text.
+    pub async fn text(self) -> Result<String, MultipartError> {
+        let bytes = self.bytes().await?;
+        String::from_utf8(bytes.into()).map_err(|_| {
+            MalformedMultipartBody::new("the content of a text field is not valid UTF-8").into()
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+/// Upper limits on the size of the fields in a `multipart/form-data` request body.
+///
+/// They're enforced by [`MultipartBody`], on top of the overall [`BodySizeLimit`].
+///
+/// # Custom limits
+///
+/// Register a custom constructor for `MultipartLimits` in your blueprint to override the defaults:
+///
+/// ```rust
+/// use pavex::blueprint::Blueprint;
+/// use pavex::f;
+/// use pavex::request::body::MultipartLimits;
+/// use pavex::unit::ToByteUnit;
+///
+/// pub fn multipart_limits() -> MultipartLimits {
+///     MultipartLimits {
+///         max_field_size: 16.kilobytes(),
+///         max_file_size: 10.megabytes(),
+///     }
+/// }
+///
+/// pub fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     bp.request_scoped(f!(crate::multipart_limits));
+///     // [...]
+///     bp
+/// }
+/// ```
+///
+/// Keep in mind that the [`BodySizeLimit`] still applies: raise it as well
+/// if you want to accept files larger than 2 MBs.
+pub struct MultipartLimits {
+    /// The maximum size of a field that isn't a file, in bytes.
+    pub max_field_size: ByteUnit,
+    /// The maximum size of a file field, in bytes.
+    pub max_file_size: ByteUnit,
+}
+
+impl MultipartLimits {
+    /// Create a new [`MultipartLimits`] using the default limits:
+    /// 64 KBs for regular fields and 2 MBs for files.
+    #[request_scoped]
+    pub fn new() -> MultipartLimits {
+        Self::default()
+    }
+}
+
+impl Default for MultipartLimits {
+    fn default() -> Self {
+        Self {
+            max_field_size: 64.kilobytes(),
+            max_file_size: 2.megabytes(),
+        }
+    }
+}
+
+/// Extract the boundary from the `Content-Type` header, after checking that
+/// it's set to `multipart/form-data`.
+fn parse_boundary(headers: &HeaderMap) -> Result<String, ExtractMultipartBodyError> {
+    let Some(content_type) = headers.get(CONTENT_TYPE) else {
+        return Err(MissingMultipartContentType.into());
+    };
+    let Ok(content_type) = content_type.to_str() else {
+        return Err(MissingMultipartContentType.into());
+    };
+    let mismatch = || MultipartContentTypeMismatch {
+        actual: content_type.to_string(),
+    };
+    let Ok(mime) = content_type.parse::<mime::Mime>() else {
+        return Err(mismatch().into());
+    };
+    if mime.type_() != mime::MULTIPART || mime.subtype() != mime::FORM_DATA {
+        return Err(mismatch().into());
+    }
+    match mime.get_param(mime::BOUNDARY) {
+        // RFC 2046 caps boundaries at 70 characters.
+        Some(boundary) if !boundary.as_str().is_empty() && boundary.as_str().len() <= 70 => {
+            Ok(boundary.as_str().to_owned())
+        }
+        _ => Err(MissingMultipartBoundary.into()),
+    }
+}
+
+/// Extract the field name and the (optional) file name from the `Content-Disposition` header.
+fn parse_content_disposition(
+    headers: &HeaderMap,
+) -> Result<(String, Option<String>), MultipartError> {
+    let invalid = || MalformedMultipartBody::new("a field has an invalid `Content-Disposition`");
+    let Some(value) = headers.get(CONTENT_DISPOSITION) else {
+        return Err(MalformedMultipartBody::new(
+            "a field is missing the `Content-Disposition` header",
+        )
+        .into());
+    };
+    // Browsers send non-ASCII file names as raw UTF-8 bytes.
+    let value = std::str::from_utf8(value.as_bytes()).map_err(|_| invalid())?;
+    let (disposition, mut params) = value.split_once(';').unwrap_or((value, ""));
+    if !disposition.trim().eq_ignore_ascii_case("form-data") {
+        return Err(invalid().into());
+    }
+
+    let mut name = None;
+    let mut file_name = None;
+    loop {
+        params = params.trim_start();
+        if params.is_empty() {
+            break;
+        }
+        let (key, rest) = params.split_once('=').ok_or_else(invalid)?;
+        let rest = rest.trim_start();
+        let (value, rest) = if let Some(quoted) = rest.strip_prefix('"') {
+            parse_quoted_string(quoted).ok_or_else(invalid)?
+        } else {
+            let end = rest.find(';').unwrap_or(rest.len());
+            (rest[..end].trim_end().to_owned(), &rest[end..])
+        };
+        match key.trim().to_ascii_lowercase().as_str() {
+            "name" => name = Some(value),
+            "filename" => file_name = Some(value),
+            _ => {}
+        }
+        params = rest.trim_start();
+        params = match params.strip_prefix(';') {
+            Some(params) => params,
+            None if params.is_empty() => params,
+            None => return Err(invalid().into()),
+        };
+    }
+    let name = name.ok_or_else(invalid)?;
+    Ok((name, file_name))
+}
+
+/// Parse a quoted string, starting right after the opening quote.
+///
+/// It returns the unescaped value and whatever comes after the closing quote.
+fn parse_quoted_string(s: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, &s[i + 1..])),
+            '\\' => value.push(chars.next()?.1),
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use http_body_util::BodyExt;
+    use ubyte::{ByteUnit, ToByteUnit};
+
+    use super::{MultipartBody, MultipartLimits, StreamingBody};
+
+    const BODY: &str = "preamble\r\n\
+        --XyZ\r\n\
+        Content-Disposition: form-data; name=\"title\"\r\n\
+        \r\n\
+        Holiday pics\r\n\
+        --XyZ\r\n\
+        Content-Disposition: form-data; name=\"file\"; filename=\"sea \\\"view\\\".png\"\r\n\
+        Content-Type: image/png\r\n\
+        \r\n\
+        PNG\r\n--not-a-boundary\r\n\
+        --XyZ--\r\n\
+        epilogue";
+
+    /// Split the body into chunks of the given size, to exercise boundaries
+    /// that straddle multiple frames.
+    fn multipart(
+        body: &'static str,
+        chunk_size: usize,
+        limits: MultipartLimits,
+        max_size: Option<ByteUnit>,
+    ) -> MultipartBody {
+        let chunks: Vec<_> = body
+            .as_bytes()
+            .chunks(chunk_size)
+            .map(|c| Ok::<_, std::convert::Infallible>(http_body::Frame::data(Bytes::from(c))))
+            .collect();
+        let body = http_body_util::StreamBody::new(futures_util::stream::iter(chunks))
+            .map_err(Into::into)
+            .boxed_unsync();
+        let body = StreamingBody::new(body, max_size, None).unwrap();
+        MultipartBody::new(body, "XyZ", limits)
+    }
+
+    #[tokio::test]
+    async fn fields_are_parsed() {
+        for chunk_size in [1, 2, 3, 7, 64, BODY.len()] {
+            let mut body = multipart(BODY, chunk_size, MultipartLimits::default(), None);
+
+            let field = body.next_field().await.unwrap().unwrap();
+            assert_eq!(field.name(), "title");
+            assert_eq!(field.file_name(), None);
+            assert_eq!(field.content_type(), None);
+            assert_eq!(field.text().await.unwrap(), "Holiday pics");
+
+            let field = body.next_field().await.unwrap().unwrap();
+            assert_eq!(field.name(), "file");
+            assert_eq!(field.file_name(), Some("sea \"view\".png"));
+            assert_eq!(field.content_type(), Some("image/png"));
+            assert_eq!(
+                field.bytes().await.unwrap(),
+                &b"PNG\r\n--not-a-boundary"[..]
+            );
+
+            assert!(body.next_field().await.unwrap().is_none());
+            assert!(body.next_field().await.unwrap().is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn unread_fields_are_skipped() {
+        let mut body = multipart(BODY, 5, MultipartLimits::default(), None);
+        assert_eq!(body.next_field().await.unwrap().unwrap().name(), "title");
+        assert_eq!(body.next_field().await.unwrap().unwrap().name(), "file");
+        assert!(body.next_field().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn field_limits_are_enforced() {
+        let limits = MultipartLimits {
+            max_field_size: 4.bytes(),
+            max_file_size: 1.kilobytes(),
+        };
+        let mut body = multipart(BODY, 64, limits, None);
+        let field = body.next_field().await.unwrap().unwrap();
+        let err = field.text().await.unwrap_err();
+        insta::assert_snapshot!(err, @"The `title` field is larger than the maximum size limit enforced by this server.");
+
+        let limits = MultipartLimits {
+            max_field_size: 1.kilobytes(),
+            max_file_size: 4.bytes(),
+        };
+        let mut body = multipart(BODY, 64, limits, None);
+        let field = body.next_field().await.unwrap().unwrap();
+        assert_eq!(field.text().await.unwrap(), "Holiday pics");
+        let field = body.next_field().await.unwrap().unwrap();
+        let err = field.bytes().await.unwrap_err();
+        insta::assert_snapshot!(err, @"The `file` field is larger than the maximum size limit enforced by this server.");
+    }
+
+    #[tokio::test]
+    async fn body_limit_is_enforced() {
+        let mut body = multipart(BODY, 16, MultipartLimits::default(), Some(32.bytes()));
+        let err = async {
+            while let Some(field) = body.next_field().await? {
+                field.bytes().await?;
+            }
+            Ok::<_, super::MultipartError>(())
+        }
+        .await
+        .unwrap_err();
+        insta::assert_snapshot!(err, @"The request body is larger than the maximum size limit enforced by this server.");
+    }
+
+    #[tokio::test]
+    async fn truncated_body_is_rejected() {
+        let mut body = multipart(
+            "--XyZ\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\nhello",
+            4,
+            MultipartLimits::default(),
+            None,
+        );
+        let field = body.next_field().await.unwrap().unwrap();
+        let err = field.text().await.unwrap_err();
+        insta::assert_snapshot!(err, @"The request body is not a valid `multipart/form-data` payload: the closing boundary is missing.");
+    }
+
+    #[tokio::test]
+    async fn content_disposition_is_required() {
+        let mut body = multipart(
+            "--XyZ\r\nContent-Type: text/plain\r\n\r\nhello\r\n--XyZ--",
+            64,
+            MultipartLimits::default(),
+            None,
+        );
+        let err = body.next_field().await.unwrap_err();
+        insta::assert_snapshot!(err, @"The request body is not a valid `multipart/form-data` payload: a field is missing the `Content-Disposition` header.");
+    }
+
+    #[test]
+    fn content_type_is_checked() {
+        let mut headers = http::HeaderMap::new();
+        let err = super::parse_boundary(&headers).unwrap_err();
+        insta::assert_snapshot!(err, @"The `Content-Type` header is missing. This endpoint expects requests with a `Content-Type` header set to `multipart/form-data`");
+
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            "application/json".parse().unwrap(),
+        );
+        let err = super::parse_boundary(&headers).unwrap_err();
+        insta::assert_snapshot!(err, @"The `Content-Type` header was set to `application/json`. This endpoint expects requests with a `Content-Type` header set to `multipart/form-data`");
+
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            "multipart/form-data".parse().unwrap(),
+        );
+        let err = super::parse_boundary(&headers).unwrap_err();
+        insta::assert_snapshot!(err, @"The `Content-Type` header is missing the `boundary` parameter, which is required for `multipart/form-data` requests");
+
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            "multipart/form-data; boundary=\"a b\"".parse().unwrap(),
+        );
+        assert_eq!(super::parse_boundary(&headers).unwrap(), "a b");
+    }
+}
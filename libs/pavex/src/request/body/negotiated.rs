@@ -0,0 +1,200 @@
+use std::marker::PhantomData;
+
+use http::HeaderMap;
+use pavex_macros::request_scoped;
+
+use crate::request::RequestHead;
+use crate::serialization::DeserializeFormats;
+
+use super::buffered_body::BufferedBody;
+use super::errors::{
+    ExtractNegotiatedBodyError, MissingNegotiatedContentType, NegotiatedContentTypeMismatch,
+    NegotiatedDeserializationError,
+};
+
+/// Parse the body of an incoming request using the format selected by its `Content-Type`.
+///
+/// `F` is the list of formats supported by the endpoint—either a single format
+/// (e.g. [`Json`](crate::response::body::Json)) or a tuple of formats (e.g. `(Json, MsgPack)`).
+/// The request body is deserialized using the first format in the list that supports
+/// the `Content-Type` of the request.
+/// Requests that don't match any format are rejected with a `415 Unsupported Media Type`.
+///
+/// Check out [`DeserializeFormat`](crate::serialization::DeserializeFormat) if you want to
+/// plug in your own formats.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::request::Negotiated;
+/// use pavex::response::body::Json;
+///
+/// #[derive(serde::Deserialize)]
+/// pub struct NewOrder {
+///     product_id: u64,
+///     quantity: u32,
+/// }
+///
+/// pub fn create_order(body: &Negotiated<NewOrder, Json>) -> String {
+///     format!("Ordered {} unit(s) of product {}", body.0.quantity, body.0.product_id)
+/// }
+/// ```
+///
+/// # Sibling extractors
+///
+/// Use [`ResponseFormat`](crate::response::body::ResponseFormat) to pick the format of
+/// the response body based on the `Accept` header.
+pub struct Negotiated<T, F>(
+    /// The deserialized request body.
+    pub T,
+    PhantomData<fn() -> F>,
+);
+
+impl<T: std::fmt::Debug, F> std::fmt::Debug for Negotiated<T, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Negotiated").field(&self.0).finish()
+    }
+}
+
+impl<T, F> Negotiated<T, F> {
+    /// The default constructor for [`Negotiated`].
+    ///
+    /// The extraction can fail for a number of reasons:
+    ///
+    /// - the `Content-Type` is missing
+    /// - the `Content-Type` header doesn't match any of the formats in `F`
+    /// - the request body can't be deserialized using the selected format
+    ///
+    /// In all of the above cases, an [`ExtractNegotiatedBodyError`] is returned.
+    #[request_scoped(
+        error_handler = "crate::request::body::errors::ExtractNegotiatedBodyError::into_response"
+    )]
+    pub fn extract<'head, 'body>(
+        request_head: &'head RequestHead,
+        buffered_body: &'body BufferedBody,
+    ) -> Result<Self, ExtractNegotiatedBodyError>
+    where
+        T: serde::Deserialize<'body>,
+        F: DeserializeFormats,
+    {
+        let media_type = content_type::<F>(&request_head.headers)?;
+        let Some(outcome) = F::deserialize(&media_type, buffered_body.bytes.as_ref()) else {
+            return Err(mismatch::<F>(media_type.to_string()).into());
+        };
+        let body = outcome.map_err(|source| NegotiatedDeserializationError {
+            media_type: media_type.essence_str().to_owned(),
+            source,
+        })?;
+        Ok(Negotiated(body, PhantomData))
+    }
+}
+
+fn content_type<F: DeserializeFormats>(
+    headers: &HeaderMap,
+) -> Result<mime::Mime, ExtractNegotiatedBodyError> {
+    let missing = || MissingNegotiatedContentType {
+        supported: supported::<F>(),
+    };
+    let Some(content_type) = headers.get(http::header::CONTENT_TYPE) else {
+        return Err(missing().into());
+    };
+    let Ok(content_type) = content_type.to_str() else {
+        return Err(missing().into());
+    };
+    content_type
+        .parse()
+        .map_err(|_| mismatch::<F>(content_type.to_owned()).into())
+}
+
+fn mismatch<F: DeserializeFormats>(actual: String) -> NegotiatedContentTypeMismatch {
+    NegotiatedContentTypeMismatch {
+        actual,
+        supported: supported::<F>(),
+    }
+}
+
+fn supported<F: DeserializeFormats>() -> Vec<String> {
+    F::media_types().iter().map(|m| m.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use http::HeaderMap;
+
+    use super::*;
+    use crate::response::body::Json;
+    use crate::serialization::{DeserializeFormat, Format, FormatError};
+
+    /// A toy format, to exercise dispatching.
+    struct Text;
+
+    impl Format for Text {
+        fn media_type() -> mime::Mime {
+            mime::TEXT_PLAIN
+        }
+    }
+
+    impl DeserializeFormat for Text {
+        fn deserialize<'de, T>(bytes: &'de [u8]) -> Result<T, FormatError>
+        where
+            T: serde::Deserialize<'de>,
+        {
+            let s = std::str::from_utf8(bytes)?;
+            Ok(T::deserialize(serde::de::value::StrDeserializer::<
+                serde::de::value::Error,
+            >::new(s))?)
+        }
+    }
+
+    fn extract<F: DeserializeFormats>(
+        content_type: Option<&str>,
+        body: &'static str,
+    ) -> Result<String, ExtractNegotiatedBodyError> {
+        let mut headers = HeaderMap::new();
+        if let Some(content_type) = content_type {
+            headers.insert(http::header::CONTENT_TYPE, content_type.parse().unwrap());
+        }
+        let request_head = RequestHead {
+            method: http::Method::POST,
+            target: "/".parse().unwrap(),
+            version: http::Version::HTTP_11,
+            headers,
+        };
+        let body = BufferedBody {
+            bytes: Bytes::from_static(body.as_bytes()),
+        };
+        Negotiated::<String, F>::extract(&request_head, &body).map(|b| b.0)
+    }
+
+    #[test]
+    fn the_format_is_selected_by_content_type() {
+        let json = extract::<(Json, Text)>(Some("application/json"), r#""hello""#).unwrap();
+        assert_eq!(json, "hello");
+        let text = extract::<(Json, Text)>(Some("text/plain; charset=utf-8"), "hello").unwrap();
+        assert_eq!(text, "hello");
+    }
+
+    #[test]
+    fn unsupported_content_types_are_rejected() {
+        let err = extract::<(Json, Text)>(Some("application/xml"), "<hello/>").unwrap_err();
+        insta::assert_snapshot!(err, @"The `Content-Type` header was set to `application/xml`. This endpoint expects requests with a `Content-Type` header set to one of: `application/json`, `text/plain`");
+        assert_eq!(
+            err.into_response().status(),
+            http::StatusCode::UNSUPPORTED_MEDIA_TYPE
+        );
+    }
+
+    #[test]
+    fn missing_content_type() {
+        let err = extract::<Json>(None, "{}").unwrap_err();
+        insta::assert_snapshot!(err, @"The `Content-Type` header is missing. This endpoint expects requests with a `Content-Type` header set to one of: `application/json`");
+    }
+
+    #[test]
+    fn deserialization_errors_are_reported() {
+        let err = extract::<Json>(Some("application/json"), "42").unwrap_err();
+        insta::assert_snapshot!(err, @"Failed to deserialize the body as `application/json`.\ninvalid type: integer `42`, expected a string at line 1 column 2");
+        assert_eq!(err.into_response().status(), http::StatusCode::BAD_REQUEST);
+    }
+}
@@ -1,7 +1,9 @@
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
-use http_body::{Body, Frame};
+use bytes::Bytes;
+use http_body::{Body, Frame, SizeHint};
+use http_body_util::Full;
 use hyper::body::Incoming;
 use pin_project_lite::pin_project;
 
@@ -18,11 +20,22 @@ pin_project! {
     /// Check out [the guide](https://pavex.dev/docs/guide/request_data/wire_data/)
     /// for a thorough introduction to `RawIncomingBody` and guidance on when to use it.
     pub struct RawIncomingBody {
-        #[pin] inner: Incoming,
+        #[pin] inner: Inner,
     }
 }
 
-// We just delegate to the underlying `Incoming` `Body` implementation.
+pin_project! {
+    #[derive(Debug)]
+    #[project = InnerProj]
+    enum Inner {
+        Incoming { #[pin] body: Incoming },
+        // A body that's already in memory—e.g. when the request is built by
+        // a test client, rather than received over the network.
+        InMemory { #[pin] body: Full<Bytes> },
+    }
+}
+
+// We just delegate to the underlying `Body` implementation.
 impl Body for RawIncomingBody {
     type Data = <Incoming as Body>::Data;
     type Error = <Incoming as Body>::Error;
@@ -31,12 +44,45 @@ impl Body for RawIncomingBody {
         self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
-        self.project().inner.poll_frame(cx)
+        match self.project().inner.project() {
+            InnerProj::Incoming { body } => body.poll_frame(cx),
+            InnerProj::InMemory { body } => body
+                .poll_frame(cx)
+                .map(|frame| frame.map(|frame| frame.map_err(|e| match e {}))),
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        match &self.inner {
+            Inner::Incoming { body } => body.is_end_stream(),
+            Inner::InMemory { body } => body.is_end_stream(),
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match &self.inner {
+            Inner::Incoming { body } => body.size_hint(),
+            Inner::InMemory { body } => body.size_hint(),
+        }
     }
 }
 
 impl From<Incoming> for RawIncomingBody {
-    fn from(inner: Incoming) -> Self {
-        Self { inner }
+    fn from(body: Incoming) -> Self {
+        Self {
+            inner: Inner::Incoming { body },
+        }
+    }
+}
+
+/// Build a request body from an in-memory buffer—e.g. to test your request handlers
+/// without going through the network.
+impl From<Bytes> for RawIncomingBody {
+    fn from(body: Bytes) -> Self {
+        Self {
+            inner: Inner::InMemory {
+                body: Full::new(body),
+            },
+        }
     }
 }
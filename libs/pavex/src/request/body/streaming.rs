@@ -0,0 +1,524 @@
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use bytes::{Buf, Bytes, BytesMut};
+use futures_util::stream::FusedStream;
+use futures_util::{Stream, StreamExt};
+use http::header::CONTENT_LENGTH;
+use http_body::Body;
+use http_body_util::BodyExt;
+use http_body_util::combinators::UnsyncBoxBody;
+use pavex_macros::request_scoped;
+use serde::de::DeserializeOwned;
+use ubyte::ByteUnit;
+
+use crate::request::RequestHead;
+
+use super::decompression::{DecodeError, Decoder};
+use super::errors::{
+    DecompressionError, ExtractStreamingBodyError, NdJsonDeserializationError, NdJsonError,
+    SizeLimitExceeded, StreamingBodyError, UnexpectedBufferError,
+};
+use super::{BodySizeLimit, RawIncomingBody};
+
+pub(super) type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+#[doc(alias = "Stream")]
+#[doc(alias = "BodyStream")]
+/// Consume the body of an incoming request as a stream of chunks, without buffering it.
+///
+/// # Guide
+///
+/// `StreamingBody` is a good fit when the body is too large to be held in memory, or when you
+/// want to start processing it before it has been fully received—e.g. to ingest
+/// newline-delimited JSON (see [`StreamingBody::ndjson`]) or to proxy it to another service.
+///
+/// Since it consumes the underlying byte stream, you must take `StreamingBody`
+/// by value in your request handler.
+///
+/// # Security
+///
+/// `StreamingBody` enforces the [`BodySizeLimit`], just like [`BufferedBody`](super::BufferedBody).
+/// Requests that declare a `Content-Length` larger than the limit are rejected upfront.
+/// Otherwise the limit is checked incrementally, as chunks are received: the stream yields a
+/// [`StreamingBodyError::SizeLimitExceeded`] error as soon as the limit is crossed.
+///
+/// # Decompression
+///
+/// If the request specifies a `Content-Encoding`, the body is decompressed on the fly:
+/// the stream yields decompressed chunks and the [`BodySizeLimit`] applies to the
+/// decompressed size.
+/// Each encoding is supported behind a cargo feature: `decompression_gzip`,
+/// `decompression_deflate`, `decompression_br` and `decompression_zstd`
+/// (or `decompression`, to enable them all).
+/// Requests that use an encoding that isn't supported are rejected with a
+/// `415 Unsupported Media Type`.
+///
+/// # Example
+///
+/// ```rust
+/// use futures_util::StreamExt;
+/// use pavex::http::StatusCode;
+/// use pavex::request::body::StreamingBody;
+/// use pavex::request::body::errors::StreamingBodyError;
+///
+/// pub async fn ingest(mut body: StreamingBody) -> Result<StatusCode, StreamingBodyError> {
+///     let mut n_bytes = 0;
+///     while let Some(chunk) = body.next().await {
+///         n_bytes += chunk?.len();
+///     }
+///     println!("The incoming request contains {n_bytes} bytes");
+///     Ok(StatusCode::OK)
+/// }
+/// ```
+pub struct StreamingBody {
+    body: UnsyncBoxBody<Bytes, BoxError>,
+    decoder: Option<Decoder>,
+    size_limit: Option<SizeLimitTracker>,
+    /// Set to `true` once the stream has yielded an error or has run out of chunks.
+    is_terminated: bool,
+}
+
+struct SizeLimitTracker {
+    max_size: ByteUnit,
+    content_length: Option<usize>,
+    n_read: u64,
+}
+
+impl std::fmt::Debug for StreamingBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamingBody").finish_non_exhaustive()
+    }
+}
+
+impl StreamingBody {
+    /// The default constructor for [`StreamingBody`].
+    ///
+    /// It fails with an [`ExtractStreamingBodyError`] if the `Content-Length` header
+    /// exceeds the [`BodySizeLimit`] or if the body is encoded using an unsupported
+    /// `Content-Encoding`.
+    #[request_scoped(
+        error_handler = "crate::request::body::errors::ExtractStreamingBodyError::into_response"
+    )]
+    pub fn extract(
+        request_head: &RequestHead,
+        body: RawIncomingBody,
+        body_size_limit: BodySizeLimit,
+    ) -> Result<Self, ExtractStreamingBodyError> {
+        Self::from_parts(
+            request_head,
+            body.map_err(Into::into).boxed_unsync(),
+            body_size_limit,
+        )
+    }
+
+    pub(super) fn from_parts(
+        request_head: &RequestHead,
+        body: UnsyncBoxBody<Bytes, BoxError>,
+        body_size_limit: BodySizeLimit,
+    ) -> Result<Self, ExtractStreamingBodyError> {
+        let max_size = match body_size_limit {
+            BodySizeLimit::Enabled { max_size } => Some(max_size),
+            BodySizeLimit::Disabled => None,
+        };
+        let decoder = Decoder::from_headers(&request_head.headers, max_size)?;
+        // The `Content-Length` of an encoded body tells us nothing about its decompressed size.
+        let content_length = match decoder {
+            Some(_) => None,
+            None => request_head
+                .headers
+                .get(CONTENT_LENGTH)
+                .and_then(|value| value.to_str().ok()?.parse::<usize>().ok()),
+        };
+        Ok(Self::new(body, max_size, content_length)?.with_decoder(decoder))
+    }
+
+    /// Wrap a body, enforcing `max_size` (if any) on it.
+    pub(super) fn new(
+        body: UnsyncBoxBody<Bytes, BoxError>,
+        max_size: Option<ByteUnit>,
+        content_length: Option<usize>,
+    ) -> Result<Self, SizeLimitExceeded> {
+        let size_limit = match max_size {
+            Some(max_size) => {
+                // Short-circuit if the client told us, upfront, that the body is too large.
+                if content_length.is_some_and(|len| len > max_size) {
+                    return Err(SizeLimitExceeded {
+                        max_size,
+                        content_length,
+                    });
+                }
+                Some(SizeLimitTracker {
+                    max_size,
+                    content_length,
+                    n_read: 0,
+                })
+            }
+            None => None,
+        };
+        Ok(Self {
+            body,
+            decoder: None,
+            size_limit,
+            is_terminated: false,
+        })
+    }
+
+    fn with_decoder(mut self, decoder: Option<Decoder>) -> Self {
+        self.decoder = decoder;
+        self
+    }
+
+    fn size_limit_exceeded(&self) -> StreamingBodyError {
+        let tracker = self
+            .size_limit
+            .as_ref()
+            .expect("The size limit can only be exceeded if there is one");
+        SizeLimitExceeded {
+            max_size: tracker.max_size,
+            content_length: tracker.content_length,
+        }
+        .into()
+    }
+
+    /// Deserialize the body as [newline-delimited JSON](https://github.com/ndjson/ndjson-spec),
+    /// one item at a time.
+    ///
+    /// Each non-blank line is deserialized as an instance of `T`.
+    /// A line that fails to deserialize doesn't terminate the stream: the error is yielded
+    /// and you can decide whether to skip it or bail.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use futures_util::StreamExt;
+    /// use pavex::http::StatusCode;
+    /// use pavex::request::body::StreamingBody;
+    /// use pavex::request::body::errors::NdJsonError;
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// pub struct Event {
+    ///     name: String,
+    /// }
+    ///
+    /// pub async fn ingest(body: StreamingBody) -> Result<StatusCode, NdJsonError> {
+    ///     let mut events = body.ndjson::<Event>();
+    ///     while let Some(event) = events.next().await {
+    ///         let event = event?;
+    ///         println!("Received `{}`", event.name);
+    ///     }
+    ///     Ok(StatusCode::OK)
+    /// }
+    /// ```
+    pub fn ndjson<T>(self) -> NdJsonStream<T>
+    where
+        T: DeserializeOwned,
+    {
+        NdJsonStream {
+            body: self,
+            buffer: BytesMut::new(),
+            n_scanned: 0,
+            line_number: 0,
+            is_body_over: false,
+            _item: PhantomData,
+        }
+    }
+}
+
+impl Stream for StreamingBody {
+    type Item = Result<Bytes, StreamingBodyError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.is_terminated {
+            return Poll::Ready(None);
+        }
+        let item = ready!(self.poll_chunk(cx));
+        // The stream is over after the first error: the state of the decoder (or of the
+        // underlying body) can't be trusted anymore.
+        if !matches!(item, Some(Ok(_))) {
+            self.is_terminated = true;
+        }
+        Poll::Ready(item)
+    }
+}
+
+impl FusedStream for StreamingBody {
+    fn is_terminated(&self) -> bool {
+        self.is_terminated
+    }
+}
+
+impl StreamingBody {
+    fn poll_chunk(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Bytes, StreamingBodyError>>> {
+        loop {
+            let Some(frame) = ready!(Pin::new(&mut self.body).poll_frame(cx)) else {
+                // Flush whatever is left in the decoder, if any.
+                let Some(mut decoder) = self.decoder.take() else {
+                    return Poll::Ready(None);
+                };
+                let data = match decoder.finish() {
+                    Ok(data) => data,
+                    Err(e) => return Poll::Ready(Some(Err(self.decode_error(e)))),
+                };
+                if data.is_empty() {
+                    return Poll::Ready(None);
+                }
+                return Poll::Ready(Some(self.track(data)));
+            };
+            let frame = match frame {
+                Ok(frame) => frame,
+                Err(e) => {
+                    return Poll::Ready(Some(Err(UnexpectedBufferError { source: e }.into())));
+                }
+            };
+            // We don't care about trailers.
+            let Ok(mut data) = frame.into_data() else {
+                continue;
+            };
+            if let Some(decoder) = &mut self.decoder {
+                data = match decoder.decode(&data) {
+                    Ok(data) => data,
+                    Err(e) => return Poll::Ready(Some(Err(self.decode_error(e)))),
+                };
+                // The decoder needs more input before it can produce any output.
+                if data.is_empty() {
+                    continue;
+                }
+            }
+            return Poll::Ready(Some(self.track(data)));
+        }
+    }
+
+    /// Check that the new chunk doesn't push us over the size limit.
+    fn track(&mut self, data: Bytes) -> Result<Bytes, StreamingBodyError> {
+        if let Some(tracker) = &mut self.size_limit {
+            tracker.n_read += data.len() as u64;
+            if tracker.n_read > tracker.max_size {
+                return Err(self.size_limit_exceeded());
+            }
+        }
+        Ok(data)
+    }
+
+    fn decode_error(&self, e: DecodeError) -> StreamingBodyError {
+        match e {
+            DecodeError::SizeLimitExceeded => self.size_limit_exceeded(),
+            DecodeError::Invalid(e) => DecompressionError { source: e }.into(),
+        }
+    }
+}
+
+/// A stream of items deserialized from a newline-delimited JSON body.
+///
+/// Built via [`StreamingBody::ndjson`].
+pub struct NdJsonStream<T> {
+    body: StreamingBody,
+    /// Bytes that haven't been deserialized yet.
+    buffer: BytesMut,
+    /// The number of bytes at the beginning of `buffer` that we know don't contain a line break.
+    n_scanned: usize,
+    line_number: usize,
+    is_body_over: bool,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<T> std::fmt::Debug for NdJsonStream<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NdJsonStream")
+            .field("line_number", &self.line_number)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> NdJsonStream<T>
+where
+    T: DeserializeOwned,
+{
+    fn parse_line(&mut self, line: &[u8]) -> Option<Result<T, NdJsonError>> {
+        self.line_number += 1;
+        let line = line.trim_ascii();
+        if line.is_empty() {
+            return None;
+        }
+        let item = serde_json::from_slice(line).map_err(|e| NdJsonDeserializationError {
+            line: self.line_number,
+            source: e,
+        });
+        Some(item.map_err(Into::into))
+    }
+}
+
+impl<T> Stream for NdJsonStream<T>
+where
+    T: DeserializeOwned,
+{
+    type Item = Result<T, NdJsonError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if let Some(i) = this.buffer[this.n_scanned..]
+                .iter()
+                .position(|b| *b == b'\n')
+            {
+                let line = this.buffer.split_to(this.n_scanned + i);
+                this.buffer.advance(1);
+                this.n_scanned = 0;
+                match this.parse_line(&line) {
+                    Some(item) => return Poll::Ready(Some(item)),
+                    None => continue,
+                }
+            }
+            this.n_scanned = this.buffer.len();
+            if this.is_body_over {
+                // The last line doesn't have to be terminated by a line break.
+                let line = std::mem::take(&mut this.buffer);
+                this.n_scanned = 0;
+                if line.is_empty() {
+                    return Poll::Ready(None);
+                }
+                match this.parse_line(&line) {
+                    Some(item) => return Poll::Ready(Some(item)),
+                    None => return Poll::Ready(None),
+                }
+            }
+            match ready!(this.body.poll_next_unpin(cx)) {
+                Some(Ok(chunk)) => this.buffer.extend_from_slice(&chunk),
+                Some(Err(e)) => {
+                    this.is_body_over = true;
+                    this.buffer.clear();
+                    return Poll::Ready(Some(Err(e.into())));
+                }
+                None => this.is_body_over = true,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use futures_util::StreamExt;
+    use http_body_util::BodyExt;
+    use ubyte::{ByteUnit, ToByteUnit};
+
+    use super::StreamingBody;
+
+    /// Split the body into chunks of the given size.
+    fn streaming(
+        body: &'static str,
+        chunk_size: usize,
+        max_size: Option<ByteUnit>,
+    ) -> StreamingBody {
+        let chunks: Vec<_> = body
+            .as_bytes()
+            .chunks(chunk_size)
+            .map(|c| Ok::<_, std::convert::Infallible>(http_body::Frame::data(Bytes::from(c))))
+            .collect();
+        let body = http_body_util::StreamBody::new(futures_util::stream::iter(chunks))
+            .map_err(Into::into)
+            .boxed_unsync();
+        StreamingBody::new(body, max_size, None).unwrap()
+    }
+
+    #[tokio::test]
+    async fn chunks_are_yielded_as_they_come() {
+        let body = streaming("hello world", 4, Some(1.kilobytes()));
+        let chunks: Vec<_> = body.map(Result::unwrap).collect().await;
+        assert_eq!(chunks, ["hell", "o wo", "rld"]);
+    }
+
+    #[tokio::test]
+    async fn size_limit_is_enforced_incrementally() {
+        let mut body = streaming("hello world", 4, Some(6.bytes()));
+        assert_eq!(body.next().await.unwrap().unwrap(), "hell");
+        let err = body.next().await.unwrap().unwrap_err();
+        insta::assert_snapshot!(err, @"The request body is larger than the maximum size limit enforced by this server.");
+        // The stream is over after the first error.
+        assert!(body.next().await.is_none());
+    }
+
+    #[cfg(feature = "decompression_gzip")]
+    #[tokio::test]
+    async fn the_stream_is_over_after_a_decoding_error() {
+        let request_head = crate::request::RequestHead {
+            method: http::Method::POST,
+            target: "/".parse().unwrap(),
+            version: http::Version::HTTP_11,
+            headers: [(http::header::CONTENT_ENCODING, "gzip".parse().unwrap())]
+                .into_iter()
+                .collect(),
+        };
+        let body = streaming("This is not gzip", 4, None).body;
+        let mut body = StreamingBody::from_parts(&request_head, body, Default::default()).unwrap();
+        let err = body.next().await.unwrap().unwrap_err();
+        insta::assert_snapshot!(err, @"Failed to decompress the request body.");
+        // The remaining chunks are not passed through as if they had been decoded.
+        assert!(body.next().await.is_none());
+    }
+
+    #[test]
+    fn content_length_is_checked_upfront() {
+        let body = http_body_util::Empty::new()
+            .map_err(Into::into)
+            .boxed_unsync();
+        let err = StreamingBody::new(body, Some(6.bytes()), Some(7)).unwrap_err();
+        assert_eq!(err.content_length, Some(7));
+    }
+
+    #[test]
+    fn unsupported_encodings_are_rejected() {
+        let mut request_head = crate::request::RequestHead {
+            method: http::Method::POST,
+            target: "/".parse().unwrap(),
+            version: http::Version::HTTP_11,
+            headers: http::HeaderMap::new(),
+        };
+        request_head
+            .headers
+            .insert(http::header::CONTENT_ENCODING, "compress".parse().unwrap());
+        let body = http_body_util::Empty::new()
+            .map_err(Into::into)
+            .boxed_unsync();
+        let err = StreamingBody::from_parts(&request_head, body, Default::default()).unwrap_err();
+        insta::assert_snapshot!(err, @"The request body is encoded using `compress`, which is not supported by this server");
+        assert_eq!(err.into_response().status().as_u16(), 415);
+    }
+
+    #[tokio::test]
+    async fn ndjson_items_are_deserialized_one_by_one() {
+        #[derive(serde::Deserialize, Debug, PartialEq)]
+        struct Event {
+            id: u32,
+        }
+
+        let input = "{\"id\": 1}\n\n{\"id\": 2}\r\n{\"id\": \"three\"}\n{\"id\": 4}";
+        for chunk_size in [1, 3, input.len()] {
+            let mut events = streaming(input, chunk_size, None).ndjson::<Event>();
+            assert_eq!(events.next().await.unwrap().unwrap(), Event { id: 1 });
+            assert_eq!(events.next().await.unwrap().unwrap(), Event { id: 2 });
+            let err = events.next().await.unwrap().unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "Failed to deserialize line 4 as a JSON document.\n\
+                invalid type: string \"three\", expected u32 at line 1 column 14"
+            );
+            assert_eq!(events.next().await.unwrap().unwrap(), Event { id: 4 });
+            assert!(events.next().await.is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn ndjson_stops_on_body_errors() {
+        let mut events =
+            streaming("{}\n{}\n{}\n", 3, Some(4.bytes())).ndjson::<serde_json::Value>();
+        assert!(events.next().await.unwrap().is_ok());
+        let err = events.next().await.unwrap().unwrap_err();
+        insta::assert_snapshot!(err, @"The request body is larger than the maximum size limit enforced by this server.");
+        assert!(events.next().await.is_none());
+    }
+}
@@ -0,0 +1,250 @@
+use http::header::{ACCEPT, ACCEPT_ENCODING, ACCEPT_LANGUAGE, GetAll};
+use http::{HeaderName, HeaderValue};
+use mime::Mime;
+
+use super::TypedHeader;
+use super::errors::HeaderDecodeError;
+use super::typed_header::list_elements;
+
+/// A value paired with its quality weight (the `q` parameter).
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityItem<T> {
+    /// The value.
+    pub value: T,
+    /// The relative weight assigned to the value by the client, between `0` and `1`.
+    ///
+    /// It defaults to `1` if the client didn't specify it.
+    /// A weight of `0` means "not acceptable".
+    pub quality: f32,
+}
+
+/// The media types accepted by the client, from the `Accept` header.
+///
+/// Media ranges are kept in the order they were sent by the client.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Accept(
+    /// The accepted media ranges (e.g. `text/*`), with their weights.
+    pub Vec<QualityItem<Mime>>,
+);
+
+impl TypedHeader for Accept {
+    const NAME: HeaderName = ACCEPT;
+
+    fn decode(values: GetAll<'_, HeaderValue>) -> Result<Self, HeaderDecodeError> {
+        parse_quality_list(values, |s| {
+            s.parse::<Mime>().map_err(|e| {
+                HeaderDecodeError::new(format!("`{s}` is not a valid media range: {e}"))
+            })
+        })
+        .map(Accept)
+    }
+}
+
+/// The natural languages preferred by the client, from the `Accept-Language` header.
+///
+/// Language ranges are kept in the order they were sent by the client.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AcceptLanguage(
+    /// The accepted language ranges (e.g. `en-US` or `*`), with their weights.
+    pub Vec<QualityItem<String>>,
+);
+
+impl TypedHeader for AcceptLanguage {
+    const NAME: HeaderName = ACCEPT_LANGUAGE;
+
+    fn decode(values: GetAll<'_, HeaderValue>) -> Result<Self, HeaderDecodeError> {
+        parse_quality_list(values, |s| {
+            let is_valid = s == "*"
+                || s.split('-').all(|subtag| {
+                    (1..=8).contains(&subtag.len())
+                        && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+                });
+            if !is_valid {
+                return Err(HeaderDecodeError::new(format!(
+                    "`{s}` is not a valid language range"
+                )));
+            }
+            Ok(s.to_owned())
+        })
+        .map(AcceptLanguage)
+    }
+}
+
+/// The content codings accepted by the client, from the `Accept-Encoding` header.
+///
+/// Codings are kept in the order they were sent by the client.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AcceptEncoding(
+    /// The accepted content codings (e.g. `gzip` or `*`), with their weights.
+    pub Vec<QualityItem<String>>,
+);
+
+impl AcceptEncoding {
+    /// The weight assigned by the client to the given content coding.
+    ///
+    /// Codings are compared case-insensitively.
+    /// If `coding` isn't listed, the weight of `*` is returned, if present.
+    /// Otherwise, the weight is `0`—with the exception of `identity`, which is
+    /// acceptable unless explicitly excluded.
+    pub fn quality(&self, coding: &str) -> f32 {
+        let weight_of = |c: &str| {
+            self.0
+                .iter()
+                .find(|item| item.value.eq_ignore_ascii_case(c))
+                .map(|item| item.quality)
+        };
+        weight_of(coding).or_else(|| weight_of("*")).unwrap_or(
+            if coding.eq_ignore_ascii_case("identity") {
+                1.0
+            } else {
+                0.0
+            },
+        )
+    }
+}
+
+impl TypedHeader for AcceptEncoding {
+    const NAME: HeaderName = ACCEPT_ENCODING;
+
+    fn decode(values: GetAll<'_, HeaderValue>) -> Result<Self, HeaderDecodeError> {
+        parse_quality_list(values, |s| {
+            let is_valid = !s.is_empty()
+                && s.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c));
+            if !is_valid {
+                return Err(HeaderDecodeError::new(format!(
+                    "`{s}` is not a valid content coding"
+                )));
+            }
+            Ok(s.to_owned())
+        })
+        .map(AcceptEncoding)
+    }
+}
+
+/// Parse a comma-separated list of values that may carry a `q` parameter.
+fn parse_quality_list<T, F>(
+    values: GetAll<'_, HeaderValue>,
+    parse: F,
+) -> Result<Vec<QualityItem<T>>, HeaderDecodeError>
+where
+    F: Fn(&str) -> Result<T, HeaderDecodeError>,
+{
+    let mut items = Vec::new();
+    for element in list_elements(values)? {
+        let mut value_end = element.len();
+        let mut quality = 1.0;
+        let mut offset = 0;
+        for param in element.split(';') {
+            let (name, q) = param.split_once('=').unwrap_or((param, ""));
+            if offset > 0 && name.trim().eq_ignore_ascii_case("q") {
+                // Everything after the weight are extension parameters, which we ignore.
+                value_end = offset - 1;
+                quality = parse_quality(q.trim())?;
+                break;
+            }
+            offset += param.len() + 1;
+        }
+        items.push(QualityItem {
+            value: parse(element[..value_end].trim())?,
+            quality,
+        });
+    }
+    Ok(items)
+}
+
+/// Parse a weight, as defined in [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#name-quality-values).
+fn parse_quality(s: &str) -> Result<f32, HeaderDecodeError> {
+    let is_valid = match s.split_once('.') {
+        None => s == "0" || s == "1",
+        Some(("0", decimals)) => {
+            decimals.len() <= 3 && decimals.chars().all(|c| c.is_ascii_digit())
+        }
+        Some(("1", decimals)) => decimals.len() <= 3 && decimals.chars().all(|c| c == '0'),
+        Some(_) => false,
+    };
+    if !is_valid {
+        return Err(HeaderDecodeError::new(format!(
+            "`{s}` is not a valid weight. Expected a number between 0 and 1, with at most three decimals"
+        )));
+    }
+    Ok(s.parse().expect("A valid weight is always a valid float"))
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+
+    fn headers(name: HeaderName, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn accept() {
+        let headers = headers(ACCEPT, "text/html, application/json;q=0.9;ext=1, */*;q=0");
+        let accept = Accept::decode(headers.get_all(ACCEPT)).unwrap();
+        assert_eq!(
+            accept.0,
+            vec![
+                QualityItem {
+                    value: mime::TEXT_HTML,
+                    quality: 1.0
+                },
+                QualityItem {
+                    value: mime::APPLICATION_JSON,
+                    quality: 0.9
+                },
+                QualityItem {
+                    value: mime::STAR_STAR,
+                    quality: 0.0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn media_type_parameters_are_preserved() {
+        let headers = headers(ACCEPT, "text/plain; charset=utf-8; q=0.5");
+        let accept = Accept::decode(headers.get_all(ACCEPT)).unwrap();
+        assert_eq!(accept.0[0].value, mime::TEXT_PLAIN_UTF_8);
+        assert_eq!(accept.0[0].quality, 0.5);
+    }
+
+    #[test]
+    fn invalid_weights_are_rejected() {
+        let headers = headers(ACCEPT, "text/html;q=1.5");
+        let err = Accept::decode(headers.get_all(ACCEPT)).unwrap_err();
+        insta::assert_snapshot!(err, @"`1.5` is not a valid weight. Expected a number between 0 and 1, with at most three decimals");
+    }
+
+    #[test]
+    fn accept_language() {
+        let headers = headers(ACCEPT_LANGUAGE, "fr-CH, fr;q=0.9, *;q=0.5");
+        let accept = AcceptLanguage::decode(headers.get_all(ACCEPT_LANGUAGE)).unwrap();
+        let languages: Vec<_> = accept
+            .0
+            .iter()
+            .map(|i| (i.value.as_str(), i.quality))
+            .collect();
+        assert_eq!(languages, vec![("fr-CH", 1.0), ("fr", 0.9), ("*", 0.5)]);
+    }
+
+    #[test]
+    fn accept_encoding() {
+        let values = headers(ACCEPT_ENCODING, "br;q=1.0, GZIP;q=0.5, *;q=0");
+        let accept = AcceptEncoding::decode(values.get_all(ACCEPT_ENCODING)).unwrap();
+        assert_eq!(accept.quality("br"), 1.0);
+        assert_eq!(accept.quality("gzip"), 0.5);
+        assert_eq!(accept.quality("zstd"), 0.0);
+        assert_eq!(accept.quality("identity"), 0.0);
+
+        let values = headers(ACCEPT_ENCODING, "gzip");
+        let accept = AcceptEncoding::decode(values.get_all(ACCEPT_ENCODING)).unwrap();
+        assert_eq!(accept.quality("br"), 0.0);
+        assert_eq!(accept.quality("identity"), 1.0);
+    }
+}
@@ -0,0 +1,153 @@
+use base64::Engine;
+use http::header::{AUTHORIZATION, GetAll};
+use http::{HeaderName, HeaderValue};
+
+use super::TypedHeader;
+use super::errors::HeaderDecodeError;
+use super::typed_header::single_value;
+
+/// The credentials sent by the client in the `Authorization` header.
+///
+/// The `Bearer` and `Basic` schemes are decoded.
+/// All other schemes are preserved as they are, in the [`Authorization::Other`] variant.
+///
+/// The `Debug` representation of this type never includes the credentials.
+#[derive(Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Authorization {
+    /// The `Bearer` scheme ([RFC 6750](https://datatracker.ietf.org/doc/html/rfc6750)).
+    Bearer {
+        /// The bearer token.
+        token: String,
+    },
+    /// The `Basic` scheme ([RFC 7617](https://datatracker.ietf.org/doc/html/rfc7617)).
+    Basic {
+        /// The user identifier.
+        username: String,
+        /// The password.
+        password: String,
+    },
+    /// Any other authentication scheme.
+    Other {
+        /// The name of the scheme, as sent by the client.
+        scheme: String,
+        /// Everything that follows the scheme name, without leading whitespace.
+        credentials: String,
+    },
+}
+
+impl std::fmt::Debug for Authorization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Authorization::Bearer { .. } => f
+                .debug_struct("Bearer")
+                .field("token", &"[REDACTED]")
+                .finish(),
+            Authorization::Basic { username, .. } => f
+                .debug_struct("Basic")
+                .field("username", username)
+                .field("password", &"[REDACTED]")
+                .finish(),
+            Authorization::Other { scheme, .. } => f
+                .debug_struct("Other")
+                .field("scheme", scheme)
+                .field("credentials", &"[REDACTED]")
+                .finish(),
+        }
+    }
+}
+
+impl TypedHeader for Authorization {
+    const NAME: HeaderName = AUTHORIZATION;
+
+    fn decode(values: GetAll<'_, HeaderValue>) -> Result<Self, HeaderDecodeError> {
+        let value = single_value(values)?.trim();
+        let (scheme, credentials) = value
+            .split_once(' ')
+            .map(|(scheme, credentials)| (scheme, credentials.trim_start()))
+            .unwrap_or((value, ""));
+        if scheme.eq_ignore_ascii_case("bearer") {
+            if credentials.is_empty() {
+                return Err(HeaderDecodeError::new("The bearer token is empty"));
+            }
+            Ok(Authorization::Bearer {
+                token: credentials.to_owned(),
+            })
+        } else if scheme.eq_ignore_ascii_case("basic") {
+            let decoded = base64::engine::general_purpose::STANDARD
+                .decode(credentials)
+                .map_err(|_| HeaderDecodeError::new("Basic credentials are not valid base64"))?;
+            let decoded = String::from_utf8(decoded)
+                .map_err(|_| HeaderDecodeError::new("Basic credentials are not valid UTF-8"))?;
+            let Some((username, password)) = decoded.split_once(':') else {
+                return Err(HeaderDecodeError::new(
+                    "Basic credentials must be formatted as `username:password`",
+                ));
+            };
+            Ok(Authorization::Basic {
+                username: username.to_owned(),
+                password: password.to_owned(),
+            })
+        } else {
+            Ok(Authorization::Other {
+                scheme: scheme.to_owned(),
+                credentials: credentials.to_owned(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+
+    fn decode(value: &str) -> Result<Authorization, HeaderDecodeError> {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, value.parse().unwrap());
+        Authorization::decode(headers.get_all(AUTHORIZATION))
+    }
+
+    #[test]
+    fn bearer() {
+        assert_eq!(
+            decode("Bearer abc.def").unwrap(),
+            Authorization::Bearer {
+                token: "abc.def".into()
+            }
+        );
+        assert!(decode("bearer ").is_err());
+    }
+
+    #[test]
+    fn basic() {
+        // `Aladdin:open sesame`
+        assert_eq!(
+            decode("Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==").unwrap(),
+            Authorization::Basic {
+                username: "Aladdin".into(),
+                password: "open sesame".into()
+            }
+        );
+        let err = decode("Basic !!!").unwrap_err();
+        insta::assert_snapshot!(err, @"Basic credentials are not valid base64");
+    }
+
+    #[test]
+    fn other_schemes_are_preserved() {
+        assert_eq!(
+            decode("Digest username=\"a\", realm=\"b\"").unwrap(),
+            Authorization::Other {
+                scheme: "Digest".into(),
+                credentials: "username=\"a\", realm=\"b\"".into()
+            }
+        );
+    }
+
+    #[test]
+    fn debug_does_not_leak_credentials() {
+        let auth = decode("Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==").unwrap();
+        insta::assert_snapshot!(format!("{auth:?}"), @r#"Basic { username: "Aladdin", password: "[REDACTED]" }"#);
+    }
+}
@@ -0,0 +1,259 @@
+use std::fmt;
+use std::str::FromStr;
+
+use http::header::{GetAll, IF_MATCH, IF_NONE_MATCH};
+use http::{HeaderName, HeaderValue};
+
+use super::TypedHeader;
+use super::errors::HeaderDecodeError;
+use super::typed_header::list_elements;
+
+/// An entity tag, used to identify a specific representation of a resource.
+///
+/// Check out [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#name-etag) for more details.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::request::headers::EntityTag;
+///
+/// let tag: EntityTag = r#"W/"xyzzy""#.parse().unwrap();
+/// assert!(tag.weak);
+/// assert_eq!(tag.tag(), "xyzzy");
+/// assert_eq!(tag.to_string(), r#"W/"xyzzy""#);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntityTag {
+    /// `true` if the tag is a weak validator, i.e. it's prefixed by `W/`.
+    pub weak: bool,
+    tag: String,
+}
+
+impl EntityTag {
+    /// Create a new strong entity tag.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `tag` contains characters that aren't allowed in an entity tag
+    /// (double quotes, whitespace, control characters).
+    #[track_caller]
+    pub fn strong(tag: impl Into<String>) -> Self {
+        Self::new(false, tag.into())
+    }
+
+    /// Create a new weak entity tag.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `tag` contains characters that aren't allowed in an entity tag
+    /// (double quotes, whitespace, control characters).
+    #[track_caller]
+    pub fn weak(tag: impl Into<String>) -> Self {
+        Self::new(true, tag.into())
+    }
+
+    #[track_caller]
+    fn new(weak: bool, tag: String) -> Self {
+        assert!(
+            is_valid_tag(&tag),
+            "`{tag}` is not a valid entity tag: double quotes, whitespace and control characters are not allowed"
+        );
+        Self { weak, tag }
+    }
+
+    /// The opaque tag, without quotes nor the `W/` prefix.
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Two entity tags are equivalent, according to the strong comparison function,
+    /// if both are strong and their opaque tags match.
+    pub fn strong_eq(&self, other: &EntityTag) -> bool {
+        !self.weak && !other.weak && self.tag == other.tag
+    }
+
+    /// Two entity tags are equivalent, according to the weak comparison function,
+    /// if their opaque tags match, regardless of either or both being tagged as "weak".
+    pub fn weak_eq(&self, other: &EntityTag) -> bool {
+        self.tag == other.tag
+    }
+}
+
+fn is_valid_tag(tag: &str) -> bool {
+    tag.bytes()
+        .all(|b| b == 0x21 || (0x23..=0x7E).contains(&b) || b >= 0x80)
+}
+
+impl fmt::Display for EntityTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.weak {
+            write!(f, "W/\"{}\"", self.tag)
+        } else {
+            write!(f, "\"{}\"", self.tag)
+        }
+    }
+}
+
+impl FromStr for EntityTag {
+    type Err = HeaderDecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (weak, quoted) = match s.strip_prefix("W/") {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let tag = quoted
+            .strip_prefix('"')
+            .and_then(|s| s.strip_suffix('"'))
+            .filter(|tag| is_valid_tag(tag))
+            .ok_or_else(|| HeaderDecodeError::new(format!("`{s}` is not a valid entity tag")))?;
+        Ok(Self {
+            weak,
+            tag: tag.to_owned(),
+        })
+    }
+}
+
+impl From<EntityTag> for HeaderValue {
+    fn from(tag: EntityTag) -> Self {
+        HeaderValue::try_from(tag.to_string())
+            .expect("A valid entity tag is always a valid header value")
+    }
+}
+
+/// The entity tags listed by the client in the `If-None-Match` header.
+///
+/// The client is asking the server to only send back a representation of the resource
+/// if its current entity tag doesn't match any of the listed ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IfNoneMatch {
+    /// `*`—it matches any current representation of the resource.
+    Any,
+    /// A list of entity tags.
+    Tags(Vec<EntityTag>),
+}
+
+impl IfNoneMatch {
+    /// Returns `true` if `etag` matches the condition, according to the weak comparison
+    /// function.
+    ///
+    /// For a `GET` or `HEAD` request, a match means the server should respond with
+    /// `304 Not Modified`.
+    pub fn matches(&self, etag: &EntityTag) -> bool {
+        match self {
+            IfNoneMatch::Any => true,
+            IfNoneMatch::Tags(tags) => tags.iter().any(|tag| tag.weak_eq(etag)),
+        }
+    }
+}
+
+impl TypedHeader for IfNoneMatch {
+    const NAME: HeaderName = IF_NONE_MATCH;
+
+    fn decode(values: GetAll<'_, HeaderValue>) -> Result<Self, HeaderDecodeError> {
+        let elements = list_elements(values)?;
+        if elements == ["*"] {
+            return Ok(IfNoneMatch::Any);
+        }
+        elements
+            .into_iter()
+            .map(EntityTag::from_str)
+            .collect::<Result<_, _>>()
+            .map(IfNoneMatch::Tags)
+    }
+}
+
+/// The entity tags listed by the client in the `If-Match` header.
+///
+/// The client is asking the server to only perform the request if the current entity tag
+/// of the resource matches one of the listed ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IfMatch {
+    /// `*`—it matches any current representation of the resource.
+    Any,
+    /// A list of entity tags.
+    Tags(Vec<EntityTag>),
+}
+
+impl IfMatch {
+    /// Returns `true` if `etag` matches the condition, according to the strong comparison
+    /// function.
+    ///
+    /// If there's no match, the server should respond with `412 Precondition Failed`.
+    pub fn matches(&self, etag: &EntityTag) -> bool {
+        match self {
+            IfMatch::Any => true,
+            IfMatch::Tags(tags) => tags.iter().any(|tag| tag.strong_eq(etag)),
+        }
+    }
+}
+
+impl TypedHeader for IfMatch {
+    const NAME: HeaderName = IF_MATCH;
+
+    fn decode(values: GetAll<'_, HeaderValue>) -> Result<Self, HeaderDecodeError> {
+        let elements = list_elements(values)?;
+        if elements == ["*"] {
+            return Ok(IfMatch::Any);
+        }
+        elements
+            .into_iter()
+            .map(EntityTag::from_str)
+            .collect::<Result<_, _>>()
+            .map(IfMatch::Tags)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+
+    fn if_none_match(value: &str) -> Result<IfNoneMatch, HeaderDecodeError> {
+        let mut headers = HeaderMap::new();
+        headers.insert(IF_NONE_MATCH, value.parse().unwrap());
+        IfNoneMatch::decode(headers.get_all(IF_NONE_MATCH))
+    }
+
+    #[test]
+    fn if_none_match_with_tags() {
+        let condition = if_none_match(r#""a", W/"b""#).unwrap();
+        assert_eq!(
+            condition,
+            IfNoneMatch::Tags(vec![EntityTag::strong("a"), EntityTag::weak("b")])
+        );
+        assert!(condition.matches(&EntityTag::strong("b")));
+        assert!(!condition.matches(&EntityTag::strong("c")));
+    }
+
+    #[test]
+    fn if_none_match_any() {
+        assert_eq!(if_none_match("*").unwrap(), IfNoneMatch::Any);
+    }
+
+    #[test]
+    fn unquoted_tags_are_rejected() {
+        let err = if_none_match("abc").unwrap_err();
+        insta::assert_snapshot!(err, @"`abc` is not a valid entity tag");
+    }
+
+    #[test]
+    fn if_match_uses_strong_comparison() {
+        let mut headers = HeaderMap::new();
+        headers.insert(IF_MATCH, r#""a", W/"b""#.parse().unwrap());
+        let condition = IfMatch::decode(headers.get_all(IF_MATCH)).unwrap();
+        assert!(condition.matches(&EntityTag::strong("a")));
+        assert!(!condition.matches(&EntityTag::weak("a")));
+        assert!(!condition.matches(&EntityTag::strong("b")));
+    }
+
+    #[test]
+    fn comparison_functions() {
+        let strong = EntityTag::strong("1");
+        let weak = EntityTag::weak("1");
+        assert!(strong.strong_eq(&strong));
+        assert!(!strong.strong_eq(&weak));
+        assert!(strong.weak_eq(&weak));
+    }
+}
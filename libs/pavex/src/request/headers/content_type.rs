@@ -0,0 +1,44 @@
+use http::header::{CONTENT_TYPE, GetAll};
+use http::{HeaderName, HeaderValue};
+use mime::Mime;
+
+use super::TypedHeader;
+use super::errors::HeaderDecodeError;
+use super::typed_header::single_value;
+
+/// The media type of the request body, from the `Content-Type` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentType(
+    /// The media type, including its parameters (e.g. `charset`).
+    pub Mime,
+);
+
+impl TypedHeader for ContentType {
+    const NAME: HeaderName = CONTENT_TYPE;
+
+    fn decode(values: GetAll<'_, HeaderValue>) -> Result<Self, HeaderDecodeError> {
+        let value = single_value(values)?;
+        value.parse().map(ContentType).map_err(|e| {
+            HeaderDecodeError::new(format!("`{value}` is not a valid media type: {e}"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+
+    #[test]
+    fn content_type() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_TYPE,
+            "application/json; charset=utf-8".parse().unwrap(),
+        );
+        let ContentType(mime) = ContentType::decode(headers.get_all(CONTENT_TYPE)).unwrap();
+        assert_eq!(mime.essence_str(), "application/json");
+        assert_eq!(mime.get_param(mime::CHARSET), Some(mime::UTF_8));
+    }
+}
@@ -0,0 +1,93 @@
+//! Errors that can happen when extracting typed headers.
+use http::HeaderName;
+use http::header::AUTHORIZATION;
+
+use crate::response::Response;
+
+/// The error returned by [`Header::extract`] and [`OptionalHeader::extract`] when
+/// the extraction fails.
+///
+/// See the documentation of each error variant for more details.
+///
+/// Pavex provides [`ExtractHeaderError::into_response`] as the default error handler for
+/// this failure.
+///
+/// [`Header::extract`]: crate::request::headers::Header::extract
+/// [`OptionalHeader::extract`]: crate::request::headers::OptionalHeader::extract
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ExtractHeaderError {
+    #[error(transparent)]
+    /// See [`MissingHeader`] for details.
+    MissingHeader(#[from] MissingHeader),
+    #[error(transparent)]
+    /// See [`InvalidHeader`] for details.
+    InvalidHeader(#[from] InvalidHeader),
+}
+
+impl ExtractHeaderError {
+    /// Convert an [`ExtractHeaderError`] into an HTTP response.
+    ///
+    /// It returns a `400 Bad Request` to the caller, with one exception:
+    /// a missing `Authorization` header results in a `401 Unauthorized`.
+    pub fn into_response(&self) -> Response {
+        match self {
+            ExtractHeaderError::MissingHeader(e) if e.name == AUTHORIZATION => {
+                Response::unauthorized()
+            }
+            ExtractHeaderError::MissingHeader(_) | ExtractHeaderError::InvalidHeader(_) => {
+                Response::bad_request()
+            }
+        }
+        .set_typed_body(format!("{}", self))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("The `{name}` header is missing")]
+#[non_exhaustive]
+/// A required header is not present in the incoming request.
+pub struct MissingHeader {
+    /// The name of the missing header.
+    pub name: HeaderName,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("The `{name}` header is invalid. {source}")]
+#[non_exhaustive]
+/// A header is present in the incoming request, but its value(s) couldn't be decoded
+/// into the expected type.
+pub struct InvalidHeader {
+    /// The name of the invalid header.
+    pub name: HeaderName,
+    #[source]
+    /// The reason why decoding failed.
+    pub source: HeaderDecodeError,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("{inner}")]
+/// The error returned by [`TypedHeader::decode`] when the header values
+/// are not valid for the target type.
+///
+/// [`TypedHeader::decode`]: crate::request::headers::TypedHeader::decode
+pub struct HeaderDecodeError {
+    inner: Box<dyn std::error::Error + Send + Sync>,
+}
+
+impl HeaderDecodeError {
+    /// Create a new [`HeaderDecodeError`] from an error or a message.
+    ///
+    /// ```rust
+    /// use pavex::request::headers::errors::HeaderDecodeError;
+    ///
+    /// let e = HeaderDecodeError::new("Expected a single value");
+    /// assert_eq!(e.to_string(), "Expected a single value");
+    /// ```
+    pub fn new<E>(e: E) -> Self
+    where
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        Self { inner: e.into() }
+    }
+}
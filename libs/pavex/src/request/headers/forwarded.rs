@@ -0,0 +1,277 @@
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use http::header::{FORWARDED, GetAll};
+use http::{HeaderName, HeaderValue};
+
+use super::TypedHeader;
+use super::errors::HeaderDecodeError;
+use super::typed_header::list_elements;
+
+/// The proxy information listed in the `Forwarded` header
+/// ([RFC 7239](https://datatracker.ietf.org/doc/html/rfc7239)).
+///
+/// # Security
+///
+/// The header is set by the client or by any proxy along the way: it can't be trusted
+/// unless you know which proxies sit in front of your application and you only
+/// look at the elements they appended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Forwarded(
+    /// One element per proxy, in the order they were appended—i.e. the element
+    /// appended by the proxy closest to your application comes last.
+    pub Vec<ForwardedElement>,
+);
+
+/// The information appended to the `Forwarded` header by a single proxy.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ForwardedElement {
+    /// The client that sent the request to the proxy (`for=`).
+    pub for_: Option<ForwardedNode>,
+    /// The interface where the request came in to the proxy (`by=`).
+    pub by: Option<ForwardedNode>,
+    /// The `Host` header field, as received by the proxy (`host=`).
+    pub host: Option<String>,
+    /// The protocol used to make the request to the proxy (`proto=`), e.g. `https`.
+    pub proto: Option<String>,
+}
+
+/// A node in a chain of proxies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ForwardedNode {
+    /// An IP address, with an optional port.
+    Ip {
+        /// The IP address.
+        addr: IpAddr,
+        /// The port, if it was specified and it wasn't obfuscated.
+        port: Option<u16>,
+    },
+    /// The proxy doesn't know the identity of the node (`unknown`).
+    Unknown,
+    /// The identity of the node has been deliberately hidden, e.g. `_hidden`.
+    Obfuscated(String),
+}
+
+impl ForwardedNode {
+    /// The IP address of the node, if it's known.
+    pub fn ip(&self) -> Option<IpAddr> {
+        match self {
+            ForwardedNode::Ip { addr, .. } => Some(*addr),
+            ForwardedNode::Unknown | ForwardedNode::Obfuscated(_) => None,
+        }
+    }
+}
+
+impl FromStr for ForwardedNode {
+    type Err = HeaderDecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || HeaderDecodeError::new(format!("`{s}` is not a valid node identifier"));
+        if s.eq_ignore_ascii_case("unknown") {
+            return Ok(ForwardedNode::Unknown);
+        }
+        if s.starts_with('_') {
+            return Ok(ForwardedNode::Obfuscated(s.to_owned()));
+        }
+        // A bare IP address. IPv6 addresses are not bracketed in `X-Forwarded-For`.
+        if let Ok(addr) = s.parse() {
+            return Ok(ForwardedNode::Ip { addr, port: None });
+        }
+        let (addr, port) = match s.strip_prefix('[') {
+            Some(rest) => {
+                let (addr, rest) = rest.split_once(']').ok_or_else(invalid)?;
+                let port = match rest {
+                    "" => None,
+                    _ => Some(rest.strip_prefix(':').ok_or_else(invalid)?),
+                };
+                (
+                    addr.parse::<std::net::Ipv6Addr>()
+                        .map_err(|_| invalid())?
+                        .into(),
+                    port,
+                )
+            }
+            None => {
+                let (addr, port) = s.split_once(':').ok_or_else(invalid)?;
+                (
+                    addr.parse::<std::net::Ipv4Addr>()
+                        .map_err(|_| invalid())?
+                        .into(),
+                    Some(port),
+                )
+            }
+        };
+        let port = match port {
+            // Obfuscated port.
+            Some(port) if port.starts_with('_') => None,
+            Some(port) => Some(port.parse().map_err(|_| invalid())?),
+            None => None,
+        };
+        Ok(ForwardedNode::Ip { addr, port })
+    }
+}
+
+impl TypedHeader for Forwarded {
+    const NAME: HeaderName = FORWARDED;
+
+    fn decode(values: GetAll<'_, HeaderValue>) -> Result<Self, HeaderDecodeError> {
+        list_elements(values)?
+            .into_iter()
+            .map(parse_element)
+            .collect::<Result<_, _>>()
+            .map(Forwarded)
+    }
+}
+
+fn parse_element(s: &str) -> Result<ForwardedElement, HeaderDecodeError> {
+    let mut element = ForwardedElement::default();
+    for pair in split_pairs(s) {
+        let Some((name, value)) = pair.split_once('=') else {
+            return Err(HeaderDecodeError::new(format!(
+                "`{pair}` is not a valid forwarded pair. Expected `<name>=<value>`"
+            )));
+        };
+        let value = unquote(value.trim());
+        match name.trim().to_ascii_lowercase().as_str() {
+            "for" => element.for_ = Some(value.parse()?),
+            "by" => element.by = Some(value.parse()?),
+            "host" => element.host = Some(value),
+            "proto" => element.proto = Some(value.to_ascii_lowercase()),
+            // Extensions are ignored.
+            _ => {}
+        }
+    }
+    Ok(element)
+}
+
+/// Split a forwarded element into its `;`-separated pairs, ignoring separators
+/// inside quoted strings.
+fn split_pairs(s: &str) -> impl Iterator<Item = &str> {
+    let mut pairs = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ';' if !in_quotes => {
+                pairs.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    pairs.push(&s[start..]);
+    pairs.into_iter().map(str::trim).filter(|p| !p.is_empty())
+}
+
+fn unquote(s: &str) -> String {
+    match s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        Some(quoted) => {
+            let mut unquoted = String::with_capacity(quoted.len());
+            let mut chars = quoted.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => unquoted.extend(chars.next()),
+                    c => unquoted.push(c),
+                }
+            }
+            unquoted
+        }
+        None => s.to_owned(),
+    }
+}
+
+/// The client addresses listed in the non-standard `X-Forwarded-For` header.
+///
+/// Check out [`Forwarded`] for its standardized counterpart.
+///
+/// # Security
+///
+/// The header is set by the client or by any proxy along the way: it can't be trusted
+/// unless you know which proxies sit in front of your application and you only
+/// look at the addresses they appended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XForwardedFor(
+    /// The addresses, in the order they were appended—i.e. the address
+    /// appended by the proxy closest to your application comes last.
+    pub Vec<ForwardedNode>,
+);
+
+impl TypedHeader for XForwardedFor {
+    const NAME: HeaderName = HeaderName::from_static("x-forwarded-for");
+
+    fn decode(values: GetAll<'_, HeaderValue>) -> Result<Self, HeaderDecodeError> {
+        list_elements(values)?
+            .into_iter()
+            .map(ForwardedNode::from_str)
+            .collect::<Result<_, _>>()
+            .map(XForwardedFor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+
+    fn decode<T: TypedHeader>(values: &[&str]) -> Result<T, HeaderDecodeError> {
+        let mut headers = HeaderMap::new();
+        for value in values {
+            headers.append(T::NAME, value.parse().unwrap());
+        }
+        T::decode(headers.get_all(T::NAME))
+    }
+
+    #[test]
+    fn forwarded() {
+        let Forwarded(elements) = decode(&[
+            r#"for=192.0.2.60;proto=HTTPS;by="[2001:db8::1]:4711", for=unknown"#,
+            "for=_hidden;host=example.com",
+        ])
+        .unwrap();
+        assert_eq!(elements.len(), 3);
+        assert_eq!(
+            elements[0].for_.as_ref().unwrap().ip(),
+            Some("192.0.2.60".parse().unwrap())
+        );
+        assert_eq!(elements[0].proto.as_deref(), Some("https"));
+        assert_eq!(
+            elements[0].by,
+            Some(ForwardedNode::Ip {
+                addr: "2001:db8::1".parse().unwrap(),
+                port: Some(4711)
+            })
+        );
+        assert_eq!(elements[1].for_, Some(ForwardedNode::Unknown));
+        assert_eq!(
+            elements[2].for_,
+            Some(ForwardedNode::Obfuscated("_hidden".into()))
+        );
+        assert_eq!(elements[2].host.as_deref(), Some("example.com"));
+    }
+
+    #[test]
+    fn invalid_forwarded_pairs_are_rejected() {
+        let err = decode::<Forwarded>(&["for"]).unwrap_err();
+        insta::assert_snapshot!(err, @"`for` is not a valid forwarded pair. Expected `<name>=<value>`");
+        let err = decode::<Forwarded>(&["for=example.com"]).unwrap_err();
+        insta::assert_snapshot!(err, @"`example.com` is not a valid node identifier");
+    }
+
+    #[test]
+    fn x_forwarded_for() {
+        let XForwardedFor(nodes) = decode(&[
+            "203.0.113.195, 2001:db8:85a3::8a2e:370:7334",
+            "10.0.0.1:8080",
+        ])
+        .unwrap();
+        let ips: Vec<_> = nodes.iter().map(|n| n.ip().unwrap().to_string()).collect();
+        assert_eq!(
+            ips,
+            vec!["203.0.113.195", "2001:db8:85a3::8a2e:370:7334", "10.0.0.1"]
+        );
+    }
+}
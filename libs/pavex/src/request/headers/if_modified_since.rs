@@ -0,0 +1,58 @@
+use http::header::{GetAll, IF_MODIFIED_SINCE};
+use http::{HeaderName, HeaderValue};
+
+use super::TypedHeader;
+use super::errors::HeaderDecodeError;
+use super::typed_header::single_value;
+
+/// The date sent by the client in the `If-Modified-Since` header.
+///
+/// The client is asking the server to only send back a representation of the resource
+/// if it has been modified after this instant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IfModifiedSince(
+    /// The instant, with second precision.
+    pub jiff::Timestamp,
+);
+
+impl IfModifiedSince {
+    /// Returns `true` if a resource last modified at `last_modified` has been
+    /// modified since the instant specified by the client.
+    ///
+    /// `last_modified` is truncated to second precision before comparing.
+    pub fn is_modified(&self, last_modified: jiff::Timestamp) -> bool {
+        last_modified.as_second() > self.0.as_second()
+    }
+}
+
+impl TypedHeader for IfModifiedSince {
+    const NAME: HeaderName = IF_MODIFIED_SINCE;
+
+    fn decode(values: GetAll<'_, HeaderValue>) -> Result<Self, HeaderDecodeError> {
+        let value = single_value(values)?;
+        jiff::fmt::rfc2822::DateTimeParser::new()
+            .parse_timestamp(value)
+            .map(IfModifiedSince)
+            .map_err(|e| HeaderDecodeError::new(format!("`{value}` is not a valid HTTP date: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+
+    #[test]
+    fn if_modified_since() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            IF_MODIFIED_SINCE,
+            "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap(),
+        );
+        let condition = IfModifiedSince::decode(headers.get_all(IF_MODIFIED_SINCE)).unwrap();
+        assert_eq!(condition.0.as_second(), 784111777);
+        assert!(!condition.is_modified(condition.0));
+        assert!(condition.is_modified(jiff::Timestamp::from_second(784111778).unwrap()));
+    }
+}
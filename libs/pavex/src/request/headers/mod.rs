@@ -0,0 +1,56 @@
+//! Extract typed headers from incoming requests.
+//!
+//! Use [`Header<T>`] for headers that must be present and [`OptionalHeader<T>`] for
+//! headers that may be missing, where `T` is one of the header types in this module or
+//! your own [`TypedHeader`].
+//!
+//! # Custom headers
+//!
+//! Derive [`TypedHeader`](derive@TypedHeader) for a newtype wrapper around a type that
+//! implements [`FromStr`](std::str::FromStr):
+//!
+//! ```rust
+//! use pavex::request::headers::TypedHeader;
+//!
+//! #[derive(TypedHeader)]
+//! #[pavex(name = "x-request-id")]
+//! pub struct RequestId(pub String);
+//! ```
+//!
+//! You can then inject `&Header<RequestId>` or `&OptionalHeader<RequestId>` in your
+//! components.
+//!
+//! # Guide
+//!
+//! Check out [the guide](https://pavex.dev/docs/guide/request_data/headers/)
+//! for more information.
+pub use accept::{Accept, AcceptEncoding, AcceptLanguage, QualityItem};
+pub use authorization::Authorization;
+pub use conditional::{EntityTag, IfMatch, IfNoneMatch};
+pub use content_type::ContentType;
+pub use forwarded::{Forwarded, ForwardedElement, ForwardedNode, XForwardedFor};
+#[cfg(feature = "time")]
+pub use if_modified_since::IfModifiedSince;
+/// Derive [`TypedHeader`](trait@TypedHeader) for a newtype wrapper around a type that
+/// implements [`FromStr`](std::str::FromStr).
+///
+/// The header name is specified via the `#[pavex(name = "...")]` attribute.
+/// It must be lowercase.
+/// The header is decoded by parsing its value, which must appear exactly once,
+/// after trimming surrounding whitespace.
+pub use pavex_macros::TypedHeader;
+pub use range::{ByteRange, Range};
+#[cfg(feature = "time")]
+pub(crate) use typed_header::decode_if_valid;
+pub use typed_header::{Header, OptionalHeader, TypedHeader, decode_from_str, single_value};
+
+mod accept;
+mod authorization;
+mod conditional;
+mod content_type;
+pub mod errors;
+mod forwarded;
+#[cfg(feature = "time")]
+mod if_modified_since;
+mod range;
+mod typed_header;
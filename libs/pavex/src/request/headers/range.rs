@@ -0,0 +1,167 @@
+use http::header::{GetAll, RANGE};
+use http::{HeaderName, HeaderValue};
+
+use super::TypedHeader;
+use super::errors::HeaderDecodeError;
+use super::typed_header::single_value;
+
+/// The byte ranges requested by the client in the `Range` header.
+///
+/// Only the `bytes` range unit is supported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Range(
+    /// The requested ranges, in the order they were sent by the client.
+    ///
+    /// It's never empty.
+    pub Vec<ByteRange>,
+);
+
+/// A single byte range.
+///
+/// Check out [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#name-byte-ranges)
+/// for more details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteRange {
+    /// `<start>-<end>`, both inclusive.
+    FromTo {
+        /// The offset of the first byte.
+        start: u64,
+        /// The offset of the last byte.
+        end: u64,
+    },
+    /// `<start>-`, from `start` to the end of the representation.
+    From {
+        /// The offset of the first byte.
+        start: u64,
+    },
+    /// `-<length>`, the last `length` bytes of the representation.
+    Suffix {
+        /// The number of bytes.
+        length: u64,
+    },
+}
+
+impl ByteRange {
+    /// Resolve the range against a representation that's `content_length` bytes long.
+    ///
+    /// It returns `None` if the range is not satisfiable—i.e. it starts after the end
+    /// of the representation, or it asks for an empty suffix.
+    /// The range is truncated if it extends past the end of the representation.
+    ///
+    /// ```rust
+    /// use pavex::request::headers::ByteRange;
+    ///
+    /// let range = ByteRange::FromTo { start: 5, end: 100 };
+    /// assert_eq!(range.resolve(10), Some(5..10));
+    /// assert_eq!(ByteRange::Suffix { length: 3 }.resolve(10), Some(7..10));
+    /// assert_eq!(ByteRange::From { start: 10 }.resolve(10), None);
+    /// ```
+    pub fn resolve(&self, content_length: u64) -> Option<std::ops::Range<u64>> {
+        match *self {
+            ByteRange::FromTo { start, end } if start < content_length => {
+                Some(start..end.saturating_add(1).min(content_length))
+            }
+            ByteRange::From { start } if start < content_length => Some(start..content_length),
+            ByteRange::Suffix { length } if length > 0 && content_length > 0 => {
+                Some(content_length.saturating_sub(length)..content_length)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl TypedHeader for Range {
+    const NAME: HeaderName = RANGE;
+
+    fn decode(values: GetAll<'_, HeaderValue>) -> Result<Self, HeaderDecodeError> {
+        let value = single_value(values)?.trim();
+        let Some((unit, ranges)) = value.split_once('=') else {
+            return Err(HeaderDecodeError::new(
+                "Expected `<unit>=<ranges>`, e.g. `bytes=0-499`",
+            ));
+        };
+        if !unit.trim().eq_ignore_ascii_case("bytes") {
+            return Err(HeaderDecodeError::new(format!(
+                "`{unit}` is not a supported range unit. Only `bytes` is supported"
+            )));
+        }
+        let ranges = ranges
+            .split(',')
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+            .map(parse_byte_range)
+            .collect::<Result<Vec<_>, _>>()?;
+        if ranges.is_empty() {
+            return Err(HeaderDecodeError::new("No range was specified"));
+        }
+        Ok(Range(ranges))
+    }
+}
+
+fn parse_byte_range(s: &str) -> Result<ByteRange, HeaderDecodeError> {
+    let invalid = || HeaderDecodeError::new(format!("`{s}` is not a valid byte range"));
+    let (start, end) = s.split_once('-').ok_or_else(invalid)?;
+    let parse = |n: &str| {
+        if n.is_empty() || !n.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        n.parse::<u64>().map_err(|_| invalid())
+    };
+    match (start.trim(), end.trim()) {
+        ("", length) => Ok(ByteRange::Suffix {
+            length: parse(length)?,
+        }),
+        (start, "") => Ok(ByteRange::From {
+            start: parse(start)?,
+        }),
+        (start, end) => {
+            let (start, end) = (parse(start)?, parse(end)?);
+            if end < start {
+                return Err(invalid());
+            }
+            Ok(ByteRange::FromTo { start, end })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+
+    fn decode(value: &str) -> Result<Range, HeaderDecodeError> {
+        let mut headers = HeaderMap::new();
+        headers.insert(RANGE, value.parse().unwrap());
+        Range::decode(headers.get_all(RANGE))
+    }
+
+    #[test]
+    fn multiple_ranges() {
+        assert_eq!(
+            decode("bytes=0-499, 500-, -100").unwrap().0,
+            vec![
+                ByteRange::FromTo { start: 0, end: 499 },
+                ByteRange::From { start: 500 },
+                ByteRange::Suffix { length: 100 },
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_ranges_are_rejected() {
+        let err = decode("bytes=10-5").unwrap_err();
+        insta::assert_snapshot!(err, @"`10-5` is not a valid byte range");
+        let err = decode("items=0-5").unwrap_err();
+        insta::assert_snapshot!(err, @"`items` is not a supported range unit. Only `bytes` is supported");
+        assert!(decode("bytes=-").is_err());
+        assert!(decode("bytes=").is_err());
+    }
+
+    #[test]
+    fn unsatisfiable_ranges() {
+        assert_eq!(ByteRange::FromTo { start: 10, end: 20 }.resolve(10), None);
+        assert_eq!(ByteRange::Suffix { length: 0 }.resolve(10), None);
+        assert_eq!(ByteRange::Suffix { length: 20 }.resolve(10), Some(0..10));
+    }
+}
@@ -0,0 +1,251 @@
+use std::str::FromStr;
+
+use http::HeaderName;
+use http::HeaderValue;
+use http::header::GetAll;
+use pavex_macros::request_scoped;
+
+use crate::request::RequestHead;
+
+use super::errors::{ExtractHeaderError, HeaderDecodeError, InvalidHeader, MissingHeader};
+
+/// A header with a well-known name whose value(s) can be decoded into a Rust type.
+///
+/// Implement this trait (or derive it, see [`TypedHeader`](derive@crate::request::headers::TypedHeader))
+/// to use your own types with the [`Header`] and [`OptionalHeader`] extractors.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::http::{HeaderName, HeaderValue, header::GetAll};
+/// use pavex::request::headers::{TypedHeader, errors::HeaderDecodeError};
+///
+/// pub struct DoNotTrack(pub bool);
+///
+/// impl TypedHeader for DoNotTrack {
+///     const NAME: HeaderName = HeaderName::from_static("dnt");
+///
+///     fn decode(values: GetAll<'_, HeaderValue>) -> Result<Self, HeaderDecodeError> {
+///         match pavex::request::headers::single_value(values)? {
+///             "0" => Ok(DoNotTrack(false)),
+///             "1" => Ok(DoNotTrack(true)),
+///             _ => Err(HeaderDecodeError::new("Expected either `0` or `1`")),
+///         }
+///     }
+/// }
+/// ```
+pub trait TypedHeader: Sized {
+    /// The name of the header.
+    const NAME: HeaderName;
+
+    /// Decode the header from all the values associated with [`Self::NAME`]
+    /// in the incoming request.
+    ///
+    /// It's only invoked if there is at least one value.
+    fn decode(values: GetAll<'_, HeaderValue>) -> Result<Self, HeaderDecodeError>;
+}
+
+/// Extract a required typed header from the incoming request.
+///
+/// The request is rejected if the header is missing or if it can't be decoded.
+/// Use [`OptionalHeader`] if the header may legitimately be absent.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::request::headers::{Authorization, Header};
+/// use pavex::response::Response;
+///
+/// pub fn me(auth: &Header<Authorization>) -> Response {
+///     match &auth.0 {
+///         Authorization::Bearer { token } => {
+///             // [...]
+///             # let _ = token;
+///             Response::ok()
+///         }
+///         _ => Response::unauthorized(),
+///     }
+/// }
+/// ```
+///
+/// # Guide
+///
+/// Check out [the guide](https://pavex.dev/docs/guide/request_data/headers/)
+/// for more information on how to use this extractor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Header<T>(
+    /// The decoded header value.
+    pub T,
+);
+
+impl<T> Header<T> {
+    /// The default constructor for [`Header`].
+    ///
+    /// If the extraction fails, an [`ExtractHeaderError`] is returned.
+    #[request_scoped(
+        error_handler = "crate::request::headers::errors::ExtractHeaderError::into_response"
+    )]
+    pub fn extract(request_head: &RequestHead) -> Result<Self, ExtractHeaderError>
+    where
+        T: TypedHeader,
+    {
+        match decode::<T>(request_head)? {
+            Some(value) => Ok(Header(value)),
+            None => Err(MissingHeader { name: T::NAME }.into()),
+        }
+    }
+}
+
+/// Extract an optional typed header from the incoming request.
+///
+/// It's set to `None` if the header is missing.
+/// The request is rejected if the header is present but can't be decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionalHeader<T>(
+    /// The decoded header value, if the header was present.
+    pub Option<T>,
+);
+
+impl<T> OptionalHeader<T> {
+    /// The default constructor for [`OptionalHeader`].
+    ///
+    /// If the extraction fails, an [`ExtractHeaderError`] is returned.
+    #[request_scoped(
+        error_handler = "crate::request::headers::errors::ExtractHeaderError::into_response"
+    )]
+    pub fn extract(request_head: &RequestHead) -> Result<Self, ExtractHeaderError>
+    where
+        T: TypedHeader,
+    {
+        decode::<T>(request_head).map(OptionalHeader)
+    }
+}
+
+fn decode<T: TypedHeader>(request_head: &RequestHead) -> Result<Option<T>, ExtractHeaderError> {
+    let values = request_head.headers.get_all(T::NAME);
+    if values.iter().next().is_none() {
+        return Ok(None);
+    }
+    T::decode(values).map(Some).map_err(|source| {
+        InvalidHeader {
+            name: T::NAME,
+            source,
+        }
+        .into()
+    })
+}
+
+/// Decode a typed header, treating invalid values as if the header was missing.
+///
+/// It's meant for headers that are optional refinements of the request semantics
+/// (e.g. conditional or range requests), where a malformed value must be ignored
+/// rather than rejected.
+#[cfg(feature = "time")]
+pub(crate) fn decode_if_valid<H: TypedHeader>(headers: &http::HeaderMap) -> Option<H> {
+    if !headers.contains_key(H::NAME) {
+        return None;
+    }
+    H::decode(headers.get_all(H::NAME)).ok()
+}
+
+/// Get the value of a header that must appear exactly once.
+///
+/// It fails if the header has been repeated or if its value isn't valid UTF-8.
+pub fn single_value<'a>(values: GetAll<'a, HeaderValue>) -> Result<&'a str, HeaderDecodeError> {
+    let mut iter = values.iter();
+    let Some(value) = iter.next() else {
+        return Err(HeaderDecodeError::new("The header has no value"));
+    };
+    if iter.next().is_some() {
+        return Err(HeaderDecodeError::new(
+            "Expected a single value, but the header was repeated",
+        ));
+    }
+    to_str(value)
+}
+
+/// Decode a header that must appear exactly once using the [`FromStr`]
+/// implementation of the target type.
+///
+/// It's the decoding logic used by `#[derive(TypedHeader)]`.
+pub fn decode_from_str<T>(values: GetAll<'_, HeaderValue>) -> Result<T, HeaderDecodeError>
+where
+    T: FromStr,
+    T::Err: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    single_value(values)?
+        .trim()
+        .parse()
+        .map_err(HeaderDecodeError::new)
+}
+
+pub(super) fn to_str(value: &HeaderValue) -> Result<&str, HeaderDecodeError> {
+    value
+        .to_str()
+        .map_err(|_| HeaderDecodeError::new("The header value is not valid UTF-8"))
+}
+
+/// Split the values of a comma-separated list header into their elements,
+/// ignoring commas inside quoted strings as well as empty elements.
+pub(super) fn list_elements<'a>(
+    values: GetAll<'a, HeaderValue>,
+) -> Result<Vec<&'a str>, HeaderDecodeError> {
+    let mut elements = Vec::new();
+    for value in values {
+        let value = to_str(value)?;
+        let mut in_quotes = false;
+        let mut escaped = false;
+        let mut start = 0;
+        for (i, c) in value.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' if in_quotes => escaped = true,
+                '"' => in_quotes = !in_quotes,
+                ',' if !in_quotes => {
+                    elements.push(value[start..i].trim());
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        elements.push(value[start..].trim());
+    }
+    elements.retain(|e| !e.is_empty());
+    Ok(elements)
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+
+    fn headers(values: &[&str]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for value in values {
+            headers.append("x-test", value.parse().unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn list_elements_respect_quotes() {
+        let headers = headers(&[r#"a, "b,c", , "d\",e""#, "f"]);
+        let elements = list_elements(headers.get_all("x-test")).unwrap();
+        assert_eq!(elements, vec!["a", r#""b,c""#, r#""d\",e""#, "f"]);
+    }
+
+    #[test]
+    fn repeated_headers_are_rejected_by_single_value() {
+        let headers = headers(&["1", "2"]);
+        let err = decode_from_str::<u32>(headers.get_all("x-test")).unwrap_err();
+        insta::assert_snapshot!(err, @"Expected a single value, but the header was repeated");
+    }
+
+    #[test]
+    fn from_str_errors_are_surfaced() {
+        let headers = headers(&["abc"]);
+        let err = decode_from_str::<u32>(headers.get_all("x-test")).unwrap_err();
+        insta::assert_snapshot!(err, @"invalid digit found in string");
+    }
+}
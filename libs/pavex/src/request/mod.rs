@@ -4,9 +4,12 @@
 //!
 //! Check out [the guide](https://pavex.dev/docs/guide/request_data/)
 //! for a thorough introduction to request-based data extractors.
+pub use body::Negotiated;
 pub use request_head::RequestHead;
 
 pub mod body;
+pub mod headers;
 pub mod path;
 pub mod query;
 mod request_head;
+pub mod validation;
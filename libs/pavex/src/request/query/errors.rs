@@ -2,20 +2,32 @@
 
 use crate::response::Response;
 
-/// The error returned by [`QueryParams::extract`] when the extraction fails.
+/// The error returned by [`QueryParams::extract`] and [`NestedQueryParams::extract`]
+/// when the extraction fails.
 ///
-/// See [`QueryParams::extract`] and the documentation of each error variant for more details.
+/// See [`QueryParams::extract`], [`NestedQueryParams::extract`] and the documentation of
+/// each error variant for more details.
 ///
 /// Pavex provides [`ExtractQueryParamsError::into_response`] as the default error handler for
 /// this failure.
 ///
 /// [`QueryParams::extract`]: crate::request::query::QueryParams::extract
+/// [`NestedQueryParams::extract`]: crate::request::query::NestedQueryParams::extract
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
 pub enum ExtractQueryParamsError {
     #[error(transparent)]
     /// See [`QueryDeserializationError`] for details.
     QueryDeserializationError(QueryDeserializationError),
+    #[error(transparent)]
+    /// See [`TooManyQueryParameters`] for details.
+    TooManyQueryParameters(#[from] TooManyQueryParameters),
+    #[error(transparent)]
+    /// See [`QueryNestingTooDeep`] for details.
+    QueryNestingTooDeep(#[from] QueryNestingTooDeep),
+    #[error(transparent)]
+    /// See [`InvalidQueryKey`] for details.
+    InvalidQueryKey(#[from] InvalidQueryKey),
 }
 
 impl ExtractQueryParamsError {
@@ -26,22 +38,114 @@ impl ExtractQueryParamsError {
         match self {
             Self::QueryDeserializationError(e) => Response::bad_request()
                 .set_typed_body(format!("Invalid query parameters.\n{:?}", e)),
+            Self::TooManyQueryParameters(_)
+            | Self::QueryNestingTooDeep(_)
+            | Self::InvalidQueryKey(_) => Response::bad_request()
+                .set_typed_body(format!("Invalid query parameters.\n{}", self)),
         }
     }
 }
 
-#[derive(Debug, thiserror::Error)]
+#[derive(thiserror::Error)]
 #[error(transparent)]
 /// Something went wrong when trying to deserialize the percent-decoded query parameters into
-/// the target type you specified—`T` in [`QueryParams<T>`].
+/// the target type you specified—`T` in [`QueryParams<T>`] or [`NestedQueryParams<T>`].
 ///
 /// [`QueryParams<T>`]: crate::request::query::QueryParams
+/// [`NestedQueryParams<T>`]: crate::request::query::NestedQueryParams
 pub struct QueryDeserializationError {
-    inner: serde_path_to_error::Error<serde_html_form::de::Error>,
+    inner: QueryDeserializationErrorRepr,
+}
+
+impl std::fmt::Debug for QueryDeserializationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = f.debug_struct("QueryDeserializationError");
+        match &self.inner {
+            QueryDeserializationErrorRepr::Flat(e) => s.field("inner", e),
+            QueryDeserializationErrorRepr::Nested(e) => s.field("inner", e),
+        };
+        s.finish()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum QueryDeserializationErrorRepr {
+    #[error(transparent)]
+    Flat(serde_path_to_error::Error<serde_html_form::de::Error>),
+    #[error(transparent)]
+    Nested(serde_path_to_error::Error<serde::de::value::Error>),
 }
 
 impl QueryDeserializationError {
     pub(super) fn new(e: serde_path_to_error::Error<serde_html_form::de::Error>) -> Self {
-        Self { inner: e }
+        Self {
+            inner: QueryDeserializationErrorRepr::Flat(e),
+        }
+    }
+
+    pub(super) fn nested(e: serde_path_to_error::Error<serde::de::value::Error>) -> Self {
+        Self {
+            inner: QueryDeserializationErrorRepr::Nested(e),
+        }
+    }
+
+    /// The path to the query parameter that couldn't be deserialized—e.g. `filter.tags[1]`.
+    pub fn path(&self) -> &serde_path_to_error::Path {
+        match &self.inner {
+            QueryDeserializationErrorRepr::Flat(e) => e.path(),
+            QueryDeserializationErrorRepr::Nested(e) => e.path(),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("The query string contains more than {max_parameters} parameters")]
+#[non_exhaustive]
+/// The query string contains more parameters than allowed by
+/// [`NestedQueryLimits::max_parameters`](crate::request::query::NestedQueryLimits::max_parameters).
+pub struct TooManyQueryParameters {
+    /// The maximum number of parameters allowed in the query string.
+    pub max_parameters: usize,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("`{key}` is nested more than {max_depth} levels deep")]
+#[non_exhaustive]
+/// A query parameter is nested deeper than allowed by
+/// [`NestedQueryLimits::max_depth`](crate::request::query::NestedQueryLimits::max_depth).
+pub struct QueryNestingTooDeep {
+    /// The (percent-decoded) key of the offending query parameter.
+    pub key: String,
+    /// The maximum nesting depth allowed for query parameters.
+    pub max_depth: usize,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("`{key}` is not a valid query parameter key: {reason}")]
+#[non_exhaustive]
+/// The key of a query parameter can't be interpreted using the bracket notation.
+///
+/// It happens when the brackets are unbalanced (e.g. `filter[status=open`) or
+/// when the same key is used both for a value and for a nested structure
+/// (e.g. `filter=open&filter[status]=open`).
+pub struct InvalidQueryKey {
+    /// The (percent-decoded) key of the offending query parameter.
+    pub key: String,
+    reason: &'static str,
+}
+
+impl InvalidQueryKey {
+    pub(super) fn malformed(key: String) -> Self {
+        Self {
+            key,
+            reason: "the bracket notation is malformed",
+        }
+    }
+
+    pub(super) fn conflicting(key: String) -> Self {
+        Self {
+            key,
+            reason: "it's used both for a value and for a nested structure",
+        }
     }
 }
@@ -1,6 +1,9 @@
 //! Extract data from the query parameters of incoming requests.
 
 pub mod errors;
+mod nested_deserializer;
+mod nested_query_params;
 mod query_params;
 
+pub use nested_query_params::{NestedQueryLimits, NestedQueryParams};
 pub use query_params::QueryParams;
@@ -0,0 +1,418 @@
+//! Parse a query string that uses the bracket notation (e.g. `filter[tags][]=a`)
+//! into a tree, then deserialize the tree into the type specified by the user.
+use std::borrow::Cow;
+
+use serde::de::value::{Error, MapDeserializer, SeqDeserializer};
+use serde::de::{self, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+
+use super::NestedQueryLimits;
+use super::errors::{
+    ExtractQueryParamsError, InvalidQueryKey, QueryNestingTooDeep, TooManyQueryParameters,
+};
+
+/// A node in the tree built out of the query parameters.
+#[derive(Debug)]
+pub(super) enum Node<'a> {
+    /// The values associated with a key, in the order they appeared in the query string.
+    ///
+    /// It holds more than one value if the key is repeated (e.g. `tag=a&tag=b`)
+    /// or if it uses the `[]` suffix (e.g. `tag[]=a&tag[]=b`).
+    Leaf(Vec<Cow<'a, str>>),
+    /// The entries of a nested structure, in the order they first appeared in the query string.
+    Map(Vec<(Cow<'a, str>, Node<'a>)>),
+}
+
+/// Build a tree out of the query parameters in `query`, while enforcing `limits`.
+pub(super) fn parse<'a>(
+    query: &'a str,
+    limits: &NestedQueryLimits,
+) -> Result<Node<'a>, ExtractQueryParamsError> {
+    let mut root = Vec::new();
+    for (i, (key, value)) in form_urlencoded::parse(query.as_bytes()).enumerate() {
+        if i >= limits.max_parameters {
+            return Err(TooManyQueryParameters {
+                max_parameters: limits.max_parameters,
+            }
+            .into());
+        }
+        let Some(segments) = segments(&key) else {
+            return Err(InvalidQueryKey::malformed(key.into_owned()).into());
+        };
+        // The `[]` suffix is a marker for sequences, it doesn't add another level of nesting.
+        let depth = segments.len() - 1;
+        if depth > limits.max_depth {
+            return Err(QueryNestingTooDeep {
+                key: key.into_owned(),
+                max_depth: limits.max_depth,
+            }
+            .into());
+        }
+        if !insert(&mut root, &segments, value) {
+            return Err(InvalidQueryKey::conflicting(key.into_owned()).into());
+        }
+    }
+    Ok(Node::Map(root))
+}
+
+/// Split a key into its segments, borrowing from the query string if possible.
+fn segments<'a>(key: &Cow<'a, str>) -> Option<Vec<Cow<'a, str>>> {
+    match key {
+        Cow::Borrowed(key) => {
+            let key: &'a str = key;
+            split_key(key).map(|s| s.into_iter().map(Cow::Borrowed).collect())
+        }
+        Cow::Owned(key) => {
+            split_key(key).map(|s| s.into_iter().map(|s| Cow::Owned(s.to_owned())).collect())
+        }
+    }
+}
+
+/// Split a key into its segments—e.g. `filter[tags][]` becomes `["filter", "tags"]`.
+///
+/// It returns `None` if the bracket notation is malformed.
+fn split_key(key: &str) -> Option<Vec<&str>> {
+    let (base, mut rest) = key.split_at(key.find('[').unwrap_or(key.len()));
+    if base.is_empty() && !rest.is_empty() {
+        return None;
+    }
+    let mut segments = vec![base];
+    while !rest.is_empty() {
+        let inner = rest.strip_prefix('[')?;
+        let end = inner.find(']')?;
+        let segment = &inner[..end];
+        rest = &inner[end + 1..];
+        if segment.contains('[') {
+            return None;
+        }
+        if segment.is_empty() {
+            // `[]` is only allowed at the very end of the key.
+            return rest.is_empty().then_some(segments);
+        }
+        segments.push(segment);
+    }
+    Some(segments)
+}
+
+/// Insert `value` at the location identified by `segments`.
+///
+/// It returns `false` if a key is used both for a value and for a nested structure
+/// (e.g. `a=1&a[b]=2`).
+fn insert<'a>(
+    entries: &mut Vec<(Cow<'a, str>, Node<'a>)>,
+    segments: &[Cow<'a, str>],
+    value: Cow<'a, str>,
+) -> bool {
+    let Some((first, rest)) = segments.split_first() else {
+        return true;
+    };
+    let position = entries.iter().position(|(key, _)| key == first);
+    let node = match position {
+        Some(position) => &mut entries[position].1,
+        None => {
+            let node = if rest.is_empty() {
+                Node::Leaf(Vec::new())
+            } else {
+                Node::Map(Vec::new())
+            };
+            entries.push((first.clone(), node));
+            &mut entries.last_mut().unwrap().1
+        }
+    };
+    match (node, rest.is_empty()) {
+        (Node::Leaf(values), true) => {
+            values.push(value);
+            true
+        }
+        (Node::Map(entries), false) => insert(entries, rest, value),
+        _ => false,
+    }
+}
+
+/// Deserialize a [`Node`].
+pub(super) struct NodeDeserializer<'de>(pub(super) Node<'de>);
+
+impl<'de> NodeDeserializer<'de> {
+    /// The value of a single-valued field.
+    fn single_value(values: Vec<Cow<'de, str>>) -> Result<ValueDeserializer<'de>, Error> {
+        let mut values = values.into_iter();
+        match (values.next(), values.next()) {
+            (Some(value), None) => Ok(ValueDeserializer(value)),
+            _ => Err(de::Error::custom(
+                "expected a single value, but the key was repeated",
+            )),
+        }
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for NodeDeserializer<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+macro_rules! forward_to_single_value {
+    ($($method:ident),* $(,)?) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                match self.0 {
+                    Node::Leaf(values) => Self::single_value(values)?.$method(visitor),
+                    Node::Map(_) => self.deserialize_any(visitor),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for NodeDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Node::Leaf(values) if values.len() == 1 => {
+                Self::single_value(values)?.deserialize_any(visitor)
+            }
+            Node::Leaf(values) => self::seq(values, ValueDeserializer, visitor),
+            Node::Map(entries) => {
+                let mut map = MapDeserializer::new(
+                    entries
+                        .into_iter()
+                        .map(|(key, node)| (ValueDeserializer(key), NodeDeserializer(node))),
+                );
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Node::Leaf(values) => self::seq(values, ValueDeserializer, visitor),
+            // Sequences can be specified using explicit indexes—e.g. `a[0]=x&a[1]=y`.
+            Node::Map(entries) => {
+                let indexed: Option<Vec<(usize, Node<'de>)>> = entries
+                    .into_iter()
+                    .map(|(key, node)| key.parse().ok().map(|index| (index, node)))
+                    .collect();
+                let Some(mut indexed) = indexed else {
+                    return Err(de::Error::invalid_type(de::Unexpected::Map, &visitor));
+                };
+                indexed.sort_by_key(|(index, _)| *index);
+                self::seq(
+                    indexed.into_iter().map(|(_, node)| node),
+                    NodeDeserializer,
+                    visitor,
+                )
+            }
+        }
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Node::Leaf(values) if values.len() == 1 => {
+                Self::single_value(values)?.deserialize_option(visitor)
+            }
+            node => visitor.visit_some(NodeDeserializer(node)),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Node::Leaf(values) => {
+                Self::single_value(values)?.deserialize_enum(name, variants, visitor)
+            }
+            Node::Map(_) => self.deserialize_any(visitor),
+        }
+    }
+
+    forward_to_single_value!(
+        deserialize_bool,
+        deserialize_i8,
+        deserialize_i16,
+        deserialize_i32,
+        deserialize_i64,
+        deserialize_i128,
+        deserialize_u8,
+        deserialize_u16,
+        deserialize_u32,
+        deserialize_u64,
+        deserialize_u128,
+        deserialize_f32,
+        deserialize_f64,
+        deserialize_char,
+        deserialize_str,
+        deserialize_string,
+        deserialize_bytes,
+        deserialize_byte_buf,
+        deserialize_identifier,
+    );
+
+    forward_to_deserialize_any! {
+        unit unit_struct map struct ignored_any
+    }
+}
+
+fn seq<'de, T, D, V>(
+    elements: impl IntoIterator<Item = T>,
+    to_deserializer: impl Fn(T) -> D,
+    visitor: V,
+) -> Result<V::Value, Error>
+where
+    D: IntoDeserializer<'de, Error>,
+    V: Visitor<'de>,
+{
+    let mut seq = SeqDeserializer::new(elements.into_iter().map(to_deserializer));
+    let value = visitor.visit_seq(&mut seq)?;
+    seq.end()?;
+    Ok(value)
+}
+
+/// Deserialize a single (percent-decoded) value.
+struct ValueDeserializer<'de>(Cow<'de, str>);
+
+impl<'de> IntoDeserializer<'de, Error> for ValueDeserializer<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+macro_rules! parse_value {
+    ($($ty:ident => $method:ident),* $(,)?) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                match self.0.parse::<$ty>() {
+                    Ok(value) => IntoDeserializer::<Error>::into_deserializer(value).$method(visitor),
+                    Err(e) => Err(de::Error::custom(e)),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Cow::Borrowed(value) => visitor.visit_borrowed_str(value),
+            Cow::Owned(value) => visitor.visit_string(value),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.0.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(IntoDeserializer::<Error>::into_deserializer(self.0))
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    parse_value!(
+        bool => deserialize_bool,
+        i8 => deserialize_i8,
+        i16 => deserialize_i16,
+        i32 => deserialize_i32,
+        i64 => deserialize_i64,
+        i128 => deserialize_i128,
+        u8 => deserialize_u8,
+        u16 => deserialize_u16,
+        u32 => deserialize_u32,
+        u64 => deserialize_u64,
+        u128 => deserialize_u128,
+        f32 => deserialize_f32,
+        f64 => deserialize_f64,
+        char => deserialize_char,
+    );
+
+    forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
@@ -0,0 +1,265 @@
+use pavex_macros::request_scoped;
+
+use crate::request::RequestHead;
+
+use super::errors::{ExtractQueryParamsError, QueryDeserializationError};
+use super::nested_deserializer::{NodeDeserializer, parse};
+
+/// Extract (typed) query parameters from the query of an incoming request,
+/// with support for nested structures and sequences.
+///
+/// It's an alternative to [`QueryParams`](super::QueryParams) that understands the
+/// bracket notation:
+///
+/// - `filter[status]=open` sets the `status` field of the nested `filter` struct
+/// - `tags[]=a&tags[]=b` and `tags=a&tags=b` both set `tags` to `["a", "b"]`
+/// - `ids[1]=b&ids[0]=a` sets `ids` to `["a", "b"]`, using the explicit indexes
+///
+/// The structure of the query string is bounded by [`NestedQueryLimits`],
+/// to protect your application against maliciously crafted requests.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::request::query::NestedQueryParams;
+///
+/// #[derive(serde::Deserialize)]
+/// pub struct Search {
+///     filter: Filter,
+/// }
+///
+/// #[derive(serde::Deserialize)]
+/// pub struct Filter {
+///     status: String,
+///     #[serde(default)]
+///     tags: Vec<String>,
+/// }
+///
+/// // Matches `?filter[status]=open&filter[tags][]=a&filter[tags][]=b`.
+/// pub fn search(params: &NestedQueryParams<Search>) -> String {
+///     let filter = &params.0.filter;
+///     format!("Status: {}, tags: {}", filter.status, filter.tags.join(", "))
+/// }
+/// ```
+///
+/// # Sequences
+///
+/// The `[]` suffix is optional: repeating a key is enough to build a sequence.
+/// A sequence of structs must use explicit indexes—e.g. `items[0][id]=1&items[1][id]=2`.
+#[doc(alias = "NestedQuery")]
+#[derive(Debug)]
+pub struct NestedQueryParams<T>(
+    /// The extracted query parameters, deserialized into `T`, the type you specified.
+    pub T,
+);
+
+impl<T> NestedQueryParams<T> {
+    /// The default constructor for [`NestedQueryParams`].
+    ///
+    /// The extraction can fail for a number of reasons:
+    ///
+    /// - the query string exceeds the [`NestedQueryLimits`]
+    /// - a key uses malformed bracket notation (e.g. `filter[status=open`)
+    /// - a key is used both for a value and for a nested structure
+    ///   (e.g. `filter=open&filter[status]=open`)
+    /// - the query parameters can't be deserialized into `T`
+    ///
+    /// In all of the above cases, an [`ExtractQueryParamsError`] is returned.
+    #[request_scoped(
+        error_handler = "crate::request::query::errors::ExtractQueryParamsError::into_response"
+    )]
+    pub fn extract<'request>(
+        request_head: &'request RequestHead,
+        limits: NestedQueryLimits,
+    ) -> Result<Self, ExtractQueryParamsError>
+    where
+        T: serde::Deserialize<'request>,
+    {
+        let query = request_head.target.query().unwrap_or_default();
+        let tree = parse(query, &limits)?;
+        serde_path_to_error::deserialize(NodeDeserializer(tree))
+            .map(NestedQueryParams)
+            .map_err(QueryDeserializationError::nested)
+            .map_err(ExtractQueryParamsError::QueryDeserializationError)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+/// Upper limits on the structure of the query strings parsed by [`NestedQueryParams`].
+///
+/// # Custom limits
+///
+/// Register a custom constructor for `NestedQueryLimits` in your blueprint to override the defaults:
+///
+/// ```rust
+/// use pavex::blueprint::Blueprint;
+/// use pavex::f;
+/// use pavex::request::query::NestedQueryLimits;
+///
+/// pub fn query_limits() -> NestedQueryLimits {
+///     NestedQueryLimits {
+///         max_parameters: 50,
+///         max_depth: 2,
+///     }
+/// }
+///
+/// pub fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     bp.request_scoped(f!(crate::query_limits));
+///     // [...]
+///     bp
+/// }
+/// ```
+pub struct NestedQueryLimits {
+    /// The maximum number of `key=value` pairs in the query string.
+    pub max_parameters: usize,
+    /// The maximum number of nesting levels for a key—e.g. `a[b][c]` is two levels deep.
+    ///
+    /// The `[]` suffix doesn't count as a nesting level.
+    pub max_depth: usize,
+}
+
+impl NestedQueryLimits {
+    /// Create a new [`NestedQueryLimits`] using the default limits:
+    /// at most 100 parameters, nested at most 5 levels deep.
+    #[request_scoped]
+    pub fn new() -> NestedQueryLimits {
+        Self::default()
+    }
+}
+
+impl Default for NestedQueryLimits {
+    fn default() -> Self {
+        Self {
+            max_parameters: 100,
+            max_depth: 5,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn extract<'a, T: serde::Deserialize<'a>>(
+        request_head: &'a RequestHead,
+        limits: NestedQueryLimits,
+    ) -> Result<T, ExtractQueryParamsError> {
+        NestedQueryParams::<T>::extract(request_head, limits).map(|p| p.0)
+    }
+
+    fn request_head(query: &str) -> RequestHead {
+        RequestHead {
+            method: http::Method::GET,
+            target: format!("/?{query}").parse().unwrap(),
+            version: http::Version::HTTP_11,
+            headers: http::HeaderMap::new(),
+        }
+    }
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Search<'a> {
+        #[serde(borrow)]
+        filter: Filter<'a>,
+        #[serde(default)]
+        ids: Vec<u32>,
+        page: Option<u32>,
+    }
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Status {
+        Open,
+        Closed,
+    }
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Filter<'a> {
+        status: Status,
+        #[serde(borrow, default)]
+        tags: Vec<Cow<'a, str>>,
+    }
+
+    #[test]
+    fn bracket_notation() {
+        let head = request_head(
+            "filter[status]=open&filter[tags][]=a&filter[tags][]=b%20c&ids[1]=7&ids[0]=3",
+        );
+        let search: Search = extract(&head, NestedQueryLimits::default()).unwrap();
+        assert_eq!(
+            search,
+            Search {
+                filter: Filter {
+                    status: Status::Open,
+                    tags: vec!["a".into(), "b c".into()],
+                },
+                ids: vec![3, 7],
+                page: None,
+            }
+        );
+
+        // Keys and values that don't need to be percent-decoded are borrowed from the request.
+        let head = request_head("filter[status]=open");
+        let map: BTreeMap<&str, BTreeMap<&str, &str>> =
+            extract(&head, NestedQueryLimits::default()).unwrap();
+        assert_eq!(map["filter"]["status"], "open");
+    }
+
+    #[test]
+    fn repeated_keys_and_maps() {
+        let head = request_head("filter[status]=closed&filter[tags]=a&filter[tags]=b&page=2");
+        let search: Search = extract(&head, NestedQueryLimits::default()).unwrap();
+        assert_eq!(search.filter.status, Status::Closed);
+        assert_eq!(search.filter.tags, vec!["a", "b"]);
+        assert_eq!(search.page, Some(2));
+
+        let head = request_head("a[x]=1&a[y]=2");
+        let map: BTreeMap<String, BTreeMap<String, u8>> =
+            extract(&head, NestedQueryLimits::default()).unwrap();
+        assert_eq!(map["a"]["y"], 2);
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_field() {
+        let head = request_head("filter[status]=open&filter[tags][]=a&ids[]=1&ids[]=x");
+        let err = extract::<Search>(&head, NestedQueryLimits::default()).unwrap_err();
+        let ExtractQueryParamsError::QueryDeserializationError(e) = &err else {
+            panic!("Unexpected error: {err:?}");
+        };
+        insta::assert_snapshot!(e, @"ids[1]: invalid digit found in string");
+
+        let head = request_head("filter[status]=pending");
+        let err = extract::<Search>(&head, NestedQueryLimits::default()).unwrap_err();
+        insta::assert_snapshot!(err, @"filter.status: unknown variant `pending`, expected `open` or `closed`");
+        assert_eq!(err.into_response().status(), http::StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn limits_are_enforced() {
+        let limits = NestedQueryLimits {
+            max_parameters: 2,
+            max_depth: 1,
+        };
+        let head = request_head("a=1&b=2&c=3");
+        let err = extract::<BTreeMap<String, String>>(&head, limits).unwrap_err();
+        insta::assert_snapshot!(err, @"The query string contains more than 2 parameters");
+
+        let head = request_head("a[b][c]=1");
+        let err = extract::<BTreeMap<String, String>>(&head, limits).unwrap_err();
+        insta::assert_snapshot!(err, @"`a[b][c]` is nested more than 1 levels deep");
+    }
+
+    #[test]
+    fn invalid_keys() {
+        let head = request_head("filter[status=open");
+        let err = extract::<Search>(&head, NestedQueryLimits::default()).unwrap_err();
+        insta::assert_snapshot!(err, @"`filter[status` is not a valid query parameter key: the bracket notation is malformed");
+
+        let head = request_head("filter=open&filter[status]=open");
+        let err = extract::<Search>(&head, NestedQueryLimits::default()).unwrap_err();
+        insta::assert_snapshot!(err, @"`filter[status]` is not a valid query parameter key: it's used both for a value and for a nested structure");
+    }
+}
@@ -0,0 +1,103 @@
+use std::fmt;
+
+use serde_path_to_error::Segment;
+
+/// The location of a field within a deserialized value—e.g. `address.street`
+/// or `items[2].quantity`.
+///
+/// It uses the same representation as the paths reported by
+/// [`serde_path_to_error`], which Pavex relies on for deserialization errors:
+/// validation errors and deserialization errors point at fields in the same way.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::request::validation::FieldPath;
+///
+/// let path = FieldPath::root().field("items").index(2).field("quantity");
+/// assert_eq!(path.to_string(), "items[2].quantity");
+///
+/// // A `&str` is converted into the path to a top-level field.
+/// let path: FieldPath = "email".into();
+/// assert_eq!(path.to_string(), "email");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FieldPath {
+    segments: Vec<Segment>,
+}
+
+impl FieldPath {
+    /// The path to the value itself, rather than one of its fields.
+    pub fn root() -> Self {
+        Self::default()
+    }
+
+    /// Append a named field (or a map key) to the path.
+    pub fn field(mut self, name: impl Into<String>) -> Self {
+        self.segments.push(Segment::Map { key: name.into() });
+        self
+    }
+
+    /// Append the index of an element in a sequence to the path.
+    pub fn index(mut self, index: usize) -> Self {
+        self.segments.push(Segment::Seq { index });
+        self
+    }
+
+    /// Prepend `prefix` to this path.
+    ///
+    /// It's used to report violations detected by the [`Validate`](super::Validate)
+    /// implementation of a nested value.
+    pub fn prefixed(mut self, prefix: &FieldPath) -> Self {
+        self.segments.splice(0..0, prefix.segments.iter().cloned());
+        self
+    }
+
+    /// Returns `true` if this is the [root path](FieldPath::root).
+    pub fn is_root(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Iterate over the segments of the path.
+    pub fn segments(&self) -> impl ExactSizeIterator<Item = &Segment> {
+        self.segments.iter()
+    }
+}
+
+impl From<&str> for FieldPath {
+    fn from(name: &str) -> Self {
+        Self::root().field(name)
+    }
+}
+
+impl From<String> for FieldPath {
+    fn from(name: String) -> Self {
+        Self::root().field(name)
+    }
+}
+
+impl From<&serde_path_to_error::Path> for FieldPath {
+    fn from(path: &serde_path_to_error::Path) -> Self {
+        Self {
+            segments: path.iter().cloned().collect(),
+        }
+    }
+}
+
+impl fmt::Display for FieldPath {
+    // Mirrors the `Display` implementation of `serde_path_to_error::Path`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.segments.is_empty() {
+            return f.write_str(".");
+        }
+        let mut separator = "";
+        for segment in &self.segments {
+            if !matches!(segment, Segment::Seq { .. }) {
+                f.write_str(separator)?;
+            }
+            write!(f, "{segment}")?;
+            separator = ".";
+        }
+        Ok(())
+    }
+}
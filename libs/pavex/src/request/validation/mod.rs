@@ -0,0 +1,19 @@
+//! Validate the data extracted from incoming requests.
+//!
+//! Wrap an extractor in [`Validated`] to check its output against the rules
+//! you specified via the [`Validate`] trait.
+//! Requests that don't satisfy them are rejected with a `422 Unprocessable Entity`.
+//!
+//! # Guide
+//!
+//! Check out [the guide](https://pavex.dev/docs/guide/request_data/validation/)
+//! for a thorough introduction to validation in Pavex.
+pub use field_path::FieldPath;
+pub use validate::{ExtractedData, Validate};
+pub use validated::Validated;
+pub use validation_error::{ValidationError, Violation};
+
+mod field_path;
+mod validate;
+mod validated;
+mod validation_error;
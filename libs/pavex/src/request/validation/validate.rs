@@ -0,0 +1,127 @@
+use super::{FieldPath, ValidationError};
+
+/// Validation rules for the data extracted from an incoming request.
+///
+/// Implement it for the types you deserialize using Pavex's extractors
+/// (e.g. [`JsonBody`](crate::request::body::JsonBody)) and wrap the extractor
+/// in [`Validated`](super::Validated) to enforce it.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::request::validation::{Validate, ValidationError};
+///
+/// #[derive(serde::Deserialize)]
+/// pub struct NewUser {
+///     username: String,
+///     age: u8,
+/// }
+///
+/// impl Validate for NewUser {
+///     fn validate(&self) -> Result<(), ValidationError> {
+///         let mut error = ValidationError::new();
+///         if !(3..=32).contains(&self.username.len()) {
+///             error.push("username", "must be between 3 and 32 characters long");
+///         }
+///         if self.age < 18 {
+///             error.push("age", "must be at least 18");
+///         }
+///         error.into_result()
+///     }
+/// }
+/// ```
+///
+/// # Nested values
+///
+/// Use [`ValidationError::nest`] to include the violations of a nested value,
+/// with their paths relative to the outer value.
+/// `Validate` is implemented for [`Option`], [`Vec`] and slices of types that
+/// implement `Validate`.
+pub trait Validate {
+    /// Check the value against the validation rules.
+    ///
+    /// It should report **all** violations, rather than stopping at the first one.
+    fn validate(&self) -> Result<(), ValidationError>;
+}
+
+impl<T: Validate + ?Sized> Validate for &T {
+    fn validate(&self) -> Result<(), ValidationError> {
+        T::validate(self)
+    }
+}
+
+impl<T: Validate> Validate for Option<T> {
+    fn validate(&self) -> Result<(), ValidationError> {
+        match self {
+            Some(value) => value.validate(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: Validate> Validate for [T] {
+    fn validate(&self) -> Result<(), ValidationError> {
+        let mut error = ValidationError::new();
+        for (index, element) in self.iter().enumerate() {
+            if let Err(e) = element.validate() {
+                error.nest(FieldPath::root().index(index), e);
+            }
+        }
+        error.into_result()
+    }
+}
+
+impl<T: Validate> Validate for Vec<T> {
+    fn validate(&self) -> Result<(), ValidationError> {
+        self.as_slice().validate()
+    }
+}
+
+/// An extractor whose output can be checked by [`Validated`](super::Validated).
+///
+/// It's implemented by Pavex's deserializing extractors
+/// (e.g. [`JsonBody`](crate::request::body::JsonBody) or
+/// [`QueryParams`](crate::request::query::QueryParams)).
+/// Implement it for your own extractors to use them with [`Validated`](super::Validated).
+pub trait ExtractedData {
+    /// The type of the extracted data.
+    type Data;
+
+    /// A reference to the extracted data.
+    fn data(&self) -> &Self::Data;
+}
+
+macro_rules! impl_extracted_data {
+    ($($(#[$attr:meta])* $extractor:ty),+ $(,)?) => {
+        $(
+            $(#[$attr])*
+            impl<T> ExtractedData for $extractor {
+                type Data = T;
+
+                fn data(&self) -> &T {
+                    &self.0
+                }
+            }
+        )+
+    };
+}
+
+impl_extracted_data!(
+    crate::request::body::JsonBody<T>,
+    crate::request::body::UrlEncodedBody<T>,
+    #[cfg(feature = "msgpack")]
+    crate::request::body::MsgPackBody<T>,
+    #[cfg(feature = "cbor")]
+    crate::request::body::CborBody<T>,
+    crate::request::query::QueryParams<T>,
+    crate::request::query::NestedQueryParams<T>,
+    crate::request::path::PathParams<T>,
+);
+
+impl<T, F> ExtractedData for crate::request::body::Negotiated<T, F> {
+    type Data = T;
+
+    fn data(&self) -> &T {
+        &self.0
+    }
+}
@@ -0,0 +1,147 @@
+use pavex_macros::request_scoped;
+
+use super::{ExtractedData, Validate, ValidationError};
+
+/// Validate the output of another extractor.
+///
+/// `E` is the wrapped extractor—e.g. [`JsonBody<T>`](crate::request::body::JsonBody)
+/// or [`QueryParams<T>`](crate::request::query::QueryParams).
+/// Once `E` has been extracted, its data is checked against the rules specified by
+/// its [`Validate`] implementation.
+/// If any rule is violated, the request is rejected with a `422 Unprocessable Entity`—check
+/// out [`ValidationError::into_response`] for the details.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::request::body::JsonBody;
+/// use pavex::request::validation::{Validate, Validated, ValidationError};
+///
+/// #[derive(serde::Deserialize)]
+/// pub struct NewUser {
+///     username: String,
+/// }
+///
+/// impl Validate for NewUser {
+///     fn validate(&self) -> Result<(), ValidationError> {
+///         let mut error = ValidationError::new();
+///         if self.username.is_empty() {
+///             error.push("username", "must not be empty");
+///         }
+///         error.into_result()
+///     }
+/// }
+///
+/// pub fn sign_up(body: Validated<JsonBody<NewUser>>) -> String {
+///     let user = body.0.0;
+///     format!("Welcome, {}!", user.username)
+/// }
+/// ```
+///
+/// # Ownership
+///
+/// [`Validated::extract`] takes ownership of the wrapped extractor.
+/// Inject `Validated<E>` in your components **instead of** `E`, rather than alongside it.
+#[derive(Debug)]
+pub struct Validated<E>(
+    /// The wrapped extractor, whose data has been successfully validated.
+    pub E,
+);
+
+impl<E> Validated<E> {
+    /// The default constructor for [`Validated`].
+    ///
+    /// It returns a [`ValidationError`] if the data extracted by `E`
+    /// violates any of the rules specified by its [`Validate`] implementation.
+    #[request_scoped(error_handler = "crate::request::validation::ValidationError::into_response")]
+    pub fn extract(extracted: E) -> Result<Self, ValidationError>
+    where
+        E: ExtractedData,
+        E::Data: Validate,
+    {
+        extracted.data().validate()?;
+        Ok(Validated(extracted))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::request::body::JsonBody;
+    use crate::request::validation::{Validate, Validated, ValidationError};
+
+    #[derive(serde::Deserialize, Debug)]
+    struct Order {
+        items: Vec<Item>,
+        note: Option<String>,
+    }
+
+    #[derive(serde::Deserialize, Debug)]
+    struct Item {
+        quantity: u32,
+    }
+
+    impl Validate for Order {
+        fn validate(&self) -> Result<(), ValidationError> {
+            let mut error = ValidationError::new();
+            if self.items.is_empty() {
+                error.push("items", "must not be empty");
+            }
+            if let Err(e) = self.items.validate() {
+                error.nest("items", e);
+            }
+            if self.note.as_ref().is_some_and(|n| n.len() > 5) {
+                error.push("note", "must be at most 5 characters long");
+            }
+            error.into_result()
+        }
+    }
+
+    impl Validate for Item {
+        fn validate(&self) -> Result<(), ValidationError> {
+            let mut error = ValidationError::new();
+            if self.quantity == 0 {
+                error.push("quantity", "must be positive");
+            }
+            error.into_result()
+        }
+    }
+
+    fn order(items: Vec<u32>, note: Option<&str>) -> JsonBody<Order> {
+        JsonBody(Order {
+            items: items
+                .into_iter()
+                .map(|quantity| Item { quantity })
+                .collect(),
+            note: note.map(ToOwned::to_owned),
+        })
+    }
+
+    #[test]
+    fn valid_data_goes_through() {
+        let validated = Validated::extract(order(vec![1, 2], Some("asap"))).unwrap();
+        assert_eq!(validated.0.0.items.len(), 2);
+    }
+
+    #[test]
+    fn all_violations_are_reported() {
+        let err =
+            Validated::extract(order(vec![1, 0, 3, 0], Some("as soon as possible"))).unwrap_err();
+        insta::assert_snapshot!(err, @r"
+        The request data is invalid.
+        - items[1].quantity: must be positive
+        - items[3].quantity: must be positive
+        - note: must be at most 5 characters long
+        ");
+    }
+
+    #[test]
+    fn violations_are_rendered_as_problem_details() {
+        let err = Validated::extract(order(vec![], None)).unwrap_err();
+        let response = err.into_response();
+        assert_eq!(response.status(), http::StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            response.headers()[http::header::CONTENT_TYPE],
+            "application/problem+json"
+        );
+    }
+}
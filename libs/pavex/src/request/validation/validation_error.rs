@@ -0,0 +1,152 @@
+use std::fmt;
+
+use http::HeaderValue;
+use http::header::CONTENT_TYPE;
+
+use crate::response::Response;
+use crate::response::body::Json;
+
+use super::FieldPath;
+
+/// One or more violations of the rules specified via [`Validate`](super::Validate).
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::request::validation::{FieldPath, ValidationError};
+///
+/// let mut error = ValidationError::new();
+/// error.push("name", "must not be empty");
+/// error.push(FieldPath::root().field("tags").index(1), "must be lowercase");
+///
+/// assert_eq!(
+///     error.to_string(),
+///     "The request data is invalid.\n- name: must not be empty\n- tags[1]: must be lowercase"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ValidationError {
+    violations: Vec<Violation>,
+}
+
+/// A validation rule that wasn't satisfied.
+#[derive(Debug, Clone)]
+pub struct Violation {
+    /// The path to the offending field.
+    pub path: FieldPath,
+    /// A human-readable description of the rule that was violated.
+    pub message: String,
+}
+
+impl ValidationError {
+    /// Create a [`ValidationError`] without any violations.
+    ///
+    /// Use [`ValidationError::push`] to record violations, then
+    /// [`ValidationError::into_result`] to return it from
+    /// [`Validate::validate`](super::Validate::validate).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a violation for the field at `path`.
+    pub fn push(&mut self, path: impl Into<FieldPath>, message: impl Into<String>) {
+        self.violations.push(Violation {
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+
+    /// Record all the violations of a nested value, located at `prefix`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::request::validation::ValidationError;
+    ///
+    /// let mut address_error = ValidationError::new();
+    /// address_error.push("postcode", "must not be empty");
+    ///
+    /// let mut error = ValidationError::new();
+    /// error.nest("address", address_error);
+    /// assert_eq!(error.violations()[0].path.to_string(), "address.postcode");
+    /// ```
+    pub fn nest(&mut self, prefix: impl Into<FieldPath>, nested: ValidationError) {
+        let prefix = prefix.into();
+        self.violations
+            .extend(nested.violations.into_iter().map(|violation| Violation {
+                path: violation.path.prefixed(&prefix),
+                message: violation.message,
+            }));
+    }
+
+    /// The recorded violations, in the order they were recorded.
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+
+    /// Returns `true` if no violations have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Returns `Ok(())` if no violations have been recorded, `Err(self)` otherwise.
+    pub fn into_result(self) -> Result<(), ValidationError> {
+        if self.is_empty() { Ok(()) } else { Err(self) }
+    }
+
+    /// Convert a [`ValidationError`] into an HTTP response.
+    ///
+    /// It returns a `422 Unprocessable Entity` with an `application/problem+json` body,
+    /// as specified in [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457).
+    /// The violations are listed in the `errors` extension member:
+    ///
+    /// ```json
+    /// {
+    ///   "type": "about:blank",
+    ///   "title": "Unprocessable Entity",
+    ///   "status": 422,
+    ///   "detail": "The request data is invalid.",
+    ///   "errors": [
+    ///     { "field": "username", "message": "must not be empty" }
+    ///   ]
+    /// }
+    /// ```
+    pub fn into_response(&self) -> Response {
+        let errors: Vec<_> = self
+            .violations
+            .iter()
+            .map(|violation| {
+                serde_json::json!({
+                    "field": violation.path.to_string(),
+                    "message": violation.message,
+                })
+            })
+            .collect();
+        let problem = serde_json::json!({
+            "type": "about:blank",
+            "title": "Unprocessable Entity",
+            "status": 422,
+            "detail": "The request data is invalid.",
+            "errors": errors,
+        });
+        let body = Json::new(problem).expect("A JSON value can always be serialized");
+        Response::unprocessable_entity()
+            .set_typed_body(body)
+            .insert_header(
+                CONTENT_TYPE,
+                HeaderValue::from_static("application/problem+json"),
+            )
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The request data is invalid.")?;
+        for violation in &self.violations {
+            write!(f, "\n- {}: {}", violation.path, violation.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
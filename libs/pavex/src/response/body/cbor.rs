@@ -0,0 +1,100 @@
+use bytes::Bytes;
+use http_body_util::Full;
+use mime::Mime;
+
+use crate::http::HeaderValue;
+use crate::request::body::cbor::is_cbor;
+use crate::serialization::{Format, FormatError, SerializeFormat};
+
+use super::TypedBody;
+
+/// A [`Response`](crate::response::Response) body with `Content-Type` set to
+/// `application/cbor`.
+///
+/// It's only available if the `cbor` feature is enabled.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::response::{Response, body::Cbor};
+/// use pavex::http::header::CONTENT_TYPE;
+///
+/// #[derive(serde::Serialize)]
+/// struct HomeDetails {
+///     city: String,
+///     postcode: String,
+/// }
+///
+/// let details = HomeDetails {
+///     city: "London".into(),
+///     postcode: "N5 2EF".into(),
+/// };
+/// let body = Cbor::new(details).expect("Failed to serialize the response body");
+/// let response = Response::ok().set_typed_body(body);
+///
+/// assert_eq!(response.headers()[CONTENT_TYPE], "application/cbor");
+/// ```
+///
+/// # Content negotiation
+///
+/// `Cbor` implements [`Format`] and [`SerializeFormat`]: you can use it with
+/// [`ResponseFormat`](crate::response::body::ResponseFormat).
+/// It doesn't implement [`DeserializeFormat`](crate::serialization::DeserializeFormat),
+/// since CBOR payloads can only be deserialized into owned types—use
+/// [`CborBody`](crate::request::body::CborBody) to parse CBOR request bodies.
+pub struct Cbor(Bytes);
+
+impl Cbor {
+    /// Build a new [`Cbor`] response by serializing to CBOR an instance of type `T`.
+    ///
+    /// It returns an error if serialization fails.
+    pub fn new<T>(value: T) -> Result<Self, CborSerializationError>
+    where
+        T: serde::Serialize,
+    {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&value, &mut bytes).map_err(CborSerializationError)?;
+        Ok(Self(bytes.into()))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to serialize the value as CBOR: {0:?}")]
+/// The error returned by [`Cbor::new`] when the serialization into CBOR fails.
+pub struct CborSerializationError(ciborium::ser::Error<std::io::Error>);
+
+impl TypedBody for Cbor {
+    type Body = Full<Bytes>;
+
+    fn content_type(&self) -> HeaderValue {
+        HeaderValue::from_static("application/cbor")
+    }
+
+    fn body(self) -> Self::Body {
+        Full::new(self.0)
+    }
+}
+
+impl Format for Cbor {
+    fn media_type() -> Mime {
+        "application/cbor"
+            .parse()
+            .expect("`application/cbor` is a valid media type")
+    }
+
+    /// `application/cbor` or another `application/*+cbor` media type.
+    fn supports(media_type: &Mime) -> bool {
+        is_cbor(media_type)
+    }
+}
+
+impl SerializeFormat for Cbor {
+    fn serialize<T>(value: &T) -> Result<Bytes, FormatError>
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(value, &mut bytes)?;
+        Ok(bytes.into())
+    }
+}
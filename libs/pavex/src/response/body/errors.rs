@@ -1,2 +1,63 @@
 //! Errors that can arise when building a [`Response`](crate::response::Response) body.
+#[cfg(feature = "cbor")]
+pub use super::cbor::CborSerializationError;
 pub use super::json::JsonSerializationError;
+#[cfg(feature = "msgpack")]
+pub use super::msgpack::MsgPackSerializationError;
+
+use crate::request::headers::errors::InvalidHeader;
+use crate::response::Response;
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+/// The error returned by [`ResponseFormat::extract`] when the negotiation fails.
+///
+/// [`ResponseFormat::extract`]: crate::response::body::ResponseFormat::extract
+pub enum NegotiateResponseFormatError {
+    #[error(transparent)]
+    /// The `Accept` header couldn't be parsed.
+    InvalidAcceptHeader(#[from] InvalidHeader),
+    #[error(transparent)]
+    /// See [`NotAcceptable`] for details.
+    NotAcceptable(#[from] NotAcceptable),
+}
+
+impl NegotiateResponseFormatError {
+    /// Convert a [`NegotiateResponseFormatError`] into an HTTP response.
+    ///
+    /// It returns a `400 Bad Request` if the `Accept` header is invalid,
+    /// a `406 Not Acceptable` otherwise.
+    pub fn into_response(&self) -> Response {
+        match self {
+            NegotiateResponseFormatError::InvalidAcceptHeader(_) => Response::bad_request(),
+            NegotiateResponseFormatError::NotAcceptable(_) => Response::not_acceptable(),
+        }
+        .set_typed_body(format!("{}", self))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "None of the media types accepted by the client is supported by this endpoint. Supported media types: {}",
+    .supported.iter().map(|m| format!("`{m}`")).collect::<Vec<_>>().join(", ")
+)]
+#[non_exhaustive]
+/// None of the formats supported by the endpoint is acceptable to the client,
+/// according to its `Accept` header.
+pub struct NotAcceptable {
+    /// The media types supported by the endpoint.
+    pub supported: Vec<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to serialize the response body as `{media_type}`")]
+#[non_exhaustive]
+/// The error returned by [`ResponseFormat::body`] when serialization fails.
+///
+/// [`ResponseFormat::body`]: crate::response::body::ResponseFormat::body
+pub struct NegotiatedSerializationError {
+    /// The media type of the format that was used to serialize the body.
+    pub media_type: String,
+    #[source]
+    pub(super) source: crate::serialization::FormatError,
+}
@@ -1,8 +1,9 @@
 use bytes::Bytes;
 use http_body_util::Full;
-use mime::APPLICATION_JSON;
+use mime::{APPLICATION_JSON, Mime};
 
 use crate::http::HeaderValue;
+use crate::serialization::{DeserializeFormat, Format, FormatError, SerializeFormat};
 
 use super::TypedBody;
 
@@ -30,6 +31,12 @@ use super::TypedBody;
 ///
 /// assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
 /// ```
+///
+/// # Content negotiation
+///
+/// `Json` implements [`Format`], [`DeserializeFormat`] and [`SerializeFormat`]:
+/// you can use it with [`Negotiated`](crate::request::body::Negotiated) and
+/// [`ResponseFormat`](crate::response::body::ResponseFormat).
 pub struct Json(Bytes);
 
 impl Json {
@@ -61,3 +68,34 @@ impl TypedBody for Json {
         Full::new(self.0)
     }
 }
+
+impl Format for Json {
+    fn media_type() -> Mime {
+        APPLICATION_JSON
+    }
+
+    /// `application/json` or another `application/*+json` media type.
+    fn supports(media_type: &Mime) -> bool {
+        media_type.type_() == mime::APPLICATION
+            && (media_type.subtype() == mime::JSON || media_type.suffix() == Some(mime::JSON))
+    }
+}
+
+impl DeserializeFormat for Json {
+    fn deserialize<'de, T>(bytes: &'de [u8]) -> Result<T, FormatError>
+    where
+        T: serde::Deserialize<'de>,
+    {
+        let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+        Ok(serde_path_to_error::deserialize(&mut deserializer)?)
+    }
+}
+
+impl SerializeFormat for Json {
+    fn serialize<T>(value: &T) -> Result<Bytes, FormatError>
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        Ok(serde_json::to_vec(value)?.into())
+    }
+}
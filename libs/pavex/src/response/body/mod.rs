@@ -3,14 +3,24 @@
 //! Check out [`Response::set_typed_body`] for more details.
 //!
 //! [`Response::set_typed_body`]: crate::response::Response::set_typed_body
+#[cfg(feature = "cbor")]
+pub use cbor::Cbor;
 pub use html::Html;
 pub use json::Json;
+#[cfg(feature = "msgpack")]
+pub use msgpack::MsgPack;
+pub use negotiated::{NegotiatedBody, ResponseFormat};
 pub use typed_body::TypedBody;
 
 pub(super) mod body_;
 mod bytes;
+#[cfg(feature = "cbor")]
+mod cbor;
 mod html;
 mod json;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+mod negotiated;
 mod plain_text;
 pub mod raw;
 
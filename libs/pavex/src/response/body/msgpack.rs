@@ -0,0 +1,108 @@
+use bytes::Bytes;
+use http_body_util::Full;
+use mime::Mime;
+
+use crate::http::HeaderValue;
+use crate::request::body::msgpack::is_msgpack;
+use crate::serialization::{DeserializeFormat, Format, FormatError, SerializeFormat};
+
+use super::TypedBody;
+
+/// A [`Response`](crate::response::Response) body with `Content-Type` set to
+/// `application/msgpack`.
+///
+/// It's only available if the `msgpack` feature is enabled.
+///
+/// Structs are serialized as maps, keyed by field name.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::response::{Response, body::MsgPack};
+/// use pavex::http::header::CONTENT_TYPE;
+///
+/// #[derive(serde::Serialize)]
+/// struct HomeDetails {
+///     city: String,
+///     postcode: String,
+/// }
+///
+/// let details = HomeDetails {
+///     city: "London".into(),
+///     postcode: "N5 2EF".into(),
+/// };
+/// let body = MsgPack::new(details).expect("Failed to serialize the response body");
+/// let response = Response::ok().set_typed_body(body);
+///
+/// assert_eq!(response.headers()[CONTENT_TYPE], "application/msgpack");
+/// ```
+///
+/// # Content negotiation
+///
+/// `MsgPack` implements [`Format`], [`DeserializeFormat`] and [`SerializeFormat`]:
+/// you can use it with [`Negotiated`](crate::request::body::Negotiated) and
+/// [`ResponseFormat`](crate::response::body::ResponseFormat).
+pub struct MsgPack(Bytes);
+
+impl MsgPack {
+    /// Build a new [`MsgPack`] response by serializing to MessagePack an instance of type `T`.
+    ///
+    /// It returns an error if serialization fails.
+    pub fn new<T>(value: T) -> Result<Self, MsgPackSerializationError>
+    where
+        T: serde::Serialize,
+    {
+        let bytes = rmp_serde::to_vec_named(&value).map_err(MsgPackSerializationError)?;
+        Ok(Self(bytes.into()))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+/// The error returned by [`MsgPack::new`] when the serialization into MessagePack fails.
+pub struct MsgPackSerializationError(rmp_serde::encode::Error);
+
+impl TypedBody for MsgPack {
+    type Body = Full<Bytes>;
+
+    fn content_type(&self) -> HeaderValue {
+        HeaderValue::from_static("application/msgpack")
+    }
+
+    fn body(self) -> Self::Body {
+        Full::new(self.0)
+    }
+}
+
+impl Format for MsgPack {
+    fn media_type() -> Mime {
+        "application/msgpack"
+            .parse()
+            .expect("`application/msgpack` is a valid media type")
+    }
+
+    /// `application/msgpack`, `application/vnd.msgpack`, `application/x-msgpack`
+    /// or another `application/*+msgpack` media type.
+    fn supports(media_type: &Mime) -> bool {
+        is_msgpack(media_type)
+    }
+}
+
+impl DeserializeFormat for MsgPack {
+    fn deserialize<'de, T>(bytes: &'de [u8]) -> Result<T, FormatError>
+    where
+        T: serde::Deserialize<'de>,
+    {
+        let mut deserializer = rmp_serde::Deserializer::from_read_ref(bytes);
+        Ok(serde_path_to_error::deserialize(&mut deserializer)?)
+    }
+}
+
+impl SerializeFormat for MsgPack {
+    fn serialize<T>(value: &T) -> Result<Bytes, FormatError>
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        Ok(rmp_serde::to_vec_named(value)?.into())
+    }
+}
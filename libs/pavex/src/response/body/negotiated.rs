@@ -0,0 +1,280 @@
+use std::marker::PhantomData;
+
+use bytes::Bytes;
+use http_body_util::Full;
+use mime::Mime;
+use pavex_macros::request_scoped;
+
+use crate::http::HeaderValue;
+use crate::request::RequestHead;
+use crate::request::headers::errors::InvalidHeader;
+use crate::request::headers::{Accept, TypedHeader};
+use crate::serialization::SerializeFormats;
+
+use super::TypedBody;
+use super::errors::{NegotiateResponseFormatError, NegotiatedSerializationError, NotAcceptable};
+
+/// The format of the response body, negotiated with the client via the `Accept` header.
+///
+/// `F` is the list of formats supported by the endpoint—either a single format
+/// (e.g. [`Json`](super::Json)) or a tuple of formats (e.g. `(Json, MsgPack)`), in order of
+/// preference.
+/// The format with the highest weight (`q` value) in the `Accept` header is selected;
+/// ties are broken using the order of `F`.
+/// The first format in `F` is selected if the `Accept` header is missing.
+///
+/// The request is rejected with a `406 Not Acceptable` if none of the formats
+/// is acceptable to the client.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::response::Response;
+/// use pavex::response::body::{Json, ResponseFormat};
+/// use pavex::response::body::errors::NegotiatedSerializationError;
+///
+/// #[derive(serde::Serialize)]
+/// pub struct Order {
+///     id: u64,
+/// }
+///
+/// pub fn get_order(format: &ResponseFormat<Json>) -> Result<Response, NegotiatedSerializationError> {
+///     let order = Order { id: 1 };
+///     Ok(Response::ok().set_typed_body(format.body(&order)?))
+/// }
+/// ```
+///
+/// # Caching
+///
+/// The response body depends on the `Accept` header of the request:
+/// remember to include `Accept` in the `Vary` header of your response if it can be cached.
+pub struct ResponseFormat<F> {
+    index: usize,
+    media_type: Mime,
+    formats: PhantomData<fn() -> F>,
+}
+
+impl<F> std::fmt::Debug for ResponseFormat<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResponseFormat")
+            .field("media_type", &self.media_type)
+            .finish()
+    }
+}
+
+impl<F> ResponseFormat<F> {
+    /// The default constructor for [`ResponseFormat`].
+    ///
+    /// It fails if the `Accept` header is invalid or if none of the formats
+    /// in `F` is acceptable to the client.
+    #[request_scoped(
+        error_handler = "crate::response::body::errors::NegotiateResponseFormatError::into_response"
+    )]
+    pub fn extract(request_head: &RequestHead) -> Result<Self, NegotiateResponseFormatError>
+    where
+        F: SerializeFormats,
+    {
+        let values = request_head.headers.get_all(Accept::NAME);
+        let accept = if values.iter().next().is_some() {
+            let accept = Accept::decode(values).map_err(|source| InvalidHeader {
+                name: Accept::NAME,
+                source,
+            })?;
+            Some(accept)
+        } else {
+            None
+        };
+        let media_types = F::media_types();
+        let Some(index) = select(accept.as_ref(), &media_types, F::supports) else {
+            return Err(NotAcceptable {
+                supported: media_types.iter().map(|m| m.to_string()).collect(),
+            }
+            .into());
+        };
+        Ok(Self {
+            index,
+            media_type: media_types[index].clone(),
+            formats: PhantomData,
+        })
+    }
+
+    /// The media type of the selected format.
+    pub fn media_type(&self) -> &Mime {
+        &self.media_type
+    }
+
+    /// Serialize `value` using the selected format.
+    pub fn body<T>(&self, value: &T) -> Result<NegotiatedBody, NegotiatedSerializationError>
+    where
+        T: serde::Serialize + ?Sized,
+        F: SerializeFormats,
+    {
+        let bytes =
+            F::serialize(self.index, value).map_err(|source| NegotiatedSerializationError {
+                media_type: self.media_type.essence_str().to_owned(),
+                source,
+            })?;
+        let content_type = HeaderValue::try_from(self.media_type.as_ref())
+            .expect("A media type is always a valid header value");
+        Ok(NegotiatedBody {
+            content_type,
+            bytes,
+        })
+    }
+}
+
+/// Pick the index of the format that the client prefers.
+fn select(
+    accept: Option<&Accept>,
+    media_types: &[Mime],
+    supports: impl Fn(usize, &Mime) -> bool,
+) -> Option<usize> {
+    let Some(accept) = accept.filter(|a| !a.0.is_empty()) else {
+        return (!media_types.is_empty()).then_some(0);
+    };
+    let mut best: Option<(usize, f32)> = None;
+    for (index, media_type) in media_types.iter().enumerate() {
+        // The weight is determined by the most specific media range that matches.
+        let quality = accept
+            .0
+            .iter()
+            .filter_map(|item| {
+                let range = &item.value;
+                let specificity = if range.type_() == mime::STAR {
+                    0
+                } else if range.subtype() == mime::STAR {
+                    if range.type_() != media_type.type_() {
+                        return None;
+                    }
+                    1
+                } else if supports(index, range) {
+                    2
+                } else {
+                    return None;
+                };
+                Some((specificity, item.quality))
+            })
+            .max_by(|a, b| a.0.cmp(&b.0))
+            .map(|(_, quality)| quality)
+            .filter(|quality| *quality > 0.0 && best.is_none_or(|(_, best)| *quality > best));
+        if let Some(quality) = quality {
+            best = Some((index, quality));
+        }
+    }
+    best.map(|(index, _)| index)
+}
+
+/// A [`Response`](crate::response::Response) body serialized using the format selected
+/// by [`ResponseFormat`].
+///
+/// Its `Content-Type` is set to the media type of the selected format.
+#[derive(Debug)]
+pub struct NegotiatedBody {
+    content_type: HeaderValue,
+    bytes: Bytes,
+}
+
+impl TypedBody for NegotiatedBody {
+    type Body = Full<Bytes>;
+
+    fn content_type(&self) -> HeaderValue {
+        self.content_type.clone()
+    }
+
+    fn body(self) -> Self::Body {
+        Full::new(self.bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+    use http::header::ACCEPT;
+
+    use super::*;
+    use crate::response::body::Json;
+    use crate::serialization::{Format, FormatError, SerializeFormat};
+
+    /// A toy format, to exercise dispatching.
+    struct Text;
+
+    impl Format for Text {
+        fn media_type() -> Mime {
+            mime::TEXT_PLAIN
+        }
+    }
+
+    impl SerializeFormat for Text {
+        fn serialize<T>(value: &T) -> Result<Bytes, FormatError>
+        where
+            T: serde::Serialize + ?Sized,
+        {
+            Ok(serde_json::to_value(value)?.to_string().into())
+        }
+    }
+
+    fn negotiate<F: SerializeFormats>(
+        accept: Option<&str>,
+    ) -> Result<ResponseFormat<F>, NegotiateResponseFormatError> {
+        let mut headers = HeaderMap::new();
+        if let Some(accept) = accept {
+            headers.insert(ACCEPT, accept.parse().unwrap());
+        }
+        let request_head = RequestHead {
+            method: http::Method::GET,
+            target: "/".parse().unwrap(),
+            version: http::Version::HTTP_11,
+            headers,
+        };
+        ResponseFormat::<F>::extract(&request_head)
+    }
+
+    fn selected(accept: Option<&str>) -> String {
+        negotiate::<(Json, Text)>(accept)
+            .unwrap()
+            .media_type()
+            .to_string()
+    }
+
+    #[test]
+    fn the_first_format_is_the_default() {
+        assert_eq!(selected(None), "application/json");
+        assert_eq!(selected(Some("*/*")), "application/json");
+    }
+
+    #[test]
+    fn weights_are_honored() {
+        assert_eq!(
+            selected(Some("application/json;q=0.5, text/plain")),
+            "text/plain"
+        );
+        assert_eq!(
+            selected(Some("text/*;q=0.9, application/*;q=0.8")),
+            "text/plain"
+        );
+        // The most specific range wins.
+        assert_eq!(selected(Some("*/*, application/json;q=0")), "text/plain");
+        // `+json` media types are handled by `Json`.
+        assert_eq!(
+            selected(Some("text/plain;q=0.1, application/problem+json")),
+            "application/json"
+        );
+    }
+
+    #[test]
+    fn nothing_acceptable() {
+        let err = negotiate::<(Json, Text)>(Some("application/xml")).unwrap_err();
+        insta::assert_snapshot!(err, @"None of the media types accepted by the client is supported by this endpoint. Supported media types: `application/json`, `text/plain`");
+        assert_eq!(
+            err.into_response().status(),
+            http::StatusCode::NOT_ACCEPTABLE
+        );
+    }
+
+    #[test]
+    fn the_body_uses_the_selected_format() {
+        let format = negotiate::<(Json, Text)>(Some("text/plain")).unwrap();
+        let body = format.body(&"hi").unwrap();
+        assert_eq!(body.content_type(), "text/plain");
+    }
+}
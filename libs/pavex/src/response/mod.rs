@@ -8,3 +8,4 @@ pub use response_::{Response, ResponseHead};
 pub mod body;
 mod into_response;
 mod response_;
+pub mod sse;
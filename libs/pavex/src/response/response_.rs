@@ -3,7 +3,7 @@ use http::header::CONTENT_TYPE;
 use http_body_util::Empty;
 
 use crate::http::StatusCode;
-use crate::http::{HeaderMap, Version};
+use crate::http::{Extensions, HeaderMap, Version};
 
 use super::ResponseBody;
 use super::body::TypedBody;
@@ -48,11 +48,12 @@ pub struct Response {
 #[derive(Debug)]
 /// All the information that is transmitted as part of an HTTP [`Response`] ahead of the body.
 ///
-/// It includes the status code, the HTTP version, and the headers.
+/// It includes the status code, the HTTP version, the headers and the extensions.
 pub struct ResponseHead {
     status: StatusCode,
     version: Version,
     headers: HeaderMap,
+    extensions: Extensions,
 }
 
 impl Response {
@@ -360,6 +361,28 @@ impl Response {
     pub fn headers_mut(&mut self) -> &mut crate::http::HeaderMap {
         self.inner.headers_mut()
     }
+
+    /// Get a mutable reference to the [`Response`] extensions.
+    ///
+    /// Extensions are typed values attached to the response, to pass information
+    /// along to post-processing middlewares. They are never sent to the client.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::response::Response;
+    ///
+    /// #[derive(Clone)]
+    /// struct CacheHit(bool);
+    ///
+    /// let mut response = Response::ok();
+    /// response.extensions_mut().insert(CacheHit(true));
+    ///
+    /// assert!(response.extensions().get::<CacheHit>().unwrap().0);
+    /// ```
+    pub fn extensions_mut(&mut self) -> &mut crate::http::Extensions {
+        self.inner.extensions_mut()
+    }
 }
 
 impl Response {
@@ -438,6 +461,16 @@ impl Response {
         self.inner.headers()
     }
 
+    /// Get a reference to the [`Response`] extensions.
+    ///
+    /// # Mutation
+    ///
+    /// Check out [`Response::extensions_mut`] if you need to modify the
+    /// [`Response`] extensions.
+    pub fn extensions(&self) -> &crate::http::Extensions {
+        self.inner.extensions()
+    }
+
     /// Get a reference to the [`Response`] body.
     ///
     /// # Mutation
@@ -505,6 +538,7 @@ impl From<ResponseHead> for http::response::Parts {
             status,
             version,
             headers,
+            extensions,
         } = head;
         // Is there no better way to do create a new `Parts` instance?
         let (mut parts, _) = http::response::Response::builder()
@@ -514,6 +548,7 @@ impl From<ResponseHead> for http::response::Parts {
         parts.status = status;
         parts.version = version;
         parts.headers = headers;
+        parts.extensions = extensions;
         parts
     }
 }
@@ -524,12 +559,14 @@ impl From<http::response::Parts> for ResponseHead {
             status,
             version,
             headers,
+            extensions,
             ..
         } = parts;
         Self {
             status,
             version,
             headers,
+            extensions,
         }
     }
 }
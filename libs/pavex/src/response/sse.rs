@@ -0,0 +1,522 @@
+//! Push a stream of events to the client, using [Server-Sent Events](https://html.spec.whatwg.org/multipage/server-sent-events.html).
+//!
+//! Return an [`Sse`] response from your request handler, wrapping a [`Stream`] of [`Event`]s.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::convert::Infallible;
+//! use std::time::Duration;
+//! use futures_util::StreamExt;
+//! use pavex::response::sse::{Event, Sse};
+//!
+//! pub fn live_updates() -> Sse<impl futures_util::Stream<Item = Result<Event, Infallible>>> {
+//!     let events = futures_util::stream::iter(1..=3).map(|i| {
+//!         Ok(Event::new().set_event("tick").set_data(i.to_string()))
+//!     });
+//!     Sse::new(events).set_keep_alive_interval(Some(Duration::from_secs(10)))
+//! }
+//! ```
+//!
+//! # Graceful shutdown
+//!
+//! Event streams are often long-lived, if not infinite.
+//! When the [`Server`](crate::server::Server) starts shutting down, [`Sse`] ends the stream
+//! on its own: the client receives the events sent up to that point and the connection can
+//! be closed without waiting for the shutdown timeout to expire.
+//! Clients will usually reconnect, reaching another instance of your application.
+use std::fmt::Write as _;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use futures_util::Stream;
+use http::HeaderValue;
+use http::header::{CACHE_CONTROL, CONTENT_TYPE};
+use http_body::Frame;
+use pin_project_lite::pin_project;
+use tokio::time::Sleep;
+
+use super::body::raw::RawBody;
+use super::{IntoResponse, Response};
+
+/// A response that streams [`Event`]s to the client, using the
+/// [Server-Sent Events](https://html.spec.whatwg.org/multipage/server-sent-events.html) protocol.
+///
+/// Its `Content-Type` is set to `text/event-stream` and caching is disabled via
+/// `Cache-Control: no-cache`.
+///
+/// A keep-alive comment is sent if no event has been sent for a while (15 seconds, by default),
+/// to prevent proxies from closing the connection.
+/// Check out [`Sse::set_keep_alive_interval`] to customize it.
+///
+/// Check out the [module-level documentation](self) for an example.
+///
+/// # Errors
+///
+/// The stream is terminated if it yields an error: the error is reported to the server,
+/// which closes the connection abruptly.
+pub struct Sse<S> {
+    stream: S,
+    keep_alive_interval: Option<Duration>,
+}
+
+impl<S> std::fmt::Debug for Sse<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sse")
+            .field("keep_alive_interval", &self.keep_alive_interval)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, E> Sse<S>
+where
+    S: Stream<Item = Result<Event, E>>,
+{
+    /// Stream the events yielded by `stream` to the client.
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            keep_alive_interval: Some(Duration::from_secs(15)),
+        }
+    }
+
+    /// Send a keep-alive comment if no event has been sent for the given duration.
+    ///
+    /// Set it to `None` to disable keep-alive comments.
+    /// It defaults to 15 seconds.
+    pub fn set_keep_alive_interval(mut self, interval: Option<Duration>) -> Self {
+        self.keep_alive_interval = interval;
+        self
+    }
+
+    /// The interval between keep-alive comments, if enabled.
+    pub fn get_keep_alive_interval(&self) -> Option<Duration> {
+        self.keep_alive_interval
+    }
+}
+
+impl<S, E> IntoResponse for Sse<S>
+where
+    S: Stream<Item = Result<Event, E>> + Send + 'static,
+    E: Into<Box<dyn std::error::Error + Send + Sync>> + 'static,
+{
+    fn into_response(self) -> Response {
+        let body = SseBody {
+            stream: self.stream,
+            keep_alive: self.keep_alive_interval.map(KeepAlive::new),
+            shutdown: drain_signal(),
+            done: false,
+        };
+        Response::ok()
+            .insert_header(CONTENT_TYPE, HeaderValue::from_static("text/event-stream"))
+            .insert_header(CACHE_CONTROL, HeaderValue::from_static("no-cache"))
+            .set_raw_body(body)
+    }
+}
+
+type ShutdownFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Listen for the shutdown of the server that's handling the current request, if any.
+#[cfg(feature = "server")]
+fn drain_signal() -> Option<ShutdownFuture> {
+    let signal = crate::server::drain_signal::DrainSignal::current()?;
+    Some(Box::pin(signal.triggered()))
+}
+
+#[cfg(not(feature = "server"))]
+fn drain_signal() -> Option<ShutdownFuture> {
+    None
+}
+
+struct KeepAlive {
+    interval: Duration,
+    // The timer is created lazily, since it requires a Tokio runtime.
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl KeepAlive {
+    fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            sleep: None,
+        }
+    }
+
+    /// Start the countdown from scratch.
+    fn reset(&mut self) {
+        if let Some(sleep) = &mut self.sleep {
+            sleep
+                .as_mut()
+                .reset(tokio::time::Instant::now() + self.interval);
+        }
+    }
+
+    /// Returns `Ready` when it's time to send a keep-alive comment.
+    fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        let interval = self.interval;
+        let sleep = self
+            .sleep
+            .get_or_insert_with(|| Box::pin(tokio::time::sleep(interval)));
+        if sleep.as_mut().poll(cx).is_ready() {
+            sleep.as_mut().reset(tokio::time::Instant::now() + interval);
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+pin_project! {
+    struct SseBody<S> {
+        #[pin]
+        stream: S,
+        keep_alive: Option<KeepAlive>,
+        shutdown: Option<ShutdownFuture>,
+        done: bool,
+    }
+}
+
+impl<S, E> RawBody for SseBody<S>
+where
+    S: Stream<Item = Result<Event, E>>,
+{
+    type Data = Bytes;
+    type Error = E;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        if *this.done {
+            return Poll::Ready(None);
+        }
+        if this
+            .shutdown
+            .as_mut()
+            .is_some_and(|shutdown| shutdown.as_mut().poll(cx).is_ready())
+        {
+            *this.done = true;
+            return Poll::Ready(None);
+        }
+        match this.stream.poll_next(cx) {
+            Poll::Ready(Some(Ok(event))) => {
+                if let Some(keep_alive) = this.keep_alive {
+                    keep_alive.reset();
+                }
+                Poll::Ready(Some(Ok(Frame::data(event.encode()))))
+            }
+            Poll::Ready(Some(Err(e))) => {
+                *this.done = true;
+                Poll::Ready(Some(Err(e)))
+            }
+            Poll::Ready(None) => {
+                *this.done = true;
+                Poll::Ready(None)
+            }
+            Poll::Pending => {
+                let Some(keep_alive) = this.keep_alive else {
+                    return Poll::Pending;
+                };
+                keep_alive
+                    .poll_tick(cx)
+                    .map(|_| Some(Ok(Frame::data(Bytes::from_static(b":\n\n")))))
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.done
+    }
+}
+
+/// A message sent to the client as part of an [`Sse`] stream.
+///
+/// All fields are optional.
+/// Check out the [specification](https://html.spec.whatwg.org/multipage/server-sent-events.html#event-stream-interpretation)
+/// for more details on how clients interpret each field.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use pavex::response::sse::Event;
+///
+/// let event = Event::new()
+///     .set_id("42")
+///     .set_event("order_shipped")
+///     .set_data(r#"{"order_id": 7}"#)
+///     .set_retry(Duration::from_secs(5));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Event {
+    id: Option<String>,
+    event: Option<String>,
+    data: Option<String>,
+    retry: Option<Duration>,
+    comment: Option<String>,
+}
+
+impl Event {
+    /// Create a new, empty event.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the event ID.
+    ///
+    /// The client sends the ID of the last event it received via the `Last-Event-ID` header
+    /// when reconnecting.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `id` contains a newline, a carriage return or a null character.
+    pub fn set_id(mut self, id: impl Into<String>) -> Self {
+        let id = id.into();
+        assert!(
+            !id.contains(['\n', '\r', '\0']),
+            "SSE event IDs can't contain newlines, carriage returns or null characters"
+        );
+        self.id = Some(id);
+        self
+    }
+
+    /// Set the event type.
+    ///
+    /// Clients can use it to dispatch the event to the appropriate listener.
+    /// If unset, clients will treat it as a `message` event.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `event` contains a newline or a carriage return.
+    pub fn set_event(mut self, event: impl Into<String>) -> Self {
+        let event = event.into();
+        assert!(
+            !event.contains(['\n', '\r']),
+            "SSE event types can't contain newlines or carriage returns"
+        );
+        self.event = Some(event);
+        self
+    }
+
+    /// Set the event payload.
+    ///
+    /// Multi-line payloads are supported: each line is sent as a separate `data` field
+    /// and the client joins them back together.
+    pub fn set_data(mut self, data: impl Into<String>) -> Self {
+        self.data = Some(data.into());
+        self
+    }
+
+    /// Serialize `value` as JSON and use it as the event payload.
+    pub fn set_json_data<T>(self, value: &T) -> Result<Self, serde_json::Error>
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        Ok(self.set_data(serde_json::to_string(value)?))
+    }
+
+    /// Tell the client how long to wait before reconnecting if the connection is lost.
+    pub fn set_retry(mut self, retry: Duration) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    /// Attach a comment to the event.
+    ///
+    /// Comments are ignored by clients—they're primarily useful for debugging.
+    /// Multi-line comments are supported.
+    pub fn set_comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// The event ID, if set.
+    pub fn get_id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// The event type, if set.
+    pub fn get_event(&self) -> Option<&str> {
+        self.event.as_deref()
+    }
+
+    /// The event payload, if set.
+    pub fn get_data(&self) -> Option<&str> {
+        self.data.as_deref()
+    }
+
+    /// The reconnection delay, if set.
+    pub fn get_retry(&self) -> Option<Duration> {
+        self.retry
+    }
+
+    /// The comment attached to the event, if any.
+    pub fn get_comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    /// Encode the event using the `text/event-stream` format.
+    fn encode(&self) -> Bytes {
+        let mut buffer = BytesMut::new();
+        if let Some(comment) = &self.comment {
+            for line in split_lines(comment) {
+                put_field(&mut buffer, "", line);
+            }
+        }
+        if let Some(event) = &self.event {
+            put_field(&mut buffer, "event", event);
+        }
+        if let Some(data) = &self.data {
+            for line in split_lines(data) {
+                put_field(&mut buffer, "data", line);
+            }
+        }
+        if let Some(id) = &self.id {
+            put_field(&mut buffer, "id", id);
+        }
+        if let Some(retry) = self.retry {
+            let mut millis = String::new();
+            let _ = write!(millis, "{}", retry.as_millis());
+            put_field(&mut buffer, "retry", &millis);
+        }
+        buffer.put_u8(b'\n');
+        buffer.freeze()
+    }
+}
+
+/// Split `value` into lines, using the same line terminators as the clients
+/// parsing the stream: `\r\n`, `\r` and `\n`.
+///
+/// Splitting on `\n` alone would allow a lone `\r` to smuggle extra fields into the stream.
+/// Unlike [`str::lines`], a trailing line terminator yields a final empty line.
+fn split_lines(value: &str) -> impl Iterator<Item = &str> {
+    let mut rest = Some(value);
+    std::iter::from_fn(move || {
+        let current = rest?;
+        let Some(end) = current.find(['\r', '\n']) else {
+            rest = None;
+            return Some(current);
+        };
+        let terminator_len = if current[end..].starts_with("\r\n") {
+            2
+        } else {
+            1
+        };
+        rest = Some(&current[end + terminator_len..]);
+        Some(&current[..end])
+    })
+}
+
+fn put_field(buffer: &mut BytesMut, name: &str, value: &str) {
+    buffer.put_slice(name.as_bytes());
+    buffer.put_slice(b": ");
+    buffer.put_slice(value.as_bytes());
+    buffer.put_u8(b'\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::time::Duration;
+
+    use bytes::Bytes;
+    use futures_util::StreamExt;
+    use http_body_util::BodyExt;
+
+    use super::{Event, Sse};
+    use crate::response::IntoResponse;
+
+    async fn next_frame(body: &mut crate::response::ResponseBody) -> Option<Bytes> {
+        let frame = body.frame().await?.unwrap();
+        Some(frame.into_data().unwrap())
+    }
+
+    #[test]
+    fn events_are_encoded() {
+        let event = Event::new()
+            .set_comment("hi")
+            .set_event("update")
+            .set_data("line 1\nline 2")
+            .set_id("42")
+            .set_retry(Duration::from_secs(3));
+        insta::assert_snapshot!(String::from_utf8(event.encode().to_vec()).unwrap(), @r###"
+        : hi
+        event: update
+        data: line 1
+        data: line 2
+        id: 42
+        retry: 3000
+        "###);
+    }
+
+    #[test]
+    fn every_line_terminator_is_honoured() {
+        let event = Event::new()
+            .set_comment("a\rid: evil")
+            .set_data("x\rid: evil\r\nevent: admin\n");
+        insta::assert_snapshot!(String::from_utf8(event.encode().to_vec()).unwrap(), @r###"
+        : a
+        : id: evil
+        data: x
+        data: id: evil
+        data: event: admin
+        data: 
+        "###);
+    }
+
+    #[test]
+    #[should_panic]
+    fn ids_cannot_contain_newlines() {
+        let _ = Event::new().set_id("4\n2");
+    }
+
+    #[tokio::test]
+    async fn the_response_streams_events() {
+        let events = futures_util::stream::iter(["a", "b"])
+            .map(|data| Ok::<_, Infallible>(Event::new().set_data(data)));
+        let response = Sse::new(events).into_response();
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+        assert_eq!(response.headers()["cache-control"], "no-cache");
+
+        let (_, mut body) = response.into_parts();
+        assert_eq!(next_frame(&mut body).await.unwrap(), "data: a\n\n");
+        assert_eq!(next_frame(&mut body).await.unwrap(), "data: b\n\n");
+        assert!(next_frame(&mut body).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn keep_alive_comments_are_sent_while_idle() {
+        let events = futures_util::stream::pending::<Result<Event, Infallible>>();
+        let response = Sse::new(events)
+            .set_keep_alive_interval(Some(Duration::from_millis(10)))
+            .into_response();
+        let (_, mut body) = response.into_parts();
+        assert_eq!(next_frame(&mut body).await.unwrap(), ":\n\n");
+        assert_eq!(next_frame(&mut body).await.unwrap(), ":\n\n");
+    }
+
+    #[cfg(feature = "server")]
+    #[tokio::test]
+    async fn the_stream_ends_when_the_server_shuts_down() {
+        use crate::server::drain_signal::drain_signal;
+
+        let (trigger, signal) = drain_signal();
+        let events = futures_util::stream::pending::<Result<Event, Infallible>>();
+        // The signal is captured when the response is built, as it happens in request handlers.
+        let response = signal
+            .scope(async {
+                Sse::new(events)
+                    .set_keep_alive_interval(None)
+                    .into_response()
+            })
+            .await;
+        let (_, mut body) = response.into_parts();
+
+        trigger.trigger();
+        assert!(next_frame(&mut body).await.is_none());
+    }
+}
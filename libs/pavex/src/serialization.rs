@@ -23,3 +23,9 @@
 /// `StructuralDeserialize` acts as a tag that tells Pavex that a type should be in scope
 /// for additional static analysis and that it's OK to make certain assumptions.
 pub trait StructuralDeserialize {}
+
+pub use format::{
+    DeserializeFormat, DeserializeFormats, Format, FormatError, SerializeFormat, SerializeFormats,
+};
+
+mod format;
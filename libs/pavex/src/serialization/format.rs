@@ -0,0 +1,197 @@
+use bytes::Bytes;
+use mime::Mime;
+
+/// The error type returned by format implementations.
+pub type FormatError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A serialization format that can be selected via content negotiation.
+///
+/// It's implemented by the response body types that represent a serialization format
+/// (e.g. [`Json`](crate::response::body::Json)).
+/// Implement [`DeserializeFormat`] and/or [`SerializeFormat`] on top of it
+/// to plug the format into [`Negotiated`](crate::request::body::Negotiated) and
+/// [`ResponseFormat`](crate::response::body::ResponseFormat).
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::mime::Mime;
+/// use pavex::response::body::raw::Bytes;
+/// use pavex::serialization::{Format, FormatError, SerializeFormat};
+///
+/// /// JSON, indented for human consumption.
+/// pub struct PrettyJson;
+///
+/// impl Format for PrettyJson {
+///     fn media_type() -> Mime {
+///         "application/vnd.pretty+json".parse().unwrap()
+///     }
+/// }
+///
+/// impl SerializeFormat for PrettyJson {
+///     fn serialize<T>(value: &T) -> Result<Bytes, FormatError>
+///     where
+///         T: serde::Serialize + ?Sized,
+///     {
+///         Ok(serde_json::to_vec_pretty(value)?.into())
+///     }
+/// }
+/// ```
+pub trait Format {
+    /// The media type of the format.
+    ///
+    /// It's used as `Content-Type` for response bodies serialized with this format.
+    fn media_type() -> Mime;
+
+    /// Returns `true` if the format can handle payloads of the given media type.
+    ///
+    /// The default implementation compares the essence (type, subtype and suffix)
+    /// of `media_type` with the one returned by [`Format::media_type`], ignoring parameters.
+    fn supports(media_type: &Mime) -> bool {
+        media_type.essence_str() == Self::media_type().essence_str()
+    }
+}
+
+/// A [`Format`] that can be used to deserialize request bodies.
+pub trait DeserializeFormat: Format {
+    /// Deserialize a value of type `T` from `bytes`.
+    fn deserialize<'de, T>(bytes: &'de [u8]) -> Result<T, FormatError>
+    where
+        T: serde::Deserialize<'de>;
+}
+
+/// A [`Format`] that can be used to serialize response bodies.
+pub trait SerializeFormat: Format {
+    /// Serialize `value` into a buffer.
+    fn serialize<T>(value: &T) -> Result<Bytes, FormatError>
+    where
+        T: serde::Serialize + ?Sized;
+}
+
+/// A list of formats to choose from when deserializing a request body.
+///
+/// It's implemented for every [`DeserializeFormat`] as well as for tuples
+/// of [`DeserializeFormat`]s (up to 8 elements), e.g. `(Json, MsgPack)`.
+/// Formats are listed in order of preference.
+pub trait DeserializeFormats {
+    /// The media types of the formats in the list, in order of preference.
+    fn media_types() -> Vec<Mime>;
+
+    /// Deserialize `bytes` using the first format in the list that supports
+    /// `media_type`.
+    ///
+    /// It returns `None` if none of the formats supports `media_type`.
+    fn deserialize<'de, T>(media_type: &Mime, bytes: &'de [u8]) -> Option<Result<T, FormatError>>
+    where
+        T: serde::Deserialize<'de>;
+}
+
+/// A list of formats to choose from when serializing a response body.
+///
+/// It's implemented for every [`SerializeFormat`] as well as for tuples
+/// of [`SerializeFormat`]s (up to 8 elements), e.g. `(Json, MsgPack)`.
+/// Formats are listed in order of preference.
+pub trait SerializeFormats {
+    /// The media types of the formats in the list, in order of preference.
+    fn media_types() -> Vec<Mime>;
+
+    /// Returns `true` if the format at position `index` in the list supports `media_type`.
+    fn supports(index: usize, media_type: &Mime) -> bool;
+
+    /// Serialize `value` using the format at position `index` in the list.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `index` is out of bounds.
+    fn serialize<T>(index: usize, value: &T) -> Result<Bytes, FormatError>
+    where
+        T: serde::Serialize + ?Sized;
+}
+
+impl<F: DeserializeFormat> DeserializeFormats for F {
+    fn media_types() -> Vec<Mime> {
+        vec![F::media_type()]
+    }
+
+    fn deserialize<'de, T>(media_type: &Mime, bytes: &'de [u8]) -> Option<Result<T, FormatError>>
+    where
+        T: serde::Deserialize<'de>,
+    {
+        F::supports(media_type).then(|| F::deserialize(bytes))
+    }
+}
+
+impl<F: SerializeFormat> SerializeFormats for F {
+    fn media_types() -> Vec<Mime> {
+        vec![F::media_type()]
+    }
+
+    fn supports(index: usize, media_type: &Mime) -> bool {
+        index == 0 && F::supports(media_type)
+    }
+
+    fn serialize<T>(index: usize, value: &T) -> Result<Bytes, FormatError>
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        assert_eq!(index, 0, "Format index out of bounds");
+        F::serialize(value)
+    }
+}
+
+macro_rules! impl_format_lists {
+    ($($index:tt => $format:ident),+) => {
+        impl<$($format: DeserializeFormat),+> DeserializeFormats for ($($format,)+) {
+            fn media_types() -> Vec<Mime> {
+                vec![$($format::media_type()),+]
+            }
+
+            fn deserialize<'de, T>(
+                media_type: &Mime,
+                bytes: &'de [u8],
+            ) -> Option<Result<T, FormatError>>
+            where
+                T: serde::Deserialize<'de>,
+            {
+                $(
+                    if $format::supports(media_type) {
+                        return Some($format::deserialize(bytes));
+                    }
+                )+
+                None
+            }
+        }
+
+        impl<$($format: SerializeFormat),+> SerializeFormats for ($($format,)+) {
+            fn media_types() -> Vec<Mime> {
+                vec![$($format::media_type()),+]
+            }
+
+            fn supports(index: usize, media_type: &Mime) -> bool {
+                match index {
+                    $($index => $format::supports(media_type),)+
+                    _ => false,
+                }
+            }
+
+            fn serialize<T>(index: usize, value: &T) -> Result<Bytes, FormatError>
+            where
+                T: serde::Serialize + ?Sized,
+            {
+                match index {
+                    $($index => $format::serialize(value),)+
+                    _ => panic!("Format index out of bounds"),
+                }
+            }
+        }
+    };
+}
+
+impl_format_lists!(0 => A);
+impl_format_lists!(0 => A, 1 => B);
+impl_format_lists!(0 => A, 1 => B, 2 => C);
+impl_format_lists!(0 => A, 1 => B, 2 => C, 3 => D);
+impl_format_lists!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E);
+impl_format_lists!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F);
+impl_format_lists!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G);
+impl_format_lists!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G, 7 => H);
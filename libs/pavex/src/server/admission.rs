@@ -0,0 +1,160 @@
+use std::cell::Cell;
+use std::num::NonZeroUsize;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use hyper_util::rt::TokioIo;
+use hyper_util::server::conn::auto::Builder;
+use tracing_log_error::log_error;
+
+use crate::response::Response;
+use crate::server::ServerConfiguration;
+use crate::server::worker::{ConnectionMessage, LocalExec};
+
+/// Enforce the limit on the number of requests that are being processed, at the same time,
+/// across all workers.
+///
+/// It's shared between workers: clones point to the same counter.
+#[derive(Clone)]
+pub(super) struct AdmissionControl {
+    in_flight: Arc<AtomicUsize>,
+    max_in_flight: Option<NonZeroUsize>,
+    overload_response: fn() -> Response,
+}
+
+impl AdmissionControl {
+    pub(super) fn new(config: &ServerConfiguration) -> Self {
+        Self {
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            max_in_flight: config.max_in_flight_requests,
+            overload_response: config.overload_response,
+        }
+    }
+
+    /// Try to admit a new request.
+    ///
+    /// It returns `None` if the server is already processing as many requests as it's allowed to.
+    /// The request is considered in flight until the returned guard is dropped.
+    pub(super) fn try_admit(&self) -> Option<InFlightGuard> {
+        let Some(max) = self.max_in_flight else {
+            // No limit, no need to keep track of the number of in-flight requests.
+            return Some(InFlightGuard(None));
+        };
+        if self.in_flight.fetch_add(1, Ordering::AcqRel) >= max.get() {
+            self.in_flight.fetch_sub(1, Ordering::AcqRel);
+            return None;
+        }
+        Some(InFlightGuard(Some(self.in_flight.clone())))
+    }
+
+    /// The response sent to requests that haven't been admitted.
+    pub(super) fn overload_response(&self) -> Response {
+        (self.overload_response)()
+    }
+
+    /// Create a [`ConnectionRejector`] for the current thread.
+    pub(super) fn rejector(&self) -> ConnectionRejector {
+        ConnectionRejector {
+            overload_response: self.overload_response,
+            in_progress: Rc::new(Cell::new(0)),
+        }
+    }
+}
+
+/// Track a request that has been admitted by [`AdmissionControl`].
+///
+/// The in-flight counter is decremented when the guard is dropped.
+pub(super) struct InFlightGuard(Option<Arc<AtomicUsize>>);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if let Some(in_flight) = &self.0 {
+            in_flight.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+}
+
+/// Reply with the overload response to connections that no worker can take on.
+///
+/// Rejected connections are served on the current thread, which must be running
+/// a [`LocalSet`](tokio::task::LocalSet).
+#[derive(Clone)]
+pub(super) struct ConnectionRejector {
+    overload_response: fn() -> Response,
+    /// The number of rejected connections that haven't been closed yet.
+    in_progress: Rc<Cell<usize>>,
+}
+
+impl ConnectionRejector {
+    /// How long a client has, at most, to send its request on a rejected connection
+    /// and read the overload response.
+    const TIMEOUT: Duration = Duration::from_secs(5);
+    /// Beyond this threshold, rejected connections are dropped without a response.
+    /// We don't want to exhaust resources while trying to tell clients we're overloaded.
+    const MAX_IN_PROGRESS: usize = 1024;
+
+    /// Serve the overload response over the connection, then close it.
+    pub(super) fn reject(&self, message: ConnectionMessage) {
+        let ConnectionMessage {
+            connection,
+            peer_addr,
+            #[cfg(feature = "tls")]
+            tls_acceptor,
+        } = message;
+        if self.in_progress.get() >= Self::MAX_IN_PROGRESS {
+            tracing::error!(
+                remote_peer = %peer_addr,
+                "Too many rejected connections in progress, dropping connection",
+            );
+            return;
+        }
+        tracing::warn!(
+            remote_peer = %peer_addr,
+            "The server is overloaded, rejecting connection",
+        );
+        let overload_response = self.overload_response;
+        let in_progress = self.in_progress.clone();
+        in_progress.set(in_progress.get() + 1);
+        tokio::task::spawn_local(async move {
+            let outcome = tokio::time::timeout(Self::TIMEOUT, async move {
+                #[cfg(feature = "tls")]
+                if let Some(tls_acceptor) = tls_acceptor {
+                    let connection = tls_acceptor.accept(connection).await?;
+                    return Self::serve(connection, overload_response).await;
+                }
+                Self::serve(connection, overload_response).await
+            })
+            .await;
+            in_progress.set(in_progress.get() - 1);
+            match outcome {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    log_error!(*e, level: tracing::Level::DEBUG, "Failed to reject an incoming connection");
+                }
+                Err(_) => {
+                    tracing::debug!("Timed out while rejecting an incoming connection");
+                }
+            }
+        });
+    }
+
+    async fn serve<Io>(
+        connection: Io,
+        overload_response: fn() -> Response,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+    where
+        Io: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + 'static,
+    {
+        let service = hyper::service::service_fn(move |_request| async move {
+            Ok::<_, hyper::Error>(hyper::Response::from(overload_response()))
+        });
+        let mut builder = Builder::new(LocalExec);
+        // Close HTTP/1 connections as soon as the response has been sent.
+        builder.http1().keep_alive(false);
+        builder
+            .serve_connection(TokioIo::new(connection), service)
+            .await
+    }
+}
@@ -1,17 +1,101 @@
 use std::num::NonZeroUsize;
+use std::time::Duration;
 
+use serde::Deserialize;
 use tracing_log_error::log_error;
+use ubyte::ByteUnit;
 
-#[derive(Debug, Clone)]
+use super::{DispatchStrategy, HttpProtocol};
+use crate::response::Response;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
 /// All the available options for customizing the behaviour of a [`Server`](super::Server).
 ///
 /// Refer to [`Server::set_config`](super::Server::set_config) for applying the configuration
 /// you assembled.
+///
+/// # Loading from configuration
+///
+/// [`ServerConfiguration`] implements [`serde::Deserialize`], so it can be loaded as part
+/// of your application configuration—e.g. via [`ConfigLoader`](crate::config::ConfigLoader).
+/// Fields that are omitted are set to their default values.
+///
+/// ```yaml
+/// server:
+///   n_workers: 4
+///   header_read_timeout: "10s"
+///   keep_alive_timeout: "1m"
+///   tls_handshake_timeout: "10s"
+///   http_protocol: auto
+///   http2_max_concurrent_streams: 100
+///   http2_initial_stream_window_size: "1 MiB"
+///   http2_initial_connection_window_size: "4 MiB"
+///   http2_adaptive_window: false
+///   http2_keep_alive_interval: "30s"
+///   http2_keep_alive_timeout: "10s"
+///   max_header_size: "32 KiB"
+///   max_connections_per_worker: 10000
+///   dispatch_strategy: least_active_connections
+///   max_queue_length: 64
+///   max_in_flight_requests: 2048
+/// ```
+///
+/// Durations use the ["friendly" format](https://docs.rs/jiff/0.2/jiff/fmt/friendly/index.html)
+/// supported by `jiff`, e.g. `10s` or `1 minute`.
+/// Byte sizes use the [format](https://docs.rs/ubyte/0.10/ubyte/struct.ByteUnit.html#deserialization)
+/// supported by `ubyte`, e.g. `32 KiB` or `1MB`.
 pub struct ServerConfiguration {
     /// Number of worker threads to spawn.
     pub(crate) n_workers: NonZeroUsize,
+    /// How long to wait for a client to transmit the headers of a request.
+    #[serde(deserialize_with = "deserialize_optional_duration")]
+    pub(crate) header_read_timeout: Option<Duration>,
+    /// How long an idle connection is kept open.
+    #[serde(deserialize_with = "deserialize_optional_duration")]
+    pub(crate) keep_alive_timeout: Option<Duration>,
+    /// How long to wait for a client to complete the TLS handshake.
+    #[serde(deserialize_with = "deserialize_optional_duration")]
+    pub(crate) tls_handshake_timeout: Option<Duration>,
+    /// The HTTP versions spoken by the server.
+    pub(crate) http_protocol: HttpProtocol,
+    /// The maximum number of concurrent streams for each HTTP/2 connection.
+    pub(crate) http2_max_concurrent_streams: u32,
+    /// The initial flow control window size for each HTTP/2 stream.
+    #[serde(deserialize_with = "deserialize_http2_window_size")]
+    pub(crate) http2_initial_stream_window_size: Option<ByteUnit>,
+    /// The initial flow control window size for each HTTP/2 connection.
+    #[serde(deserialize_with = "deserialize_http2_window_size")]
+    pub(crate) http2_initial_connection_window_size: Option<ByteUnit>,
+    /// Whether to use an adaptive flow control window for HTTP/2 connections.
+    pub(crate) http2_adaptive_window: bool,
+    /// How often to send HTTP/2 keep-alive pings.
+    #[serde(deserialize_with = "deserialize_optional_duration")]
+    pub(crate) http2_keep_alive_interval: Option<Duration>,
+    /// How long to wait for the acknowledgement of an HTTP/2 keep-alive ping.
+    #[serde(deserialize_with = "deserialize_duration")]
+    pub(crate) http2_keep_alive_timeout: Duration,
+    /// The maximum size of the request head.
+    #[serde(deserialize_with = "deserialize_max_header_size")]
+    pub(crate) max_header_size: Option<ByteUnit>,
+    /// The maximum number of connections a single worker can have open at any given time.
+    pub(crate) max_connections_per_worker: Option<NonZeroUsize>,
+    /// How connections are distributed across workers.
+    pub(crate) dispatch_strategy: DispatchStrategy,
+    /// The maximum number of connections waiting to be picked up by each worker.
+    pub(crate) max_queue_length: NonZeroUsize,
+    /// The maximum number of requests being processed, at the same time, across all workers.
+    pub(crate) max_in_flight_requests: Option<NonZeroUsize>,
+    /// The response sent when the server is overloaded.
+    #[serde(skip)]
+    pub(crate) overload_response: fn() -> Response,
 }
 
+/// The smallest value accepted by [`ServerConfiguration::set_max_header_size`].
+const MIN_MAX_HEADER_SIZE: ByteUnit = ByteUnit::Byte(8192);
+/// The largest flow control window allowed by the HTTP/2 specification.
+const MAX_HTTP2_WINDOW_SIZE: ByteUnit = ByteUnit::Byte((1 << 31) - 1);
+
 impl Default for ServerConfiguration {
     fn default() -> Self {
         Self::new()
@@ -34,7 +118,25 @@ impl ServerConfiguration {
                 fallback
             }
         };
-        Self { n_workers }
+        Self {
+            n_workers,
+            header_read_timeout: Some(Duration::from_secs(30)),
+            keep_alive_timeout: None,
+            tls_handshake_timeout: Some(Duration::from_secs(10)),
+            http_protocol: HttpProtocol::default(),
+            http2_max_concurrent_streams: 200,
+            http2_initial_stream_window_size: None,
+            http2_initial_connection_window_size: None,
+            http2_adaptive_window: false,
+            http2_keep_alive_interval: None,
+            http2_keep_alive_timeout: Duration::from_secs(20),
+            max_header_size: None,
+            max_connections_per_worker: None,
+            dispatch_strategy: DispatchStrategy::default(),
+            max_queue_length: NonZeroUsize::new(15).unwrap(),
+            max_in_flight_requests: None,
+            overload_response: Response::service_unavailable,
+        }
     }
 
     /// Set the number of worker threads to be spawned.
@@ -63,4 +165,558 @@ impl ServerConfiguration {
     pub fn get_n_workers(&self) -> NonZeroUsize {
         self.n_workers
     }
+
+    /// Set how long the server waits for a client to transmit the full head (request line
+    /// and headers) of an HTTP/1 request.
+    /// The connection is closed if the timeout expires.
+    ///
+    /// The timer starts as soon as the server is ready to read a new request, including
+    /// when a keep-alive connection is waiting for its next request.
+    ///
+    /// Set it to `None` to disable the timeout altogether.
+    /// This is **not** recommended: your server will be vulnerable to
+    /// [slowloris](https://en.wikipedia.org/wiki/Slowloris_(computer_security)) attacks.
+    ///
+    /// # Default
+    ///
+    /// 30 seconds.
+    pub fn set_header_read_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.header_read_timeout = timeout;
+        self
+    }
+
+    /// Get the timeout for reading the head of HTTP/1 requests.
+    ///
+    /// Check out [`ServerConfiguration::set_header_read_timeout`] for more details.
+    pub fn get_header_read_timeout(&self) -> Option<Duration> {
+        self.header_read_timeout
+    }
+
+    /// Set how long a connection can stay idle—i.e. without in-flight requests and without
+    /// any data being exchanged—before the server closes it.
+    ///
+    /// It applies to both HTTP/1 keep-alive connections and HTTP/2 connections.
+    ///
+    /// Set it to `None` to keep idle connections open until the client closes them.
+    ///
+    /// # Default
+    ///
+    /// `None`.
+    /// Idle HTTP/1 connections are nonetheless closed when the
+    /// [header read timeout](ServerConfiguration::set_header_read_timeout) expires.
+    pub fn set_keep_alive_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.keep_alive_timeout = timeout;
+        self
+    }
+
+    /// Get the idle timeout for keep-alive connections.
+    ///
+    /// Check out [`ServerConfiguration::set_keep_alive_timeout`] for more details.
+    pub fn get_keep_alive_timeout(&self) -> Option<Duration> {
+        self.keep_alive_timeout
+    }
+
+    /// Set how long the server waits for a client to complete the TLS handshake.
+    /// The connection is closed if the timeout expires.
+    ///
+    /// It only applies to listeners that terminate TLS (see `IncomingStream::bind_tls`).
+    /// The [header read timeout](ServerConfiguration::set_header_read_timeout) only starts
+    /// once the handshake has been completed.
+    ///
+    /// Set it to `None` to disable the timeout altogether.
+    /// This is **not** recommended: a client that opens a connection without ever
+    /// sending a `ClientHello` would hold on to it forever.
+    ///
+    /// # Default
+    ///
+    /// 10 seconds.
+    pub fn set_tls_handshake_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.tls_handshake_timeout = timeout;
+        self
+    }
+
+    /// Get the timeout for completing the TLS handshake.
+    ///
+    /// Check out [`ServerConfiguration::set_tls_handshake_timeout`] for more details.
+    pub fn get_tls_handshake_timeout(&self) -> Option<Duration> {
+        self.tls_handshake_timeout
+    }
+
+    /// Set the HTTP versions spoken by the server.
+    ///
+    /// Check out [`HttpProtocol`] for a discussion of the available options.
+    ///
+    /// # Default
+    ///
+    /// [`HttpProtocol::Auto`].
+    pub fn set_http_protocol(mut self, protocol: HttpProtocol) -> Self {
+        self.http_protocol = protocol;
+        self
+    }
+
+    /// Get the HTTP versions spoken by the server.
+    pub fn get_http_protocol(&self) -> HttpProtocol {
+        self.http_protocol
+    }
+
+    /// Set the maximum number of requests that a client can have in flight, at the same time,
+    /// on a single HTTP/2 connection.
+    ///
+    /// # Default
+    ///
+    /// 200.
+    pub fn set_http2_max_concurrent_streams(mut self, max: u32) -> Self {
+        self.http2_max_concurrent_streams = max;
+        self
+    }
+
+    /// Get the maximum number of concurrent streams for each HTTP/2 connection.
+    pub fn get_http2_max_concurrent_streams(&self) -> u32 {
+        self.http2_max_concurrent_streams
+    }
+
+    /// Set the initial flow control window size for each HTTP/2 stream—i.e. how much data
+    /// a client can send, for a single request, before waiting for the server to ask for more.
+    ///
+    /// It's ignored if the [adaptive window](ServerConfiguration::set_http2_adaptive_window)
+    /// is enabled.
+    ///
+    /// # Panics
+    ///
+    /// The value can't be larger than `2^31 - 1` bytes.
+    ///
+    /// # Default
+    ///
+    /// `None`: `hyper`'s default applies (1 MiB).
+    #[track_caller]
+    pub fn set_http2_initial_stream_window_size(mut self, size: Option<ByteUnit>) -> Self {
+        assert_http2_window_size(size);
+        self.http2_initial_stream_window_size = size;
+        self
+    }
+
+    /// Get the initial flow control window size for each HTTP/2 stream.
+    pub fn get_http2_initial_stream_window_size(&self) -> Option<ByteUnit> {
+        self.http2_initial_stream_window_size
+    }
+
+    /// Set the initial flow control window size for each HTTP/2 connection—i.e. how much data
+    /// a client can send, across all its requests, before waiting for the server to ask for more.
+    ///
+    /// It's ignored if the [adaptive window](ServerConfiguration::set_http2_adaptive_window)
+    /// is enabled.
+    ///
+    /// # Panics
+    ///
+    /// The value can't be larger than `2^31 - 1` bytes.
+    ///
+    /// # Default
+    ///
+    /// `None`: `hyper`'s default applies (1 MiB).
+    #[track_caller]
+    pub fn set_http2_initial_connection_window_size(mut self, size: Option<ByteUnit>) -> Self {
+        assert_http2_window_size(size);
+        self.http2_initial_connection_window_size = size;
+        self
+    }
+
+    /// Get the initial flow control window size for each HTTP/2 connection.
+    pub fn get_http2_initial_connection_window_size(&self) -> Option<ByteUnit> {
+        self.http2_initial_connection_window_size
+    }
+
+    /// Enable an adaptive flow control window for HTTP/2 connections.
+    ///
+    /// The window sizes are adjusted over time, based on an estimate of the
+    /// bandwidth-delay product of each connection.
+    /// The initial window sizes you might have set are ignored.
+    ///
+    /// # Default
+    ///
+    /// `false`.
+    pub fn set_http2_adaptive_window(mut self, enabled: bool) -> Self {
+        self.http2_adaptive_window = enabled;
+        self
+    }
+
+    /// Check if the adaptive flow control window is enabled for HTTP/2 connections.
+    pub fn get_http2_adaptive_window(&self) -> bool {
+        self.http2_adaptive_window
+    }
+
+    /// Set how often the server sends a keep-alive ping over each HTTP/2 connection.
+    ///
+    /// Connections whose pings aren't acknowledged within the
+    /// [keep-alive timeout](ServerConfiguration::set_http2_keep_alive_timeout) are closed.
+    /// It helps to detect peers that have gone away without closing their connections.
+    ///
+    /// Set it to `None` to disable keep-alive pings.
+    ///
+    /// # Default
+    ///
+    /// `None`.
+    pub fn set_http2_keep_alive_interval(mut self, interval: Option<Duration>) -> Self {
+        self.http2_keep_alive_interval = interval;
+        self
+    }
+
+    /// Get how often the server sends a keep-alive ping over each HTTP/2 connection.
+    pub fn get_http2_keep_alive_interval(&self) -> Option<Duration> {
+        self.http2_keep_alive_interval
+    }
+
+    /// Set how long the server waits for the acknowledgement of an HTTP/2 keep-alive ping
+    /// before closing the connection.
+    ///
+    /// It has no effect unless a
+    /// [keep-alive interval](ServerConfiguration::set_http2_keep_alive_interval) is set.
+    ///
+    /// # Default
+    ///
+    /// 20 seconds.
+    pub fn set_http2_keep_alive_timeout(mut self, timeout: Duration) -> Self {
+        self.http2_keep_alive_timeout = timeout;
+        self
+    }
+
+    /// Get how long the server waits for the acknowledgement of an HTTP/2 keep-alive ping.
+    pub fn get_http2_keep_alive_timeout(&self) -> Duration {
+        self.http2_keep_alive_timeout
+    }
+
+    /// Set the maximum size of the head (request line and headers) of incoming requests.
+    ///
+    /// For HTTP/1 connections, it caps the size of the connection read buffer.
+    /// For HTTP/2 connections, it caps the size of the header list.
+    ///
+    /// # Panics
+    ///
+    /// The value must be at least 8 KiB.
+    ///
+    /// # Default
+    ///
+    /// `None`: `hyper`'s default limits apply (~400 KiB for HTTP/1, 16 KiB for HTTP/2).
+    #[track_caller]
+    pub fn set_max_header_size(mut self, max_size: Option<ByteUnit>) -> Self {
+        if let Some(max_size) = max_size {
+            assert!(
+                max_size >= MIN_MAX_HEADER_SIZE,
+                "The maximum header size must be at least {MIN_MAX_HEADER_SIZE}"
+            );
+        }
+        self.max_header_size = max_size;
+        self
+    }
+
+    /// Get the maximum size of the head of incoming requests.
+    ///
+    /// Check out [`ServerConfiguration::set_max_header_size`] for more details.
+    pub fn get_max_header_size(&self) -> Option<ByteUnit> {
+        self.max_header_size
+    }
+
+    /// Set the maximum number of connections that each worker can have open at the same time.
+    ///
+    /// When a worker reaches its limit, new connections are dispatched to other workers.
+    /// If all workers are at capacity, the server replies with the
+    /// [overload response](ServerConfiguration::set_overload_response).
+    ///
+    /// # Default
+    ///
+    /// `None`: there is no limit.
+    pub fn set_max_connections_per_worker(mut self, max: Option<NonZeroUsize>) -> Self {
+        self.max_connections_per_worker = max;
+        self
+    }
+
+    /// Get the maximum number of connections that each worker can have open at the same time.
+    pub fn get_max_connections_per_worker(&self) -> Option<NonZeroUsize> {
+        self.max_connections_per_worker
+    }
+
+    /// Set the strategy used to distribute incoming connections across workers.
+    ///
+    /// Check out [`DispatchStrategy`] for a discussion of the available options.
+    ///
+    /// # Default
+    ///
+    /// [`DispatchStrategy::RoundRobin`].
+    pub fn set_dispatch_strategy(mut self, strategy: DispatchStrategy) -> Self {
+        self.dispatch_strategy = strategy;
+        self
+    }
+
+    /// Get the strategy used to distribute incoming connections across workers.
+    pub fn get_dispatch_strategy(&self) -> DispatchStrategy {
+        self.dispatch_strategy
+    }
+
+    /// Set the maximum number of connections that can be waiting, for each worker,
+    /// to be picked up.
+    ///
+    /// When a worker's queue is full, new connections are dispatched to other workers.
+    /// If all queues are full, the server replies with the
+    /// [overload response](ServerConfiguration::set_overload_response).
+    ///
+    /// # Default
+    ///
+    /// 15.
+    pub fn set_max_queue_length(mut self, max: NonZeroUsize) -> Self {
+        self.max_queue_length = max;
+        self
+    }
+
+    /// Get the maximum number of connections that can be waiting, for each worker,
+    /// to be picked up.
+    pub fn get_max_queue_length(&self) -> NonZeroUsize {
+        self.max_queue_length
+    }
+
+    /// Set the maximum number of requests that the server processes at the same time,
+    /// across all workers.
+    ///
+    /// A request is in flight from the moment its head has been received until your
+    /// request handler returns a response.
+    /// When the limit is reached, new requests are immediately answered with the
+    /// [overload response](ServerConfiguration::set_overload_response), rather than
+    /// piling up while your application (or one of its dependencies) is struggling
+    /// to keep up.
+    ///
+    /// # Default
+    ///
+    /// `None`: there is no limit.
+    pub fn set_max_in_flight_requests(mut self, max: Option<NonZeroUsize>) -> Self {
+        self.max_in_flight_requests = max;
+        self
+    }
+
+    /// Get the maximum number of requests that the server processes at the same time.
+    pub fn get_max_in_flight_requests(&self) -> Option<NonZeroUsize> {
+        self.max_in_flight_requests
+    }
+
+    /// Set the function that builds the response sent when the server is overloaded—i.e.
+    /// when the [in-flight limit](ServerConfiguration::set_max_in_flight_requests) has been
+    /// reached or when all [worker queues](ServerConfiguration::set_max_queue_length) are full.
+    ///
+    /// It can't be loaded from configuration files.
+    ///
+    /// # Default
+    ///
+    /// An empty `503 Service Unavailable` response.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::http::{HeaderValue, header::RETRY_AFTER};
+    /// use pavex::response::Response;
+    /// use pavex::server::ServerConfiguration;
+    ///
+    /// fn overloaded() -> Response {
+    ///     Response::service_unavailable()
+    ///         .insert_header(RETRY_AFTER, HeaderValue::from_static("5"))
+    /// }
+    ///
+    /// let config = ServerConfiguration::new().set_overload_response(overloaded);
+    /// ```
+    pub fn set_overload_response(mut self, f: fn() -> Response) -> Self {
+        self.overload_response = f;
+        self
+    }
+}
+
+fn deserialize_optional_duration<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let Some(duration) = Option::<jiff::SignedDuration>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    if duration.is_negative() {
+        return Err(serde::de::Error::custom("timeouts can't be negative"));
+    }
+    duration
+        .try_into()
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+fn deserialize_duration<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let duration = jiff::SignedDuration::deserialize(deserializer)?;
+    if duration.is_negative() {
+        return Err(serde::de::Error::custom("timeouts can't be negative"));
+    }
+    duration.try_into().map_err(serde::de::Error::custom)
+}
+
+#[track_caller]
+fn assert_http2_window_size(size: Option<ByteUnit>) {
+    if let Some(size) = size {
+        assert!(
+            size <= MAX_HTTP2_WINDOW_SIZE,
+            "HTTP/2 flow control windows can't be larger than {} bytes",
+            MAX_HTTP2_WINDOW_SIZE.as_u64()
+        );
+    }
+}
+
+fn deserialize_http2_window_size<'de, D>(deserializer: D) -> Result<Option<ByteUnit>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let size = Option::<ByteUnit>::deserialize(deserializer)?;
+    if size.is_some_and(|size| size > MAX_HTTP2_WINDOW_SIZE) {
+        return Err(serde::de::Error::custom(format!(
+            "HTTP/2 flow control windows can't be larger than {} bytes",
+            MAX_HTTP2_WINDOW_SIZE.as_u64()
+        )));
+    }
+    Ok(size)
+}
+
+fn deserialize_max_header_size<'de, D>(deserializer: D) -> Result<Option<ByteUnit>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let max_size = Option::<ByteUnit>::deserialize(deserializer)?;
+    if max_size.is_some_and(|max_size| max_size < MIN_MAX_HEADER_SIZE) {
+        return Err(serde::de::Error::custom(format!(
+            "the maximum header size must be at least {MIN_MAX_HEADER_SIZE}"
+        )));
+    }
+    Ok(max_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use ubyte::ToByteUnit;
+
+    use super::ServerConfiguration;
+    use crate::server::{DispatchStrategy, HttpProtocol};
+
+    #[test]
+    fn missing_fields_use_defaults() {
+        let config: ServerConfiguration = serde_json::from_str(r#"{"n_workers": 3}"#).unwrap();
+        assert_eq!(config.get_n_workers().get(), 3);
+        assert_eq!(
+            config.get_header_read_timeout(),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(config.get_keep_alive_timeout(), None);
+        assert_eq!(
+            config.get_tls_handshake_timeout(),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(config.get_http_protocol(), HttpProtocol::Auto);
+        assert_eq!(config.get_http2_max_concurrent_streams(), 200);
+        assert_eq!(config.get_http2_initial_stream_window_size(), None);
+        assert_eq!(config.get_http2_initial_connection_window_size(), None);
+        assert!(!config.get_http2_adaptive_window());
+        assert_eq!(config.get_http2_keep_alive_interval(), None);
+        assert_eq!(
+            config.get_http2_keep_alive_timeout(),
+            Duration::from_secs(20)
+        );
+        assert_eq!(config.get_max_header_size(), None);
+        assert_eq!(config.get_max_connections_per_worker(), None);
+        assert_eq!(config.get_dispatch_strategy(), DispatchStrategy::RoundRobin);
+        assert_eq!(config.get_max_queue_length().get(), 15);
+        assert_eq!(config.get_max_in_flight_requests(), None);
+    }
+
+    #[test]
+    fn all_fields() {
+        let config: ServerConfiguration = serde_json::from_str(
+            r#"{
+                "n_workers": 2,
+                "header_read_timeout": "10s",
+                "keep_alive_timeout": "1 minute",
+                "tls_handshake_timeout": "5s",
+                "http_protocol": "http2_only",
+                "http2_max_concurrent_streams": 50,
+                "http2_initial_stream_window_size": "1 MiB",
+                "http2_initial_connection_window_size": "4 MiB",
+                "http2_adaptive_window": true,
+                "http2_keep_alive_interval": "30s",
+                "http2_keep_alive_timeout": "10s",
+                "max_header_size": "32 KiB",
+                "max_connections_per_worker": 100,
+                "dispatch_strategy": "least_active_connections",
+                "max_queue_length": 64,
+                "max_in_flight_requests": 2048
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.get_header_read_timeout(),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(
+            config.get_keep_alive_timeout(),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            config.get_tls_handshake_timeout(),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(config.get_http_protocol(), HttpProtocol::Http2Only);
+        assert_eq!(config.get_http2_max_concurrent_streams(), 50);
+        assert_eq!(
+            config.get_http2_initial_stream_window_size(),
+            Some(1.mebibytes())
+        );
+        assert_eq!(
+            config.get_http2_initial_connection_window_size(),
+            Some(4.mebibytes())
+        );
+        assert!(config.get_http2_adaptive_window());
+        assert_eq!(
+            config.get_http2_keep_alive_interval(),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            config.get_http2_keep_alive_timeout(),
+            Duration::from_secs(10)
+        );
+        assert_eq!(config.get_max_header_size(), Some(32.kibibytes()));
+        assert_eq!(
+            config.get_max_connections_per_worker().map(|n| n.get()),
+            Some(100)
+        );
+        assert_eq!(
+            config.get_dispatch_strategy(),
+            DispatchStrategy::LeastActiveConnections
+        );
+        assert_eq!(config.get_max_queue_length().get(), 64);
+        assert_eq!(
+            config.get_max_in_flight_requests().map(|n| n.get()),
+            Some(2048)
+        );
+    }
+
+    #[test]
+    fn null_disables_the_header_read_timeout() {
+        let config: ServerConfiguration =
+            serde_json::from_str(r#"{"header_read_timeout": null}"#).unwrap();
+        assert_eq!(config.get_header_read_timeout(), None);
+    }
+
+    #[test]
+    fn invalid_values_are_rejected() {
+        let err = serde_json::from_str::<ServerConfiguration>(r#"{"n_workers": 0}"#).unwrap_err();
+        insta::assert_snapshot!(err, @"invalid value: integer `0`, expected a nonzero usize at line 1 column 15");
+        let err = serde_json::from_str::<ServerConfiguration>(r#"{"max_header_size": "1 KiB"}"#)
+            .unwrap_err();
+        insta::assert_snapshot!(err, @"the maximum header size must be at least 8KiB at line 1 column 28");
+        let err = serde_json::from_str::<ServerConfiguration>(
+            r#"{"http2_initial_stream_window_size": "4 GiB"}"#,
+        )
+        .unwrap_err();
+        insta::assert_snapshot!(err, @"HTTP/2 flow control windows can't be larger than 2147483647 bytes at line 1 column 45");
+    }
 }
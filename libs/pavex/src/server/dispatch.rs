@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use super::{IncomingStream, ServerConfiguration};
+
+/// Determine how incoming connections are distributed across the worker threads
+/// of a [`Server`](super::Server).
+///
+/// Use [`ServerConfiguration::set_dispatch_strategy`] to pick a strategy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum DispatchStrategy {
+    /// The acceptor thread hands out connections to workers in turn.
+    ///
+    /// It works best when connections are short-lived and similar to each other.
+    /// If a worker's queue is full, the connection is handed to the next worker.
+    #[default]
+    RoundRobin,
+    /// The acceptor thread hands out each connection to the worker that has the lowest
+    /// number of open connections.
+    ///
+    /// It keeps the load balanced when some connections live much longer than
+    /// others—e.g. HTTP/2 connections or streaming responses—at the cost of inspecting
+    /// the load on every worker for each new connection.
+    LeastActiveConnections,
+    /// Each worker accepts connections on its own listening socket, bound to the same
+    /// address via the `SO_REUSEPORT` socket option.
+    /// The operating system distributes incoming connections across the listeners.
+    ///
+    /// There is no acceptor thread on the hot path, but the OS doesn't know how busy
+    /// each worker is: on Linux, connections are assigned based on a hash of the
+    /// client's address and port.
+    ///
+    /// A worker that crashes can't be restarted, since its listening sockets are closed
+    /// along with it: the server shuts down instead, gracefully if
+    /// [`Server::shutdown_on_signals`](super::Server::shutdown_on_signals) was configured.
+    ///
+    /// # Requirements
+    ///
+    /// All listeners must be TCP sockets with `SO_REUSEPORT` enabled—e.g. created via
+    /// [`IncomingStream::bind_reuse_port`].
+    /// Serving fails otherwise.
+    #[cfg(unix)]
+    ReusePort,
+}
+
+/// The listening sockets of a [`Server`](super::Server), arranged according to
+/// its [`DispatchStrategy`].
+pub(super) struct Listeners {
+    /// The listeners monitored by the acceptor thread.
+    pub(super) shared: Vec<IncomingStream>,
+    /// The listeners owned by each worker, if workers are in charge of accepting
+    /// their own connections.
+    pub(super) per_worker: Vec<Vec<IncomingStream>>,
+}
+
+impl Listeners {
+    pub(super) fn new(
+        incoming: Vec<IncomingStream>,
+        config: &ServerConfiguration,
+    ) -> std::io::Result<Self> {
+        // TLS listeners must only advertise the protocols that the server is going to speak.
+        #[cfg(feature = "tls")]
+        let incoming = incoming
+            .into_iter()
+            .map(|incoming| match incoming.tls_config().cloned() {
+                Some(tls_config) => {
+                    incoming.with_tls(tls_config.restrict_alpn(config.http_protocol))
+                }
+                None => incoming,
+            })
+            .collect();
+        match config.dispatch_strategy {
+            DispatchStrategy::RoundRobin | DispatchStrategy::LeastActiveConnections => Ok(Self {
+                shared: incoming,
+                per_worker: Vec::new(),
+            }),
+            #[cfg(unix)]
+            DispatchStrategy::ReusePort => Ok(Self {
+                shared: Vec::new(),
+                per_worker: reuse_port_listeners(incoming, config.n_workers.get())?,
+            }),
+        }
+    }
+}
+
+/// Bind a listener for each worker to the address of each incoming stream.
+///
+/// The original listener is assigned to the first worker.
+#[cfg(unix)]
+fn reuse_port_listeners(
+    incoming: Vec<IncomingStream>,
+    n_workers: usize,
+) -> std::io::Result<Vec<Vec<IncomingStream>>> {
+    let mut per_worker: Vec<Vec<IncomingStream>> = (0..n_workers).map(|_| Vec::new()).collect();
+    for incoming in incoming {
+        let addr = incoming.local_addr().map_err(|e| {
+            std::io::Error::new(
+                e.kind(),
+                "The `reuse_port` dispatch strategy only supports TCP listeners",
+            )
+        })?;
+        if !socket2::SockRef::from(&incoming).reuse_port()? {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "The listener bound to {addr} doesn't have `SO_REUSEPORT` enabled, \
+                    a requirement for the `reuse_port` dispatch strategy"
+                ),
+            ));
+        }
+        for worker_listeners in per_worker.iter_mut().skip(1) {
+            #[allow(unused_mut)]
+            let mut listener = IncomingStream::reuse_port_listener(addr)?;
+            #[cfg(feature = "tls")]
+            if let Some(tls_config) = incoming.tls_config() {
+                listener = listener.with_tls(tls_config.clone());
+            }
+            worker_listeners.push(listener);
+        }
+        per_worker[0].push(incoming);
+    }
+    Ok(per_worker)
+}
+
+/// Slow down the acceptance of new connections when the process, or the system as a whole,
+/// runs out of resources—e.g. file descriptors.
+///
+/// `accept` keeps failing until some resources are released: retrying right away
+/// would keep the thread busy without getting anything done.
+#[derive(Default)]
+pub(super) struct AcceptBackoff {
+    delay: Option<Duration>,
+}
+
+impl AcceptBackoff {
+    const INITIAL_DELAY: Duration = Duration::from_millis(5);
+    const MAX_DELAY: Duration = Duration::from_secs(1);
+
+    /// Accepting a connection succeeded: the next failure starts with the initial delay.
+    pub(super) fn reset(&mut self) {
+        self.delay = None;
+    }
+
+    /// Wait before the next `accept` attempt, if the error was caused by resource exhaustion.
+    ///
+    /// The delay doubles on each consecutive failure, up to a maximum.
+    pub(super) async fn wait(&mut self, e: &std::io::Error) {
+        if !is_resource_exhaustion(e) {
+            return;
+        }
+        let delay = self.delay.map_or(Self::INITIAL_DELAY, |delay| {
+            (delay * 2).min(Self::MAX_DELAY)
+        });
+        self.delay = Some(delay);
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// `true` if `accept` failed because there aren't enough resources to set up
+/// the new connection.
+fn is_resource_exhaustion(e: &std::io::Error) -> bool {
+    #[cfg(unix)]
+    {
+        matches!(
+            e.raw_os_error(),
+            Some(libc::EMFILE | libc::ENFILE | libc::ENOBUFS | libc::ENOMEM)
+        )
+    }
+    #[cfg(not(unix))]
+    {
+        e.kind() == std::io::ErrorKind::OutOfMemory
+    }
+}
@@ -0,0 +1,75 @@
+use std::future::Future;
+
+tokio::task_local! {
+    /// The drain signal of the worker that's handling the current request.
+    static DRAIN_SIGNAL: DrainSignal;
+}
+
+/// Create a new drain trigger, alongside the signal it controls.
+pub(crate) fn drain_signal() -> (DrainTrigger, DrainSignal) {
+    let (sender, receiver) = tokio::sync::watch::channel(false);
+    (DrainTrigger(sender), DrainSignal(receiver))
+}
+
+/// Notify all [`DrainSignal`]s that the server is shutting down.
+pub(crate) struct DrainTrigger(tokio::sync::watch::Sender<bool>);
+
+impl DrainTrigger {
+    /// Let long-lived responses know that they should wrap up.
+    ///
+    /// It's idempotent.
+    pub(crate) fn trigger(&self) {
+        // We don't care if there are no receivers left: the value is stored anyway.
+        self.0.send_replace(true);
+    }
+}
+
+/// A signal that fires when the server starts draining its connections, i.e. when it
+/// starts shutting down.
+///
+/// Not to be confused with [`ShutdownSignals`](super::signals::ShutdownSignals), which
+/// listens for the OS signals that may cause the shutdown in the first place.
+///
+/// A graceful shutdown waits for in-flight responses to complete.
+/// Long-lived responses (e.g. [server-sent events](crate::response::sse)) must
+/// listen for this signal and terminate, otherwise the graceful shutdown would
+/// only complete when its timeout expires.
+#[derive(Debug, Clone)]
+pub(crate) struct DrainSignal(tokio::sync::watch::Receiver<bool>);
+
+impl DrainSignal {
+    /// The signal of the worker that's handling the current request, if any.
+    ///
+    /// It's `None` if the caller isn't running as part of a request handled by
+    /// a [`Server`](crate::server::Server)—e.g. in a test.
+    pub(crate) fn current() -> Option<Self> {
+        DRAIN_SIGNAL.try_with(Clone::clone).ok()
+    }
+
+    /// Make this signal available via [`DrainSignal::current`] while `f` is running.
+    pub(crate) fn scope<F: Future>(self, f: F) -> impl Future<Output = F::Output> {
+        DRAIN_SIGNAL.scope(self, f)
+    }
+
+    /// Resolves when the server starts shutting down.
+    pub(crate) async fn triggered(mut self) {
+        // If the trigger has been dropped, the worker is gone: we're shutting down.
+        let _ = self.0.wait_for(|triggered| *triggered).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn the_signal_is_only_available_within_its_scope() {
+        let (trigger, signal) = drain_signal();
+        assert!(DrainSignal::current().is_none());
+        let current = signal.scope(async { DrainSignal::current() }).await;
+        let current = current.expect("The signal should be in scope");
+
+        trigger.trigger();
+        current.triggered().await;
+    }
+}
@@ -0,0 +1,125 @@
+use std::sync::Arc;
+
+use tokio::sync::watch;
+
+/// A notable event in the lifecycle of a [`Server`](super::Server).
+///
+/// Subscribe to them via [`ServerHandle::events`](super::ServerHandle::events).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ServerEvent {
+    /// The server owns its listening sockets and is about to start its worker threads.
+    Bound,
+    /// A worker thread is up and ready to handle connections.
+    ///
+    /// It's emitted again, with the same `worker_id`, if a worker is restarted after a crash.
+    WorkerStarted {
+        /// The identifier of the worker thread.
+        worker_id: usize,
+    },
+    /// All worker threads are up: the server is ready to handle requests.
+    ///
+    /// It's emitted at most once.
+    Ready,
+    /// The server stopped accepting new connections and it's waiting for in-flight
+    /// requests to complete.
+    ///
+    /// It's only emitted for [graceful shutdowns](super::ShutdownMode::Graceful).
+    Draining,
+    /// The server has shut down.
+    ///
+    /// It's always the last event.
+    Stopped,
+}
+
+/// A subscription to the [`ServerEvent`]s emitted by a [`Server`](super::Server).
+///
+/// You get a [`ServerEvents`] from [`ServerHandle::events`](super::ServerHandle::events).
+///
+/// Events are never lost: every subscription starts from the very first event emitted by the
+/// server, no matter when it was created.
+///
+/// # Example: waiting for the server to be ready
+///
+/// ```rust
+/// use pavex::server::{ServerEvent, ServerHandle};
+///
+/// # async fn t(server_handle: ServerHandle) {
+/// let mut events = server_handle.events();
+/// while let Some(event) = events.next().await {
+///     if event == ServerEvent::Ready {
+///         // Time to report that we're ready to the orchestrator!
+///         break;
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ServerEvents {
+    log: watch::Receiver<Vec<ServerEvent>>,
+    /// The index of the next event to be returned.
+    cursor: usize,
+}
+
+impl ServerEvents {
+    pub(super) fn new(log: watch::Receiver<Vec<ServerEvent>>) -> Self {
+        Self { log, cursor: 0 }
+    }
+
+    /// Wait for the next event.
+    ///
+    /// It returns `None` once [`ServerEvent::Stopped`] has been returned and the server
+    /// threads have exited.
+    pub async fn next(&mut self) -> Option<ServerEvent> {
+        loop {
+            if let Some(event) = self.log.borrow_and_update().get(self.cursor) {
+                self.cursor += 1;
+                return Some(event.clone());
+            }
+            // All senders have been dropped: no more events will be emitted.
+            self.log.changed().await.ok()?;
+        }
+    }
+}
+
+/// Emit [`ServerEvent`]s.
+///
+/// The server keeps a log of all the events it emitted,
+/// that's how new subscribers can catch up with the events they missed.
+#[derive(Clone)]
+pub(super) struct EventEmitter {
+    log: Arc<watch::Sender<Vec<ServerEvent>>>,
+    n_workers: usize,
+}
+
+impl EventEmitter {
+    pub(super) fn new(n_workers: usize) -> Self {
+        let (log, _) = watch::channel(Vec::new());
+        Self {
+            log: Arc::new(log),
+            n_workers,
+        }
+    }
+
+    pub(super) fn subscribe(&self) -> watch::Receiver<Vec<ServerEvent>> {
+        self.log.subscribe()
+    }
+
+    pub(super) fn emit(&self, event: ServerEvent) {
+        let n_workers = self.n_workers;
+        self.log.send_modify(|log| {
+            let is_worker_start = matches!(event, ServerEvent::WorkerStarted { .. });
+            log.push(event);
+            if !is_worker_start || log.contains(&ServerEvent::Ready) {
+                return;
+            }
+            let n_started = log
+                .iter()
+                .filter(|e| matches!(e, ServerEvent::WorkerStarted { .. }))
+                .count();
+            if n_started == n_workers {
+                log.push(ServerEvent::Ready);
+            }
+        });
+    }
+}
@@ -0,0 +1,225 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::task::{Context, Poll, ready};
+use std::time::Duration;
+
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::time::{Instant, Sleep};
+
+/// Shared bookkeeping to determine if a connection is idle.
+///
+/// A connection is idle when there are no in-flight requests and no data
+/// has been exchanged with the client for a while.
+///
+/// It's `Send` since `hyper` requires it for connections that can be upgraded
+/// (e.g. to a WebSocket), even though it's only ever accessed from the worker thread.
+#[derive(Clone)]
+pub(super) struct IdleTracker(Arc<IdleState>);
+
+struct IdleState {
+    in_flight: AtomicUsize,
+    created_at: Instant,
+    /// Nanoseconds elapsed between `created_at` and the last activity on the connection.
+    last_activity: AtomicU64,
+}
+
+impl IdleTracker {
+    pub(super) fn new() -> Self {
+        Self(Arc::new(IdleState {
+            in_flight: AtomicUsize::new(0),
+            created_at: Instant::now(),
+            last_activity: AtomicU64::new(0),
+        }))
+    }
+
+    /// Mark the beginning of a request.
+    ///
+    /// The connection won't be considered idle until the returned guard is dropped.
+    pub(super) fn request_started(&self) -> InFlightGuard {
+        self.0.in_flight.fetch_add(1, Ordering::Relaxed);
+        InFlightGuard(self.clone())
+    }
+
+    fn touch(&self) {
+        // A connection would have to be open for centuries to overflow a `u64`.
+        let elapsed = self.0.created_at.elapsed().as_nanos() as u64;
+        self.0.last_activity.store(elapsed, Ordering::Relaxed);
+    }
+
+    fn is_busy(&self) -> bool {
+        self.0.in_flight.load(Ordering::Relaxed) > 0
+    }
+
+    fn deadline(&self, timeout: Duration) -> Instant {
+        let last_activity =
+            self.0.created_at + Duration::from_nanos(self.0.last_activity.load(Ordering::Relaxed));
+        last_activity + timeout
+    }
+}
+
+/// Returned by [`IdleTracker::request_started`].
+pub(super) struct InFlightGuard(IdleTracker);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        (self.0).0.in_flight.fetch_sub(1, Ordering::Relaxed);
+        self.0.touch();
+    }
+}
+
+pin_project! {
+    /// A response body that keeps its connection busy until it has been fully sent.
+    ///
+    /// Streaming bodies (e.g. Server-Sent Events) may stay silent for longer than the
+    /// keep-alive timeout: the connection isn't idle until they're over.
+    pub(super) struct InFlightBody<B> {
+        #[pin]
+        body: B,
+        // `None` once the body is over.
+        guard: Option<InFlightGuard>,
+    }
+}
+
+impl<B> InFlightBody<B> {
+    pub(super) fn new(body: B, guard: InFlightGuard) -> Self {
+        Self {
+            body,
+            guard: Some(guard),
+        }
+    }
+}
+
+impl<B: Body> Body for InFlightBody<B> {
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+        let frame = ready!(this.body.as_mut().poll_frame(cx));
+        if !matches!(frame, Some(Ok(_))) || this.body.is_end_stream() {
+            this.guard.take();
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.body.size_hint()
+    }
+}
+
+pin_project! {
+    /// An IO wrapper that signals the end of the stream (EOF) to `hyper` once the
+    /// connection has been idle for longer than the configured timeout.
+    ///
+    /// `hyper` treats an EOF on an idle connection as a graceful closure.
+    pub(super) struct IdleTimeoutIo<Io> {
+        #[pin]
+        io: Io,
+        // Boxed to keep the wrapper `Unpin` if `Io` is.
+        sleep: Pin<Box<Sleep>>,
+        tracker: IdleTracker,
+        // `None` if idle connections should never be closed.
+        timeout: Option<Duration>,
+    }
+}
+
+impl<Io> IdleTimeoutIo<Io> {
+    pub(super) fn new(io: Io, tracker: IdleTracker, timeout: Option<Duration>) -> Self {
+        // The timer is reset on first use, the initial deadline doesn't matter
+        // if the timeout is disabled.
+        let deadline = tracker.deadline(timeout.unwrap_or_default());
+        Self {
+            io,
+            sleep: Box::pin(tokio::time::sleep_until(deadline)),
+            tracker,
+            timeout,
+        }
+    }
+}
+
+impl<Io: AsyncRead> AsyncRead for IdleTimeoutIo<Io> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = self.project();
+        let n_filled = buf.filled().len();
+        match this.io.poll_read(cx, buf) {
+            Poll::Ready(outcome) => {
+                if buf.filled().len() > n_filled {
+                    this.tracker.touch();
+                }
+                Poll::Ready(outcome)
+            }
+            Poll::Pending => {
+                let Some(timeout) = *this.timeout else {
+                    return Poll::Pending;
+                };
+                if this.tracker.is_busy() {
+                    return Poll::Pending;
+                }
+                let deadline = this.tracker.deadline(timeout);
+                if this.sleep.deadline() != deadline {
+                    this.sleep.as_mut().reset(deadline);
+                }
+                match this.sleep.as_mut().poll(cx) {
+                    // We leave `buf` untouched: it signals EOF.
+                    Poll::Ready(()) => Poll::Ready(Ok(())),
+                    Poll::Pending => Poll::Pending,
+                }
+            }
+        }
+    }
+}
+
+impl<Io: AsyncWrite> AsyncWrite for IdleTimeoutIo<Io> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.project();
+        let outcome = this.io.poll_write(cx, buf);
+        if matches!(outcome, Poll::Ready(Ok(n)) if n > 0) {
+            this.tracker.touch();
+        }
+        outcome
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[std::io::IoSlice<'_>],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.project();
+        let outcome = this.io.poll_write_vectored(cx, bufs);
+        if matches!(outcome, Poll::Ready(Ok(n)) if n > 0) {
+            this.tracker.touch();
+        }
+        outcome
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.io.is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.project().io.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.project().io.poll_shutdown(cx)
+    }
+}
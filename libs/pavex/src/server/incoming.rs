@@ -1,7 +1,17 @@
 use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 
 use socket2::Domain;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
 use tokio::net::{TcpListener, TcpStream};
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
+
+use crate::connection::PeerAddr;
+
+#[cfg(feature = "tls")]
+use super::TlsConfig;
 
 /// A stream of incoming connections.  
 ///
@@ -10,8 +20,15 @@ use tokio::net::{TcpListener, TcpStream};
 /// Incoming connections will be usually passed to a [`Server`](super::Server) instance to be handled.
 /// Check out [`Server::bind`](super::Server::bind) or
 /// [`Server::listen`](super::Server::listen) for more information.
+///
+/// # Transports
+///
+/// An [`IncomingStream`] can accept connections over TCP ([`IncomingStream::bind`]) or,
+/// on Unix platforms, over a Unix domain socket ([`IncomingStream::bind_unix`]).
 pub struct IncomingStream {
-    listener: TcpListener,
+    listener: Listener,
+    #[cfg(feature = "tls")]
+    tls: Option<TlsConfig>,
 }
 
 impl IncomingStream {
@@ -65,6 +82,44 @@ impl IncomingStream {
     /// # }
     /// ````
     pub async fn bind(addr: SocketAddr) -> std::io::Result<Self> {
+        Self::bind_tcp(addr, false)
+    }
+
+    /// Create a new [`IncomingStream`] by binding to a socket address, with the
+    /// `SO_REUSEPORT` option enabled.
+    /// The socket is otherwise configured in the same way as [`IncomingStream::bind`].
+    ///
+    /// `SO_REUSEPORT` allows multiple sockets to bind to the same address. It's required
+    /// by the [`ReusePort`](super::DispatchStrategy::ReusePort) dispatch strategy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::net::SocketAddr;
+    /// use pavex::server::{DispatchStrategy, IncomingStream, Server, ServerConfiguration};
+    ///
+    /// # async fn t() -> std::io::Result<()> {
+    /// let addr = SocketAddr::from(([127, 0, 0, 1], 8080));
+    /// let config = ServerConfiguration::new().set_dispatch_strategy(DispatchStrategy::ReusePort);
+    /// let server = Server::new()
+    ///     .set_config(config)
+    ///     .listen(IncomingStream::bind_reuse_port(addr).await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub async fn bind_reuse_port(addr: SocketAddr) -> std::io::Result<Self> {
+        Self::bind_tcp(addr, true)
+    }
+
+    /// Bind an additional listener to an address that's already in use by a listener
+    /// with `SO_REUSEPORT` enabled.
+    #[cfg(unix)]
+    pub(super) fn reuse_port_listener(addr: SocketAddr) -> std::io::Result<Self> {
+        Self::bind_tcp(addr, true)
+    }
+
+    fn bind_tcp(addr: SocketAddr, reuse_port: bool) -> std::io::Result<Self> {
         let socket = socket2::Socket::new(
             Domain::for_address(addr),
             socket2::Type::STREAM,
@@ -73,28 +128,168 @@ impl IncomingStream {
         .expect("Failed to create a socket");
 
         socket.set_reuse_address(true)?;
+        #[cfg(unix)]
+        if reuse_port {
+            socket.set_reuse_port(true)?;
+        }
+        #[cfg(not(unix))]
+        let _ = reuse_port;
         socket.set_nonblocking(true)?;
         socket.bind(&addr.into())?;
         socket.listen(1024_i32)?;
 
         let listener = std::net::TcpListener::from(socket);
-        Ok(Self {
-            listener: TcpListener::from_std(listener)?,
-        })
+        Ok(TcpListener::from_std(listener)?.into())
+    }
+
+    /// Create a new [`IncomingStream`] by binding to a socket address.
+    /// All connections accepted from this stream will be terminated using TLS,
+    /// according to the provided [`TlsConfig`].
+    ///
+    /// The socket is configured in the same way as [`IncomingStream::bind`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::net::SocketAddr;
+    /// use pavex::server::{IncomingStream, TlsConfig};
+    ///
+    /// # async fn t() -> anyhow::Result<()> {
+    /// let tls_config = TlsConfig::from_pem_files("certs/server.crt", "certs/server.key")?;
+    /// let addr = SocketAddr::from(([127, 0, 0, 1], 8443));
+    /// let incoming = IncomingStream::bind_tls(addr, tls_config).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "tls")]
+    pub async fn bind_tls(addr: SocketAddr, tls_config: TlsConfig) -> std::io::Result<Self> {
+        Ok(Self::bind(addr).await?.with_tls(tls_config))
+    }
+
+    /// Terminate all connections accepted from this stream using TLS,
+    /// according to the provided [`TlsConfig`].
+    ///
+    /// It's primarily useful when you've built the [`IncomingStream`] from a custom
+    /// [`TcpListener`](std::net::TcpListener).
+    /// Use [`IncomingStream::bind_tls`] otherwise.
+    #[cfg(feature = "tls")]
+    pub fn with_tls(mut self, tls_config: TlsConfig) -> Self {
+        self.tls = Some(tls_config);
+        self
+    }
+
+    /// The TLS configuration used to terminate connections accepted from this stream, if any.
+    #[cfg(feature = "tls")]
+    pub fn tls_config(&self) -> Option<&TlsConfig> {
+        self.tls.as_ref()
+    }
+
+    /// Create a new [`IncomingStream`] by binding to a Unix domain socket at the given path.
+    ///
+    /// Binding fails if a file already exists at the given path.
+    /// It's your responsibility to clean up stale socket files left behind by previous
+    /// runs of your application.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use pavex::server::IncomingStream;
+    ///
+    /// # async fn t() -> std::io::Result<()> {
+    /// let incoming = IncomingStream::bind_unix("/run/my-app/http.sock").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub async fn bind_unix(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        Ok(UnixListener::bind(path)?.into())
+    }
+
+    /// Build an [`IncomingStream`] for each listening socket inherited by the current process
+    /// via the `LISTEN_FDS` protocol—e.g. using systemd's
+    /// [socket activation](https://www.freedesktop.org/software/systemd/man/latest/systemd.socket.html)
+    /// or when a previous instance of the server
+    /// [hands off its listeners](super::ServerHandle::hand_off_listeners).
+    ///
+    /// The streams are returned in the same order as the inherited file descriptors.
+    /// An empty vector is returned if no sockets have been passed to the current process.
+    ///
+    /// The sockets are only taken into account if `LISTEN_PID` matches the pid of the current
+    /// process. Once they've been claimed, `LISTEN_PID`, `LISTEN_FDS` and `LISTEN_FDNAMES` are
+    /// removed from the environment of the current process, to prevent them from being
+    /// inherited by the processes it spawns.
+    ///
+    /// It must be invoked from within a Tokio runtime.
+    ///
+    /// Since it modifies the environment, invoke it during startup, before other threads
+    /// start reading from or writing to it—the same caveats of [`std::env::remove_var`] apply.
+    ///
+    /// # Errors
+    ///
+    /// It fails if the inherited file descriptors are not TCP or Unix domain stream sockets,
+    /// or if the listening sockets have already been claimed by a previous invocation.
+    /// The inherited file descriptors are left untouched if the function fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::net::SocketAddr;
+    /// use pavex::server::{IncomingStream, Server};
+    ///
+    /// # async fn t() -> std::io::Result<()> {
+    /// let mut server = Server::new();
+    /// let inherited = IncomingStream::from_listen_fds()?;
+    /// if inherited.is_empty() {
+    ///     // We haven't been socket-activated, we bind to the address ourselves.
+    ///     server = server.bind(SocketAddr::from(([127, 0, 0, 1], 8080))).await?;
+    /// }
+    /// for incoming in inherited {
+    ///     server = server.listen(incoming);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn from_listen_fds() -> std::io::Result<Vec<Self>> {
+        super::listen_fds::from_listen_fds()
     }
 
     /// Returns the address that this [`IncomingStream`] is bound to.
+    ///
+    /// It returns an error if the [`IncomingStream`] isn't bound to a TCP socket.
+    /// Use [`IncomingStream::local_unix_addr`] for Unix domain sockets.
     pub fn local_addr(&self) -> std::io::Result<SocketAddr> {
-        // The address we bound to may not be the same as the one we requested.
-        // This happens, for example, when binding to port 0—this will cause the OS to pick a random
-        // port for us which we won't know unless we call `local_addr` on the listener.
-        self.listener.local_addr()
+        match &self.listener {
+            // The address we bound to may not be the same as the one we requested.
+            // This happens, for example, when binding to port 0—this will cause the OS to pick a random
+            // port for us which we won't know unless we call `local_addr` on the listener.
+            Listener::Tcp(listener) => listener.local_addr(),
+            #[cfg(unix)]
+            Listener::Unix(_) => Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "The incoming stream is bound to a Unix domain socket, not to a TCP socket",
+            )),
+        }
+    }
+
+    /// Returns the address of the Unix domain socket that this [`IncomingStream`] is bound to.
+    ///
+    /// It returns an error if the [`IncomingStream`] isn't bound to a Unix domain socket.
+    #[cfg(unix)]
+    pub fn local_unix_addr(&self) -> std::io::Result<tokio::net::unix::SocketAddr> {
+        match &self.listener {
+            Listener::Unix(listener) => listener.local_addr(),
+            Listener::Tcp(_) => Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "The incoming stream is bound to a TCP socket, not to a Unix domain socket",
+            )),
+        }
     }
 
     /// Accepts a new incoming connection from the underlying listener.
     ///
-    /// This function will yield once a new TCP connection is established. When
-    /// established, the corresponding [`TcpStream`] and the remote peer's
+    /// This function will yield once a new connection is established. When
+    /// established, the corresponding [`IncomingConnection`] and the remote peer's
     /// address will be returned.
     ///
     /// # Example
@@ -108,14 +303,25 @@ impl IncomingStream {
     /// let incoming = IncomingStream::bind(address).await?;
     ///
     /// match incoming.accept().await {
-    ///     Ok((_socket, addr)) => println!("new client: {:?}", addr),
+    ///     Ok((_socket, addr)) => println!("new client: {}", addr),
     ///     Err(e) => println!("couldn't get client: {:?}", e),
     /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn accept(&self) -> std::io::Result<(TcpStream, SocketAddr)> {
-        self.listener.accept().await
+    pub async fn accept(&self) -> std::io::Result<(IncomingConnection, PeerAddr)> {
+        match &self.listener {
+            Listener::Tcp(listener) => {
+                let (stream, addr) = listener.accept().await?;
+                Ok((IncomingConnection::Tcp(stream), PeerAddr::Ip(addr)))
+            }
+            #[cfg(unix)]
+            Listener::Unix(listener) => {
+                let (stream, addr) = listener.accept().await?;
+                let path = addr.as_pathname().map(ToOwned::to_owned);
+                Ok((IncomingConnection::Unix(stream), PeerAddr::Unix(path)))
+            }
+        }
     }
 }
 
@@ -129,14 +335,143 @@ impl TryFrom<std::net::TcpListener> for IncomingStream {
         // Forgetting to set the socket to non-blocking mode will likely result in
         // mysterious server hangs.
         v.set_nonblocking(true)?;
-        Ok(Self {
-            listener: TcpListener::from_std(v)?,
-        })
+        Ok(TcpListener::from_std(v)?.into())
     }
 }
 
 impl From<TcpListener> for IncomingStream {
     fn from(v: TcpListener) -> Self {
-        Self { listener: v }
+        Self {
+            listener: Listener::Tcp(v),
+            #[cfg(feature = "tls")]
+            tls: None,
+        }
+    }
+}
+
+#[cfg(unix)]
+impl TryFrom<std::os::unix::net::UnixListener> for IncomingStream {
+    type Error = std::io::Error;
+
+    fn try_from(v: std::os::unix::net::UnixListener) -> std::io::Result<Self> {
+        // See the comment in the `TryFrom<std::net::TcpListener>` implementation.
+        v.set_nonblocking(true)?;
+        Ok(UnixListener::from_std(v)?.into())
+    }
+}
+
+#[cfg(unix)]
+impl From<UnixListener> for IncomingStream {
+    fn from(v: UnixListener) -> Self {
+        Self {
+            listener: Listener::Unix(v),
+            #[cfg(feature = "tls")]
+            tls: None,
+        }
+    }
+}
+
+#[cfg(unix)]
+impl std::os::fd::AsFd for IncomingStream {
+    fn as_fd(&self) -> std::os::fd::BorrowedFd<'_> {
+        match &self.listener {
+            Listener::Tcp(listener) => listener.as_fd(),
+            Listener::Unix(listener) => listener.as_fd(),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl std::os::fd::AsRawFd for IncomingStream {
+    fn as_raw_fd(&self) -> std::os::fd::RawFd {
+        use std::os::fd::AsFd;
+
+        self.as_fd().as_raw_fd()
+    }
+}
+
+/// The listening socket wrapped by an [`IncomingStream`].
+enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// A connection accepted by an [`IncomingStream`].
+///
+/// It can be read from and written to via [`AsyncRead`] and [`AsyncWrite`],
+/// regardless of the underlying transport.
+pub enum IncomingConnection {
+    /// A TCP connection.
+    Tcp(TcpStream),
+    /// A connection over a Unix domain socket.
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+// We delegate to the underlying stream, for each variant.
+impl AsyncRead for IncomingConnection {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            IncomingConnection::Tcp(s) => Pin::new(s).poll_read(cx, buf),
+            #[cfg(unix)]
+            IncomingConnection::Unix(s) => Pin::new(s).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for IncomingConnection {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        match self.get_mut() {
+            IncomingConnection::Tcp(s) => Pin::new(s).poll_write(cx, buf),
+            #[cfg(unix)]
+            IncomingConnection::Unix(s) => Pin::new(s).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[std::io::IoSlice<'_>],
+    ) -> Poll<std::io::Result<usize>> {
+        match self.get_mut() {
+            IncomingConnection::Tcp(s) => Pin::new(s).poll_write_vectored(cx, bufs),
+            #[cfg(unix)]
+            IncomingConnection::Unix(s) => Pin::new(s).poll_write_vectored(cx, bufs),
+        }
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        match self {
+            IncomingConnection::Tcp(s) => s.is_write_vectored(),
+            #[cfg(unix)]
+            IncomingConnection::Unix(s) => s.is_write_vectored(),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            IncomingConnection::Tcp(s) => Pin::new(s).poll_flush(cx),
+            #[cfg(unix)]
+            IncomingConnection::Unix(s) => Pin::new(s).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            IncomingConnection::Tcp(s) => Pin::new(s).poll_shutdown(cx),
+            #[cfg(unix)]
+            IncomingConnection::Unix(s) => Pin::new(s).poll_shutdown(cx),
+        }
     }
 }
@@ -0,0 +1,395 @@
+//! Socket activation (`LISTEN_FDS`) and listener handoff between processes.
+//!
+//! The protocol is documented in [`sd_listen_fds(3)`](https://www.freedesktop.org/software/systemd/man/latest/sd_listen_fds.html).
+use std::collections::BTreeMap;
+use std::ffi::{CString, OsStr, OsString};
+use std::os::fd::{AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::IncomingStream;
+
+/// The first file descriptor passed by the service manager.
+/// `0`, `1` and `2` are reserved for stdin, stdout and stderr.
+const SD_LISTEN_FDS_START: RawFd = 3;
+
+/// The environment variables used by the protocol.
+const LISTEN_VARS: [&str; 3] = ["LISTEN_PID", "LISTEN_FDS", "LISTEN_FDNAMES"];
+
+/// Set to `true` once the inherited file descriptors have been claimed.
+///
+/// The descriptors must be owned by a single [`IncomingStream`]: we don't hand them out twice.
+static LISTEN_FDS_CLAIMED: AtomicBool = AtomicBool::new(false);
+
+pub(super) fn from_listen_fds() -> std::io::Result<Vec<IncomingStream>> {
+    let n_fds = listen_fds_count(
+        std::env::var_os("LISTEN_PID").as_deref(),
+        std::env::var_os("LISTEN_FDS").as_deref(),
+        std::process::id(),
+    )?;
+    if n_fds == 0 {
+        return Ok(Vec::new());
+    }
+    let fds = SD_LISTEN_FDS_START..SD_LISTEN_FDS_START + n_fds;
+    // We don't take ownership of the descriptors until we know they're listening sockets:
+    // dropping an `OwnedFd` would close a descriptor that we don't own.
+    for fd in fds.clone() {
+        // SAFETY: the descriptor is only borrowed for the duration of the check.
+        // `fcntl` and `getsockopt` fail gracefully if it isn't open.
+        check_listener(unsafe { BorrowedFd::borrow_raw(fd) })?;
+    }
+    if LISTEN_FDS_CLAIMED.swap(true, Ordering::SeqCst) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            "The file descriptors passed via `LISTEN_FDS` have already been claimed",
+        ));
+    }
+    // The variables must not be inherited by the processes we spawn:
+    // the descriptors are not going to be passed to them.
+    for var in LISTEN_VARS {
+        // SAFETY: `IncomingStream::from_listen_fds` must not be invoked while
+        // other threads are accessing the environment, as documented.
+        unsafe { std::env::remove_var(var) };
+    }
+
+    let mut incoming = Vec::with_capacity(n_fds as usize);
+    for fd in fds {
+        // SAFETY: the service manager (or the process that handed off its listeners to us)
+        // guarantees that these file descriptors are open and that we own them.
+        // `LISTEN_FDS_CLAIMED` ensures that we take ownership at most once.
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+        incoming.push(incoming_from_fd(fd)?);
+    }
+    Ok(incoming)
+}
+
+/// Determine how many file descriptors have been passed to this process.
+fn listen_fds_count(
+    listen_pid: Option<&OsStr>,
+    listen_fds: Option<&OsStr>,
+    own_pid: u32,
+) -> std::io::Result<RawFd> {
+    fn invalid(var: &str, value: &OsStr) -> std::io::Error {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "`{var}` is set to an invalid value: {}",
+                value.to_string_lossy()
+            ),
+        )
+    }
+
+    // `LISTEN_PID` must match our pid—otherwise the variables were meant
+    // for another process and we inherited them by accident.
+    let Some(listen_pid) = listen_pid else {
+        return Ok(0);
+    };
+    let pid: u32 = listen_pid
+        .to_str()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| invalid("LISTEN_PID", listen_pid))?;
+    if pid != own_pid {
+        return Ok(0);
+    }
+    let Some(listen_fds) = listen_fds else {
+        return Ok(0);
+    };
+    listen_fds
+        .to_str()
+        .and_then(|s| s.parse::<RawFd>().ok())
+        .filter(|n| *n >= 0 && n.checked_add(SD_LISTEN_FDS_START).is_some())
+        .ok_or_else(|| invalid("LISTEN_FDS", listen_fds))
+}
+
+/// Check that an inherited file descriptor is a stream socket.
+fn check_listener(fd: BorrowedFd<'_>) -> std::io::Result<()> {
+    let raw_fd = fd.as_raw_fd();
+    let socket = socket2::SockRef::from(&fd);
+    if socket.r#type()? != socket2::Type::STREAM {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("The inherited file descriptor {raw_fd} is not a stream socket"),
+        ));
+    }
+    Ok(())
+}
+
+/// Convert an inherited file descriptor into an [`IncomingStream`], after checking
+/// that it's bound to an IP address or to a Unix domain socket.
+fn incoming_from_fd(fd: OwnedFd) -> std::io::Result<IncomingStream> {
+    let raw_fd = fd.as_raw_fd();
+    let socket = socket2::Socket::from(fd);
+    // Inherited descriptors don't have the close-on-exec flag set.
+    // We don't want to leak them to processes we spawn.
+    socket.set_cloexec(true)?;
+    let local_addr = socket.local_addr()?;
+    if local_addr.is_unix() {
+        let listener = std::os::unix::net::UnixListener::from(OwnedFd::from(socket));
+        listener.try_into()
+    } else if local_addr.as_socket().is_some() {
+        let listener = std::net::TcpListener::from(socket);
+        listener.try_into()
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "The inherited file descriptor {raw_fd} is neither a TCP socket nor a Unix domain socket"
+            ),
+        ))
+    }
+}
+
+/// The listening sockets of a running [`Server`](super::Server), ready to be handed off
+/// to a successor process.
+///
+/// You get a [`ListenerHandoff`] from [`ServerHandle::hand_off_listeners`](super::ServerHandle::hand_off_listeners).
+/// Check out its documentation for an example.
+///
+/// # Protocol
+///
+/// The listeners are passed using the same protocol used by systemd for socket activation:
+/// they're available in the successor process as file descriptors `3`, `4`, etc., in the
+/// same order they were given to the [`Server`](super::Server), `LISTEN_FDS` is set
+/// to their number and `LISTEN_PID` is set to the pid of the successor.
+/// The successor can retrieve them via [`IncomingStream::from_listen_fds`].
+///
+/// TLS settings are not handed off: the successor must configure them again, via
+/// [`IncomingStream::with_tls`].
+pub struct ListenerHandoff {
+    fds: Vec<OwnedFd>,
+}
+
+impl ListenerHandoff {
+    pub(super) fn new(fds: Vec<OwnedFd>) -> Self {
+        Self { fds }
+    }
+
+    /// The number of listeners that will be handed off.
+    pub fn len(&self) -> usize {
+        self.fds.len()
+    }
+
+    /// Returns `true` if there are no listeners to hand off.
+    pub fn is_empty(&self) -> bool {
+        self.fds.is_empty()
+    }
+
+    /// Configure `command` to pass the listeners to the process it spawns.
+    ///
+    /// The listeners are kept open until `command` is dropped.
+    ///
+    /// `LISTEN_PID` can only be set once the successor has been forked, so `command` is
+    /// executed by `pavex` rather than by [`Command::spawn`]: the program, the arguments
+    /// and the environment of `command` are captured when this method is invoked.
+    /// Invoke it after you're done configuring them.
+    pub fn configure(self, command: &mut Command) -> &mut Command {
+        let Self { fds } = self;
+        let n_fds = fds.len() as RawFd;
+        let mut exec = SuccessorExec::new(command, n_fds);
+        // Allocated upfront: we can't allocate in the child process, after `fork`.
+        let mut scratch: Vec<RawFd> = vec![-1; fds.len()];
+        let move_to_start = move || {
+            // We first move all listeners above the target range, to make sure that
+            // we don't overwrite a listener while moving another one into its final slot.
+            for (fd, tmp) in fds.iter().zip(scratch.iter_mut()) {
+                // SAFETY: `fcntl` is async-signal-safe and `fd` is open.
+                let moved = unsafe {
+                    libc::fcntl(
+                        fd.as_raw_fd(),
+                        libc::F_DUPFD_CLOEXEC,
+                        SD_LISTEN_FDS_START + n_fds,
+                    )
+                };
+                if moved == -1 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                *tmp = moved;
+            }
+            for (i, tmp) in scratch.iter().enumerate() {
+                // `dup2` clears the close-on-exec flag on the target descriptor,
+                // so the listener survives `exec`.
+                // SAFETY: `dup2` is async-signal-safe and `tmp` is open.
+                if unsafe { libc::dup2(*tmp, SD_LISTEN_FDS_START + i as RawFd) } == -1 {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }
+            match &mut exec {
+                Ok(exec) => Err(exec.exec()),
+                Err(_) => Err(std::io::Error::from_raw_os_error(libc::EINVAL)),
+            }
+        };
+        // SAFETY: the closure only invokes async-signal-safe functions and doesn't allocate.
+        unsafe { command.pre_exec(move_to_start) }
+    }
+}
+
+/// The prefix of the `LISTEN_PID` entry in the environment of the successor.
+const LISTEN_PID_PREFIX: &[u8] = b"LISTEN_PID=";
+
+/// Everything we need to `execve` the successor from the forked child process.
+///
+/// It's computed upfront, in the parent process: we can't allocate after `fork`.
+/// We can't rely on [`Command`] to set `LISTEN_PID` either, since its environment is
+/// finalized before `fork`.
+struct SuccessorExec {
+    /// The resolved path of the program.
+    program: CString,
+    /// The NUL-terminated arguments, pointing into `_args`.
+    argv: Vec<*const libc::c_char>,
+    _args: Vec<CString>,
+    /// The NUL-terminated environment, pointing into `_env`.
+    /// The slot before the trailing null pointer is reserved for `LISTEN_PID`.
+    envp: Vec<*const libc::c_char>,
+    _env: Vec<CString>,
+    /// The buffer for the `LISTEN_PID` entry, big enough for any pid.
+    listen_pid: Box<[u8]>,
+}
+
+// SAFETY: the raw pointers point into heap allocations owned by the struct itself.
+unsafe impl Send for SuccessorExec {}
+// SAFETY: see above.
+unsafe impl Sync for SuccessorExec {}
+
+impl SuccessorExec {
+    fn new(command: &Command, n_fds: RawFd) -> Result<Self, std::ffi::NulError> {
+        let mut env: BTreeMap<OsString, OsString> = std::env::vars_os().collect();
+        for (key, value) in command.get_envs() {
+            match value {
+                Some(value) => env.insert(key.to_owned(), value.to_owned()),
+                None => env.remove(key),
+            };
+        }
+        for var in LISTEN_VARS {
+            env.remove(OsStr::new(var));
+        }
+        env.insert("LISTEN_FDS".into(), n_fds.to_string().into());
+
+        let program = resolve_program(
+            Path::new(command.get_program()),
+            env.get(OsStr::new("PATH")).map(OsString::as_os_str),
+        );
+        let args = std::iter::once(command.get_program())
+            .chain(command.get_args())
+            .map(|arg| CString::new(arg.as_bytes()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let env = env
+            .into_iter()
+            .map(|(mut key, value)| {
+                key.push("=");
+                key.push(value);
+                CString::new(key.into_vec())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut envp = nul_terminated(&env);
+        envp.insert(envp.len() - 1, std::ptr::null());
+        Ok(Self {
+            program: CString::new(program.into_os_string().into_vec())?,
+            argv: nul_terminated(&args),
+            envp,
+            _args: args,
+            _env: env,
+            // The longest `u64` has 20 digits, plus the trailing NUL.
+            listen_pid: vec![0; LISTEN_PID_PREFIX.len() + 21].into_boxed_slice(),
+        })
+    }
+
+    /// Set `LISTEN_PID` to the pid of the current process, then `execve` the successor.
+    ///
+    /// It only returns on failure.
+    /// It's async-signal-safe and it doesn't allocate.
+    fn exec(&mut self) -> std::io::Error {
+        // SAFETY: `getpid` is async-signal-safe and it never fails.
+        let pid = unsafe { libc::getpid() } as u64;
+        self.listen_pid[..LISTEN_PID_PREFIX.len()].copy_from_slice(LISTEN_PID_PREFIX);
+        let digits = &mut self.listen_pid[LISTEN_PID_PREFIX.len()..];
+        let n_digits = pid.checked_ilog10().unwrap_or(0) as usize + 1;
+        let mut rest = pid;
+        for digit in digits[..n_digits].iter_mut().rev() {
+            *digit = b'0' + (rest % 10) as u8;
+            rest /= 10;
+        }
+        digits[n_digits] = 0;
+        let slot = self.envp.len() - 2;
+        self.envp[slot] = self.listen_pid.as_ptr().cast();
+        // SAFETY: `execve` is async-signal-safe and all pointers are valid, NUL-terminated.
+        unsafe {
+            libc::execve(
+                self.program.as_ptr(),
+                self.argv.as_ptr(),
+                self.envp.as_ptr(),
+            )
+        };
+        std::io::Error::last_os_error()
+    }
+}
+
+/// Collect pointers to `strings`, followed by a null pointer.
+fn nul_terminated<'a>(strings: impl IntoIterator<Item = &'a CString>) -> Vec<*const libc::c_char> {
+    strings
+        .into_iter()
+        .map(|s| s.as_ptr())
+        .chain(std::iter::once(std::ptr::null()))
+        .collect()
+}
+
+/// Look up `program` in `PATH`, the same way `execvp` would.
+///
+/// `program` is returned as is if it contains a slash or if it can't be found.
+fn resolve_program(program: &Path, path: Option<&OsStr>) -> PathBuf {
+    if program.as_os_str().as_bytes().contains(&b'/') {
+        return program.to_owned();
+    }
+    let path = path.unwrap_or(OsStr::new("/usr/local/bin:/usr/bin:/bin"));
+    std::env::split_paths(path)
+        .map(|dir| dir.join(program))
+        .find(|candidate| {
+            candidate
+                .metadata()
+                .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        })
+        .unwrap_or_else(|| program.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsStr;
+    use std::path::Path;
+
+    use super::{listen_fds_count, resolve_program};
+
+    #[test]
+    fn listen_fds_count_checks_the_pid() {
+        let count = |pid: Option<&str>, fds: Option<&str>| {
+            listen_fds_count(pid.map(OsStr::new), fds.map(OsStr::new), 42)
+        };
+        assert_eq!(count(Some("42"), Some("2")).unwrap(), 2);
+        // Meant for another process.
+        assert_eq!(count(Some("7"), Some("2")).unwrap(), 0);
+        // `LISTEN_PID` is required.
+        assert_eq!(count(None, Some("1")).unwrap(), 0);
+        assert_eq!(count(None, None).unwrap(), 0);
+        assert_eq!(count(Some("42"), None).unwrap(), 0);
+
+        insta::assert_snapshot!(count(Some("42"), Some("two")).unwrap_err(), @"`LISTEN_FDS` is set to an invalid value: two");
+        insta::assert_snapshot!(count(Some("pid"), Some("2")).unwrap_err(), @"`LISTEN_PID` is set to an invalid value: pid");
+        insta::assert_snapshot!(count(Some("42"), Some("-1")).unwrap_err(), @"`LISTEN_FDS` is set to an invalid value: -1");
+    }
+
+    #[test]
+    fn programs_are_resolved_via_path() {
+        let path = OsStr::new("/nonexistent:/bin:/usr/bin");
+        assert!(resolve_program(Path::new("sh"), Some(path)).ends_with("bin/sh"));
+        assert_eq!(
+            resolve_program(Path::new("./app"), Some(path)),
+            Path::new("./app")
+        );
+        assert_eq!(
+            resolve_program(Path::new("not-a-program"), Some(path)),
+            Path::new("not-a-program")
+        );
+    }
+}
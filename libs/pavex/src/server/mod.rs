@@ -30,15 +30,36 @@
 //!
 //! Check out [`Server`]'s documentation for more information.
 pub use configuration::ServerConfiguration;
-pub use incoming::IncomingStream;
+pub use dispatch::DispatchStrategy;
+pub use events::{ServerEvent, ServerEvents};
+pub use incoming::{IncomingConnection, IncomingStream};
+#[cfg(unix)]
+pub use listen_fds::ListenerHandoff;
+pub use protocol::HttpProtocol;
 pub use server::Server;
 pub use server_handle::ServerHandle;
 pub use shutdown_mode::ShutdownMode;
+pub use stats::{ServerStats, WorkerStats};
+#[cfg(feature = "tls")]
+pub use tls::{TlsConfig, TlsConfigError, TlsPemFiles};
 
+mod admission;
 mod configuration;
+mod dispatch;
+pub(crate) mod drain_signal;
+mod events;
+mod idle_timeout;
 mod incoming;
+#[cfg(unix)]
+mod listen_fds;
+mod protocol;
 #[allow(clippy::module_inception)]
 mod server;
 mod server_handle;
 mod shutdown_mode;
+mod signals;
+mod stats;
+#[cfg(feature = "tls")]
+mod tls;
+pub(crate) mod upgrade;
 mod worker;
@@ -0,0 +1,40 @@
+use serde::Deserialize;
+
+/// The HTTP protocol versions spoken by a [`Server`](super::Server).
+///
+/// Use [`ServerConfiguration::set_http_protocol`](super::ServerConfiguration::set_http_protocol)
+/// to pick one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum HttpProtocol {
+    /// Serve both HTTP/1 and HTTP/2.
+    ///
+    /// On plaintext connections, the protocol is detected by looking at the first bytes
+    /// sent by the client: connections that start with the HTTP/2 connection preface
+    /// are served using HTTP/2 ("prior knowledge"), all others using HTTP/1.
+    /// On TLS connections, the protocol is negotiated via ALPN.
+    #[default]
+    Auto,
+    /// Only serve HTTP/1.
+    ///
+    /// Clients that try to speak HTTP/2 will have their connections closed.
+    Http1Only,
+    /// Only serve HTTP/2.
+    ///
+    /// Plaintext connections must use HTTP/2 with prior knowledge (a.k.a. `h2c`),
+    /// as required by most gRPC clients.
+    Http2Only,
+}
+
+impl HttpProtocol {
+    /// Returns `true` if the given ALPN protocol identifier is compatible with this setting.
+    #[cfg(feature = "tls")]
+    pub(super) fn allows_alpn(self, alpn_protocol: &[u8]) -> bool {
+        match self {
+            HttpProtocol::Auto => true,
+            HttpProtocol::Http1Only => alpn_protocol != b"h2",
+            HttpProtocol::Http2Only => alpn_protocol == b"h2",
+        }
+    }
+}
@@ -1,8 +1,10 @@
 use std::future::Future;
 use std::net::SocketAddr;
+use std::time::Duration;
 
 use crate::connection::ConnectionInfo;
 use crate::server::configuration::ServerConfiguration;
+use crate::server::dispatch::Listeners;
 use crate::server::server_handle::ServerHandle;
 
 use super::IncomingStream;
@@ -48,6 +50,7 @@ use super::IncomingStream;
 ///
 /// By default, [`Server::serve`] creates a worker per CPU core and distributes connection from an
 /// acceptor thread using a round-robin strategy.
+/// Check out [`DispatchStrategy`](super::DispatchStrategy) for the alternatives.
 ///
 /// Each worker has its own single-threaded [`tokio`] runtime—there is no work stealing across
 /// workers.
@@ -57,6 +60,7 @@ use super::IncomingStream;
 pub struct Server {
     config: ServerConfiguration,
     incoming: Vec<IncomingStream>,
+    shutdown_on_signals: Option<Duration>,
 }
 
 impl Default for Server {
@@ -71,6 +75,7 @@ impl Server {
         Self {
             config: ServerConfiguration::default(),
             incoming: Vec::new(),
+            shutdown_on_signals: None,
         }
     }
 
@@ -91,6 +96,42 @@ impl Server {
         &self.config
     }
 
+    /// Shut down the server when the process receives a termination signal—`SIGTERM` or
+    /// `SIGINT` on Unix, `Ctrl+C` on Windows.
+    ///
+    /// The first signal triggers a [graceful shutdown](super::ShutdownMode::Graceful),
+    /// with the given `timeout`.
+    /// If another signal is received while the server is draining open connections,
+    /// the server is [shut down immediately](super::ShutdownMode::Forced).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::net::SocketAddr;
+    /// use std::time::Duration;
+    /// use pavex::server::Server;
+    ///
+    /// # #[derive(Clone)] struct ApplicationState;
+    /// # async fn router(_req: hyper::Request<hyper::body::Incoming>, _conn_info: Option<pavex::connection::ConnectionInfo>, _state: ApplicationState) -> pavex::response::Response { todo!() }
+    /// # async fn t() -> std::io::Result<()> {
+    /// # let application_state = ApplicationState;
+    /// let addr = SocketAddr::from(([127, 0, 0, 1], 8080));
+    ///
+    /// Server::new()
+    ///     .shutdown_on_signals(Duration::from_secs(30))
+    ///     .bind(addr)
+    ///     .await?
+    ///     .serve(router, application_state)
+    ///     // Returns when the server has shut down, e.g. after a `SIGTERM`.
+    ///     .await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn shutdown_on_signals(mut self, timeout: Duration) -> Self {
+        self.shutdown_on_signals = Some(timeout);
+        self
+    }
+
     /// Bind the server to the given address: the server will accept incoming connections from this
     /// address when started.
     /// Binding an address may fail (e.g. if the address is already in use), therefore this method
@@ -150,6 +191,74 @@ impl Server {
         Ok(self)
     }
 
+    /// Bind the server to a Unix domain socket at the given path: the server will accept incoming
+    /// connections from this socket when started.
+    ///
+    /// Check out [`IncomingStream::bind_unix`] for more details.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use pavex::server::Server;
+    ///
+    /// # async fn t() -> std::io::Result<()> {
+    /// Server::new()
+    ///     .bind_unix("/run/my-app/http.sock")
+    ///     .await?
+    ///     # ;
+    ///     // [...]
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub async fn bind_unix(mut self, path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let incoming = IncomingStream::bind_unix(path).await?;
+        self.incoming.push(incoming);
+        Ok(self)
+    }
+
+    /// Bind the server to the given address, terminating incoming connections using TLS.
+    ///
+    /// It behaves like [`Server::bind`], but every connection accepted from this address
+    /// goes through a TLS handshake, using the provided [`TlsConfig`], before being handed
+    /// over to your request handler.
+    ///
+    /// Use [`IncomingStream::bind_tls`] together with [`Server::listen`] if you need
+    /// more control over the socket configuration.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::net::SocketAddr;
+    /// use pavex::server::{Server, TlsConfig};
+    ///
+    /// # async fn t() -> anyhow::Result<()> {
+    /// let tls_config = TlsConfig::from_pem_files("certs/server.crt", "certs/server.key")?;
+    /// let http_addr = SocketAddr::from(([127, 0, 0, 1], 8080));
+    /// let https_addr = SocketAddr::from(([127, 0, 0, 1], 8443));
+    ///
+    /// // You can mix plaintext and TLS listeners.
+    /// Server::new()
+    ///     .bind(http_addr)
+    ///     .await?
+    ///     .bind_tls(https_addr, tls_config)
+    ///     .await?
+    ///     # ;
+    ///     // [...]
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "tls")]
+    pub async fn bind_tls(
+        mut self,
+        addr: SocketAddr,
+        tls_config: super::TlsConfig,
+    ) -> std::io::Result<Self> {
+        let incoming = IncomingStream::bind_tls(addr, tls_config).await?;
+        self.incoming.push(incoming);
+        Ok(self)
+    }
+
     /// Ask the server to process incoming connections from the provided [`IncomingStream`].
     ///
     /// # [`Server::listen`] vs [`Server::bind`]
@@ -249,6 +358,8 @@ impl Server {
     ///
     /// This method will panic if the [`Server`] has no registered sources of incoming connections,
     /// i.e. if you did not call [`Server::bind`] or [`Server::listen`] before calling `serve`.
+    /// It will also panic if the listeners don't meet the requirements of the configured
+    /// [`DispatchStrategy`](super::DispatchStrategy).
     ///
     /// If you'd rather handle the error, use [`Server::try_serve`] instead.
     pub fn serve<HandlerFuture, ApplicationState>(
@@ -271,7 +382,8 @@ impl Server {
     ///
     /// It will return an error, rather than panicking, if the [`Server`] has no registered sources
     /// of incoming connections, i.e. if you did not call [`Server::bind`] or [`Server::listen`]
-    /// before calling `serve`.
+    /// before calling `serve`, or if the listeners don't meet the requirements of the configured
+    /// [`DispatchStrategy`](super::DispatchStrategy).
     pub fn try_serve<HandlerFuture, ApplicationState>(
         self,
         handler: fn(
@@ -289,11 +401,13 @@ impl Server {
             let err_msg = "Cannot serve: there is no source of incoming connections. You must call `bind` or `listen` on the `Server` instance before invoking `serve`.";
             return Err(std::io::Error::new(std::io::ErrorKind::Other, err_msg));
         }
+        let listeners = Listeners::new(self.incoming, &self.config)?;
         Ok(ServerHandle::new(
             self.config,
-            self.incoming,
+            listeners,
             handler,
             application_state,
+            self.shutdown_on_signals,
         ))
     }
 }
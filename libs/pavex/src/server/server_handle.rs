@@ -1,21 +1,31 @@
 use std::future::{Future, IntoFuture, poll_fn};
 use std::io::Error;
 use std::marker::PhantomData;
-use std::net::SocketAddr;
+#[cfg(unix)]
+use std::os::fd::{AsFd, OwnedFd};
 use std::pin::Pin;
 use std::task::Poll;
 use std::thread;
+use std::time::Duration;
 
-use tokio::net::TcpStream;
+use smallvec::SmallVec;
 use tokio::sync::mpsc::error::TrySendError;
 use tokio::task::{JoinError, JoinSet, LocalSet};
 use tracing_log_error::log_error;
 
-use crate::connection::ConnectionInfo;
+use crate::connection::{ConnectionInfo, PeerAddr};
+use crate::server::admission::AdmissionControl;
 use crate::server::configuration::ServerConfiguration;
+use crate::server::dispatch::{AcceptBackoff, DispatchStrategy, Listeners};
+use crate::server::events::EventEmitter;
+use crate::server::signals::ShutdownSignals;
 use crate::server::worker::{ConnectionMessage, Worker, WorkerHandle};
 
-use super::{IncomingStream, ShutdownMode};
+#[cfg(unix)]
+use super::ListenerHandoff;
+use super::{
+    IncomingConnection, IncomingStream, ServerEvent, ServerEvents, ServerStats, ShutdownMode,
+};
 
 /// A handle to a running [`Server`](super::Server).
 ///
@@ -45,30 +55,73 @@ use super::{IncomingStream, ShutdownMode};
 #[derive(Clone)]
 pub struct ServerHandle {
     command_outbox: tokio::sync::mpsc::Sender<ServerCommand>,
+    events: tokio::sync::watch::Receiver<Vec<ServerEvent>>,
 }
 
 impl ServerHandle {
     pub(super) fn new<HandlerFuture, ApplicationState>(
         config: ServerConfiguration,
-        incoming: Vec<IncomingStream>,
+        listeners: Listeners,
         handler: fn(
             http::Request<hyper::body::Incoming>,
             Option<ConnectionInfo>,
             ApplicationState,
         ) -> HandlerFuture,
         application_state: ApplicationState,
+        shutdown_on_signals: Option<Duration>,
     ) -> Self
     where
         HandlerFuture: Future<Output = crate::response::Response> + 'static,
         ApplicationState: Clone + Send + Sync + 'static,
     {
         let (command_outbox, command_inbox) = tokio::sync::mpsc::channel(32);
-        let acceptor = Acceptor::new(config, incoming, handler, application_state, command_inbox);
+        let event_emitter = EventEmitter::new(config.n_workers.get());
+        let events = event_emitter.subscribe();
+        let acceptor = Acceptor::new(
+            config,
+            listeners,
+            handler,
+            application_state,
+            command_inbox,
+            event_emitter,
+            shutdown_on_signals,
+        );
         let _ = acceptor.spawn();
-        Self { command_outbox }
+        Self {
+            command_outbox,
+            events,
+        }
+    }
+
+    /// Subscribe to the [`ServerEvent`]s emitted by the [`Server`](super::Server)
+    /// over its lifetime—e.g. to find out when it's ready to handle requests.
+    ///
+    /// The subscription replays all the events that have already been emitted,
+    /// no matter when you call this method.
+    /// Check out [`ServerEvents`] for an example.
+    pub fn events(&self) -> ServerEvents {
+        ServerEvents::new(self.events.clone())
+    }
+
+    /// Take a snapshot of the load on the [`Server`](super::Server): the number of connections
+    /// that are being handled or are waiting to be picked up, for each worker thread.
+    ///
+    /// It returns `None` if the server has already shut down.
+    pub async fn stats(&self) -> Option<ServerStats> {
+        let (completion_notifier, completion) = tokio::sync::oneshot::channel();
+        self.command_outbox
+            .send(ServerCommand::Stats {
+                completion_notifier,
+            })
+            .await
+            .ok()?;
+        completion.await.ok()
     }
 
     /// Instruct the [`Server`](super::Server) to stop accepting new connections.
+    ///
+    /// If a graceful shutdown is already in progress, asking for a
+    /// [forced shutdown](ShutdownMode::Forced) cuts it short.
     #[doc(alias("stop"))]
     pub async fn shutdown(self, mode: ShutdownMode) {
         let (completion_notifier, completion) = tokio::sync::oneshot::channel();
@@ -87,6 +140,55 @@ impl ServerHandle {
             let _ = completion.await;
         }
     }
+
+    /// Duplicate the listening sockets of the [`Server`](super::Server), in order to hand them
+    /// off to a successor process—e.g. to restart your application without downtime.
+    ///
+    /// The server keeps accepting connections until you [shut it down](ServerHandle::shutdown).
+    /// While both processes are running, incoming connections are distributed between them
+    /// by the operating system.
+    ///
+    /// The successor can take ownership of the listeners via
+    /// [`IncomingStream::from_listen_fds`](super::IncomingStream::from_listen_fds).
+    /// Check out [`ListenerHandoff`] for more details on the protocol.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    /// use pavex::server::{ServerHandle, ShutdownMode};
+    ///
+    /// # async fn t(server_handle: ServerHandle) -> std::io::Result<()> {
+    /// let handoff = server_handle.hand_off_listeners().await?;
+    /// let mut command = Command::new(std::env::current_exe()?);
+    /// handoff.configure(&mut command);
+    /// let _successor = command.spawn()?;
+    /// // [...] Wait for the successor to be ready.
+    /// // Then stop accepting new connections and drain the in-flight ones.
+    /// server_handle
+    ///     .shutdown(ShutdownMode::Graceful { timeout: Duration::from_secs(30) })
+    ///     .await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub async fn hand_off_listeners(&self) -> std::io::Result<ListenerHandoff> {
+        let (completion_notifier, completion) = tokio::sync::oneshot::channel();
+        let server_is_down = || {
+            std::io::Error::new(
+                std::io::ErrorKind::NotConnected,
+                "The server has already shut down, there are no listeners to hand off",
+            )
+        };
+        self.command_outbox
+            .send(ServerCommand::HandOffListeners {
+                completion_notifier,
+            })
+            .await
+            .map_err(|_| server_is_down())?;
+        completion.await.map_err(|_| server_is_down())?
+    }
 }
 
 impl IntoFuture for ServerHandle {
@@ -103,23 +205,38 @@ enum ServerCommand {
         completion_notifier: tokio::sync::oneshot::Sender<()>,
         mode: ShutdownMode,
     },
+    Stats {
+        completion_notifier: tokio::sync::oneshot::Sender<ServerStats>,
+    },
+    #[cfg(unix)]
+    HandOffListeners {
+        completion_notifier: tokio::sync::oneshot::Sender<std::io::Result<ListenerHandoff>>,
+    },
 }
 
 #[must_use]
 struct Acceptor<HandlerFuture, ApplicationState> {
     command_inbox: tokio::sync::mpsc::Receiver<ServerCommand>,
     incoming: Vec<IncomingStream>,
+    /// Duplicates of the listening sockets, in the same order as `incoming`.
+    /// We keep them around to serve [`ServerCommand::HandOffListeners`], since `incoming`
+    /// is moved into the tasks that accept connections.
+    #[cfg(unix)]
+    listener_fds: std::io::Result<Vec<OwnedFd>>,
     worker_handles: Vec<WorkerHandle>,
-    #[allow(dead_code)]
     config: ServerConfiguration,
     next_worker: usize,
-    max_queue_length: usize,
+    admission: AdmissionControl,
     handler: fn(
         http::Request<hyper::body::Incoming>,
         Option<ConnectionInfo>,
         ApplicationState,
     ) -> HandlerFuture,
     application_state: ApplicationState,
+    events: EventEmitter,
+    /// If set, shut down gracefully, with the given timeout, when a termination signal
+    /// is received.
+    shutdown_on_signals: Option<Duration>,
     // We use a `fn() -> HandlerFuture` instead of a `HandlerFuture` because we need `Acceptor`
     // to be `Send` and `Sync`. That wouldn't work with `PhantomData<HandlerFuture>`.
     // In the end, we just need to stash the generic type *somewhere*.
@@ -128,7 +245,14 @@ struct Acceptor<HandlerFuture, ApplicationState> {
 
 enum AcceptorInboxMessage {
     ServerCommand(ServerCommand),
-    Connection(Option<Result<(IncomingStream, TcpStream, SocketAddr), JoinError>>),
+    ShutdownSignal,
+    WorkerExited(usize),
+    Connection(Option<Result<(IncomingStream, IncomingConnection, PeerAddr), JoinError>>),
+}
+
+enum DrainingInboxMessage {
+    ServerCommand(ServerCommand),
+    ShutdownSignal,
 }
 
 impl<HandlerFuture, ApplicationState> Acceptor<HandlerFuture, ApplicationState>
@@ -138,7 +262,7 @@ where
 {
     fn new(
         config: ServerConfiguration,
-        incoming: Vec<IncomingStream>,
+        listeners: Listeners,
         handler: fn(
             http::Request<hyper::body::Incoming>,
             Option<ConnectionInfo>,
@@ -146,28 +270,54 @@ where
         ) -> HandlerFuture,
         application_state: ApplicationState,
         command_inbox: tokio::sync::mpsc::Receiver<ServerCommand>,
+        events: EventEmitter,
+        shutdown_on_signals: Option<Duration>,
     ) -> Self {
-        // TODO: make this configurable
-        let max_queue_length = 15;
+        events.emit(ServerEvent::Bound);
+        let admission = AdmissionControl::new(&config);
+        let Listeners {
+            shared: incoming,
+            per_worker,
+        } = listeners;
+        // If workers own their listeners, we hand off the ones given to the first worker:
+        // they're the listeners that were originally passed to the server.
+        #[cfg(unix)]
+        let listener_fds = incoming
+            .iter()
+            .chain(per_worker.first().into_iter().flatten())
+            .map(|i| i.as_fd().try_clone_to_owned())
+            .collect();
         let n_workers = config.n_workers.get();
+        let mut per_worker = per_worker.into_iter();
         let mut worker_handles = Vec::with_capacity(n_workers);
         for i in 0..n_workers {
-            let (worker, handle) =
-                Worker::new(i, max_queue_length, handler, application_state.clone());
+            let (worker, handle) = Worker::new(
+                i,
+                &config,
+                admission.clone(),
+                handler,
+                application_state.clone(),
+                events.clone(),
+                per_worker.next().unwrap_or_default(),
+            );
             worker_handles.push(handle);
             // TODO: should we panic here?
             worker.spawn().expect("Failed to spawn worker thread");
         }
         Self {
             command_inbox,
+            #[cfg(unix)]
+            listener_fds,
             incoming,
             worker_handles,
             config,
-            max_queue_length,
+            admission,
             handler,
             handler_output_future: Default::default(),
             next_worker: 0,
             application_state,
+            events,
+            shutdown_on_signals,
         }
     }
 
@@ -179,7 +329,7 @@ where
         /// If accepting a certain connection fails, log the error and keep trying with the next connection.
         async fn accept_connection(
             incoming: IncomingStream,
-        ) -> (IncomingStream, TcpStream, SocketAddr) {
+        ) -> (IncomingStream, IncomingConnection, PeerAddr) {
             #[allow(deprecated)]
             // This has been inlined from `tokio`'s codebase, since it's not public API.
             fn is_rt_shutdown_err(err: &Error) -> bool {
@@ -196,6 +346,7 @@ where
                 inner.source().is_none() && inner.description() == RT_SHUTDOWN_ERR
             }
 
+            let mut backoff = AcceptBackoff::default();
             loop {
                 match incoming.accept().await {
                     Ok((connection, remote_peer)) => return (incoming, connection, remote_peer),
@@ -205,6 +356,7 @@ where
                         } else {
                             log_error!(e, level: tracing::Level::INFO, "Failed to accept connection");
                         }
+                        backoff.wait(&e).await;
                         continue;
                     }
                 }
@@ -216,40 +368,147 @@ where
             mut next_worker,
             mut worker_handles,
             incoming,
-            config: _,
-            max_queue_length,
+            #[cfg(unix)]
+            listener_fds,
+            config,
+            admission,
             handler,
             application_state,
+            events,
+            shutdown_on_signals,
             handler_output_future: _,
         } = self;
 
         let n_workers = worker_handles.len();
+        let rejector = admission.rejector();
+        // The signal handlers are installed on the acceptor runtime, which lives as long
+        // as the server itself.
+        let mut shutdown_signals = match shutdown_on_signals.map(|_| ShutdownSignals::new()) {
+            Some(Ok(signals)) => Some(signals),
+            Some(Err(e)) => {
+                log_error!(
+                    e,
+                    "Failed to listen for termination signals. \
+                    The server won't shut down when it receives one."
+                );
+                None
+            }
+            None => None,
+        };
 
         let mut incoming_join_set = JoinSet::new();
         for incoming in incoming.into_iter() {
             incoming_join_set.spawn(accept_connection(incoming));
         }
 
+        // Workers that own their listeners never receive connections from the acceptor,
+        // so we can't find out that they crashed when dispatching: we watch them instead.
+        let mut exited_workers = JoinSet::new();
+        #[cfg(unix)]
+        if config.dispatch_strategy == DispatchStrategy::ReusePort {
+            for worker_handle in &worker_handles {
+                let worker_id = worker_handle.id();
+                let exited = worker_handle.exited();
+                exited_workers.spawn(async move {
+                    exited.await;
+                    worker_id
+                });
+            }
+        }
+
         let error = 'event_loop: loop {
             // Check if there is work to be done.
-            let message =
-                poll_fn(|cx| Self::poll_inboxes(cx, &mut command_inbox, &mut incoming_join_set))
-                    .await;
+            let message = poll_fn(|cx| {
+                Self::poll_inboxes(
+                    cx,
+                    &mut command_inbox,
+                    &mut shutdown_signals,
+                    &mut exited_workers,
+                    &mut incoming_join_set,
+                )
+            })
+            .await;
             match message {
+                AcceptorInboxMessage::ShutdownSignal => {
+                    // `shutdown_signals` is only set if `shutdown_on_signals` is.
+                    let timeout = shutdown_on_signals.unwrap_or_default();
+                    tracing::info!(
+                        "Received a termination signal. Shutting down the server gracefully."
+                    );
+                    Self::shutdown(
+                        None,
+                        ShutdownMode::Graceful { timeout },
+                        incoming_join_set,
+                        #[cfg(unix)]
+                        listener_fds,
+                        worker_handles,
+                        &mut command_inbox,
+                        &mut shutdown_signals,
+                        &events,
+                    )
+                    .await;
+                    return;
+                }
+                AcceptorInboxMessage::WorkerExited(worker_id) => {
+                    // The worker can't be restarted: its listening sockets were closed when it
+                    // crashed, except for the ones kept open to hand them off to a successor.
+                    // Connections assigned to those sockets by the OS would never be accepted.
+                    tracing::error!(
+                        worker_id = worker_id,
+                        "Worker crashed. It owned its listening sockets, so it can't be restarted: \
+                        shutting down the server."
+                    );
+                    let mode = match shutdown_on_signals {
+                        Some(timeout) => ShutdownMode::Graceful { timeout },
+                        None => ShutdownMode::Forced,
+                    };
+                    Self::shutdown(
+                        None,
+                        mode,
+                        incoming_join_set,
+                        #[cfg(unix)]
+                        listener_fds,
+                        worker_handles,
+                        &mut command_inbox,
+                        &mut shutdown_signals,
+                        &events,
+                    )
+                    .await;
+                    return;
+                }
                 AcceptorInboxMessage::ServerCommand(command) => match command {
                     ServerCommand::Shutdown {
                         completion_notifier,
                         mode,
                     } => {
                         Self::shutdown(
-                            completion_notifier,
+                            Some(completion_notifier),
                             mode,
                             incoming_join_set,
+                            #[cfg(unix)]
+                            listener_fds,
                             worker_handles,
+                            &mut command_inbox,
+                            &mut shutdown_signals,
+                            &events,
                         )
                         .await;
                         return;
                     }
+                    ServerCommand::Stats {
+                        completion_notifier,
+                    } => {
+                        let stats = ServerStats {
+                            workers: worker_handles.iter().map(|w| w.stats()).collect(),
+                        };
+                        let _ = completion_notifier.send(stats);
+                    }
+                    #[cfg(unix)]
+                    ServerCommand::HandOffListeners {
+                        completion_notifier,
+                    } => {
+                        let _ = completion_notifier.send(Self::hand_off_listeners(&listener_fds));
+                    }
                 },
                 AcceptorInboxMessage::Connection(msg) => {
                     let (incoming, connection, remote_peer) = match msg {
@@ -273,41 +532,46 @@ where
                             )
                         }
                     };
+                    #[cfg(feature = "tls")]
+                    let tls_acceptor = incoming.tls_config().map(|c| c.acceptor());
                     // Re-spawn the task to keep accepting connections from the same socket.
                     incoming_join_set.spawn(accept_connection(incoming));
 
-                    // A flag to track if the connection has been successfully sent to a worker.
-                    let mut has_been_handled = false;
                     // We try to send the connection to a worker (`ConnectionMessage`).
                     // If the worker's inbox is full, we try the next worker until we find one that can
                     // accept the connection or we've tried all workers.
-                    let mut connection_message = ConnectionMessage {
+                    // It's set to `None` once the connection has been successfully sent to a worker.
+                    let mut unhandled = Some(ConnectionMessage {
                         connection,
                         peer_addr: remote_peer,
-                    };
-                    for _ in 0..n_workers {
+                        #[cfg(feature = "tls")]
+                        tls_acceptor,
+                    });
+                    let candidates = Self::dispatch_order(
+                        config.dispatch_strategy,
+                        next_worker,
+                        &worker_handles,
+                    );
+                    next_worker = (next_worker + 1) % n_workers;
+                    for worker_id in candidates {
+                        let Some(connection_message) = unhandled.take() else {
+                            // We've successfully sent the connection to a worker, so we can stop trying
+                            // to send it to other workers.
+                            break;
+                        };
                         // Track if the worker has crashed.
                         let mut has_crashed: Option<usize> = None;
-                        let worker_handle = &worker_handles[next_worker];
-                        match worker_handle.dispatch(connection_message) {
-                            Err(e) => {
-                                connection_message = match e {
-                                    TrySendError::Full(message) => message,
-                                    // A closed channel implies that the worker thread is no longer running,
-                                    // therefore we need to restart it.
-                                    TrySendError::Closed(conn) => {
-                                        has_crashed = Some(worker_handle.id());
-                                        conn
-                                    }
-                                };
-                                next_worker = (next_worker + 1) % n_workers;
-                            }
-                            _ => {
-                                // We've successfully sent the connection to a worker, so we can stop trying
-                                // to send it to other workers.
-                                has_been_handled = true;
-                                break;
-                            }
+                        let worker_handle = &worker_handles[worker_id];
+                        if let Err(e) = worker_handle.dispatch(connection_message) {
+                            unhandled = Some(match e {
+                                TrySendError::Full(message) => message,
+                                // A closed channel implies that the worker thread is no longer running,
+                                // therefore we need to restart it.
+                                TrySendError::Closed(conn) => {
+                                    has_crashed = Some(worker_handle.id());
+                                    conn
+                                }
+                            });
                         }
 
                         // Restart the crashed worker thread.
@@ -315,9 +579,14 @@ where
                             tracing::warn!(worker_id = worker_id, "Worker crashed, restarting it");
                             let (worker, worker_handle) = Worker::new(
                                 worker_id,
-                                max_queue_length,
+                                &config,
+                                admission.clone(),
                                 handler,
                                 application_state.clone(),
+                                events.clone(),
+                                // Workers that own their listeners never receive connections
+                                // from the acceptor, so they can't be detected as crashed.
+                                Vec::new(),
                             );
                             // TODO: what if we fail to spawn the worker thread? We don't want to panic here!
                             worker.spawn().expect("Failed to spawn worker thread");
@@ -325,11 +594,9 @@ where
                         }
                     }
 
-                    if !has_been_handled {
-                        tracing::error!(
-                            remote_peer = %remote_peer,
-                            "All workers are busy, dropping connection",
-                        );
+                    // All workers are busy: let the client know that we're overloaded.
+                    if let Some(connection_message) = unhandled {
+                        rejector.reject(connection_message);
                     }
                 }
             }
@@ -339,22 +606,80 @@ where
             error,
             "Failed to accept new connections. The acceptor thread will exit now."
         );
+        events.emit(ServerEvent::Stopped);
+    }
+
+    /// The order in which workers should be offered a new connection, according to
+    /// the configured [`DispatchStrategy`].
+    fn dispatch_order(
+        strategy: DispatchStrategy,
+        next_worker: usize,
+        worker_handles: &[WorkerHandle],
+    ) -> SmallVec<[usize; 16]> {
+        let n_workers = worker_handles.len();
+        // Round-robin, starting from `next_worker`.
+        let mut order: SmallVec<[usize; 16]> = (0..n_workers)
+            .map(|i| (next_worker + i) % n_workers)
+            .collect();
+        match strategy {
+            DispatchStrategy::RoundRobin => {}
+            DispatchStrategy::LeastActiveConnections => {
+                // The sort is stable: ties are broken in round-robin order.
+                order.sort_by_cached_key(|&i| worker_handles[i].open_connections());
+            }
+            // Workers accept connections on their own, nothing to dispatch.
+            #[cfg(unix)]
+            DispatchStrategy::ReusePort => {}
+        }
+        order
     }
 
     /// Check if there is work to be done.
     fn poll_inboxes(
         cx: &mut std::task::Context<'_>,
         server_command_inbox: &mut tokio::sync::mpsc::Receiver<ServerCommand>,
-        incoming_join_set: &mut JoinSet<(IncomingStream, TcpStream, SocketAddr)>,
+        shutdown_signals: &mut Option<ShutdownSignals>,
+        exited_workers: &mut JoinSet<usize>,
+        incoming_join_set: &mut JoinSet<(IncomingStream, IncomingConnection, PeerAddr)>,
     ) -> Poll<AcceptorInboxMessage> {
         // Order matters here: we want to prioritize shutdown messages over incoming connections.
         if let Poll::Ready(Some(message)) = server_command_inbox.poll_recv(cx) {
             return Poll::Ready(AcceptorInboxMessage::ServerCommand(message));
         }
-        if let Poll::Ready(message) = incoming_join_set.poll_join_next(cx) {
-            return Poll::Ready(AcceptorInboxMessage::Connection(message));
+        if shutdown_signals
+            .as_mut()
+            .is_some_and(|signals| signals.poll_recv(cx).is_ready())
+        {
+            return Poll::Ready(AcceptorInboxMessage::ShutdownSignal);
+        }
+        if let Poll::Ready(Some(Ok(worker_id))) = exited_workers.poll_join_next(cx) {
+            return Poll::Ready(AcceptorInboxMessage::WorkerExited(worker_id));
+        }
+        // The set is empty if workers accept connections on their own listeners.
+        if incoming_join_set.is_empty() {
+            return Poll::Pending;
         }
-        Poll::Pending
+        incoming_join_set
+            .poll_join_next(cx)
+            .map(AcceptorInboxMessage::Connection)
+    }
+
+    /// Duplicate the listening sockets, once again, for a successor process.
+    #[cfg(unix)]
+    fn hand_off_listeners(
+        listener_fds: &std::io::Result<Vec<OwnedFd>>,
+    ) -> std::io::Result<ListenerHandoff> {
+        let listener_fds = listener_fds.as_ref().map_err(|e| {
+            std::io::Error::new(
+                e.kind(),
+                format!("Failed to duplicate the listening sockets when the server started: {e}"),
+            )
+        })?;
+        let fds = listener_fds
+            .iter()
+            .map(|fd| fd.try_clone())
+            .collect::<Result<_, _>>()?;
+        Ok(ListenerHandoff::new(fds))
     }
 
     fn spawn(self) -> thread::JoinHandle<()> {
@@ -370,24 +695,42 @@ where
             .expect("Failed to spawn acceptor thread")
     }
 
+    /// Stop accepting new connections and shut down all workers.
+    ///
+    /// While a graceful shutdown is in progress, we keep listening for commands and signals:
+    /// a request for a forced shutdown (or a termination signal) cuts the draining phase short.
+    #[allow(clippy::too_many_arguments)]
     async fn shutdown(
-        completion_notifier: tokio::sync::oneshot::Sender<()>,
+        completion_notifier: Option<tokio::sync::oneshot::Sender<()>>,
         mode: ShutdownMode,
-        incoming_join_set: JoinSet<(IncomingStream, TcpStream, SocketAddr)>,
+        incoming_join_set: JoinSet<(IncomingStream, IncomingConnection, PeerAddr)>,
+        #[cfg(unix)] listener_fds: std::io::Result<Vec<OwnedFd>>,
         worker_handles: Vec<WorkerHandle>,
+        command_inbox: &mut tokio::sync::mpsc::Receiver<ServerCommand>,
+        shutdown_signals: &mut Option<ShutdownSignals>,
+        events: &EventEmitter,
     ) {
+        if mode.is_graceful() {
+            events.emit(ServerEvent::Draining);
+        }
         // This drops the `JoinSet`, which will cause all the tasks that are still running to
         // be cancelled.
         // It will in turn cause the `Incoming` to be dropped, which will cause the `TcpListener`
         // to be dropped, thus closing the socket and stopping acceptance of new connections.
         drop(incoming_join_set);
+        // The duplicates we kept for hand-offs would keep the sockets open: the kernel would
+        // keep completing handshakes on their behalf and those clients would hang until the
+        // process exits. We close them too, so that new connections are refused right away.
+        #[cfg(unix)]
+        drop(listener_fds);
 
+        // Everyone who asked for the server to shut down must be notified when it's done.
+        let mut completion_notifiers: Vec<_> = completion_notifier.into_iter().collect();
         let mut shutdown_join_set = JoinSet::new();
-        for worker_handle in worker_handles {
-            let mode2 = mode.clone();
+        for worker_handle in &worker_handles {
             // The shutdown command is enqueued immediately, before the future is polled for the
             // first time.
-            let future = worker_handle.shutdown(mode2);
+            let future = worker_handle.shutdown(mode.clone());
             if mode.is_graceful() {
                 shutdown_join_set.spawn_local(future);
             }
@@ -396,13 +739,77 @@ where
         if let ShutdownMode::Graceful { timeout } = mode {
             // Wait for all workers to shut down, or for the timeout to expire,
             // whichever happens first.
-            let _ = tokio::time::timeout(timeout, async move {
-                while shutdown_join_set.join_next().await.is_some() {}
-            })
-            .await;
+            let mut deadline = std::pin::pin!(tokio::time::sleep(timeout));
+            loop {
+                let message = poll_fn(|cx| {
+                    if let Poll::Ready(Some(command)) = command_inbox.poll_recv(cx) {
+                        return Poll::Ready(Some(DrainingInboxMessage::ServerCommand(command)));
+                    }
+                    if shutdown_signals
+                        .as_mut()
+                        .is_some_and(|signals| signals.poll_recv(cx).is_ready())
+                    {
+                        return Poll::Ready(Some(DrainingInboxMessage::ShutdownSignal));
+                    }
+                    while let Poll::Ready(outcome) = shutdown_join_set.poll_join_next(cx) {
+                        if outcome.is_none() {
+                            // All workers have shut down.
+                            return Poll::Ready(None);
+                        }
+                    }
+                    if deadline.as_mut().poll(cx).is_ready() {
+                        return Poll::Ready(None);
+                    }
+                    Poll::Pending
+                })
+                .await;
+                let force = match message {
+                    None => break,
+                    Some(DrainingInboxMessage::ShutdownSignal) => {
+                        tracing::warn!(
+                            "Received a termination signal while shutting down gracefully. \
+                            Forcing the server to shut down now."
+                        );
+                        true
+                    }
+                    Some(DrainingInboxMessage::ServerCommand(command)) => match command {
+                        ServerCommand::Shutdown {
+                            completion_notifier,
+                            mode,
+                        } => {
+                            completion_notifiers.push(completion_notifier);
+                            mode.is_forced()
+                        }
+                        ServerCommand::Stats {
+                            completion_notifier,
+                        } => {
+                            let stats = ServerStats {
+                                workers: worker_handles.iter().map(|w| w.stats()).collect(),
+                            };
+                            let _ = completion_notifier.send(stats);
+                            false
+                        }
+                        // The listeners have already been closed, there is nothing to hand off.
+                        // Dropping the notifier is enough to let the caller know.
+                        #[cfg(unix)]
+                        ServerCommand::HandOffListeners { .. } => false,
+                    },
+                };
+                if force {
+                    for worker_handle in &worker_handles {
+                        // The command is enqueued immediately, we don't need to poll the future.
+                        // We don't wait for the workers either: it's a forced shutdown.
+                        drop(worker_handle.shutdown(ShutdownMode::Forced));
+                    }
+                    break;
+                }
+            }
         }
 
-        // Notify the caller that the server has shut down.
-        let _ = completion_notifier.send(());
+        events.emit(ServerEvent::Stopped);
+        // Notify the callers that the server has shut down.
+        for completion_notifier in completion_notifiers {
+            let _ = completion_notifier.send(());
+        }
     }
 }
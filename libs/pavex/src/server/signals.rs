@@ -0,0 +1,63 @@
+use std::task::{Context, Poll};
+
+/// Listen for the signals that ask the process to terminate.
+///
+/// On Unix, it listens for `SIGTERM` and `SIGINT`.
+/// On Windows, it listens for `Ctrl+C`.
+pub(super) struct ShutdownSignals {
+    #[cfg(unix)]
+    sigterm: tokio::signal::unix::Signal,
+    #[cfg(unix)]
+    sigint: tokio::signal::unix::Signal,
+    #[cfg(windows)]
+    ctrl_c: tokio::signal::windows::CtrlC,
+}
+
+impl ShutdownSignals {
+    /// Install the signal handlers.
+    ///
+    /// It must be invoked from within a Tokio runtime with IO enabled.
+    /// Signals are no longer received once that runtime is shut down.
+    pub(super) fn new() -> std::io::Result<Self> {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{SignalKind, signal};
+
+            Ok(Self {
+                sigterm: signal(SignalKind::terminate())?,
+                sigint: signal(SignalKind::interrupt())?,
+            })
+        }
+        #[cfg(windows)]
+        {
+            Ok(Self {
+                ctrl_c: tokio::signal::windows::ctrl_c()?,
+            })
+        }
+        #[cfg(not(any(unix, windows)))]
+        {
+            Ok(Self {})
+        }
+    }
+
+    /// Poll for the next termination signal.
+    pub(super) fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        #[cfg(unix)]
+        {
+            if self.sigterm.poll_recv(cx).is_ready() || self.sigint.poll_recv(cx).is_ready() {
+                return Poll::Ready(());
+            }
+        }
+        #[cfg(windows)]
+        {
+            if self.ctrl_c.poll_recv(cx).is_ready() {
+                return Poll::Ready(());
+            }
+        }
+        #[cfg(not(any(unix, windows)))]
+        {
+            let _ = cx;
+        }
+        Poll::Pending
+    }
+}
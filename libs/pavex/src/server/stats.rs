@@ -0,0 +1,36 @@
+/// A snapshot of the load on a running [`Server`](super::Server).
+///
+/// You get a [`ServerStats`] from [`ServerHandle::stats`](super::ServerHandle::stats).
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ServerStats {
+    /// Statistics for each worker thread, ordered by worker id.
+    pub workers: Vec<WorkerStats>,
+}
+
+impl ServerStats {
+    /// The total number of connections that are being handled by the server.
+    pub fn active_connections(&self) -> usize {
+        self.workers.iter().map(|w| w.active_connections).sum()
+    }
+
+    /// The total number of connections that have been accepted, but are still waiting
+    /// to be picked up by a worker thread.
+    pub fn queue_depth(&self) -> usize {
+        self.workers.iter().map(|w| w.queue_depth).sum()
+    }
+}
+
+/// A snapshot of the load on a single worker thread.
+///
+/// Check out [`ServerStats`] for more details.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct WorkerStats {
+    /// The identifier of the worker thread.
+    pub worker_id: usize,
+    /// The number of connections waiting in the worker's queue.
+    pub queue_depth: usize,
+    /// The number of connections the worker is currently handling.
+    pub active_connections: usize,
+}
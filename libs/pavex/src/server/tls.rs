@@ -0,0 +1,265 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::TlsAcceptor;
+
+use super::HttpProtocol;
+
+/// The protocols advertised via ALPN, in order of preference.
+const ALPN_PROTOCOLS: [&[u8]; 2] = [b"h2", b"http/1.1"];
+
+#[derive(Clone)]
+/// The TLS settings used by a [`Server`](super::Server) to terminate TLS connections.
+///
+/// # ALPN
+///
+/// Unless you are providing your own [`rustls::ServerConfig`] via [`TlsConfig::from_rustls`],
+/// the server will advertise both HTTP/2 (`h2`) and HTTP/1.1 (`http/1.1`) via ALPN,
+/// preferring HTTP/2.
+/// If the server is restricted to a single [`HttpProtocol`](super::HttpProtocol), only
+/// the corresponding protocol is advertised.
+/// The negotiated protocol is available via
+/// [`ConnectionInfo::alpn_protocol`](crate::connection::ConnectionInfo::alpn_protocol).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::net::SocketAddr;
+/// use pavex::server::{Server, TlsConfig};
+///
+/// # async fn t() -> anyhow::Result<()> {
+/// let tls_config = TlsConfig::from_pem_files("certs/server.crt", "certs/server.key")?;
+/// let addr = SocketAddr::from(([127, 0, 0, 1], 8443));
+///
+/// Server::new()
+///     .bind_tls(addr, tls_config)
+///     .await?
+///     # ;
+///     // [...]
+/// # Ok(())
+/// # }
+/// ```
+pub struct TlsConfig {
+    config: Arc<rustls::ServerConfig>,
+    /// Whether the ALPN protocols have been set by Pavex, rather than by the user.
+    managed_alpn: bool,
+}
+
+impl std::fmt::Debug for TlsConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsConfig")
+            .field("alpn_protocols", &self.config.alpn_protocols)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TlsConfig {
+    /// Build a new [`TlsConfig`] from a PEM-encoded certificate chain and a PEM-encoded private key.
+    ///
+    /// The certificate chain must start with the end-entity certificate, followed by
+    /// any intermediate certificate.
+    pub fn from_pem(certificate_chain: &[u8], private_key: &[u8]) -> Result<Self, TlsConfigError> {
+        let certificate_chain = CertificateDer::pem_slice_iter(certificate_chain)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| TlsConfigError::InvalidCertificateChain(e.into()))?;
+        let private_key = PrivateKeyDer::from_pem_slice(private_key)
+            .map_err(|e| TlsConfigError::InvalidPrivateKey(e.into()))?;
+        Self::from_der(certificate_chain, private_key)
+    }
+
+    /// Build a new [`TlsConfig`] by reading a PEM-encoded certificate chain and a
+    /// PEM-encoded private key from the file system.
+    ///
+    /// Check out [`TlsConfig::from_pem`] for more details.
+    pub fn from_pem_files(
+        certificate_chain_path: impl AsRef<Path>,
+        private_key_path: impl AsRef<Path>,
+    ) -> Result<Self, TlsConfigError> {
+        let certificate_chain =
+            fs_err::read(certificate_chain_path.as_ref()).map_err(TlsConfigError::Io)?;
+        let private_key = fs_err::read(private_key_path.as_ref()).map_err(TlsConfigError::Io)?;
+        Self::from_pem(&certificate_chain, &private_key)
+    }
+
+    /// Build a new [`TlsConfig`] from a DER-encoded certificate chain and a DER-encoded private key.
+    pub fn from_der(
+        certificate_chain: Vec<CertificateDer<'static>>,
+        private_key: PrivateKeyDer<'static>,
+    ) -> Result<Self, TlsConfigError> {
+        if certificate_chain.is_empty() {
+            return Err(TlsConfigError::InvalidCertificateChain(
+                "The certificate chain doesn't contain any certificate".into(),
+            ));
+        }
+        // We honour the process-wide crypto provider, if one has been installed.
+        // We fall back to `ring` otherwise.
+        let provider = rustls::crypto::CryptoProvider::get_default()
+            .cloned()
+            .unwrap_or_else(|| Arc::new(rustls::crypto::ring::default_provider()));
+        let mut config = rustls::ServerConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .map_err(TlsConfigError::Rustls)?
+            .with_no_client_auth()
+            .with_single_cert(certificate_chain, private_key)
+            .map_err(TlsConfigError::Rustls)?;
+        config.alpn_protocols = ALPN_PROTOCOLS.iter().map(|p| p.to_vec()).collect();
+        Ok(Self {
+            config: Arc::new(config),
+            managed_alpn: true,
+        })
+    }
+
+    /// Use a [`rustls::ServerConfig`] that you assembled yourself.
+    ///
+    /// The configuration is used as is: Pavex won't set the ALPN protocols on your behalf.
+    /// Make sure to include `h2` in [`rustls::ServerConfig::alpn_protocols`] if you want
+    /// to serve HTTP/2 traffic over TLS.
+    pub fn from_rustls(config: Arc<rustls::ServerConfig>) -> Self {
+        Self {
+            config,
+            managed_alpn: false,
+        }
+    }
+
+    /// Get a reference to the underlying [`rustls::ServerConfig`].
+    pub fn rustls_config(&self) -> &Arc<rustls::ServerConfig> {
+        &self.config
+    }
+
+    /// Only advertise, via ALPN, the protocols that the server is going to speak.
+    ///
+    /// User-provided configurations are left untouched.
+    pub(super) fn restrict_alpn(self, protocol: HttpProtocol) -> Self {
+        if !self.managed_alpn || protocol == HttpProtocol::Auto {
+            return self;
+        }
+        let mut config = (*self.config).clone();
+        config.alpn_protocols.retain(|p| protocol.allows_alpn(p));
+        Self {
+            config: Arc::new(config),
+            managed_alpn: true,
+        }
+    }
+
+    pub(super) fn acceptor(&self) -> TlsAcceptor {
+        TlsAcceptor::from(self.config.clone())
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+/// The location of the PEM files required to terminate TLS connections.
+///
+/// It's designed to be loaded as part of your application configuration,
+/// e.g. via [`ConfigLoader`](crate::config::ConfigLoader).
+///
+/// # Example
+///
+/// ```yaml
+/// tls:
+///   certificate_chain: "certs/server.crt"
+///   private_key: "certs/server.key"
+/// ```
+///
+/// ```rust,no_run
+/// use pavex::server::{TlsConfig, TlsPemFiles};
+///
+/// # fn t(pem_files: TlsPemFiles) -> Result<(), pavex::server::TlsConfigError> {
+/// let tls_config: TlsConfig = pem_files.load()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct TlsPemFiles {
+    /// The path to the PEM-encoded certificate chain.
+    pub certificate_chain: PathBuf,
+    /// The path to the PEM-encoded private key.
+    pub private_key: PathBuf,
+}
+
+impl TlsPemFiles {
+    /// Read the PEM files from disk and build a [`TlsConfig`].
+    pub fn load(&self) -> Result<TlsConfig, TlsConfigError> {
+        TlsConfig::from_pem_files(&self.certificate_chain, &self.private_key)
+    }
+}
+
+impl TryFrom<TlsPemFiles> for TlsConfig {
+    type Error = TlsConfigError;
+
+    fn try_from(value: TlsPemFiles) -> Result<Self, Self::Error> {
+        value.load()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+/// The error returned when a [`TlsConfig`] can't be built.
+pub enum TlsConfigError {
+    #[error("Failed to read TLS material from disk")]
+    /// We couldn't read the certificate chain or the private key from disk.
+    Io(#[source] std::io::Error),
+    #[error("The TLS certificate chain is invalid")]
+    /// The certificate chain is empty or it couldn't be parsed.
+    InvalidCertificateChain(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("The TLS private key is invalid")]
+    /// The private key is missing or it couldn't be parsed.
+    InvalidPrivateKey(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("Failed to assemble the TLS server configuration")]
+    /// `rustls` rejected the provided certificate chain and private key.
+    Rustls(#[source] rustls::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TlsConfig;
+    use crate::server::HttpProtocol;
+
+    #[test]
+    fn self_signed_pem_roundtrip() {
+        let certified = rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap();
+        let config = TlsConfig::from_pem(
+            certified.cert.pem().as_bytes(),
+            certified.key_pair.serialize_pem().as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(
+            config.rustls_config().alpn_protocols,
+            vec![b"h2".to_vec(), b"http/1.1".to_vec()]
+        );
+    }
+
+    #[test]
+    fn empty_certificate_chain_is_rejected() {
+        let certified = rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap();
+        let err =
+            TlsConfig::from_pem(b"", certified.key_pair.serialize_pem().as_bytes()).unwrap_err();
+        insta::assert_snapshot!(err, @"The TLS certificate chain is invalid");
+    }
+
+    #[test]
+    fn alpn_is_restricted_to_the_configured_protocol() {
+        let certified = rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap();
+        let config = TlsConfig::from_pem(
+            certified.cert.pem().as_bytes(),
+            certified.key_pair.serialize_pem().as_bytes(),
+        )
+        .unwrap();
+        let alpn = |protocol| {
+            config
+                .clone()
+                .restrict_alpn(protocol)
+                .rustls_config()
+                .alpn_protocols
+                .clone()
+        };
+        assert_eq!(alpn(HttpProtocol::Http1Only), vec![b"http/1.1".to_vec()]);
+        assert_eq!(alpn(HttpProtocol::Http2Only), vec![b"h2".to_vec()]);
+        assert_eq!(alpn(HttpProtocol::Auto).len(), 2);
+
+        // User-provided configurations are left alone.
+        let custom = TlsConfig::from_rustls(config.rustls_config().clone())
+            .restrict_alpn(HttpProtocol::Http1Only);
+        assert_eq!(custom.rustls_config().alpn_protocols.len(), 2);
+    }
+}
@@ -0,0 +1,115 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use hyper::upgrade::OnUpgrade;
+
+tokio::task_local! {
+    /// The upgrade context of the request that's currently being handled.
+    static UPGRADE: UpgradeContext;
+}
+
+struct UpgradeContext {
+    on_upgrade: RefCell<Option<OnUpgrade>>,
+    sessions: UpgradedSessions,
+}
+
+type Session = Pin<Box<dyn Future<Output = ()>>>;
+
+/// The sessions running over the upgraded connection (e.g. a WebSocket), if any.
+///
+/// `hyper` is done with a connection as soon as it's upgraded.
+/// The worker runs the sessions afterwards, as part of the connection task:
+/// the connection keeps counting as open and graceful shutdowns wait for them to complete.
+#[derive(Clone, Default)]
+pub(super) struct UpgradedSessions(Rc<RefCell<Vec<Session>>>);
+
+impl UpgradedSessions {
+    /// Make the pending upgrade of the current request available via [`take_on_upgrade`]
+    /// while `f` is running.
+    ///
+    /// `on_upgrade` is `None` if the connection can't be upgraded.
+    pub(super) fn scope<F: Future>(
+        &self,
+        on_upgrade: Option<OnUpgrade>,
+        f: F,
+    ) -> impl Future<Output = F::Output> {
+        let context = UpgradeContext {
+            on_upgrade: RefCell::new(on_upgrade),
+            sessions: self.clone(),
+        };
+        UPGRADE.scope(context, f)
+    }
+
+    /// Returns `true` if no session has been registered.
+    pub(super) fn is_empty(&self) -> bool {
+        self.0.borrow().is_empty()
+    }
+
+    /// Run all registered sessions to completion.
+    pub(super) async fn run(self) {
+        let sessions = std::mem::take(&mut *self.0.borrow_mut());
+        futures_util::future::join_all(sessions).await;
+    }
+}
+
+/// The outcome of [`take_on_upgrade`].
+#[cfg_attr(not(feature = "websocket"), allow(dead_code))]
+pub(crate) enum PendingUpgrade {
+    /// The connection will be upgraded once the response has been sent.
+    Available(OnUpgrade),
+    /// The request is being handled by a [`Server`](super::Server), but its connection
+    /// can't be upgraded—e.g. the upgrade has already been claimed, or the server
+    /// is configured to only serve HTTP/1.
+    Unavailable,
+    /// The request isn't being handled by a [`Server`](super::Server)—e.g. in a test.
+    NoServer,
+}
+
+/// Take ownership of the pending upgrade of the current request.
+#[cfg_attr(not(feature = "websocket"), allow(dead_code))]
+pub(crate) fn take_on_upgrade() -> PendingUpgrade {
+    UPGRADE
+        .try_with(|context| match context.on_upgrade.borrow_mut().take() {
+            Some(on_upgrade) => PendingUpgrade::Available(on_upgrade),
+            None => PendingUpgrade::Unavailable,
+        })
+        .unwrap_or(PendingUpgrade::NoServer)
+}
+
+/// Run `session` over the upgraded connection, once the current request has been upgraded.
+///
+/// It returns `false`, without running `session`, if the caller isn't running as part of
+/// a request handled by a [`Server`](super::Server).
+#[cfg_attr(not(feature = "websocket"), allow(dead_code))]
+pub(crate) fn register_session<F>(session: F) -> bool
+where
+    F: Future<Output = ()> + 'static,
+{
+    UPGRADE
+        .try_with(|context| context.sessions.0.borrow_mut().push(Box::pin(session)))
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn sessions_are_registered_within_the_scope() {
+        assert!(matches!(take_on_upgrade(), PendingUpgrade::NoServer));
+        assert!(!register_session(async {}));
+
+        let sessions = UpgradedSessions::default();
+        let (pending, registered) = sessions
+            .scope(None, async {
+                (take_on_upgrade(), register_session(async {}))
+            })
+            .await;
+        assert!(matches!(pending, PendingUpgrade::Unavailable));
+        assert!(registered);
+        assert!(!sessions.is_empty());
+        sessions.run().await;
+    }
+}
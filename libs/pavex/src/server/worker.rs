@@ -1,21 +1,40 @@
 use std::future::{Future, poll_fn};
-use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::task::Poll;
 use std::thread;
+use std::time::Duration;
 
 use anyhow::Context;
-use hyper_util::rt::TokioIo;
-use hyper_util::server::graceful::GracefulShutdown;
-use tokio::net::TcpStream;
+use hyper::upgrade::OnUpgrade;
+use hyper_util::rt::{TokioIo, TokioTimer};
+use hyper_util::server::conn::auto::Builder;
+use hyper_util::server::graceful::{GracefulShutdown, Watcher};
+use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::sync::mpsc::error::TrySendError;
+use tokio::task::JoinSet;
 use tracing_log_error::log_error;
+use ubyte::ByteUnit;
 
-use crate::connection::ConnectionInfo;
-use crate::server::ShutdownMode;
+use crate::connection::{ConnectionInfo, PeerAddr};
+use crate::server::admission::{AdmissionControl, ConnectionRejector};
+use crate::server::dispatch::AcceptBackoff;
+use crate::server::drain_signal::{DrainSignal, DrainTrigger, drain_signal};
+use crate::server::events::EventEmitter;
+use crate::server::idle_timeout::{IdleTimeoutIo, IdleTracker, InFlightBody};
+use crate::server::upgrade::UpgradedSessions;
+use crate::server::{
+    HttpProtocol, IncomingConnection, IncomingStream, ServerConfiguration, ServerEvent,
+    ShutdownMode, WorkerStats,
+};
 
 pub(super) struct ConnectionMessage {
-    pub(super) connection: TcpStream,
-    pub(super) peer_addr: SocketAddr,
+    pub(super) connection: IncomingConnection,
+    pub(super) peer_addr: PeerAddr,
+    /// Set if the connection must be terminated using TLS.
+    #[cfg(feature = "tls")]
+    pub(super) tls_acceptor: Option<tokio_rustls::TlsAcceptor>,
 }
 
 /// A handle to dispatch incoming connections to a worker thread.
@@ -24,16 +43,35 @@ pub(super) struct WorkerHandle {
     // We use an unbounded channel because we want to be able to send a shutdown command
     // synchronously.
     shutdown_outbox: tokio::sync::mpsc::UnboundedSender<ShutdownWorkerCommand>,
+    load: Arc<WorkerLoad>,
+    max_connections: Option<NonZeroUsize>,
     id: usize,
 }
 
 impl WorkerHandle {
     /// Dispatch a connection to the worker thread.
+    ///
+    /// The connection is rejected, as if the worker's queue was full, if the worker
+    /// has reached its maximum number of open connections.
     pub(super) fn dispatch(
         &self,
         connection: ConnectionMessage,
     ) -> Result<(), TrySendError<ConnectionMessage>> {
-        self.connection_outbox.try_send(connection)
+        if self
+            .max_connections
+            .is_some_and(|max| self.load.open.load(Ordering::Relaxed) >= max.get())
+        {
+            return Err(TrySendError::Full(connection));
+        }
+        // We increment the counter *before* sending the connection, otherwise the worker
+        // might close the connection (and decrement the counter) before we get a chance to
+        // increment it.
+        self.load.open.fetch_add(1, Ordering::Relaxed);
+        self.connection_outbox
+            .try_send(connection)
+            .inspect_err(|_| {
+                self.load.open.fetch_sub(1, Ordering::Relaxed);
+            })
     }
 
     /// Get the worker's ID.
@@ -41,13 +79,38 @@ impl WorkerHandle {
         self.id
     }
 
+    /// The number of connections dispatched to the worker that haven't been closed yet,
+    /// including the ones waiting in its queue.
+    pub(super) fn open_connections(&self) -> usize {
+        self.load.open.load(Ordering::Relaxed)
+    }
+
+    /// Take a snapshot of the load on the worker.
+    pub(super) fn stats(&self) -> WorkerStats {
+        let outbox = &self.connection_outbox;
+        WorkerStats {
+            worker_id: self.id,
+            queue_depth: outbox.max_capacity() - outbox.capacity(),
+            active_connections: self.load.active.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Resolves when the worker thread stops running—e.g. because it panicked.
+    pub(super) fn exited(&self) -> impl Future<Output = ()> + Send + use<> {
+        let outbox = self.connection_outbox.clone();
+        async move { outbox.closed().await }
+    }
+
     /// Shutdown the worker thread.
     ///
     /// # Implementation notes
     ///
     /// We use a sync function to ensure that the shutdown command is enqueued immediately,
     /// even if the returned future is never polled.
-    pub(super) fn shutdown(self, mode: ShutdownMode) -> impl Future<Output = ()> {
+    ///
+    /// A forced shutdown can be requested while a graceful one is in progress:
+    /// the worker will stop waiting for its open connections to be closed.
+    pub(super) fn shutdown(&self, mode: ShutdownMode) -> impl Future<Output = ()> + use<> {
         let (completion_notifier, completion) = tokio::sync::oneshot::channel();
         let sent = self
             .shutdown_outbox
@@ -85,6 +148,13 @@ pub(super) struct Worker<HandlerFuture, ApplicationState> {
     application_state: ApplicationState,
     id: usize,
     shutdown_coordinator: GracefulShutdown,
+    connection_settings: ConnectionSettings,
+    load: Arc<WorkerLoad>,
+    events: EventEmitter,
+    own_listeners: OwnListeners,
+    /// Let long-lived responses know that they should wrap up.
+    drain_trigger: DrainTrigger,
+    drain_signal: DrainSignal,
 }
 
 impl<HandlerFuture, ApplicationState> Worker<HandlerFuture, ApplicationState>
@@ -94,20 +164,27 @@ where
 {
     /// Configure a new worker without spawning it.
     ///
-    /// `max_queue_length` is the maximum number of connections that can be queued up for this
-    /// worker.
+    /// `admission` is shared across all workers, to enforce server-wide limits.
+    /// `listeners` are the listening sockets that the worker must accept connections from,
+    /// in addition to the connections dispatched by the acceptor thread.
     pub(super) fn new(
         id: usize,
-        max_queue_length: usize,
+        config: &ServerConfiguration,
+        admission: AdmissionControl,
         handler: fn(
             http::Request<hyper::body::Incoming>,
             Option<ConnectionInfo>,
             ApplicationState,
         ) -> HandlerFuture,
         application_state: ApplicationState,
+        events: EventEmitter,
+        listeners: Vec<IncomingStream>,
     ) -> (Self, WorkerHandle) {
-        let (connection_outbox, connection_inbox) = tokio::sync::mpsc::channel(max_queue_length);
+        let (connection_outbox, connection_inbox) =
+            tokio::sync::mpsc::channel(config.max_queue_length.get());
         let (shutdown_outbox, shutdown_inbox) = tokio::sync::mpsc::unbounded_channel();
+        let load = Arc::new(WorkerLoad::default());
+        let (drain_trigger, drain_signal) = drain_signal();
         let self_ = Self {
             connection_inbox,
             shutdown_inbox,
@@ -115,10 +192,22 @@ where
             application_state,
             id,
             shutdown_coordinator: GracefulShutdown::new(),
+            connection_settings: ConnectionSettings::new(config, admission),
+            load: load.clone(),
+            events,
+            own_listeners: OwnListeners {
+                listeners,
+                outbox: connection_outbox.clone(),
+                max_connections: config.max_connections_per_worker,
+            },
+            drain_trigger,
+            drain_signal,
         };
         let handle = WorkerHandle {
             connection_outbox,
             shutdown_outbox,
+            load,
+            max_connections: config.max_connections_per_worker,
             id,
         };
         (self_, handle)
@@ -142,6 +231,8 @@ where
 
                 // Use a `LocalSet` to allow the worker to spawn !Send futures.
                 let local = tokio::task::LocalSet::new();
+                self.events
+                    .emit(ServerEvent::WorkerStarted { worker_id: id });
                 local.block_on(&runtime, self.run());
             })
             .with_context(|| format!("Failed to spawn worker thread `{}`", name()))
@@ -156,7 +247,15 @@ where
             application_state,
             id,
             shutdown_coordinator,
+            connection_settings,
+            load,
+            events: _,
+            own_listeners,
+            drain_trigger,
+            drain_signal,
         } = self;
+        let rejector = connection_settings.admission.rejector();
+        let mut accept_tasks = own_listeners.spawn_accept_tasks(&load, &rejector);
         'event_loop: loop {
             let message =
                 poll_fn(|cx| Self::poll_inboxes(cx, &mut shutdown_inbox, &mut connection_inbox))
@@ -168,6 +267,9 @@ where
                         handler,
                         application_state.clone(),
                         &shutdown_coordinator,
+                        &drain_signal,
+                        &connection_settings,
+                        &load,
                     );
                 }
                 WorkerInboxMessage::Shutdown(shutdown) => {
@@ -175,6 +277,11 @@ where
                         completion_notifier,
                         mode,
                     } = shutdown;
+                    // Stop accepting connections on our own listeners, if we have any.
+                    accept_tasks.abort_all();
+                    // Long-lived responses (e.g. server-sent events) would otherwise
+                    // hold their connection open until the shutdown timeout expires.
+                    drain_trigger.trigger();
                     match mode {
                         ShutdownMode::Graceful { timeout } => {
                             // Stop accepting new connections.
@@ -187,16 +294,46 @@ where
                                     handler,
                                     application_state.clone(),
                                     &shutdown_coordinator,
+                                    &drain_signal,
+                                    &connection_settings,
+                                    &load,
                                 );
                             }
 
-                            // Wait for all live connections to be closed or for the timeout to expire.
-                            let _ = tokio::time::timeout(timeout, shutdown_coordinator.shutdown())
+                            // Wait for all live connections to be closed or for the timeout to expire,
+                            // unless we're asked to force the shutdown in the meantime.
+                            let mut drain = std::pin::pin!(tokio::time::timeout(
+                                timeout,
+                                shutdown_coordinator.shutdown()
+                            ));
+                            let mut completion_notifiers = vec![completion_notifier];
+                            loop {
+                                let next = poll_fn(|cx| {
+                                    if drain.as_mut().poll(cx).is_ready() {
+                                        return Poll::Ready(None);
+                                    }
+                                    match shutdown_inbox.poll_recv(cx) {
+                                        Poll::Ready(Some(command)) => Poll::Ready(Some(command)),
+                                        _ => Poll::Pending,
+                                    }
+                                })
                                 .await;
+                                let Some(command) = next else {
+                                    break;
+                                };
+                                completion_notifiers.push(command.completion_notifier);
+                                if command.mode.is_forced() {
+                                    break;
+                                }
+                            }
+                            for completion_notifier in completion_notifiers {
+                                let _ = completion_notifier.send(());
+                            }
+                        }
+                        ShutdownMode::Forced => {
+                            let _ = completion_notifier.send(());
                         }
-                        ShutdownMode::Forced => {}
                     }
-                    let _ = completion_notifier.send(());
                     break 'event_loop;
                 }
             }
@@ -213,33 +350,188 @@ where
         ) -> HandlerFuture,
         application_state: ApplicationState,
         shutdown_coordinator: &GracefulShutdown,
+        drain_signal: &DrainSignal,
+        connection_settings: &ConnectionSettings,
+        load: &Arc<WorkerLoad>,
     ) {
         let ConnectionMessage {
             connection,
             peer_addr,
+            #[cfg(feature = "tls")]
+            tls_acceptor,
         } = connection_message;
+        // The watchers are created synchronously, before the connection task is spawned,
+        // to make sure that a graceful shutdown waits for this connection too.
+        let watchers = ConnectionWatchers {
+            http: shutdown_coordinator.watcher(),
+            upgraded: shutdown_coordinator.watcher(),
+        };
+        let open_connection = OpenConnectionGuard::new(load.clone());
+        let settings = connection_settings.clone();
+        let drain_signal = drain_signal.clone();
+        let connection_info = ConnectionInfo {
+            peer_addr,
+            alpn_protocol: None,
+            server_name: None,
+            is_tls: false,
+        };
+
+        #[cfg(feature = "tls")]
+        if let Some(tls_acceptor) = tls_acceptor {
+            tokio::task::spawn_local(async move {
+                let _open_connection = open_connection;
+                // The TLS handshake is performed on the worker thread, rather than the acceptor
+                // thread, to avoid blocking the acceptance of new connections.
+                // It's bounded in time: until it completes, the connection holds a slot
+                // but none of the HTTP-level timeouts apply.
+                let handshake = tls_acceptor.accept(connection);
+                let outcome = match settings.tls_handshake_timeout {
+                    Some(timeout) => tokio::time::timeout(timeout, handshake).await,
+                    None => Ok(handshake.await),
+                };
+                let connection = match outcome {
+                    Ok(Ok(connection)) => connection,
+                    Ok(Err(e)) => {
+                        log_error!(e, level: tracing::Level::WARN, "Failed to complete the TLS handshake for an incoming connection");
+                        return;
+                    }
+                    Err(_) => {
+                        tracing::debug!(
+                            remote_peer = %connection_info.peer_addr,
+                            "Timed out while waiting for the TLS handshake of an incoming connection"
+                        );
+                        return;
+                    }
+                };
+                let (_, session) = connection.get_ref();
+                let connection_info = ConnectionInfo {
+                    alpn_protocol: session.alpn_protocol().map(Into::into),
+                    server_name: session.server_name().map(Into::into),
+                    is_tls: true,
+                    ..connection_info
+                };
+                Self::serve_connection(
+                    connection,
+                    connection_info,
+                    handler,
+                    application_state,
+                    watchers,
+                    drain_signal,
+                    settings,
+                )
+                .await;
+            });
+            return;
+        }
+
+        tokio::task::spawn_local(async move {
+            let _open_connection = open_connection;
+            Self::serve_connection(
+                connection,
+                connection_info,
+                handler,
+                application_state,
+                watchers,
+                drain_signal,
+                settings,
+            )
+            .await;
+        });
+    }
+
+    /// Serve HTTP requests over an established connection until it is closed.
+    async fn serve_connection<Io>(
+        connection: Io,
+        connection_info: ConnectionInfo,
+        handler: fn(
+            http::Request<hyper::body::Incoming>,
+            Option<ConnectionInfo>,
+            ApplicationState,
+        ) -> HandlerFuture,
+        application_state: ApplicationState,
+        watchers: ConnectionWatchers,
+        drain_signal: DrainSignal,
+        settings: ConnectionSettings,
+    ) where
+        Io: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let idle_tracker = IdleTracker::new();
+        let connection = IdleTimeoutIo::new(
+            connection,
+            idle_tracker.clone(),
+            settings.keep_alive_timeout,
+        );
+        let admission = settings.admission.clone();
+        let sessions = UpgradedSessions::default();
+        let upgradable = settings.upgradable;
+        let ConnectionWatchers { http, upgraded } = watchers;
         // A tiny bit of glue to adapt our handler to hyper's service interface.
-        let handler = hyper::service::service_fn(move |request| {
-            let state = application_state.clone();
-
-            async move {
-                let handler = (handler)(request, Some(ConnectionInfo { peer_addr }), state);
-                let response = handler.await;
-                let response = hyper::Response::from(response);
-                Ok::<_, hyper::Error>(response)
+        let handler = hyper::service::service_fn({
+            let idle_tracker = idle_tracker.clone();
+            let sessions = sessions.clone();
+            move |mut request: http::Request<hyper::body::Incoming>| {
+                let state = application_state.clone();
+                let connection_info = connection_info.clone();
+                let in_flight = idle_tracker.request_started();
+                // Requests beyond the server-wide limit are rejected right away.
+                let admitted = admission.try_admit();
+                let admission = admission.clone();
+                let drain_signal = drain_signal.clone();
+                let sessions = sessions.clone();
+                // The pending upgrade is dropped by the router, together with the other
+                // request extensions: we stash it away for the handler to claim it.
+                let on_upgrade = request
+                    .extensions_mut()
+                    .remove::<OnUpgrade>()
+                    .filter(|_| upgradable);
+
+                async move {
+                    let response = match admitted {
+                        Some(_admitted) => {
+                            let response = (handler)(request, Some(connection_info), state);
+                            // Long-lived response bodies retrieve the signal while the handler runs.
+                            drain_signal
+                                .scope(sessions.scope(on_upgrade, response))
+                                .await
+                        }
+                        None => admission.overload_response(),
+                    };
+                    // The connection is busy until the response body has been fully sent,
+                    // not just until the handler returns.
+                    let response = hyper::Response::from(response)
+                        .map(|body| InFlightBody::new(body, in_flight));
+                    Ok::<_, hyper::Error>(response)
+                }
             }
         });
-        // TODO: expose all the config options for `auto::Builder` through the top-level
-        //   `ServerConfiguration` object.
-        let builder = hyper_util::server::conn::auto::Builder::new(LocalExec);
         let connection = TokioIo::new(connection);
-        let connection_future =
-            shutdown_coordinator.watch(builder.serve_connection(connection, handler).into_owned());
-        tokio::task::spawn_local(async move {
-            if let Err(e) = connection_future.await {
-                log_error!(*e, level: tracing::Level::WARN, "Failed to serve an incoming connection");
-            }
-        });
+        let outcome = if upgradable {
+            http.watch(
+                settings
+                    .builder
+                    .serve_connection_with_upgrades(connection, handler)
+                    .into_owned(),
+            )
+            .await
+        } else {
+            http.watch(
+                settings
+                    .builder
+                    .serve_connection(connection, handler)
+                    .into_owned(),
+            )
+            .await
+        };
+        if let Err(e) = outcome {
+            log_error!(*e, level: tracing::Level::WARN, "Failed to serve an incoming connection");
+        }
+        if !sessions.is_empty() {
+            // `hyper` has handed the connection over to the sessions (e.g. a WebSocket):
+            // it must not be closed for being idle while they're running.
+            let _in_flight = idle_tracker.request_started();
+            let _upgraded = upgraded;
+            sessions.run().await;
+        }
     }
 
     /// Check if there is work to be done.
@@ -276,12 +568,189 @@ impl From<ShutdownWorkerCommand> for WorkerInboxMessage {
     }
 }
 
+/// Keep graceful shutdowns waiting for a connection until it's closed.
+struct ConnectionWatchers {
+    /// Released by `hyper` as soon as the connection is upgraded.
+    http: Watcher,
+    /// Held while sessions over the upgraded connection (e.g. WebSockets) are running.
+    upgraded: Watcher,
+}
+
+/// Per-connection settings, derived from the [`ServerConfiguration`].
+#[derive(Clone)]
+struct ConnectionSettings {
+    builder: Builder<LocalExec>,
+    keep_alive_timeout: Option<Duration>,
+    #[cfg(feature = "tls")]
+    tls_handshake_timeout: Option<Duration>,
+    admission: AdmissionControl,
+    /// Whether connections can be upgraded to other protocols (e.g. WebSockets).
+    ///
+    /// `hyper-util` ignores `http1_only`/`http2_only` for upgradable connections,
+    /// therefore upgrades are only supported if both protocols are enabled.
+    upgradable: bool,
+}
+
+impl ConnectionSettings {
+    fn new(config: &ServerConfiguration, admission: AdmissionControl) -> Self {
+        let builder = Builder::new(LocalExec);
+        let mut builder = match config.http_protocol {
+            HttpProtocol::Auto => builder,
+            HttpProtocol::Http1Only => builder.http1_only(),
+            HttpProtocol::Http2Only => builder.http2_only(),
+        };
+        builder
+            .http1()
+            .timer(TokioTimer::new())
+            .header_read_timeout(config.header_read_timeout);
+        // Window sizes are validated when set, they always fit in a `u32`.
+        let window_size = |size: Option<ByteUnit>| size.map(|s| s.as_u64() as u32);
+        builder
+            .http2()
+            .timer(TokioTimer::new())
+            .max_concurrent_streams(config.http2_max_concurrent_streams)
+            .initial_stream_window_size(window_size(config.http2_initial_stream_window_size))
+            .initial_connection_window_size(window_size(
+                config.http2_initial_connection_window_size,
+            ))
+            .adaptive_window(config.http2_adaptive_window)
+            .keep_alive_interval(config.http2_keep_alive_interval)
+            .keep_alive_timeout(config.http2_keep_alive_timeout);
+        if let Some(max_header_size) = config.max_header_size {
+            // We saturate if the value doesn't fit, it's a limit after all.
+            let max_header_size = max_header_size.as_u64();
+            builder
+                .http1()
+                .max_buf_size(max_header_size.try_into().unwrap_or(usize::MAX));
+            builder
+                .http2()
+                .max_header_list_size(max_header_size.try_into().unwrap_or(u32::MAX));
+        }
+        Self {
+            builder,
+            keep_alive_timeout: config.keep_alive_timeout,
+            #[cfg(feature = "tls")]
+            tls_handshake_timeout: config.tls_handshake_timeout,
+            admission,
+            upgradable: config.http_protocol == HttpProtocol::Auto,
+        }
+    }
+}
+
+/// The listening sockets owned by a worker.
+struct OwnListeners {
+    listeners: Vec<IncomingStream>,
+    /// Accepted connections are sent to the worker's own inbox, where they're processed
+    /// in the same way as the connections dispatched by the acceptor thread.
+    outbox: tokio::sync::mpsc::Sender<ConnectionMessage>,
+    max_connections: Option<NonZeroUsize>,
+}
+
+impl OwnListeners {
+    /// Spawn a task for each listener, to accept incoming connections.
+    ///
+    /// Listeners are closed when the tasks are aborted.
+    fn spawn_accept_tasks(
+        self,
+        load: &Arc<WorkerLoad>,
+        rejector: &ConnectionRejector,
+    ) -> JoinSet<()> {
+        let mut accept_tasks = JoinSet::new();
+        for incoming in self.listeners {
+            accept_tasks.spawn_local(Self::accept_connections(
+                incoming,
+                self.outbox.clone(),
+                load.clone(),
+                self.max_connections,
+                rejector.clone(),
+            ));
+        }
+        accept_tasks
+    }
+
+    async fn accept_connections(
+        incoming: IncomingStream,
+        outbox: tokio::sync::mpsc::Sender<ConnectionMessage>,
+        load: Arc<WorkerLoad>,
+        max_connections: Option<NonZeroUsize>,
+        rejector: ConnectionRejector,
+    ) {
+        let mut backoff = AcceptBackoff::default();
+        loop {
+            let (connection, peer_addr) = match incoming.accept().await {
+                Ok(accepted) => {
+                    backoff.reset();
+                    accepted
+                }
+                Err(e) => {
+                    log_error!(e, level: tracing::Level::INFO, "Failed to accept connection");
+                    backoff.wait(&e).await;
+                    continue;
+                }
+            };
+            let message = ConnectionMessage {
+                connection,
+                peer_addr,
+                #[cfg(feature = "tls")]
+                tls_acceptor: incoming.tls_config().map(|c| c.acceptor()),
+            };
+            if max_connections.is_some_and(|max| load.open.load(Ordering::Relaxed) >= max.get()) {
+                rejector.reject(message);
+                continue;
+            }
+            load.open.fetch_add(1, Ordering::Relaxed);
+            match outbox.try_send(message) {
+                Ok(()) => {}
+                Err(TrySendError::Full(message)) => {
+                    load.open.fetch_sub(1, Ordering::Relaxed);
+                    rejector.reject(message);
+                }
+                Err(TrySendError::Closed(_)) => {
+                    // The worker is shutting down.
+                    load.open.fetch_sub(1, Ordering::Relaxed);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Connection counters for a worker, shared between the worker and its handle.
+#[derive(Default)]
+struct WorkerLoad {
+    /// The number of connections that have been dispatched to the worker and haven't been
+    /// closed yet, including the ones that are still waiting in the worker's queue.
+    open: AtomicUsize,
+    /// The number of connections that the worker has picked up from its queue and
+    /// hasn't closed yet.
+    active: AtomicUsize,
+}
+
+/// Track a connection that's being handled by a worker.
+///
+/// The connection counters are decremented when the guard is dropped.
+struct OpenConnectionGuard(Arc<WorkerLoad>);
+
+impl OpenConnectionGuard {
+    fn new(load: Arc<WorkerLoad>) -> Self {
+        load.active.fetch_add(1, Ordering::Relaxed);
+        Self(load)
+    }
+}
+
+impl Drop for OpenConnectionGuard {
+    fn drop(&mut self) {
+        self.0.active.fetch_sub(1, Ordering::Relaxed);
+        self.0.open.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
 /// HTTP2 requires `hyper` to be able to spawn tasks, therefore we need to pass to `hyper`'s
 /// `Server` an executor and a way to perform the spawning.
 ///
 /// We use `spawn_local` since we want each worker thread to be able to spawn !Send futures.
 #[derive(Clone, Copy, Debug)]
-struct LocalExec;
+pub(super) struct LocalExec;
 
 impl<F> hyper::rt::Executor<F> for LocalExec
 where
@@ -0,0 +1,66 @@
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use bytes::{Bytes, BytesMut};
+use http_body::{Frame, SizeHint};
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::response::body::raw::RawBody;
+
+/// The maximum number of bytes read from the file for each frame.
+const CHUNK_SIZE: u64 = 64 * 1024;
+
+/// A body that streams `length` bytes from a file, starting at its current position.
+pub(super) struct FileBody {
+    file: tokio::fs::File,
+    remaining: u64,
+    buffer: BytesMut,
+}
+
+impl FileBody {
+    pub(super) fn new(file: tokio::fs::File, length: u64) -> Self {
+        Self {
+            file,
+            remaining: length,
+            buffer: BytesMut::new(),
+        }
+    }
+}
+
+impl RawBody for FileBody {
+    type Data = Bytes;
+    type Error = std::io::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        if this.remaining == 0 {
+            return Poll::Ready(None);
+        }
+        // It fits in a `usize`, since it's no larger than `CHUNK_SIZE`.
+        let chunk_size = this.remaining.min(CHUNK_SIZE) as usize;
+        this.buffer.resize(chunk_size, 0);
+        let mut buffer = ReadBuf::new(&mut this.buffer);
+        ready!(Pin::new(&mut this.file).poll_read(cx, &mut buffer))?;
+        let n_read = buffer.filled().len();
+        if n_read == 0 {
+            return Poll::Ready(Some(Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "The file was truncated while it was being served",
+            ))));
+        }
+        this.remaining -= n_read as u64;
+        let chunk = this.buffer.split_to(n_read).freeze();
+        Poll::Ready(Some(Ok(Frame::data(chunk))))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.remaining == 0
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::with_exact(self.remaining)
+    }
+}
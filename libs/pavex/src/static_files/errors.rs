@@ -0,0 +1,48 @@
+//! Errors that can occur while serving static files.
+use std::path::PathBuf;
+
+use crate::response::Response;
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+/// The error returned by [`serve_static_file`](super::serve_static_file) and
+/// [`StaticFiles::serve`](super::StaticFiles::serve).
+pub enum ServeStaticFileError {
+    #[error(transparent)]
+    /// See [`MissingPathParameter`] for details.
+    MissingPathParameter(#[from] MissingPathParameter),
+    #[error(transparent)]
+    /// See [`ReadFileError`] for details.
+    ReadFileError(#[from] ReadFileError),
+}
+
+impl ServeStaticFileError {
+    /// Convert a [`ServeStaticFileError`] into an HTTP response.
+    ///
+    /// It returns a `500 Internal Server Error` to the caller,
+    /// since failure is due to misconfiguration or a problem with the
+    /// underlying filesystem.
+    pub fn into_response(&self) -> Response {
+        Response::internal_server_error()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "The route serving static files doesn't have any path parameter. \
+    Its path must end with a catch-all parameter, e.g. `/static/{{*path}}`"
+)]
+#[non_exhaustive]
+/// The route doesn't have a path parameter to determine which file should be served.
+pub struct MissingPathParameter;
+
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to read `{}`", path.display())]
+#[non_exhaustive]
+/// The file exists, but it couldn't be read.
+pub struct ReadFileError {
+    /// The path of the file.
+    pub path: PathBuf,
+    #[source]
+    pub(super) source: std::io::Error,
+}
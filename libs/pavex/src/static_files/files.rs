@@ -0,0 +1,638 @@
+use std::fs::Metadata;
+use std::io::SeekFrom;
+use std::path::{Component, Path, PathBuf};
+
+use http::header::{
+    ACCEPT_RANGES, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, ETAG, IF_RANGE,
+    LAST_MODIFIED, LOCATION, VARY,
+};
+use http::{HeaderMap, HeaderValue, Method};
+use tokio::io::AsyncSeekExt;
+
+use super::body::FileBody;
+use super::content_type;
+use super::errors::{MissingPathParameter, ReadFileError, ServeStaticFileError};
+use crate::request::RequestHead;
+use crate::request::headers::{
+    AcceptEncoding, EntityTag, IfModifiedSince, IfNoneMatch, Range, TypedHeader, decode_if_valid,
+};
+use crate::request::path::RawPathParams;
+use crate::response::Response;
+
+/// The precompressed variants we look for, as (content coding, file extension) pairs.
+///
+/// On ties, the client is served the variant that comes first.
+const PRECOMPRESSED: [(&str, &str); 2] = [("br", "br"), ("gzip", "gz")];
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+/// Configure how [`StaticFiles`] serves files.
+///
+/// # Example
+///
+/// ```yaml
+/// static_files:
+///   directory: "assets"
+///   index_file: "index.html"
+///   precompressed: true
+/// ```
+#[non_exhaustive]
+pub struct StaticFilesConfig {
+    /// The directory to serve files from.
+    ///
+    /// Relative paths are resolved against the current working directory.
+    /// It defaults to `static`.
+    pub directory: PathBuf,
+    /// The file served when a directory is requested.
+    ///
+    /// Directories can't be requested if it's set to `None`.
+    /// It defaults to `index.html`.
+    pub index_file: Option<String>,
+    /// Serve the precompressed siblings of the requested file (e.g. `app.js.br` or
+    /// `app.js.gz` for `app.js`), if they exist and the client accepts their encoding.
+    ///
+    /// It defaults to `true`.
+    pub precompressed: bool,
+}
+
+impl Default for StaticFilesConfig {
+    fn default() -> Self {
+        Self {
+            directory: PathBuf::from("static"),
+            index_file: Some("index.html".into()),
+            precompressed: true,
+        }
+    }
+}
+
+impl StaticFilesConfig {
+    /// Create a configuration that serves files from `directory`, with default values
+    /// for all other options.
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Serve the files in a directory.
+///
+/// [`StaticFiles`] takes care of:
+///
+/// - setting `Content-Type`, guessed from the file extension
+/// - setting `ETag` and `Last-Modified`, returning `304 Not Modified` if the client's copy
+///   is still fresh (`If-None-Match`, `If-Modified-Since`)
+/// - serving partial content for requests with a single byte range (`Range`, `If-Range`).
+///   Requests for multiple ranges are served the whole file
+/// - serving precompressed variants of the requested file, negotiated via `Accept-Encoding`
+/// - rejecting paths that would escape the directory (e.g. `../secret`)
+///
+/// You don't usually interact with [`StaticFiles`] directly:
+/// [`StaticFilesKit`](super::StaticFilesKit) registers a route that serves
+/// files via [`serve_static_file`].
+#[derive(Debug, Clone)]
+pub struct StaticFiles {
+    config: StaticFilesConfig,
+}
+
+impl From<StaticFilesConfig> for StaticFiles {
+    fn from(config: StaticFilesConfig) -> Self {
+        Self::new(config)
+    }
+}
+
+impl StaticFiles {
+    /// Serve files according to the given configuration.
+    pub fn new(config: StaticFilesConfig) -> Self {
+        Self { config }
+    }
+
+    /// The configuration used to serve files.
+    pub fn config(&self) -> &StaticFilesConfig {
+        &self.config
+    }
+
+    /// Serve the file at `path`, relative to the configured directory.
+    ///
+    /// `path` must be percent-decoded.
+    /// A `404 Not Found` response is returned if the file doesn't exist or if
+    /// `path` contains a component that could escape the directory—i.e. `..`,
+    /// a backslash, a NUL byte or a drive prefix.
+    pub async fn serve(
+        &self,
+        request_head: &RequestHead,
+        path: &str,
+    ) -> Result<Response, ServeStaticFileError> {
+        let Some(relative_path) = sanitize(path) else {
+            return Ok(Response::not_found());
+        };
+        let mut file_path = self.config.directory.join(relative_path);
+        let Some(mut metadata) = metadata(&file_path).await? else {
+            return Ok(Response::not_found());
+        };
+        if metadata.is_dir() {
+            let Some(index_file) = &self.config.index_file else {
+                return Ok(Response::not_found());
+            };
+            let target_path = request_head.target.path();
+            if !target_path.ends_with('/') {
+                // Relative links in the index file would otherwise be resolved
+                // against the parent directory.
+                let location = match request_head.target.query() {
+                    Some(query) => format!("{target_path}/?{query}"),
+                    None => format!("{target_path}/"),
+                };
+                let location = HeaderValue::try_from(location)
+                    .expect("The path of a URI is always a valid header value");
+                return Ok(Response::permanent_redirect().insert_header(LOCATION, location));
+            }
+            file_path.push(index_file);
+            match self::metadata(&file_path).await? {
+                Some(m) => metadata = m,
+                None => return Ok(Response::not_found()),
+            }
+        }
+        if !metadata.is_file() {
+            return Ok(Response::not_found());
+        }
+
+        let headers = &request_head.headers;
+        let content_type = content_type::guess(&file_path);
+        let (file_path, metadata, encoding) = self
+            .negotiate_encoding(headers, file_path, metadata)
+            .await?;
+
+        let length = metadata.len();
+        let last_modified = metadata
+            .modified()
+            .ok()
+            .and_then(|t| jiff::Timestamp::try_from(t).ok());
+        let etag = last_modified.map(|t| {
+            let suffix = encoding.map(|e| format!("-{e}")).unwrap_or_default();
+            EntityTag::strong(format!("{:x}-{length:x}{suffix}", t.as_second()))
+        });
+
+        let mut validators = HeaderMap::new();
+        if let Some(etag) = &etag {
+            validators.insert(ETAG, etag.clone().into());
+        }
+        if let Some(date) = last_modified.and_then(http_date) {
+            validators.insert(LAST_MODIFIED, date);
+        }
+        if self.config.precompressed {
+            validators.insert(VARY, HeaderValue::from_static("accept-encoding"));
+        }
+
+        if is_not_modified(headers, etag.as_ref(), last_modified) {
+            let mut response = Response::not_modified();
+            response.headers_mut().extend(validators);
+            return Ok(response);
+        }
+
+        let range = if request_head.method == Method::GET
+            && if_range_holds(headers, etag.as_ref(), last_modified)
+        {
+            decode_if_valid::<Range>(headers).and_then(|Range(ranges)| match ranges.as_slice() {
+                [range] => Some(*range),
+                _ => None,
+            })
+        } else {
+            None
+        };
+        let (response, range) = match range.map(|range| range.resolve(length)) {
+            None => (Response::ok(), 0..length),
+            Some(Some(range)) => {
+                let content_range = format!("bytes {}-{}/{length}", range.start, range.end - 1);
+                let response = Response::partial_content().insert_header(
+                    CONTENT_RANGE,
+                    HeaderValue::try_from(content_range).expect("A valid header value"),
+                );
+                (response, range)
+            }
+            Some(None) => {
+                let content_range = format!("bytes */{length}");
+                return Ok(Response::range_not_satisfiable().insert_header(
+                    CONTENT_RANGE,
+                    HeaderValue::try_from(content_range).expect("A valid header value"),
+                ));
+            }
+        };
+        let mut response = response.insert_header(CONTENT_TYPE, content_type);
+        if let Some(encoding) = encoding {
+            response = response.insert_header(CONTENT_ENCODING, HeaderValue::from_static(encoding));
+        }
+        self.respond_with_file(request_head, response, &file_path, range, validators)
+            .await
+    }
+
+    /// Pick the precompressed variant of the file that's preferred by the client, if any.
+    async fn negotiate_encoding(
+        &self,
+        headers: &HeaderMap,
+        file_path: PathBuf,
+        metadata: Metadata,
+    ) -> Result<(PathBuf, Metadata, Option<&'static str>), ServeStaticFileError> {
+        if !self.config.precompressed {
+            return Ok((file_path, metadata, None));
+        }
+        let Some(accept_encoding) = decode_if_valid::<AcceptEncoding>(headers) else {
+            return Ok((file_path, metadata, None));
+        };
+        let mut candidates: Vec<_> = PRECOMPRESSED
+            .into_iter()
+            .map(|(coding, extension)| (coding, extension, accept_encoding.quality(coding)))
+            .filter(|(_, _, quality)| *quality > 0.0)
+            .collect();
+        candidates.sort_by(|a, b| b.2.total_cmp(&a.2));
+        for (coding, extension, _) in candidates {
+            let mut sibling = file_path.clone().into_os_string();
+            sibling.push(".");
+            sibling.push(extension);
+            let sibling = PathBuf::from(sibling);
+            match self::metadata(&sibling).await? {
+                Some(m) if m.is_file() => return Ok((sibling, m, Some(coding))),
+                _ => continue,
+            }
+        }
+        Ok((file_path, metadata, None))
+    }
+
+    /// Stream `range` from the file at `path` as the body of `response`.
+    async fn respond_with_file(
+        &self,
+        request_head: &RequestHead,
+        response: Response,
+        path: &Path,
+        range: std::ops::Range<u64>,
+        validators: HeaderMap,
+    ) -> Result<Response, ServeStaticFileError> {
+        let length = range.end - range.start;
+        let mut response = response
+            .insert_header(ACCEPT_RANGES, HeaderValue::from_static("bytes"))
+            .insert_header(CONTENT_LENGTH, HeaderValue::from(length));
+        response.headers_mut().extend(validators);
+        if request_head.method == Method::HEAD {
+            return Ok(response);
+        }
+        let read_error = |source| ReadFileError {
+            path: path.to_owned(),
+            source,
+        };
+        let mut file = match tokio::fs::File::open(path).await {
+            Ok(file) => file,
+            // The file was removed after we checked its metadata.
+            Err(e) if is_not_found(&e) => return Ok(Response::not_found()),
+            Err(e) => return Err(read_error(e).into()),
+        };
+        if range.start > 0 {
+            file.seek(SeekFrom::Start(range.start))
+                .await
+                .map_err(read_error)?;
+        }
+        Ok(response.set_raw_body(FileBody::new(file, length)))
+    }
+}
+
+/// Serve the file at the path captured by the last path parameter of the route,
+/// relative to the directory configured in [`StaticFiles`].
+///
+/// The route path must end with a catch-all parameter—e.g. `/static/{*path}`.
+/// The captured value is percent-decoded before looking up the file.
+///
+/// Check out [`StaticFiles`] for more details on how files are served.
+pub async fn serve_static_file(
+    request_head: &RequestHead,
+    path_params: &RawPathParams<'_, '_>,
+    static_files: &StaticFiles,
+) -> Result<Response, ServeStaticFileError> {
+    let Some((_, path)) = path_params.iter().last() else {
+        return Err(MissingPathParameter.into());
+    };
+    let Ok(path) = path.decode() else {
+        return Ok(Response::not_found());
+    };
+    static_files.serve(request_head, &path).await
+}
+
+/// Convert a decoded request path into a relative filesystem path.
+///
+/// It returns `None` if the path contains a component that could be used to escape
+/// the root directory.
+fn sanitize(path: &str) -> Option<PathBuf> {
+    let mut relative_path = PathBuf::new();
+    for segment in path.split('/') {
+        if segment.is_empty() || segment == "." {
+            continue;
+        }
+        if segment.contains(['\\', '\0']) {
+            return None;
+        }
+        // `..` and, on Windows, drive prefixes (e.g. `C:`) are rejected here.
+        let mut components = Path::new(segment).components();
+        match (components.next(), components.next()) {
+            (Some(Component::Normal(component)), None) => relative_path.push(component),
+            _ => return None,
+        }
+    }
+    Some(relative_path)
+}
+
+/// Retrieve the metadata of the file at `path`, or `None` if it doesn't exist.
+async fn metadata(path: &Path) -> Result<Option<Metadata>, ReadFileError> {
+    match tokio::fs::metadata(path).await {
+        Ok(metadata) => Ok(Some(metadata)),
+        Err(e) if is_not_found(&e) => Ok(None),
+        Err(source) => Err(ReadFileError {
+            path: path.to_owned(),
+            source,
+        }),
+    }
+}
+
+fn is_not_found(e: &std::io::Error) -> bool {
+    use std::io::ErrorKind;
+
+    matches!(
+        e.kind(),
+        ErrorKind::NotFound | ErrorKind::NotADirectory | ErrorKind::InvalidFilename
+    )
+}
+
+/// Returns `true` if the client's copy is still fresh.
+fn is_not_modified(
+    headers: &HeaderMap,
+    etag: Option<&EntityTag>,
+    last_modified: Option<jiff::Timestamp>,
+) -> bool {
+    // `If-Modified-Since` must be ignored when `If-None-Match` is present.
+    if headers.contains_key(IfNoneMatch::NAME) {
+        return decode_if_valid::<IfNoneMatch>(headers)
+            .zip(etag)
+            .is_some_and(|(condition, etag)| condition.matches(etag));
+    }
+    decode_if_valid::<IfModifiedSince>(headers)
+        .zip(last_modified)
+        .is_some_and(|(condition, last_modified)| !condition.is_modified(last_modified))
+}
+
+/// Returns `true` if the `Range` header should be honored, according to `If-Range`.
+fn if_range_holds(
+    headers: &HeaderMap,
+    etag: Option<&EntityTag>,
+    last_modified: Option<jiff::Timestamp>,
+) -> bool {
+    let Some(value) = headers.get(IF_RANGE) else {
+        return true;
+    };
+    let Ok(value) = value.to_str() else {
+        return false;
+    };
+    if value.starts_with('"') || value.starts_with("W/") {
+        // Only strong validators are allowed.
+        value
+            .parse::<EntityTag>()
+            .ok()
+            .zip(etag)
+            .is_some_and(|(condition, etag)| condition.strong_eq(etag))
+    } else {
+        jiff::fmt::rfc2822::DateTimeParser::new()
+            .parse_timestamp(value)
+            .ok()
+            .zip(last_modified)
+            .is_some_and(|(condition, last_modified)| {
+                condition.as_second() == last_modified.as_second()
+            })
+    }
+}
+
+/// Format a timestamp as an HTTP date—e.g. `Sun, 06 Nov 1994 08:49:37 GMT`.
+///
+/// It returns `None` if the timestamp can't be represented as an HTTP date.
+fn http_date(timestamp: jiff::Timestamp) -> Option<HeaderValue> {
+    let date = jiff::fmt::rfc2822::DateTimePrinter::new()
+        .timestamp_to_rfc9110_string(&timestamp)
+        .ok()?;
+    Some(HeaderValue::try_from(date).expect("An HTTP date is always a valid header value"))
+}
+
+#[cfg(test)]
+mod tests {
+    use http::header::{ACCEPT_ENCODING, IF_MODIFIED_SINCE, IF_NONE_MATCH, RANGE};
+    use http::{HeaderName, StatusCode, Version};
+    use http_body_util::BodyExt;
+
+    use super::*;
+
+    fn fixture() -> (tempfile::TempDir, StaticFiles) {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("public");
+        std::fs::create_dir_all(root.join("docs")).unwrap();
+        std::fs::write(root.join("hello.txt"), "Hello, world!").unwrap();
+        std::fs::write(root.join("docs/index.html"), "<h1>Docs</h1>").unwrap();
+        std::fs::write(dir.path().join("secret.txt"), "Top secret").unwrap();
+        let static_files = StaticFiles::new(StaticFilesConfig::new(root));
+        (dir, static_files)
+    }
+
+    fn request(method: Method, target: &str, headers: &[(HeaderName, &str)]) -> RequestHead {
+        RequestHead {
+            method,
+            target: target.parse().unwrap(),
+            version: Version::HTTP_11,
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.clone(), value.parse().unwrap()))
+                .collect(),
+        }
+    }
+
+    async fn get(
+        static_files: &StaticFiles,
+        path: &str,
+        headers: &[(HeaderName, &str)],
+    ) -> Response {
+        let head = request(Method::GET, &format!("/static/{path}"), headers);
+        static_files.serve(&head, path).await.unwrap()
+    }
+
+    async fn body(response: Response) -> String {
+        let (_, body) = response.into_parts();
+        let bytes = body.collect().await.unwrap().to_bytes();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn files_are_served() {
+        let (_dir, static_files) = fixture();
+        let response = get(&static_files, "hello.txt", &[]).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let headers = response.headers();
+        assert_eq!(headers[CONTENT_TYPE], "text/plain; charset=utf-8");
+        assert_eq!(headers[CONTENT_LENGTH], "13");
+        assert_eq!(headers[ACCEPT_RANGES], "bytes");
+        assert!(headers.contains_key(ETAG));
+        assert!(headers.contains_key(LAST_MODIFIED));
+        assert_eq!(body(response).await, "Hello, world!");
+
+        let head = request(Method::HEAD, "/static/hello.txt", &[]);
+        let response = static_files.serve(&head, "hello.txt").await.unwrap();
+        assert_eq!(response.headers()[CONTENT_LENGTH], "13");
+        assert_eq!(body(response).await, "");
+
+        let response = get(&static_files, "missing.txt", &[]).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = get(&static_files, "hello.txt/nested", &[]).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn fresh_copies_are_not_sent_again() {
+        let (_dir, static_files) = fixture();
+        let response = get(&static_files, "hello.txt", &[]).await;
+        let etag = response.headers()[ETAG].to_str().unwrap().to_owned();
+        let last_modified = response.headers()[LAST_MODIFIED]
+            .to_str()
+            .unwrap()
+            .to_owned();
+
+        let response = get(&static_files, "hello.txt", &[(IF_NONE_MATCH, &etag)]).await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[ETAG], etag.as_str());
+        assert_eq!(body(response).await, "");
+
+        let response = get(
+            &static_files,
+            "hello.txt",
+            &[(IF_MODIFIED_SINCE, &last_modified)],
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        // `If-None-Match` takes precedence over `If-Modified-Since`.
+        let response = get(
+            &static_files,
+            "hello.txt",
+            &[
+                (IF_NONE_MATCH, r#""stale""#),
+                (IF_MODIFIED_SINCE, &last_modified),
+            ],
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = get(
+            &static_files,
+            "hello.txt",
+            &[(IF_MODIFIED_SINCE, "Sun, 06 Nov 1994 08:49:37 GMT")],
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn byte_ranges_are_served() {
+        let (_dir, static_files) = fixture();
+        let response = get(&static_files, "hello.txt", &[(RANGE, "bytes=0-4")]).await;
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()[CONTENT_RANGE], "bytes 0-4/13");
+        assert_eq!(response.headers()[CONTENT_LENGTH], "5");
+        assert_eq!(body(response).await, "Hello");
+
+        let response = get(&static_files, "hello.txt", &[(RANGE, "bytes=-6")]).await;
+        assert_eq!(response.headers()[CONTENT_RANGE], "bytes 7-12/13");
+        assert_eq!(body(response).await, "world!");
+
+        let response = get(&static_files, "hello.txt", &[(RANGE, "bytes=20-")]).await;
+        assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(response.headers()[CONTENT_RANGE], "bytes */13");
+
+        // Multiple ranges are not supported, the whole file is served.
+        let response = get(&static_files, "hello.txt", &[(RANGE, "bytes=0-1, 3-4")]).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body(response).await, "Hello, world!");
+
+        // The range is ignored if the file has changed.
+        let response = get(
+            &static_files,
+            "hello.txt",
+            &[(RANGE, "bytes=0-4"), (IF_RANGE, r#""stale""#)],
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn precompressed_variants_are_negotiated() {
+        let (dir, static_files) = fixture();
+        let root = dir.path().join("public");
+        std::fs::write(root.join("hello.txt.gz"), "gzip-encoded").unwrap();
+        std::fs::write(root.join("hello.txt.br"), "br-encoded").unwrap();
+
+        let response = get(&static_files, "hello.txt", &[(ACCEPT_ENCODING, "gzip")]).await;
+        assert_eq!(response.headers()[CONTENT_ENCODING], "gzip");
+        assert_eq!(
+            response.headers()[CONTENT_TYPE],
+            "text/plain; charset=utf-8"
+        );
+        assert_eq!(response.headers()[VARY], "accept-encoding");
+        assert_eq!(body(response).await, "gzip-encoded");
+
+        let response = get(&static_files, "hello.txt", &[(ACCEPT_ENCODING, "gzip, br")]).await;
+        assert_eq!(response.headers()[CONTENT_ENCODING], "br");
+        assert_eq!(body(response).await, "br-encoded");
+
+        let response = get(
+            &static_files,
+            "hello.txt",
+            &[(ACCEPT_ENCODING, "br;q=0.5, gzip")],
+        )
+        .await;
+        assert_eq!(response.headers()[CONTENT_ENCODING], "gzip");
+
+        let response = get(&static_files, "hello.txt", &[]).await;
+        assert!(!response.headers().contains_key(CONTENT_ENCODING));
+        assert_eq!(body(response).await, "Hello, world!");
+    }
+
+    #[tokio::test]
+    async fn directories_are_served_via_their_index_file() {
+        let (_dir, static_files) = fixture();
+        let response = get(&static_files, "docs/", &[]).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/html; charset=utf-8");
+        assert_eq!(body(response).await, "<h1>Docs</h1>");
+
+        let response = get(&static_files, "docs", &[]).await;
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(response.headers()[LOCATION], "/static/docs/");
+    }
+
+    #[tokio::test]
+    async fn path_traversal_is_rejected() {
+        let (_dir, static_files) = fixture();
+        let mut router = matchit::Router::new();
+        router.insert("/static/{*path}", ()).unwrap();
+
+        let serve = async |target: &str| {
+            let params = router.at(target).unwrap().params;
+            let head = request(Method::GET, target, &[]);
+            serve_static_file(&head, &RawPathParams::from(params), &static_files)
+                .await
+                .unwrap()
+        };
+        for target in [
+            "/static/../secret.txt",
+            "/static/%2e%2e/secret.txt",
+            "/static/docs/..%2f..%2fsecret.txt",
+            "/static/%2E%2E%5Csecret.txt",
+            "/static/hello.txt%00",
+        ] {
+            let response = serve(target).await;
+            assert_eq!(response.status(), StatusCode::NOT_FOUND, "{target}");
+        }
+
+        let response = serve("/static/%68ello.txt").await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
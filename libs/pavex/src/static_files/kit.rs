@@ -0,0 +1,116 @@
+use crate::blueprint::Blueprint;
+use crate::blueprint::config::ConfigType;
+use crate::blueprint::constructor::Constructor;
+use crate::blueprint::linter::Lint;
+use crate::blueprint::router::{GET, HEAD, Route};
+use crate::{f, t};
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+/// A collection of components required to serve the files in a directory.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::blueprint::Blueprint;
+/// use pavex::static_files::StaticFilesKit;
+///
+/// let mut bp = Blueprint::new();
+/// // Serves `GET /static/{*path}` from the `static` directory, by default.
+/// let kit = StaticFilesKit::new().register(&mut bp);
+/// ```
+///
+/// # Custom path
+///
+/// Replace the bundled route to serve files under a different path.
+/// Its path must end with a catch-all parameter, used to determine which file should
+/// be served.
+///
+/// ```rust
+/// use pavex::blueprint::Blueprint;
+/// use pavex::blueprint::router::{GET, HEAD, Route};
+/// use pavex::f;
+/// use pavex::static_files::StaticFilesKit;
+///
+/// let mut bp = Blueprint::new();
+/// let mut kit = StaticFilesKit::new();
+/// kit.route = Some(
+///     Route::new(GET.or(HEAD), "/assets/{*path}", f!(pavex::static_files::serve_static_file))
+///         .error_handler(f!(pavex::static_files::errors::ServeStaticFileError::into_response)),
+/// );
+/// kit.register(&mut bp);
+/// ```
+pub struct StaticFilesKit {
+    /// The route that serves the files.
+    ///
+    /// By default, it handles `GET` and `HEAD` requests for `/static/{*path}`
+    /// via [`serve_static_file`].
+    /// The error is handled by [`ServeStaticFileError::into_response`].
+    ///
+    /// [`ServeStaticFileError::into_response`]: super::errors::ServeStaticFileError::into_response
+    /// [`serve_static_file`]: super::serve_static_file
+    pub route: Option<Route>,
+    /// The constructor for [`StaticFiles`].
+    ///
+    /// By default, it uses [`StaticFiles::from`].
+    ///
+    /// [`StaticFiles`]: super::StaticFiles
+    /// [`StaticFiles::from`]: super::StaticFiles::from
+    pub static_files: Option<Constructor>,
+    /// Register [`StaticFilesConfig`] as a configuration type.
+    ///
+    /// By default, it uses `static_files` as its configuration key.
+    ///
+    /// [`StaticFilesConfig`]: super::StaticFilesConfig
+    pub static_files_config: Option<ConfigType>,
+}
+
+impl Default for StaticFilesKit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StaticFilesKit {
+    /// Create a new [`StaticFilesKit`] with all the bundled components.
+    pub fn new() -> Self {
+        let route = Route::new(
+            GET.or(HEAD),
+            "/static/{*path}",
+            f!(super::serve_static_file),
+        )
+        .error_handler(f!(super::errors::ServeStaticFileError::into_response));
+        let static_files = Constructor::singleton(f!(<super::StaticFiles as std::convert::From<
+            super::StaticFilesConfig,
+        >>::from))
+        .ignore(Lint::Unused);
+        let static_files_config =
+            ConfigType::new("static_files", t!(super::StaticFilesConfig)).default_if_missing();
+        Self {
+            route: Some(route),
+            static_files: Some(static_files),
+            static_files_config: Some(static_files_config),
+        }
+    }
+
+    /// Register all the bundled components with a [`Blueprint`].
+    ///
+    /// If a component is set to `None` it will not be registered.
+    pub fn register(self, bp: &mut Blueprint) -> RegisteredStaticFilesKit {
+        if let Some(route) = self.route {
+            route.register(bp);
+        }
+        if let Some(static_files) = self.static_files {
+            static_files.register(bp);
+        }
+        if let Some(static_files_config) = self.static_files_config {
+            static_files_config.register(bp);
+        }
+        RegisteredStaticFilesKit {}
+    }
+}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+/// The type returned by [`StaticFilesKit::register`].
+pub struct RegisteredStaticFilesKit {}
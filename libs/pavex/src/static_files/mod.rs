@@ -0,0 +1,33 @@
+//! Serve the files in a directory—e.g. the assets of your frontend.
+//!
+//! Register a [`StaticFilesKit`] with your [`Blueprint`](crate::blueprint::Blueprint) to serve
+//! the files in the configured directory under a catch-all path pattern (`/static/{*path}`,
+//! by default).
+//! Check out [`StaticFiles`] for an overview of the HTTP features supported when
+//! serving files.
+//!
+//! It's only available if the `static_files` feature is enabled.
+//!
+//! # Configuration
+//!
+//! The directory and the other serving options are set via [`StaticFilesConfig`],
+//! under the `static_files` configuration key.
+//!
+//! ```yaml
+//! static_files:
+//!   directory: "frontend/dist"
+//! ```
+//!
+//! # Precompressed files
+//!
+//! Files are served as they are, without compressing them on the fly.
+//! Compress your assets ahead of time instead: if `app.js.br` or `app.js.gz` exist next to
+//! `app.js`, they are served to clients that accept the corresponding encoding.
+mod body;
+mod content_type;
+pub mod errors;
+mod files;
+mod kit;
+
+pub use files::{StaticFiles, StaticFilesConfig, serve_static_file};
+pub use kit::{RegisteredStaticFilesKit, StaticFilesKit};
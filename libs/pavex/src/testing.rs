@@ -0,0 +1,418 @@
+//! Test your application in-process, without going through the network.
+//!
+//! # The test client
+//!
+//! Pavex generates a `TestClient` in your server SDK crate.
+//! It sends requests straight into the request processing pipeline of your application:
+//! there is no server to start, no port to bind, no socket to connect to.
+//!
+//! ```rust,ignore
+//! use pavex::http::StatusCode;
+//! use pavex::testing::TestRequest;
+//! use server_sdk::{ApplicationState, TestClient};
+//!
+//! #[tokio::test]
+//! async fn greet() {
+//!     let application_state = ApplicationState::new(/* */).await.unwrap();
+//!     let client = TestClient::new(application_state);
+//!
+//!     client
+//!         .send(TestRequest::get("/greet/Ursula"))
+//!         .await
+//!         .assert_status(StatusCode::OK)
+//!         .assert_text("Hello, Ursula!");
+//! }
+//! ```
+//!
+//! Requests are built using [`TestRequest`].
+//! You can also send an [`http::Request`] directly, as long as its
+//! body can be converted into [`Bytes`].
+//!
+//! Responses are returned as [`TestResponse`]s, with their body already buffered in memory.
+//! Use its assertion helpers to check that they match your expectations.
+use std::net::{Ipv4Addr, SocketAddr};
+
+use bytes::Bytes;
+use http::header::CONTENT_TYPE;
+use http::{HeaderMap, HeaderName, HeaderValue, Method, Request, StatusCode, Uri};
+use http_body_util::BodyExt;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::connection::{ConnectionInfo, PeerAddr};
+use crate::request::body::RawIncomingBody;
+use crate::response::Response;
+
+/// A request to be sent to your application via its test client.
+///
+/// Check out the [module-level documentation](crate::testing) for an overview.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::http::{HeaderValue, header::ACCEPT};
+/// use pavex::testing::TestRequest;
+///
+/// #[derive(serde::Serialize)]
+/// struct NewUser {
+///     name: String,
+/// }
+///
+/// let request = TestRequest::post("/users")
+///     .insert_header(ACCEPT, HeaderValue::from_static("application/json"))
+///     .set_json_body(&NewUser { name: "Ursula".into() });
+/// ```
+#[derive(Debug)]
+pub struct TestRequest {
+    request: Request<Bytes>,
+    peer_addr: PeerAddr,
+}
+
+impl TestRequest {
+    /// Build a new request with the given method and target, e.g. `/users?page=2`.
+    ///
+    /// The body is empty and there are no headers.
+    /// The request is treated as if it came from `127.0.0.1`—use
+    /// [`set_peer_addr`](TestRequest::set_peer_addr) to change it.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `uri` is not a valid URI.
+    #[track_caller]
+    pub fn new(method: Method, uri: &str) -> Self {
+        let uri: Uri = match uri.parse() {
+            Ok(uri) => uri,
+            Err(e) => panic!("`{uri}` is not a valid URI: {e}"),
+        };
+        let mut request = Request::new(Bytes::new());
+        *request.method_mut() = method;
+        *request.uri_mut() = uri;
+        request.into()
+    }
+
+    /// Build a new `GET` request.
+    ///
+    /// Check out [`TestRequest::new`] for more details.
+    #[track_caller]
+    pub fn get(uri: &str) -> Self {
+        Self::new(Method::GET, uri)
+    }
+
+    /// Build a new `POST` request.
+    ///
+    /// Check out [`TestRequest::new`] for more details.
+    #[track_caller]
+    pub fn post(uri: &str) -> Self {
+        Self::new(Method::POST, uri)
+    }
+
+    /// Build a new `PUT` request.
+    ///
+    /// Check out [`TestRequest::new`] for more details.
+    #[track_caller]
+    pub fn put(uri: &str) -> Self {
+        Self::new(Method::PUT, uri)
+    }
+
+    /// Build a new `PATCH` request.
+    ///
+    /// Check out [`TestRequest::new`] for more details.
+    #[track_caller]
+    pub fn patch(uri: &str) -> Self {
+        Self::new(Method::PATCH, uri)
+    }
+
+    /// Build a new `DELETE` request.
+    ///
+    /// Check out [`TestRequest::new`] for more details.
+    #[track_caller]
+    pub fn delete(uri: &str) -> Self {
+        Self::new(Method::DELETE, uri)
+    }
+
+    /// Insert a header value, replacing any existing value for the same header name.
+    pub fn insert_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.request.headers_mut().insert(name, value);
+        self
+    }
+
+    /// Append a header value, keeping any existing value for the same header name.
+    pub fn append_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.request.headers_mut().append(name, value);
+        self
+    }
+
+    /// Set the request body.
+    ///
+    /// The `Content-Type` header is left untouched.
+    pub fn set_body(mut self, body: impl Into<Bytes>) -> Self {
+        *self.request.body_mut() = body.into();
+        self
+    }
+
+    /// Serialize `body` as JSON and use it as the request body.
+    ///
+    /// The `Content-Type` header is set to `application/json`.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `body` can't be serialized as JSON.
+    #[track_caller]
+    pub fn set_json_body<T: Serialize + ?Sized>(self, body: &T) -> Self {
+        let body = match serde_json::to_vec(body) {
+            Ok(body) => body,
+            Err(e) => panic!("Failed to serialize the request body as JSON: {e}"),
+        };
+        self.insert_header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+            .set_body(body)
+    }
+
+    /// Serialize `body` as a URL-encoded form and use it as the request body.
+    ///
+    /// The `Content-Type` header is set to `application/x-www-form-urlencoded`.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `body` can't be serialized as a URL-encoded form.
+    #[track_caller]
+    pub fn set_form_body<T: Serialize + ?Sized>(self, body: &T) -> Self {
+        let body = match serde_html_form::to_string(body) {
+            Ok(body) => body,
+            Err(e) => panic!("Failed to serialize the request body as a URL-encoded form: {e}"),
+        };
+        self.insert_header(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/x-www-form-urlencoded"),
+        )
+        .set_body(body)
+    }
+
+    /// Set the address of the peer that's sending the request.
+    ///
+    /// It determines the [`ConnectionInfo`] seen by your application.
+    pub fn set_peer_addr(mut self, peer_addr: PeerAddr) -> Self {
+        self.peer_addr = peer_addr;
+        self
+    }
+
+    /// Convert the request into the inputs expected by the request processing pipeline.
+    ///
+    /// You don't need to invoke this method yourself: the generated test client does it for you.
+    pub fn into_parts(self) -> (Request<RawIncomingBody>, Option<ConnectionInfo>) {
+        let connection_info = ConnectionInfo {
+            peer_addr: self.peer_addr,
+            alpn_protocol: None,
+            server_name: None,
+            is_tls: false,
+        };
+        (self.request.map(Into::into), Some(connection_info))
+    }
+}
+
+impl<B> From<Request<B>> for TestRequest
+where
+    B: Into<Bytes>,
+{
+    fn from(request: Request<B>) -> Self {
+        Self {
+            request: request.map(Into::into),
+            peer_addr: PeerAddr::Ip(SocketAddr::from((Ipv4Addr::LOCALHOST, 0))),
+        }
+    }
+}
+
+/// A response returned by your application to its test client.
+///
+/// The body has already been buffered in memory, so it can be inspected
+/// as many times as you need.
+///
+/// All assertion helpers panic, with a message including the response body,
+/// if their expectations aren't met.
+/// They return a reference to the response, so they can be chained.
+#[derive(Debug)]
+pub struct TestResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl TestResponse {
+    /// Buffer the body of the response returned by the request processing pipeline.
+    ///
+    /// You don't need to invoke this method yourself: the generated test client does it for you.
+    ///
+    /// # Panics
+    ///
+    /// It panics if an error occurs while the body is being produced.
+    pub async fn new(response: Response) -> Self {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let (_, body) = response.into_parts();
+        let body = match body.collect().await {
+            Ok(body) => body.to_bytes(),
+            Err(e) => panic!("Failed to buffer the response body: {e}"),
+        };
+        Self {
+            status,
+            headers,
+            body,
+        }
+    }
+
+    /// The status code of the response.
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// The headers of the response.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// The body of the response.
+    pub fn body(&self) -> &Bytes {
+        &self.body
+    }
+
+    /// The body of the response, as text.
+    ///
+    /// # Panics
+    ///
+    /// It panics if the body is not valid UTF-8.
+    #[track_caller]
+    pub fn text(&self) -> &str {
+        match std::str::from_utf8(&self.body) {
+            Ok(text) => text,
+            Err(e) => panic!("The response body is not valid UTF-8: {e}"),
+        }
+    }
+
+    /// Deserialize the body of the response from JSON.
+    ///
+    /// # Panics
+    ///
+    /// It panics if the body can't be deserialized as `T`.
+    #[track_caller]
+    pub fn json<T: DeserializeOwned>(&self) -> T {
+        match serde_json::from_slice(&self.body) {
+            Ok(body) => body,
+            Err(e) => panic!(
+                "Failed to deserialize the response body as JSON: {e}\n{}",
+                self.describe_body()
+            ),
+        }
+    }
+
+    /// Assert that the response has the expected status code.
+    #[track_caller]
+    pub fn assert_status(&self, expected: StatusCode) -> &Self {
+        assert_eq!(
+            self.status,
+            expected,
+            "Unexpected status code.\n{}",
+            self.describe_body()
+        );
+        self
+    }
+
+    /// Assert that the response has a header with the expected value.
+    ///
+    /// If the header has multiple values, it's enough for one of them to match.
+    #[track_caller]
+    pub fn assert_header(&self, name: HeaderName, expected: &str) -> &Self {
+        let values: Vec<_> = self.headers.get_all(&name).iter().collect();
+        assert!(
+            values.iter().any(|v| v.as_bytes() == expected.as_bytes()),
+            "Expected the `{name}` header to be set to `{expected}`, found {values:?}"
+        );
+        self
+    }
+
+    /// Assert that the response doesn't have a header with the given name.
+    #[track_caller]
+    pub fn assert_no_header(&self, name: HeaderName) -> &Self {
+        assert!(
+            !self.headers.contains_key(&name),
+            "Expected the `{name}` header to be missing, found {:?}",
+            self.headers.get(&name)
+        );
+        self
+    }
+
+    /// Assert that the body of the response matches the expected text.
+    #[track_caller]
+    pub fn assert_text(&self, expected: &str) -> &Self {
+        assert_eq!(self.text(), expected, "Unexpected response body");
+        self
+    }
+
+    /// Assert that the body of the response is JSON and that it's equivalent to `expected`,
+    /// once serialized.
+    ///
+    /// The comparison ignores formatting and the order of object keys.
+    #[track_caller]
+    pub fn assert_json<T: Serialize + ?Sized>(&self, expected: &T) -> &Self {
+        let actual: serde_json::Value = self.json();
+        let expected = match serde_json::to_value(expected) {
+            Ok(expected) => expected,
+            Err(e) => panic!("Failed to serialize the expected body as JSON: {e}"),
+        };
+        assert_eq!(actual, expected, "Unexpected response body");
+        self
+    }
+
+    fn describe_body(&self) -> String {
+        match std::str::from_utf8(&self.body) {
+            Ok(text) => format!("Response body:\n{text}"),
+            Err(_) => format!("Response body: {} bytes of binary data", self.body.len()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+    use http::header::{CONTENT_TYPE, LOCATION};
+
+    use super::{TestRequest, TestResponse};
+    use crate::response::Response;
+
+    #[tokio::test]
+    async fn requests_carry_their_body() {
+        let request = TestRequest::post("/users?page=2")
+            .set_json_body(&serde_json::json!({"name": "Ursula"}));
+        let (request, connection_info) = request.into_parts();
+        assert_eq!(request.uri().query(), Some("page=2"));
+        assert_eq!(request.headers()[CONTENT_TYPE], "application/json");
+        assert!(connection_info.is_some());
+
+        let body = http_body_util::BodyExt::collect(request.into_body())
+            .await
+            .unwrap()
+            .to_bytes();
+        assert_eq!(body, r#"{"name":"Ursula"}"#);
+    }
+
+    #[tokio::test]
+    async fn assertions_are_chainable() {
+        let response = Response::created()
+            .insert_header(LOCATION, "/users/1".parse().unwrap())
+            .set_typed_body(
+                crate::response::body::Json::new(serde_json::json!({"id": 1})).unwrap(),
+            );
+        TestResponse::new(response)
+            .await
+            .assert_status(StatusCode::CREATED)
+            .assert_header(LOCATION, "/users/1")
+            .assert_no_header(http::header::ETAG)
+            .assert_json(&serde_json::json!({"id": 1}));
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Unexpected status code")]
+    async fn mismatches_panic() {
+        TestResponse::new(Response::not_found())
+            .await
+            .assert_status(StatusCode::OK);
+    }
+}
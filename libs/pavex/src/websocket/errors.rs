@@ -0,0 +1,160 @@
+//! Errors that can occur while establishing or using a WebSocket connection.
+use http::header::{ALLOW, CONNECTION, SEC_WEBSOCKET_VERSION, UPGRADE};
+use http::{HeaderValue, StatusCode};
+
+use crate::response::Response;
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+/// The error returned by [`WebSocketUpgrade::extract`] when the request
+/// isn't a valid WebSocket handshake.
+///
+/// [`WebSocketUpgrade::extract`]: crate::websocket::WebSocketUpgrade::extract
+pub enum WebSocketUpgradeError {
+    #[error(transparent)]
+    /// See [`NotAGetRequest`] for details.
+    NotAGetRequest(#[from] NotAGetRequest),
+    #[error(transparent)]
+    /// See [`UnsupportedHttpVersion`] for details.
+    UnsupportedHttpVersion(#[from] UnsupportedHttpVersion),
+    #[error(transparent)]
+    /// See [`MissingUpgradeHeader`] for details.
+    MissingUpgradeHeader(#[from] MissingUpgradeHeader),
+    #[error(transparent)]
+    /// See [`MissingConnectionUpgrade`] for details.
+    MissingConnectionUpgrade(#[from] MissingConnectionUpgrade),
+    #[error(transparent)]
+    /// See [`UnsupportedWebSocketVersion`] for details.
+    UnsupportedWebSocketVersion(#[from] UnsupportedWebSocketVersion),
+    #[error(transparent)]
+    /// See [`InvalidWebSocketKey`] for details.
+    InvalidWebSocketKey(#[from] InvalidWebSocketKey),
+    #[error(transparent)]
+    /// See [`UpgradeUnavailable`] for details.
+    UpgradeUnavailable(#[from] UpgradeUnavailable),
+}
+
+impl WebSocketUpgradeError {
+    /// Convert a [`WebSocketUpgradeError`] into an HTTP response.
+    pub fn into_response(&self) -> Response {
+        match self {
+            WebSocketUpgradeError::NotAGetRequest(_) => {
+                Response::method_not_allowed().insert_header(ALLOW, HeaderValue::from_static("GET"))
+            }
+            WebSocketUpgradeError::MissingUpgradeHeader(_) => {
+                Response::new(StatusCode::UPGRADE_REQUIRED)
+                    .insert_header(CONNECTION, HeaderValue::from_static("upgrade"))
+                    .insert_header(UPGRADE, HeaderValue::from_static("websocket"))
+            }
+            WebSocketUpgradeError::UnsupportedWebSocketVersion(_) => {
+                Response::new(StatusCode::UPGRADE_REQUIRED)
+                    .insert_header(SEC_WEBSOCKET_VERSION, HeaderValue::from_static("13"))
+            }
+            WebSocketUpgradeError::UnsupportedHttpVersion(_)
+            | WebSocketUpgradeError::MissingConnectionUpgrade(_)
+            | WebSocketUpgradeError::InvalidWebSocketKey(_) => Response::bad_request(),
+            WebSocketUpgradeError::UpgradeUnavailable(_) => Response::not_implemented(),
+        }
+        .set_typed_body(format!("{}", self))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("WebSocket handshakes must use the `GET` method, but the request used `{method}`")]
+#[non_exhaustive]
+/// The handshake request didn't use the `GET` method.
+pub struct NotAGetRequest {
+    pub(super) method: http::Method,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "WebSocket connections can only be established over HTTP/1.1, but the request used {version:?}"
+)]
+#[non_exhaustive]
+/// The handshake request wasn't sent over HTTP/1.1.
+pub struct UnsupportedHttpVersion {
+    pub(super) version: http::Version,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "This endpoint expects a WebSocket handshake, but the `Upgrade` header is missing or doesn't include `websocket`"
+)]
+#[non_exhaustive]
+/// The `Upgrade` header is missing, or it doesn't include `websocket`.
+pub struct MissingUpgradeHeader;
+
+#[derive(Debug, thiserror::Error)]
+#[error("The `Connection` header of a WebSocket handshake must include `upgrade`")]
+#[non_exhaustive]
+/// The `Connection` header is missing, or it doesn't include `upgrade`.
+pub struct MissingConnectionUpgrade;
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "The `Sec-WebSocket-Version` header is missing or unsupported. This endpoint only supports version `13`"
+)]
+#[non_exhaustive]
+/// The `Sec-WebSocket-Version` header is missing, or it's not set to `13`.
+pub struct UnsupportedWebSocketVersion;
+
+#[derive(Debug, thiserror::Error)]
+#[error("The `Sec-WebSocket-Key` header is missing, or it's not a base64-encoded 16-byte value")]
+#[non_exhaustive]
+/// The `Sec-WebSocket-Key` header is missing or invalid.
+pub struct InvalidWebSocketKey;
+
+#[derive(Debug, thiserror::Error)]
+#[error("This connection can't be upgraded to a WebSocket")]
+#[non_exhaustive]
+/// The handshake is valid, but the server can't upgrade the connection.
+///
+/// Upgrades require the server to be configured with
+/// [`HttpProtocol::Auto`](crate::server::HttpProtocol::Auto), the default.
+pub struct UpgradeUnavailable;
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+/// The error returned by [`WebSocket`](super::WebSocket) when sending or receiving messages.
+pub enum WebSocketError {
+    #[error("Failed to read from or write to the underlying connection")]
+    /// An I/O error occurred on the underlying connection.
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    /// See [`ProtocolViolation`] for details.
+    ProtocolViolation(#[from] ProtocolViolation),
+    #[error(transparent)]
+    /// See [`MessageTooLarge`] for details.
+    MessageTooLarge(#[from] MessageTooLarge),
+    #[error("A text message from the client isn't valid UTF-8")]
+    /// A text message, or the reason of a close frame, isn't valid UTF-8.
+    InvalidUtf8,
+    #[error("The payload of control messages (ping, pong and close) can't exceed 125 bytes")]
+    /// A control message is too large to be sent.
+    ControlPayloadTooLarge,
+    #[error("The WebSocket connection is closing, no more messages can be sent")]
+    /// A close message has already been sent.
+    Closed,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("The client violated the WebSocket protocol: {reason}")]
+#[non_exhaustive]
+/// The client sent a frame that doesn't comply with [RFC 6455](https://datatracker.ietf.org/doc/html/rfc6455).
+pub struct ProtocolViolation {
+    pub(super) reason: &'static str,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "The client sent a message larger than {max_size} bytes, the maximum size allowed for this WebSocket"
+)]
+#[non_exhaustive]
+/// The client sent a message larger than the configured limit.
+///
+/// Check out [`WebSocketUpgrade::set_max_message_size`](super::WebSocketUpgrade::set_max_message_size)
+/// to change it.
+pub struct MessageTooLarge {
+    pub(super) max_size: usize,
+}
@@ -0,0 +1,226 @@
+//! Encoding and decoding of WebSocket frames, as specified in
+//! [RFC 6455](https://datatracker.ietf.org/doc/html/rfc6455#section-5.2).
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use super::errors::{MessageTooLarge, ProtocolViolation, WebSocketError};
+use super::message::CloseFrame;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum OpCode {
+    Continuation = 0x0,
+    Text = 0x1,
+    Binary = 0x2,
+    Close = 0x8,
+    Ping = 0x9,
+    Pong = 0xA,
+}
+
+impl OpCode {
+    fn is_control(self) -> bool {
+        (self as u8) & 0x8 != 0
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct Frame {
+    pub(super) fin: bool,
+    pub(super) opcode: OpCode,
+    pub(super) payload: Bytes,
+}
+
+fn violation(reason: &'static str) -> WebSocketError {
+    ProtocolViolation { reason }.into()
+}
+
+/// Decode the frame at the beginning of `buf`, if it has been received in its entirety.
+///
+/// Frames sent by the client must be masked, the payload is unmasked.
+pub(super) fn decode(
+    buf: &mut BytesMut,
+    max_payload_size: usize,
+) -> Result<Option<Frame>, WebSocketError> {
+    if buf.len() < 2 {
+        return Ok(None);
+    }
+    let (first, second) = (buf[0], buf[1]);
+    if first & 0x70 != 0 {
+        return Err(violation(
+            "reserved bits must be unset, since no extension was negotiated",
+        ));
+    }
+    let fin = first & 0x80 != 0;
+    let opcode = match first & 0x0F {
+        0x0 => OpCode::Continuation,
+        0x1 => OpCode::Text,
+        0x2 => OpCode::Binary,
+        0x8 => OpCode::Close,
+        0x9 => OpCode::Ping,
+        0xA => OpCode::Pong,
+        _ => return Err(violation("unknown opcode")),
+    };
+    if second & 0x80 == 0 {
+        return Err(violation("frames sent by the client must be masked"));
+    }
+    let (payload_len, offset) = match second & 0x7F {
+        126 => {
+            if buf.len() < 4 {
+                return Ok(None);
+            }
+            (u16::from_be_bytes([buf[2], buf[3]]) as u64, 4)
+        }
+        127 => {
+            if buf.len() < 10 {
+                return Ok(None);
+            }
+            let len = u64::from_be_bytes(buf[2..10].try_into().unwrap());
+            (len, 10)
+        }
+        len => (len as u64, 2),
+    };
+    if opcode.is_control() {
+        if !fin {
+            return Err(violation("control frames must not be fragmented"));
+        }
+        if payload_len > 125 {
+            return Err(violation(
+                "control frames can't have a payload larger than 125 bytes",
+            ));
+        }
+    }
+    if payload_len > max_payload_size as u64 {
+        return Err(MessageTooLarge {
+            max_size: max_payload_size,
+        }
+        .into());
+    }
+    let payload_len = payload_len as usize;
+    let header_len = offset + 4;
+    if buf.len() < header_len + payload_len {
+        // We don't reserve room for the whole payload upfront: the buffer grows as data
+        // arrives, otherwise a bare header would be enough to make us allocate up to
+        // `max_payload_size` bytes.
+        return Ok(None);
+    }
+    let mask: [u8; 4] = buf[offset..header_len].try_into().unwrap();
+    buf.advance(header_len);
+    let mut payload = buf.split_to(payload_len);
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[i % 4];
+    }
+    Ok(Some(Frame {
+        fin,
+        opcode,
+        payload: payload.freeze(),
+    }))
+}
+
+/// Append an unfragmented frame to `buf`.
+///
+/// Frames sent by the server are never masked.
+pub(super) fn encode(opcode: OpCode, payload: &[u8], buf: &mut BytesMut) {
+    buf.reserve(10 + payload.len());
+    buf.put_u8(0x80 | opcode as u8);
+    match payload.len() {
+        len if len < 126 => buf.put_u8(len as u8),
+        len if len <= u16::MAX as usize => {
+            buf.put_u8(126);
+            buf.put_u16(len as u16);
+        }
+        len => {
+            buf.put_u8(127);
+            buf.put_u64(len as u64);
+        }
+    }
+    buf.put_slice(payload);
+}
+
+/// Append a close frame to `buf`.
+pub(super) fn encode_close(frame: Option<&CloseFrame>, buf: &mut BytesMut) {
+    let mut payload = Vec::new();
+    if let Some(frame) = frame {
+        payload.extend_from_slice(&frame.code.0.to_be_bytes());
+        payload.extend_from_slice(frame.reason.as_bytes());
+    }
+    encode(OpCode::Close, &payload, buf);
+}
+
+/// Encode a frame as a client would, i.e. masked.
+#[cfg(test)]
+pub(super) fn client_frame(first: u8, payload: &[u8]) -> BytesMut {
+    let mask = [0x37, 0xfa, 0x21, 0x3d];
+    let mut buf = BytesMut::new();
+    buf.put_u8(first);
+    match payload.len() {
+        len if len < 126 => buf.put_u8(0x80 | len as u8),
+        len => {
+            buf.put_u8(0x80 | 126);
+            buf.put_u16(len as u16);
+        }
+    }
+    buf.put_slice(&mask);
+    buf.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masked_frames_are_decoded() {
+        // The example from RFC 6455, section 5.7.
+        let mut buf = BytesMut::from(
+            &[
+                0x81, 0x85, 0x37, 0xfa, 0x21, 0x3d, 0x7f, 0x9f, 0x4d, 0x51, 0x58,
+            ][..],
+        );
+        let frame = decode(&mut buf, 1024).unwrap().unwrap();
+        assert!(frame.fin);
+        assert_eq!(frame.opcode, OpCode::Text);
+        assert_eq!(frame.payload, "Hello");
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn partial_frames_are_not_decoded() {
+        let frame = client_frame(0x82, &[7; 300]);
+        let mut buf = BytesMut::from(&frame[..frame.len() - 1]);
+        assert!(decode(&mut buf, 1024).unwrap().is_none());
+        buf.extend_from_slice(&frame[frame.len() - 1..]);
+        let frame = decode(&mut buf, 1024).unwrap().unwrap();
+        assert_eq!(frame.payload.len(), 300);
+    }
+
+    #[test]
+    fn the_payload_size_is_not_reserved_upfront() {
+        // A binary frame that announces a 1 MiB payload, without sending it.
+        let mut buf = BytesMut::from(&[0x82, 0x80 | 127][..]);
+        buf.put_u64(1024 * 1024);
+        buf.put_slice(&[0x37, 0xfa, 0x21, 0x3d]);
+        assert!(decode(&mut buf, 64 * 1024 * 1024).unwrap().is_none());
+        assert!(buf.capacity() < 1024);
+    }
+
+    #[test]
+    fn invalid_frames_are_rejected() {
+        let mut unmasked = BytesMut::from(&[0x81, 0x00][..]);
+        let err = decode(&mut unmasked, 1024).unwrap_err();
+        insta::assert_snapshot!(err, @"The client violated the WebSocket protocol: frames sent by the client must be masked");
+
+        let mut fragmented_ping = client_frame(0x09, b"");
+        let err = decode(&mut fragmented_ping, 1024).unwrap_err();
+        insta::assert_snapshot!(err, @"The client violated the WebSocket protocol: control frames must not be fragmented");
+
+        let mut too_large = client_frame(0x82, &[0; 200]);
+        let err = decode(&mut too_large, 100).unwrap_err();
+        insta::assert_snapshot!(err, @"The client sent a message larger than 100 bytes, the maximum size allowed for this WebSocket");
+    }
+
+    #[test]
+    fn server_frames_are_not_masked() {
+        let mut buf = BytesMut::new();
+        encode(OpCode::Binary, &[1; 200], &mut buf);
+        assert_eq!(&buf[..4], &[0x82, 126, 0, 200]);
+        assert_eq!(buf.len(), 204);
+    }
+}
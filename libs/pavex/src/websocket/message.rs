@@ -0,0 +1,89 @@
+use bytes::Bytes;
+
+/// A message exchanged over a [`WebSocket`](super::WebSocket).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// A UTF-8 encoded text message.
+    Text(String),
+    /// A binary message.
+    Binary(Bytes),
+    /// A ping.
+    ///
+    /// [`WebSocket`](super::WebSocket) replies to the client's pings on its own.
+    Ping(Bytes),
+    /// A pong, in response to a ping.
+    Pong(Bytes),
+    /// The beginning of the closing handshake.
+    ///
+    /// [`WebSocket`](super::WebSocket) acknowledges the client's close messages on its own.
+    Close(Option<CloseFrame>),
+}
+
+impl From<String> for Message {
+    fn from(text: String) -> Self {
+        Message::Text(text)
+    }
+}
+
+impl From<&str> for Message {
+    fn from(text: &str) -> Self {
+        Message::Text(text.to_owned())
+    }
+}
+
+impl From<Bytes> for Message {
+    fn from(data: Bytes) -> Self {
+        Message::Binary(data)
+    }
+}
+
+impl From<Vec<u8>> for Message {
+    fn from(data: Vec<u8>) -> Self {
+        Message::Binary(data.into())
+    }
+}
+
+/// The status code and the reason attached to a close [`Message`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloseFrame {
+    /// Why the connection is being closed.
+    pub code: CloseCode,
+    /// A human-readable explanation.
+    ///
+    /// It can't be longer than 123 bytes.
+    pub reason: String,
+}
+
+/// The status code of a [`CloseFrame`].
+///
+/// Check out [RFC 6455](https://datatracker.ietf.org/doc/html/rfc6455#section-7.4.1) for
+/// the full list of codes.
+/// Codes in the 4000-4999 range are reserved for private use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CloseCode(pub u16);
+
+impl CloseCode {
+    /// The purpose for which the connection was established has been fulfilled.
+    pub const NORMAL: CloseCode = CloseCode(1000);
+    /// The server is going down, or the client navigated away from the page.
+    pub const GOING_AWAY: CloseCode = CloseCode(1001);
+    /// A protocol error was detected.
+    pub const PROTOCOL_ERROR: CloseCode = CloseCode(1002);
+    /// A message was received in a format that can't be accepted.
+    pub const UNSUPPORTED_DATA: CloseCode = CloseCode(1003);
+    /// The data within a message isn't consistent with its type—e.g. invalid UTF-8 in a text message.
+    pub const INVALID_PAYLOAD: CloseCode = CloseCode(1007);
+    /// A message violated a policy of the endpoint.
+    pub const POLICY_VIOLATION: CloseCode = CloseCode(1008);
+    /// A message was too large to be processed.
+    pub const MESSAGE_TOO_BIG: CloseCode = CloseCode(1009);
+    /// The endpoint encountered an unexpected condition.
+    pub const INTERNAL_ERROR: CloseCode = CloseCode(1011);
+
+    /// Returns `true` if the code can be sent over the wire.
+    ///
+    /// Some codes are reserved by the protocol—e.g. `1005`, which stands for "no status code".
+    pub fn is_allowed(self) -> bool {
+        matches!(self.0, 1000..=1003 | 1007..=1014 | 3000..=4999)
+    }
+}
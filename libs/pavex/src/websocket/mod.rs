@@ -0,0 +1,51 @@
+//! Exchange messages with the client over [WebSockets](https://datatracker.ietf.org/doc/html/rfc6455).
+//!
+//! Inject [`WebSocketUpgrade`] in your request handler to validate the handshake,
+//! then return the response built by [`WebSocketUpgrade::on_upgrade`]:
+//! your callback is invoked with a [`WebSocket`] once the connection has been upgraded.
+//!
+//! It's only available if the `websocket` feature is enabled.
+//!
+//! # Example
+//!
+//! ```rust
+//! use pavex::response::Response;
+//! use pavex::websocket::{Message, WebSocketUpgrade};
+//!
+//! // Echo back every text message.
+//! pub fn echo(upgrade: WebSocketUpgrade) -> Response {
+//!     upgrade.on_upgrade(|mut socket| async move {
+//!         while let Some(Ok(message)) = socket.recv().await {
+//!             if let Message::Text(text) = message {
+//!                 if socket.send(text).await.is_err() {
+//!                     break;
+//!                 }
+//!             }
+//!         }
+//!     })
+//! }
+//! ```
+//!
+//! # Requirements
+//!
+//! WebSocket connections are upgraded from HTTP/1.1 requests.
+//! The [`Server`](crate::server::Server) must be configured with
+//! [`HttpProtocol::Auto`](crate::server::HttpProtocol::Auto), the default:
+//! the handshake is rejected otherwise.
+//!
+//! # Graceful shutdown
+//!
+//! Upgraded connections are tracked by the server: a graceful shutdown waits for your
+//! callback to return.
+//! When the server starts shutting down, [`WebSocket`] sends a close message with the
+//! [`GOING_AWAY`](CloseCode::GOING_AWAY) status code and ends its stream of messages,
+//! giving your callback a chance to wrap up.
+pub mod errors;
+mod frame;
+mod message;
+mod socket;
+mod upgrade;
+
+pub use message::{CloseCode, CloseFrame, Message};
+pub use socket::WebSocket;
+pub use upgrade::WebSocketUpgrade;
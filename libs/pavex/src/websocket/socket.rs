@@ -0,0 +1,510 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use bytes::{Buf, Bytes, BytesMut};
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use super::errors::{MessageTooLarge, ProtocolViolation, WebSocketError};
+use super::frame::{self, Frame, OpCode};
+use super::message::{CloseCode, CloseFrame, Message};
+use crate::server::drain_signal::DrainSignal;
+
+/// How many bytes we try to read from the connection at once.
+const READ_CHUNK_SIZE: usize = 8 * 1024;
+/// Outgoing messages are flushed before accepting new ones once the write buffer
+/// reaches this size.
+const WRITE_BUFFER_HIGH_WATER_MARK: usize = 64 * 1024;
+
+trait Io: AsyncRead + AsyncWrite + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Send> Io for T {}
+
+type ShutdownFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// A WebSocket connection, upgraded from an HTTP request via
+/// [`WebSocketUpgrade::on_upgrade`](super::WebSocketUpgrade::on_upgrade).
+///
+/// It's a [`Stream`] of the [`Message`]s sent by the client and a [`Sink`] for the
+/// messages you want to send back—use [`StreamExt::split`] if you want to handle
+/// the two directions in separate tasks.
+/// [`WebSocket::recv`] and [`WebSocket::send`] are available as shortcuts.
+///
+/// # Protocol handling
+///
+/// Pings are answered with pongs, and close messages are acknowledged, on your behalf.
+/// Both are still yielded by the stream, in case you want to act on them.
+///
+/// The stream ends when the closing handshake is complete, or when the connection is closed.
+/// If the client violates the protocol, the stream yields an error and the connection is
+/// closed with the appropriate status code.
+pub struct WebSocket {
+    io: Pin<Box<dyn Io>>,
+    read_buf: BytesMut,
+    write_buf: BytesMut,
+    /// The message that's being assembled, if it was fragmented by the client.
+    fragments: Option<(OpCode, BytesMut)>,
+    max_message_size: usize,
+    protocol: Option<String>,
+    shutdown: Option<ShutdownFuture>,
+    close_sent: bool,
+    /// The stream ends as soon as the pending frames have been written.
+    closing: bool,
+    terminated: bool,
+}
+
+impl std::fmt::Debug for WebSocket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebSocket")
+            .field("protocol", &self.protocol)
+            .field("max_message_size", &self.max_message_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WebSocket {
+    pub(super) fn new<I>(
+        io: I,
+        protocol: Option<String>,
+        max_message_size: usize,
+        drain_signal: Option<DrainSignal>,
+    ) -> Self
+    where
+        I: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        Self {
+            io: Box::pin(io),
+            read_buf: BytesMut::new(),
+            write_buf: BytesMut::new(),
+            fragments: None,
+            max_message_size,
+            protocol,
+            shutdown: drain_signal.map(|signal| Box::pin(signal.triggered()) as ShutdownFuture),
+            close_sent: false,
+            closing: false,
+            terminated: false,
+        }
+    }
+
+    /// The subprotocol that was agreed upon during the handshake, if any.
+    ///
+    /// Check out [`WebSocketUpgrade::set_protocol`](super::WebSocketUpgrade::set_protocol)
+    /// for more details.
+    pub fn protocol(&self) -> Option<&str> {
+        self.protocol.as_deref()
+    }
+
+    /// Wait for the next message from the client.
+    ///
+    /// It returns `None` once the connection has been closed.
+    pub async fn recv(&mut self) -> Option<Result<Message, WebSocketError>> {
+        StreamExt::next(self).await
+    }
+
+    /// Send a message to the client.
+    ///
+    /// It waits until the message has been written to the connection.
+    pub async fn send(&mut self, message: impl Into<Message>) -> Result<(), WebSocketError> {
+        SinkExt::send(self, message.into()).await
+    }
+
+    /// Write the pending frames to the connection, then flush it.
+    fn poll_write_buf(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        while !self.write_buf.is_empty() {
+            let n = ready!(self.io.as_mut().poll_write(cx, &self.write_buf))?;
+            if n == 0 {
+                return Poll::Ready(Err(std::io::ErrorKind::WriteZero.into()));
+            }
+            self.write_buf.advance(n);
+        }
+        self.io.as_mut().poll_flush(cx)
+    }
+
+    /// Read more data from the connection.
+    ///
+    /// It returns the number of bytes that have been read, `0` on EOF.
+    fn poll_read_buf(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<usize>> {
+        let filled = self.read_buf.len();
+        self.read_buf.resize(filled + READ_CHUNK_SIZE, 0);
+        let mut buf = ReadBuf::new(&mut self.read_buf[filled..]);
+        let outcome = self.io.as_mut().poll_read(cx, &mut buf);
+        let n = buf.filled().len();
+        self.read_buf.truncate(filled + n);
+        outcome.map_ok(|()| n)
+    }
+
+    /// Start the closing handshake, unless it's already underway.
+    fn queue_close(&mut self, frame: Option<&CloseFrame>) {
+        if !self.close_sent {
+            frame::encode_close(frame, &mut self.write_buf);
+            self.close_sent = true;
+        }
+    }
+
+    /// Close the connection with a status code that matches the error.
+    fn fail(&mut self, cx: &mut Context<'_>, e: WebSocketError) -> WebSocketError {
+        let code = match &e {
+            WebSocketError::MessageTooLarge(_) => Some(CloseCode::MESSAGE_TOO_BIG),
+            WebSocketError::InvalidUtf8 => Some(CloseCode::INVALID_PAYLOAD),
+            WebSocketError::ProtocolViolation(_) => Some(CloseCode::PROTOCOL_ERROR),
+            _ => None,
+        };
+        if let Some(code) = code {
+            self.queue_close(Some(&CloseFrame {
+                code,
+                reason: String::new(),
+            }));
+            // Best effort: we'll try again when the stream is polled next.
+            let _ = self.poll_write_buf(cx);
+            self.closing = true;
+        } else {
+            self.terminated = true;
+        }
+        e
+    }
+
+    /// Process a frame sent by the client.
+    ///
+    /// It returns a message if the frame completes one.
+    fn on_frame(&mut self, frame: Frame) -> Result<Option<Message>, WebSocketError> {
+        let Frame {
+            fin,
+            opcode,
+            payload,
+        } = frame;
+        match opcode {
+            OpCode::Text | OpCode::Binary => {
+                if self.fragments.is_some() {
+                    return Err(ProtocolViolation {
+                        reason: "a new message started before the previous one was complete",
+                    }
+                    .into());
+                }
+                if fin {
+                    return data_message(opcode, payload).map(Some);
+                }
+                self.fragments = Some((opcode, BytesMut::from(payload)));
+                Ok(None)
+            }
+            OpCode::Continuation => {
+                let Some((_, buffer)) = &mut self.fragments else {
+                    return Err(ProtocolViolation {
+                        reason: "a continuation frame was sent without a message to continue",
+                    }
+                    .into());
+                };
+                if buffer.len() + payload.len() > self.max_message_size {
+                    return Err(MessageTooLarge {
+                        max_size: self.max_message_size,
+                    }
+                    .into());
+                }
+                buffer.extend_from_slice(&payload);
+                if !fin {
+                    return Ok(None);
+                }
+                let (opcode, buffer) = self.fragments.take().unwrap();
+                data_message(opcode, buffer.freeze()).map(Some)
+            }
+            OpCode::Ping => {
+                if !self.close_sent {
+                    frame::encode(OpCode::Pong, &payload, &mut self.write_buf);
+                }
+                Ok(Some(Message::Ping(payload)))
+            }
+            OpCode::Pong => Ok(Some(Message::Pong(payload))),
+            OpCode::Close => {
+                let close = parse_close(&payload)?;
+                // We echo the status code, as recommended by RFC 6455.
+                self.queue_close(
+                    close
+                        .as_ref()
+                        .map(|close| CloseFrame {
+                            code: close.code,
+                            reason: String::new(),
+                        })
+                        .as_ref(),
+                );
+                self.closing = true;
+                Ok(Some(Message::Close(close)))
+            }
+        }
+    }
+}
+
+fn data_message(opcode: OpCode, payload: Bytes) -> Result<Message, WebSocketError> {
+    if opcode == OpCode::Text {
+        let text = String::from_utf8(payload.into()).map_err(|_| WebSocketError::InvalidUtf8)?;
+        Ok(Message::Text(text))
+    } else {
+        Ok(Message::Binary(payload))
+    }
+}
+
+fn parse_close(payload: &[u8]) -> Result<Option<CloseFrame>, WebSocketError> {
+    let (code, reason) = match payload {
+        [] => return Ok(None),
+        [_] => {
+            return Err(ProtocolViolation {
+                reason: "the payload of a close frame can't be a single byte",
+            }
+            .into());
+        }
+        [first, second, reason @ ..] => (CloseCode(u16::from_be_bytes([*first, *second])), reason),
+    };
+    if !code.is_allowed() {
+        return Err(ProtocolViolation {
+            reason: "the close frame uses a reserved status code",
+        }
+        .into());
+    }
+    let reason = std::str::from_utf8(reason).map_err(|_| WebSocketError::InvalidUtf8)?;
+    Ok(Some(CloseFrame {
+        code,
+        reason: reason.to_owned(),
+    }))
+}
+
+impl Stream for WebSocket {
+    type Item = Result<Message, WebSocketError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if this.terminated {
+                return Poll::Ready(None);
+            }
+            if !this.write_buf.is_empty() {
+                match this.poll_write_buf(cx) {
+                    Poll::Ready(Ok(())) => {}
+                    Poll::Ready(Err(e)) => {
+                        this.terminated = true;
+                        return Poll::Ready(Some(Err(e.into())));
+                    }
+                    // Pongs can wait, but the closing frame must be written
+                    // before the stream ends.
+                    Poll::Pending if this.closing => return Poll::Pending,
+                    // Every ping we read queues a pong: we stop reading if the client
+                    // isn't reading our frames, otherwise the write buffer would grow
+                    // without bounds.
+                    Poll::Pending if this.write_buf.len() >= WRITE_BUFFER_HIGH_WATER_MARK => {
+                        return Poll::Pending;
+                    }
+                    Poll::Pending => {}
+                }
+            }
+            if this.closing {
+                this.terminated = true;
+                continue;
+            }
+            if this
+                .shutdown
+                .as_mut()
+                .is_some_and(|shutdown| shutdown.as_mut().poll(cx).is_ready())
+            {
+                this.shutdown = None;
+                this.queue_close(Some(&CloseFrame {
+                    code: CloseCode::GOING_AWAY,
+                    reason: String::new(),
+                }));
+                this.closing = true;
+                continue;
+            }
+            let frame = match frame::decode(&mut this.read_buf, this.max_message_size) {
+                Ok(Some(frame)) => frame,
+                Ok(None) => match ready!(this.poll_read_buf(cx)) {
+                    Ok(0) => {
+                        this.terminated = true;
+                        return Poll::Ready(None);
+                    }
+                    Ok(_) => continue,
+                    Err(e) => {
+                        this.terminated = true;
+                        return Poll::Ready(Some(Err(e.into())));
+                    }
+                },
+                Err(e) => return Poll::Ready(Some(Err(this.fail(cx, e)))),
+            };
+            match this.on_frame(frame) {
+                Ok(Some(message)) => {
+                    if !this.write_buf.is_empty() {
+                        // Best effort: we'll try again when the stream is polled next.
+                        let _ = this.poll_write_buf(cx);
+                    }
+                    return Poll::Ready(Some(Ok(message)));
+                }
+                Ok(None) => continue,
+                Err(e) => return Poll::Ready(Some(Err(this.fail(cx, e)))),
+            }
+        }
+    }
+}
+
+impl Sink<Message> for WebSocket {
+    type Error = WebSocketError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if self.write_buf.len() >= WRITE_BUFFER_HIGH_WATER_MARK {
+            ready!(self.poll_write_buf(cx))?;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, message: Message) -> Result<(), Self::Error> {
+        if self.close_sent {
+            return Err(WebSocketError::Closed);
+        }
+        let buf = &mut self.write_buf;
+        match message {
+            Message::Text(text) => frame::encode(OpCode::Text, text.as_bytes(), buf),
+            Message::Binary(data) => frame::encode(OpCode::Binary, &data, buf),
+            Message::Ping(data) | Message::Pong(data) if data.len() > 125 => {
+                return Err(WebSocketError::ControlPayloadTooLarge);
+            }
+            Message::Ping(data) => frame::encode(OpCode::Ping, &data, buf),
+            Message::Pong(data) => frame::encode(OpCode::Pong, &data, buf),
+            Message::Close(close) => {
+                if close.as_ref().is_some_and(|close| close.reason.len() > 123) {
+                    return Err(WebSocketError::ControlPayloadTooLarge);
+                }
+                self.queue_close(close.as_ref());
+            }
+        }
+        Ok(())
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.poll_write_buf(cx).map_err(Into::into)
+    }
+
+    /// Send a close message with the [`NORMAL`](CloseCode::NORMAL) status code,
+    /// unless one has already been sent, then close the connection.
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.queue_close(Some(&CloseFrame {
+            code: CloseCode::NORMAL,
+            reason: String::new(),
+        }));
+        ready!(self.poll_write_buf(cx))?;
+        self.io.as_mut().poll_shutdown(cx).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt, DuplexStream};
+
+    use super::*;
+    use crate::server::drain_signal::drain_signal;
+    use crate::websocket::frame::client_frame;
+
+    fn socket(drain_signal: Option<DrainSignal>) -> (WebSocket, DuplexStream) {
+        let (server, client) = tokio::io::duplex(64 * 1024);
+        (WebSocket::new(server, None, 1024, drain_signal), client)
+    }
+
+    async fn read_frame(client: &mut DuplexStream) -> Vec<u8> {
+        let mut header = [0; 2];
+        client.read_exact(&mut header).await.unwrap();
+        let mut payload = vec![0; header[1] as usize];
+        client.read_exact(&mut payload).await.unwrap();
+        [&header[..], &payload].concat()
+    }
+
+    #[tokio::test]
+    async fn fragmented_messages_are_reassembled() {
+        let (mut socket, mut client) = socket(None);
+        client.write_all(&client_frame(0x01, b"Hel")).await.unwrap();
+        // Control frames can be interleaved with the fragments of a message.
+        client
+            .write_all(&client_frame(0x89, b"ping"))
+            .await
+            .unwrap();
+        client.write_all(&client_frame(0x80, b"lo")).await.unwrap();
+
+        assert_eq!(
+            socket.recv().await.unwrap().unwrap(),
+            Message::Ping("ping".into())
+        );
+        assert_eq!(
+            socket.recv().await.unwrap().unwrap(),
+            Message::Text("Hello".into())
+        );
+        // The ping has been answered.
+        assert_eq!(read_frame(&mut client).await, b"\x8a\x04ping");
+
+        socket.send("Hi!").await.unwrap();
+        assert_eq!(read_frame(&mut client).await, b"\x81\x03Hi!");
+    }
+
+    #[tokio::test]
+    async fn the_closing_handshake_ends_the_stream() {
+        let (mut socket, mut client) = socket(None);
+        client
+            .write_all(&client_frame(0x88, b"\x03\xe8bye"))
+            .await
+            .unwrap();
+
+        let close = socket.recv().await.unwrap().unwrap();
+        assert_eq!(
+            close,
+            Message::Close(Some(CloseFrame {
+                code: CloseCode::NORMAL,
+                reason: "bye".into()
+            }))
+        );
+        assert!(socket.recv().await.is_none());
+        assert_eq!(read_frame(&mut client).await, b"\x88\x02\x03\xe8");
+        assert!(matches!(
+            socket.send("too late").await,
+            Err(WebSocketError::Closed)
+        ));
+    }
+
+    #[tokio::test]
+    async fn invalid_text_messages_close_the_connection() {
+        let (mut socket, mut client) = socket(None);
+        client
+            .write_all(&client_frame(0x81, &[0xff]))
+            .await
+            .unwrap();
+
+        let err = socket.recv().await.unwrap().unwrap_err();
+        insta::assert_snapshot!(err, @"A text message from the client isn't valid UTF-8");
+        assert!(socket.recv().await.is_none());
+        // 1007, invalid payload.
+        assert_eq!(read_frame(&mut client).await, b"\x88\x02\x03\xef");
+    }
+
+    #[tokio::test]
+    async fn reading_stops_if_the_client_does_not_read_its_pongs() {
+        let (mut socket, mut client) = socket(None);
+        let ping = client_frame(0x89, &[0; 125]);
+        // Keep pinging, without ever reading the pongs.
+        tokio::spawn(async move { while client.write_all(&ping).await.is_ok() {} });
+
+        // The pongs for 10k pings are way more than what the connection and
+        // the write buffer can hold.
+        for _ in 0..10_000 {
+            let next = tokio::time::timeout(std::time::Duration::from_millis(100), socket.recv());
+            let Ok(message) = next.await else {
+                // The socket stopped reading from the connection.
+                assert!(socket.write_buf.len() < WRITE_BUFFER_HIGH_WATER_MARK + 256);
+                return;
+            };
+            assert!(matches!(message, Some(Ok(Message::Ping(_)))));
+        }
+        panic!("The socket kept reading pings, even if the client wasn't reading the pongs");
+    }
+
+    #[tokio::test]
+    async fn the_connection_is_closed_on_shutdown() {
+        let (trigger, signal) = drain_signal();
+        let (mut socket, mut client) = socket(Some(signal));
+
+        trigger.trigger();
+        assert!(socket.recv().await.is_none());
+        // 1001, going away.
+        assert_eq!(read_frame(&mut client).await, b"\x88\x02\x03\xe9");
+    }
+}
@@ -0,0 +1,312 @@
+use std::future::Future;
+
+use base64::Engine;
+use http::header::{
+    CONNECTION, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY, SEC_WEBSOCKET_PROTOCOL,
+    SEC_WEBSOCKET_VERSION, UPGRADE,
+};
+use http::{HeaderMap, HeaderName, HeaderValue, Method, Version};
+use hyper::upgrade::OnUpgrade;
+use hyper_util::rt::TokioIo;
+use pavex_macros::request_scoped;
+use sha1::{Digest, Sha1};
+use tracing_log_error::log_error;
+use ubyte::{ByteUnit, ToByteUnit};
+
+use super::WebSocket;
+use super::errors::{
+    InvalidWebSocketKey, MissingConnectionUpgrade, MissingUpgradeHeader, NotAGetRequest,
+    UnsupportedHttpVersion, UnsupportedWebSocketVersion, UpgradeUnavailable, WebSocketUpgradeError,
+};
+use crate::request::RequestHead;
+use crate::response::Response;
+use crate::server::drain_signal::DrainSignal;
+use crate::server::upgrade::{PendingUpgrade, register_session, take_on_upgrade};
+
+/// The GUID that's appended to the client key to compute `Sec-WebSocket-Accept`.
+const WEBSOCKET_GUID: &[u8] = b"258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// A valid WebSocket handshake, ready to be accepted.
+///
+/// Return the response built by [`WebSocketUpgrade::on_upgrade`] from your request handler
+/// to upgrade the connection.
+///
+/// Check out the [module-level documentation](super) for an example.
+#[derive(Debug)]
+pub struct WebSocketUpgrade {
+    accept_key: HeaderValue,
+    requested_protocols: Vec<String>,
+    protocol: Option<String>,
+    max_message_size: ByteUnit,
+    /// `None` if the request isn't being handled by a [`Server`](crate::server::Server).
+    on_upgrade: Option<OnUpgrade>,
+}
+
+impl WebSocketUpgrade {
+    /// The default constructor for [`WebSocketUpgrade`].
+    ///
+    /// The extraction fails if:
+    ///
+    /// - the request doesn't use the `GET` method, over HTTP/1.1
+    /// - the `Upgrade` header doesn't include `websocket`
+    /// - the `Connection` header doesn't include `upgrade`
+    /// - the `Sec-WebSocket-Version` header isn't set to `13`
+    /// - the `Sec-WebSocket-Key` header is missing or invalid
+    /// - the connection can't be upgraded
+    ///
+    /// In all of the above cases, a [`WebSocketUpgradeError`] is returned.
+    #[request_scoped(
+        error_handler = "crate::websocket::errors::WebSocketUpgradeError::into_response"
+    )]
+    pub fn extract(request_head: &RequestHead) -> Result<Self, WebSocketUpgradeError> {
+        let headers = &request_head.headers;
+        if request_head.method != Method::GET {
+            return Err(NotAGetRequest {
+                method: request_head.method.clone(),
+            }
+            .into());
+        }
+        if request_head.version != Version::HTTP_11 {
+            return Err(UnsupportedHttpVersion {
+                version: request_head.version,
+            }
+            .into());
+        }
+        if !tokens(headers, UPGRADE).any(|t| t.eq_ignore_ascii_case("websocket")) {
+            return Err(MissingUpgradeHeader.into());
+        }
+        if !tokens(headers, CONNECTION).any(|t| t.eq_ignore_ascii_case("upgrade")) {
+            return Err(MissingConnectionUpgrade.into());
+        }
+        if headers.get(SEC_WEBSOCKET_VERSION).is_none_or(|v| v != "13") {
+            return Err(UnsupportedWebSocketVersion.into());
+        }
+        let Some(key) = headers.get(SEC_WEBSOCKET_KEY).filter(|key| {
+            base64::engine::general_purpose::STANDARD
+                .decode(key.as_bytes())
+                .is_ok_and(|nonce| nonce.len() == 16)
+        }) else {
+            return Err(InvalidWebSocketKey.into());
+        };
+        let on_upgrade = match take_on_upgrade() {
+            PendingUpgrade::Available(on_upgrade) => Some(on_upgrade),
+            PendingUpgrade::Unavailable => return Err(UpgradeUnavailable.into()),
+            PendingUpgrade::NoServer => None,
+        };
+        Ok(Self {
+            accept_key: accept_key(key.as_bytes()),
+            requested_protocols: tokens(headers, SEC_WEBSOCKET_PROTOCOL)
+                .map(ToOwned::to_owned)
+                .collect(),
+            protocol: None,
+            max_message_size: 64.mebibytes(),
+            on_upgrade,
+        })
+    }
+
+    /// The subprotocols requested by the client via the `Sec-WebSocket-Protocol` header,
+    /// in order of preference.
+    pub fn requested_protocols(&self) -> &[String] {
+        &self.requested_protocols
+    }
+
+    /// Pick the subprotocol that will be used over the connection.
+    ///
+    /// No subprotocol is selected by default.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `protocol` isn't one of the [requested protocols](Self::requested_protocols).
+    pub fn set_protocol(mut self, protocol: Option<&str>) -> Self {
+        if let Some(protocol) = protocol {
+            assert!(
+                self.requested_protocols.iter().any(|p| p == protocol),
+                "`{protocol}` wasn't requested by the client"
+            );
+        }
+        self.protocol = protocol.map(ToOwned::to_owned);
+        self
+    }
+
+    /// The subprotocol that will be used over the connection, if any.
+    pub fn get_protocol(&self) -> Option<&str> {
+        self.protocol.as_deref()
+    }
+
+    /// Set the maximum size of the messages sent by the client.
+    ///
+    /// The connection is closed if a message exceeds the limit.
+    /// It defaults to 64 MiB.
+    pub fn set_max_message_size(mut self, size: ByteUnit) -> Self {
+        self.max_message_size = size;
+        self
+    }
+
+    /// The maximum size of the messages sent by the client.
+    pub fn get_max_message_size(&self) -> ByteUnit {
+        self.max_message_size
+    }
+
+    /// Accept the handshake.
+    ///
+    /// The returned `101 Switching Protocols` response must be returned by your request handler.
+    /// Once it has been sent, `callback` is invoked with the [`WebSocket`] for the upgraded
+    /// connection.
+    ///
+    /// `callback` is never invoked if the request isn't being handled by a
+    /// [`Server`](crate::server::Server)—e.g. when sent via a test client.
+    pub fn on_upgrade<F, Fut>(self, callback: F) -> Response
+    where
+        F: FnOnce(WebSocket) -> Fut + 'static,
+        Fut: Future<Output = ()> + 'static,
+    {
+        let Self {
+            accept_key,
+            protocol,
+            max_message_size,
+            on_upgrade,
+            requested_protocols: _,
+        } = self;
+        if let Some(on_upgrade) = on_upgrade {
+            let drain_signal = DrainSignal::current();
+            let protocol = protocol.clone();
+            // We saturate if the value doesn't fit, it's a limit after all.
+            let max_message_size = max_message_size.as_u64().try_into().unwrap_or(usize::MAX);
+            let session = async move {
+                let upgraded = match on_upgrade.await {
+                    Ok(upgraded) => upgraded,
+                    Err(e) => {
+                        log_error!(e, level: tracing::Level::WARN, "Failed to upgrade the connection to a WebSocket");
+                        return;
+                    }
+                };
+                let socket = WebSocket::new(
+                    TokioIo::new(upgraded),
+                    protocol,
+                    max_message_size,
+                    drain_signal,
+                );
+                callback(socket).await;
+            };
+            if !register_session(session) {
+                tracing::warn!(
+                    "`WebSocketUpgrade::on_upgrade` must be called while the request is being handled, the WebSocket connection will be dropped"
+                );
+            }
+        }
+        let response = Response::switching_protocols()
+            .insert_header(CONNECTION, HeaderValue::from_static("upgrade"))
+            .insert_header(UPGRADE, HeaderValue::from_static("websocket"))
+            .insert_header(SEC_WEBSOCKET_ACCEPT, accept_key);
+        match protocol {
+            Some(protocol) => response.insert_header(
+                SEC_WEBSOCKET_PROTOCOL,
+                // It was sent by the client, it's a valid header value.
+                HeaderValue::try_from(protocol).unwrap(),
+            ),
+            None => response,
+        }
+    }
+}
+
+/// The comma-separated tokens in all the values of the given header.
+fn tokens(headers: &HeaderMap, name: HeaderName) -> impl Iterator<Item = &str> {
+    headers
+        .get_all(name)
+        .into_iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
+
+/// Compute the value of the `Sec-WebSocket-Accept` header for the given client key.
+fn accept_key(key: &[u8]) -> HeaderValue {
+    let mut hasher = Sha1::new();
+    hasher.update(key);
+    hasher.update(WEBSOCKET_GUID);
+    let accept = base64::engine::general_purpose::STANDARD.encode(hasher.finalize());
+    HeaderValue::try_from(accept).expect("Base64 is always a valid header value")
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+
+    use super::*;
+
+    fn handshake() -> RequestHead {
+        let mut headers = HeaderMap::new();
+        headers.insert(UPGRADE, "websocket".parse().unwrap());
+        headers.insert(CONNECTION, "keep-alive, Upgrade".parse().unwrap());
+        headers.insert(SEC_WEBSOCKET_VERSION, "13".parse().unwrap());
+        // The example from RFC 6455, section 1.3.
+        headers.insert(
+            SEC_WEBSOCKET_KEY,
+            "dGhlIHNhbXBsZSBub25jZQ==".parse().unwrap(),
+        );
+        headers.insert(SEC_WEBSOCKET_PROTOCOL, "chat, superchat".parse().unwrap());
+        RequestHead {
+            method: Method::GET,
+            target: "/ws".parse().unwrap(),
+            version: Version::HTTP_11,
+            headers,
+        }
+    }
+
+    #[test]
+    fn the_handshake_is_accepted() {
+        let upgrade = WebSocketUpgrade::extract(&handshake()).unwrap();
+        assert_eq!(upgrade.requested_protocols(), ["chat", "superchat"]);
+
+        let response = upgrade.set_protocol(Some("chat")).on_upgrade(|_| async {});
+        assert_eq!(response.status(), StatusCode::SWITCHING_PROTOCOLS);
+        let headers = response.headers();
+        assert_eq!(
+            headers[SEC_WEBSOCKET_ACCEPT],
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+        assert_eq!(headers[SEC_WEBSOCKET_PROTOCOL], "chat");
+        assert_eq!(headers[UPGRADE], "websocket");
+    }
+
+    #[test]
+    fn invalid_handshakes_are_rejected() {
+        let mut request = handshake();
+        request.method = Method::POST;
+        let err = WebSocketUpgrade::extract(&request).unwrap_err();
+        insta::assert_snapshot!(err, @"WebSocket handshakes must use the `GET` method, but the request used `POST`");
+        assert_eq!(err.into_response().status(), StatusCode::METHOD_NOT_ALLOWED);
+
+        let mut request = handshake();
+        request.headers.remove(UPGRADE);
+        let err = WebSocketUpgrade::extract(&request).unwrap_err();
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::UPGRADE_REQUIRED);
+        assert_eq!(response.headers()[UPGRADE], "websocket");
+
+        let mut request = handshake();
+        request
+            .headers
+            .insert(SEC_WEBSOCKET_VERSION, "8".parse().unwrap());
+        let err = WebSocketUpgrade::extract(&request).unwrap_err();
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::UPGRADE_REQUIRED);
+        assert_eq!(response.headers()[SEC_WEBSOCKET_VERSION], "13");
+
+        let mut request = handshake();
+        request
+            .headers
+            .insert(SEC_WEBSOCKET_KEY, "c2hvcnQ=".parse().unwrap());
+        let err = WebSocketUpgrade::extract(&request).unwrap_err();
+        insta::assert_snapshot!(err, @"The `Sec-WebSocket-Key` header is missing, or it's not a base64-encoded 16-byte value");
+        assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    #[should_panic(expected = "`mqtt` wasn't requested by the client")]
+    fn only_requested_protocols_can_be_selected() {
+        let upgrade = WebSocketUpgrade::extract(&handshake()).unwrap();
+        let _ = upgrade.set_protocol(Some("mqtt"));
+    }
+}
@@ -1,12 +1,18 @@
 use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
 use std::time::Duration;
 
-use http::Request;
+use http::header::RETRY_AFTER;
+use http::{HeaderValue, Request};
 use hyper::body::Incoming;
 
 use pavex::connection::ConnectionInfo;
 use pavex::response::Response;
-use pavex::server::{IncomingStream, Server, ServerConfiguration, ShutdownMode};
+use pavex::server::{
+    DispatchStrategy, HttpProtocol, IncomingStream, Server, ServerConfiguration, ServerEvent,
+    ServerHandle, ShutdownMode,
+};
 
 // A dummy handler for our server tests.
 async fn test_handler(
@@ -78,6 +84,47 @@ async fn multi_bind() {
     std::net::TcpStream::connect(addr3).unwrap();
 }
 
+#[cfg(unix)]
+#[tokio::test]
+async fn unix_domain_socket() {
+    use hyper_util::rt::TokioIo;
+
+    async fn peer_handler(
+        _request: Request<Incoming>,
+        connection_info: Option<ConnectionInfo>,
+        _state: (),
+    ) -> Response {
+        let peer_addr = connection_info.unwrap().peer_addr().to_owned();
+        Response::ok().set_typed_body(peer_addr.to_string())
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pavex.sock");
+    Server::new()
+        .set_config(test_server_config())
+        .bind_unix(&path)
+        .await
+        .unwrap()
+        .serve(peer_handler, ());
+
+    // `reqwest` doesn't support Unix domain sockets, so we drop down to `hyper`'s client.
+    let stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+    let (mut sender, connection) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+        .await
+        .unwrap();
+    tokio::spawn(connection);
+    let request = Request::get("/")
+        .header("Host", "localhost")
+        .body(http_body_util::Empty::<bytes::Bytes>::new())
+        .unwrap();
+    let response = sender.send_request(request).await.unwrap();
+    assert!(response.status().is_success());
+
+    use http_body_util::BodyExt;
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(body, "unix:(unnamed)");
+}
+
 #[tokio::test]
 async fn serve() {
     let (incoming, addr) = test_incoming().await;
@@ -155,6 +202,57 @@ async fn graceful() {
     get_response.await.expect("The server shutdown without waiting for an ongoing connection to complete within the allocated timeout");
 }
 
+#[tokio::test]
+async fn new_connections_are_refused_while_draining() {
+    let (incoming, addr) = test_incoming().await;
+    let delay = Duration::from_secs(2);
+    let (mut has_started, state) = SlowHandlerState::new(delay);
+
+    let server_handle = Server::new()
+        .set_config(test_server_config())
+        .listen(incoming)
+        .serve(slow_handler, state);
+    let mut events = server_handle.events();
+
+    let get_response = tokio::task::spawn(async move {
+        let url = format!("http://localhost:{}", addr.port());
+        reqwest::get(url).await.unwrap().error_for_status().unwrap();
+    });
+    has_started.recv().await.unwrap();
+
+    // Start a graceful shutdown: the drain lasts until the slow request completes.
+    let shutdown_future = tokio::task::spawn(
+        server_handle
+            .clone()
+            .shutdown(ShutdownMode::Graceful { timeout: delay * 2 }),
+    );
+    while let Some(event) = events.next().await {
+        if event == ServerEvent::Draining {
+            break;
+        }
+    }
+
+    // The listening socket is closed asynchronously, shortly after the drain starts.
+    let refused = async {
+        loop {
+            match tokio::net::TcpStream::connect(addr).await {
+                Err(e) if e.kind() == std::io::ErrorKind::ConnectionRefused => break,
+                _ => tokio::time::sleep(Duration::from_millis(10)).await,
+            }
+        }
+    };
+    tokio::time::timeout(delay / 2, refused)
+        .await
+        .expect("New connections are still accepted while the server is draining");
+    assert!(
+        !get_response.is_finished(),
+        "The drain completed before we could check that new connections are refused"
+    );
+
+    shutdown_future.await.unwrap();
+    get_response.await.unwrap();
+}
+
 #[tokio::test]
 async fn forced() {
     let (incoming, addr) = test_incoming().await;
@@ -223,3 +321,581 @@ async fn graceful_but_too_fast() {
         "The server was supposed to shutdown forcefully the slow request, but it waited instead"
     );
 }
+
+#[tokio::test]
+async fn slow_clients_are_disconnected() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let (incoming, addr) = test_incoming().await;
+    Server::new()
+        .set_config(test_server_config().set_header_read_timeout(Some(Duration::from_millis(100))))
+        .listen(incoming)
+        .serve(test_handler, ());
+
+    // We never finish sending the request head.
+    let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    stream.write_all(b"GET / HTTP/1.1\r\n").await.unwrap();
+
+    let mut buf = Vec::new();
+    tokio::time::timeout(Duration::from_secs(2), stream.read_to_end(&mut buf))
+        .await
+        .expect("The server didn't close the connection")
+        .unwrap();
+}
+
+#[tokio::test]
+async fn idle_connections_are_closed() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let (incoming, addr) = test_incoming().await;
+    Server::new()
+        .set_config(test_server_config().set_keep_alive_timeout(Some(Duration::from_millis(100))))
+        .listen(incoming)
+        .serve(test_handler, ());
+
+    let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    stream
+        .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+
+    // The response is sent, then the connection is closed once it goes idle.
+    let mut buf = Vec::new();
+    tokio::time::timeout(Duration::from_secs(2), stream.read_to_end(&mut buf))
+        .await
+        .expect("The server didn't close the idle connection")
+        .unwrap();
+    assert!(buf.starts_with(b"HTTP/1.1 200 OK"));
+}
+
+// Sends its body in two chunks, with a pause in between.
+async fn slow_body_handler(
+    _request: Request<Incoming>,
+    _connection_info: Option<ConnectionInfo>,
+    _state: (),
+) -> Response {
+    use futures_util::StreamExt as _;
+
+    let chunks = futures_util::stream::unfold(0, |i| async move {
+        match i {
+            0 => Some(("first,", 1)),
+            1 => {
+                tokio::time::sleep(Duration::from_millis(300)).await;
+                Some(("second", 2))
+            }
+            _ => None,
+        }
+    })
+    .map(|chunk| {
+        Ok::<_, std::convert::Infallible>(http_body::Frame::data(bytes::Bytes::from_static(
+            chunk.as_bytes(),
+        )))
+    });
+    Response::ok().set_raw_body(http_body_util::StreamBody::new(chunks))
+}
+
+#[tokio::test]
+async fn connections_are_not_idle_while_streaming_a_response() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let (incoming, addr) = test_incoming().await;
+    Server::new()
+        .set_config(test_server_config().set_keep_alive_timeout(Some(Duration::from_millis(100))))
+        .listen(incoming)
+        .serve(slow_body_handler, ());
+
+    let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    stream
+        .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+
+    // The pause in the body is longer than the keep-alive timeout,
+    // but the connection must not be closed until the body is over.
+    let mut buf = Vec::new();
+    tokio::time::timeout(Duration::from_secs(2), stream.read_to_end(&mut buf))
+        .await
+        .expect("The server didn't close the connection")
+        .unwrap();
+    let response = String::from_utf8(buf).unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.contains("first,"), "{response}");
+    assert!(response.contains("second"), "{response}");
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn listeners_can_be_handed_off() {
+    let (incoming, _) = test_incoming().await;
+    let server_handle = Server::new()
+        .set_config(test_server_config())
+        .listen(incoming)
+        .serve(test_handler, ());
+
+    let handoff = server_handle.hand_off_listeners().await.unwrap();
+    assert_eq!(handoff.len(), 1);
+    let mut command = std::process::Command::new("sh");
+    command
+        .args([
+            "-c",
+            r#"echo "$LISTEN_FDS"; [ "$LISTEN_PID" = "$$" ] && echo "pid"; readlink /proc/self/fd/3"#,
+        ])
+        .env("LISTEN_FDNAMES", "stale");
+    handoff.configure(&mut command);
+    let output = command.output().unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let mut lines = stdout.lines();
+    assert_eq!(lines.next(), Some("1"));
+    assert_eq!(lines.next(), Some("pid"));
+    assert!(lines.next().unwrap().starts_with("socket:"));
+
+    // There are no listeners to hand off once the server is down.
+    server_handle
+        .clone()
+        .shutdown(ShutdownMode::Graceful {
+            timeout: Duration::from_secs(1),
+        })
+        .await;
+    assert!(server_handle.hand_off_listeners().await.is_err());
+}
+
+/// The environment variable used by [`handoff_successor`] to tell if it's been spawned
+/// as a successor by [`handed_off_listeners_are_claimed_once`].
+#[cfg(target_os = "linux")]
+const SUCCESSOR_MODE: &str = "PAVEX_TEST_HANDOFF_SUCCESSOR";
+
+/// Spawn this test binary, running [`handoff_successor`] only.
+#[cfg(target_os = "linux")]
+fn successor_command(mode: &str) -> std::process::Command {
+    let mut command = std::process::Command::new(std::env::current_exe().unwrap());
+    command
+        .args(["handoff_successor", "--exact", "--test-threads=1"])
+        .env(SUCCESSOR_MODE, mode);
+    command
+}
+
+/// A no-op, unless it's been spawned by [`handed_off_listeners_are_claimed_once`].
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn handoff_successor() {
+    let Ok(mode) = std::env::var(SUCCESSOR_MODE) else {
+        return;
+    };
+    let inherited = IncomingStream::from_listen_fds().unwrap();
+    match mode.as_str() {
+        "claim" => {
+            assert_eq!(inherited.len(), 1);
+            // The variables are removed, they can't leak to our own children.
+            assert!(std::env::var_os("LISTEN_FDS").is_none());
+            assert!(std::env::var_os("LISTEN_PID").is_none());
+            // The descriptors can't be claimed twice.
+            assert!(IncomingStream::from_listen_fds().unwrap().is_empty());
+        }
+        "ignore" => {
+            // The variables were meant for another process: we leave them alone.
+            assert!(inherited.is_empty());
+            assert!(std::env::var_os("LISTEN_FDS").is_some());
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn handed_off_listeners_are_claimed_once() {
+    let (incoming, addr) = test_incoming().await;
+    let server_handle = Server::new()
+        .set_config(test_server_config())
+        .listen(incoming)
+        .serve(test_handler, ());
+
+    let handoff = server_handle.hand_off_listeners().await.unwrap();
+    let mut command = successor_command("claim");
+    handoff.configure(&mut command);
+    let output = command.output().unwrap();
+    assert!(output.status.success(), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("1 passed"));
+
+    // `LISTEN_PID` doesn't match the pid of the successor.
+    let output = successor_command("ignore")
+        .env("LISTEN_FDS", "1")
+        .env("LISTEN_PID", "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("1 passed"));
+
+    // The server is still listening.
+    std::net::TcpStream::connect(addr).unwrap();
+}
+
+#[tokio::test]
+async fn lifecycle_events() {
+    let (incoming, _) = test_incoming().await;
+    let server_handle = Server::new()
+        .set_config(ServerConfiguration::new().set_n_workers(2))
+        .listen(incoming)
+        .serve(test_handler, ());
+
+    let mut events = server_handle.events();
+    let next = async |events: &mut pavex::server::ServerEvents| {
+        tokio::time::timeout(Duration::from_secs(2), events.next())
+            .await
+            .expect("Timed out waiting for the next server event")
+    };
+    assert_eq!(next(&mut events).await, Some(ServerEvent::Bound));
+    let mut started = Vec::new();
+    for _ in 0..2 {
+        match next(&mut events).await {
+            Some(ServerEvent::WorkerStarted { worker_id }) => started.push(worker_id),
+            e => panic!("Expected a worker to start, got {e:?}"),
+        }
+    }
+    started.sort();
+    assert_eq!(started, vec![0, 1]);
+    assert_eq!(next(&mut events).await, Some(ServerEvent::Ready));
+
+    server_handle
+        .clone()
+        .shutdown(ShutdownMode::Graceful {
+            timeout: Duration::from_secs(1),
+        })
+        .await;
+    assert_eq!(next(&mut events).await, Some(ServerEvent::Draining));
+    assert_eq!(next(&mut events).await, Some(ServerEvent::Stopped));
+    assert_eq!(next(&mut events).await, None);
+
+    // Late subscribers see the whole history.
+    let mut late = server_handle.events();
+    assert_eq!(next(&mut late).await, Some(ServerEvent::Bound));
+}
+
+#[tokio::test]
+async fn stats() {
+    let (incoming, addr) = test_incoming().await;
+    let server_handle = Server::new()
+        .set_config(test_server_config())
+        .listen(incoming)
+        .serve(test_handler, ());
+
+    let stats = server_handle.stats().await.unwrap();
+    assert_eq!(stats.workers.len(), 1);
+    assert_eq!(stats.active_connections(), 0);
+
+    // An idle connection counts as active until it's closed.
+    let _connection = tokio::net::TcpStream::connect(addr).await.unwrap();
+    tokio::time::timeout(Duration::from_secs(2), async {
+        while server_handle.stats().await.unwrap().active_connections() != 1 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("The connection was never reported as active");
+
+    server_handle.clone().shutdown(ShutdownMode::Forced).await;
+    assert!(server_handle.stats().await.is_none());
+}
+
+/// Wait until the number of active connections on each worker matches the expected values.
+async fn wait_for_load(server_handle: &ServerHandle, expected: &[usize]) {
+    tokio::time::timeout(Duration::from_secs(2), async {
+        loop {
+            let stats = server_handle.stats().await.unwrap();
+            let load: Vec<_> = stats.workers.iter().map(|w| w.active_connections).collect();
+            if load == expected {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("The load on the workers never reached {expected:?}"));
+}
+
+#[tokio::test]
+async fn least_active_connections() {
+    let (incoming, addr) = test_incoming().await;
+    let config = ServerConfiguration::new()
+        .set_n_workers(2)
+        .set_dispatch_strategy(DispatchStrategy::LeastActiveConnections);
+    let server_handle = Server::new()
+        .set_config(config)
+        .listen(incoming)
+        .serve(test_handler, ());
+
+    let connect = || tokio::net::TcpStream::connect(addr);
+    let a = connect().await.unwrap();
+    wait_for_load(&server_handle, &[1, 0]).await;
+    let _b = connect().await.unwrap();
+    wait_for_load(&server_handle, &[1, 1]).await;
+    let c = connect().await.unwrap();
+    wait_for_load(&server_handle, &[2, 1]).await;
+    drop(a);
+    drop(c);
+    wait_for_load(&server_handle, &[0, 1]).await;
+
+    // Round-robin would pick the second worker, it's the first worker's turn.
+    let _d = connect().await.unwrap();
+    wait_for_load(&server_handle, &[1, 1]).await;
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn reuse_port() {
+    let incoming = IncomingStream::bind_reuse_port("127.0.0.1:0".parse().unwrap())
+        .await
+        .unwrap();
+    let addr = incoming.local_addr().unwrap();
+    let config = ServerConfiguration::new()
+        .set_n_workers(2)
+        .set_dispatch_strategy(DispatchStrategy::ReusePort);
+    let server_handle = Server::new()
+        .set_config(config)
+        .listen(incoming)
+        .serve(test_handler, ());
+
+    let url = format!("http://localhost:{}", addr.port());
+    for _ in 0..10 {
+        reqwest::get(&url)
+            .await
+            .unwrap()
+            .error_for_status()
+            .unwrap();
+    }
+
+    server_handle
+        .shutdown(ShutdownMode::Graceful {
+            timeout: Duration::from_secs(1),
+        })
+        .await;
+    // The listeners owned by the workers have been closed.
+    assert!(reqwest::get(&url).await.is_err());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn reuse_port_requires_so_reuseport() {
+    let (incoming, _) = test_incoming().await;
+    let config = ServerConfiguration::new()
+        .set_n_workers(2)
+        .set_dispatch_strategy(DispatchStrategy::ReusePort);
+    let Err(e) = Server::new()
+        .set_config(config)
+        .listen(incoming)
+        .try_serve(test_handler, ())
+    else {
+        panic!("Serving should have failed");
+    };
+    assert!(e.to_string().contains("SO_REUSEPORT"), "{e}");
+}
+
+async fn gated_handler(
+    request: Request<Incoming>,
+    _connection_info: Option<ConnectionInfo>,
+    (started, gate): (tokio::sync::mpsc::Sender<()>, Arc<tokio::sync::Semaphore>),
+) -> Response {
+    if request.uri().path() == "/gated" {
+        started.send(()).await.unwrap();
+        let _permit = gate.acquire().await.unwrap();
+    }
+    Response::ok()
+}
+
+fn overloaded() -> Response {
+    Response::service_unavailable().insert_header(RETRY_AFTER, HeaderValue::from_static("1"))
+}
+
+#[tokio::test]
+async fn requests_beyond_the_in_flight_limit_are_rejected() {
+    let (incoming, addr) = test_incoming().await;
+    let (started_tx, mut started_rx) = tokio::sync::mpsc::channel(1);
+    let gate = Arc::new(tokio::sync::Semaphore::new(0));
+    let config = ServerConfiguration::new()
+        .set_n_workers(2)
+        .set_max_in_flight_requests(Some(NonZeroUsize::new(1).unwrap()))
+        .set_overload_response(overloaded);
+    Server::new()
+        .set_config(config)
+        .listen(incoming)
+        .serve(gated_handler, (started_tx, gate.clone()));
+
+    let url = format!("http://localhost:{}", addr.port());
+    let gated = tokio::spawn(reqwest::get(format!("{url}/gated")));
+    started_rx.recv().await.unwrap();
+
+    // The limit is server-wide: it doesn't matter which worker handles the request.
+    for _ in 0..2 {
+        let response = reqwest::Client::new().get(&url).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 503);
+        assert_eq!(response.headers()[RETRY_AFTER], "1");
+    }
+
+    gate.add_permits(1);
+    assert_eq!(gated.await.unwrap().unwrap().status().as_u16(), 200);
+    let response = reqwest::get(&url).await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn connections_are_rejected_when_all_workers_are_busy() {
+    let (incoming, addr) = test_incoming().await;
+    let config = ServerConfiguration::new()
+        .set_n_workers(1)
+        .set_max_connections_per_worker(Some(NonZeroUsize::new(1).unwrap()))
+        .set_max_queue_length(NonZeroUsize::new(1).unwrap());
+    let server_handle = Server::new()
+        .set_config(config)
+        .listen(incoming)
+        .serve(test_handler, ());
+
+    let busy = tokio::net::TcpStream::connect(addr).await.unwrap();
+    wait_for_load(&server_handle, &[1]).await;
+
+    let url = format!("http://localhost:{}", addr.port());
+    let response = reqwest::get(&url).await.unwrap();
+    assert_eq!(response.status().as_u16(), 503);
+
+    drop(busy);
+    wait_for_load(&server_handle, &[0]).await;
+    let response = reqwest::get(&url).await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn http2_only_serves_h2c_with_prior_knowledge() {
+    let (incoming, addr) = test_incoming().await;
+    let config = test_server_config().set_http_protocol(HttpProtocol::Http2Only);
+    Server::new()
+        .set_config(config)
+        .listen(incoming)
+        .serve(test_handler, ());
+
+    let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let (mut sender, connection) = hyper::client::conn::http2::handshake(
+        hyper_util::rt::TokioExecutor::new(),
+        hyper_util::rt::TokioIo::new(stream),
+    )
+    .await
+    .unwrap();
+    tokio::spawn(connection);
+    let request = Request::get(format!("http://localhost:{}", addr.port()))
+        .body(http_body_util::Empty::<bytes::Bytes>::new())
+        .unwrap();
+    let response = sender.send_request(request).await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(response.version(), http::Version::HTTP_2);
+
+    // HTTP/1 clients are turned away.
+    let url = format!("http://localhost:{}", addr.port());
+    assert!(reqwest::get(url).await.is_err());
+}
+
+#[tokio::test]
+async fn http1_only_rejects_h2c() {
+    let (incoming, addr) = test_incoming().await;
+    let config = test_server_config().set_http_protocol(HttpProtocol::Http1Only);
+    Server::new()
+        .set_config(config)
+        .listen(incoming)
+        .serve(test_handler, ());
+
+    let url = format!("http://localhost:{}", addr.port());
+    reqwest::get(url).await.unwrap().error_for_status().unwrap();
+
+    let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let outcome = hyper::client::conn::http2::handshake(
+        hyper_util::rt::TokioExecutor::new(),
+        hyper_util::rt::TokioIo::new(stream),
+    )
+    .await;
+    let Ok((mut sender, connection)) = outcome else {
+        return;
+    };
+    tokio::spawn(connection);
+    let request = Request::get(format!("http://localhost:{}", addr.port()))
+        .body(http_body_util::Empty::<bytes::Bytes>::new())
+        .unwrap();
+    assert!(sender.send_request(request).await.is_err());
+}
+
+#[cfg(feature = "websocket")]
+async fn echo_handler(
+    request: Request<Incoming>,
+    _connection_info: Option<ConnectionInfo>,
+    _state: (),
+) -> Response {
+    use pavex::websocket::{Message, WebSocketUpgrade};
+
+    let (head, _) = request.into_parts();
+    let upgrade = match WebSocketUpgrade::extract(&head.into()) {
+        Ok(upgrade) => upgrade,
+        Err(e) => return e.into_response(),
+    };
+    upgrade.on_upgrade(|mut socket| async move {
+        while let Some(Ok(message)) = socket.recv().await {
+            if let Message::Text(text) = message {
+                let _ = socket.send(text).await;
+            }
+        }
+    })
+}
+
+#[cfg(feature = "websocket")]
+#[tokio::test]
+async fn websockets_are_tracked_until_shutdown() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let (incoming, addr) = test_incoming().await;
+    let server_handle = Server::new()
+        .set_config(test_server_config().set_keep_alive_timeout(Some(Duration::from_millis(50))))
+        .listen(incoming)
+        .serve(echo_handler, ());
+
+    let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    stream
+        .write_all(
+            b"GET /ws HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+            Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n\r\n",
+        )
+        .await
+        .unwrap();
+    let mut head = Vec::new();
+    while !head.ends_with(b"\r\n\r\n") {
+        head.push(stream.read_u8().await.unwrap());
+    }
+    let head = String::from_utf8(head).unwrap();
+    assert!(
+        head.starts_with("HTTP/1.1 101 Switching Protocols"),
+        "{head}"
+    );
+    assert!(head.contains("sec-websocket-accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+
+    // Upgraded connections are not closed for being idle.
+    tokio::time::sleep(Duration::from_millis(150)).await;
+
+    // A masked text frame, "Hello", from RFC 6455.
+    stream
+        .write_all(&[
+            0x81, 0x85, 0x37, 0xfa, 0x21, 0x3d, 0x7f, 0x9f, 0x4d, 0x51, 0x58,
+        ])
+        .await
+        .unwrap();
+    let mut echo = [0; 7];
+    stream.read_exact(&mut echo).await.unwrap();
+    assert_eq!(&echo, b"\x81\x05Hello");
+
+    let shutdown = tokio::task::spawn(server_handle.shutdown(ShutdownMode::Graceful {
+        timeout: Duration::from_secs(10),
+    }));
+    // The server says goodbye, with a "going away" status code.
+    let mut close = [0; 4];
+    stream.read_exact(&mut close).await.unwrap();
+    assert_eq!(&close, b"\x88\x02\x03\xe9");
+    // The shutdown completes as soon as the session is over, well before the timeout.
+    tokio::time::timeout(Duration::from_secs(2), shutdown)
+        .await
+        .expect("The shutdown waited for the timeout to expire")
+        .unwrap();
+}
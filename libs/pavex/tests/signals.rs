@@ -0,0 +1,72 @@
+//! Signals are delivered to the whole process, that's why these tests live in their own binary.
+#![cfg(unix)]
+use std::time::Duration;
+
+use http::Request;
+use hyper::body::Incoming;
+
+use pavex::connection::ConnectionInfo;
+use pavex::response::Response;
+use pavex::server::{IncomingStream, Server, ServerConfiguration, ServerEvent, ServerEvents};
+
+async fn slow_handler(
+    _request: Request<Incoming>,
+    _connection_info: Option<ConnectionInfo>,
+    started: tokio::sync::mpsc::Sender<()>,
+) -> Response {
+    started.send(()).await.unwrap();
+    tokio::time::sleep(Duration::from_secs(60)).await;
+    Response::ok()
+}
+
+fn send_sigterm() {
+    let status = std::process::Command::new("kill")
+        .args(["-TERM", &std::process::id().to_string()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+async fn wait_for(events: &mut ServerEvents, expected: ServerEvent) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while let Some(event) = events.next().await {
+            if event == expected {
+                return;
+            }
+        }
+        panic!("The server stopped emitting events before {expected:?}");
+    })
+    .await
+    .unwrap_or_else(|_| panic!("Timed out waiting for {expected:?}"));
+}
+
+#[tokio::test]
+async fn a_second_signal_forces_the_shutdown() {
+    let incoming = IncomingStream::bind("127.0.0.1:0".parse().unwrap())
+        .await
+        .unwrap();
+    let addr = incoming.local_addr().unwrap();
+    let (started_tx, mut started_rx) = tokio::sync::mpsc::channel(1);
+    let server_handle = Server::new()
+        .set_config(ServerConfiguration::new().set_n_workers(1))
+        // Much longer than the test timeouts: the shutdown must be forced.
+        .shutdown_on_signals(Duration::from_secs(60))
+        .listen(incoming)
+        .serve(slow_handler, started_tx);
+    let mut events = server_handle.events();
+    wait_for(&mut events, ServerEvent::Ready).await;
+
+    // Keep a request in flight, to prevent the graceful shutdown from completing.
+    let request = tokio::spawn(reqwest::get(format!("http://localhost:{}", addr.port())));
+    started_rx.recv().await.unwrap();
+
+    send_sigterm();
+    wait_for(&mut events, ServerEvent::Draining).await;
+    send_sigterm();
+    wait_for(&mut events, ServerEvent::Stopped).await;
+
+    tokio::time::timeout(Duration::from_secs(5), server_handle)
+        .await
+        .expect("The server didn't shut down");
+    assert!(request.await.unwrap().is_err());
+}
@@ -0,0 +1,108 @@
+#![cfg(feature = "tls")]
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use http::Request;
+use hyper::body::Incoming;
+
+use pavex::connection::ConnectionInfo;
+use pavex::response::Response;
+use pavex::server::{IncomingStream, Server, ServerConfiguration, TlsConfig};
+
+// Echo back what we know about the TLS session.
+async fn tls_info_handler(
+    _request: Request<Incoming>,
+    connection_info: Option<ConnectionInfo>,
+    _state: (),
+) -> Response {
+    let connection_info = connection_info.unwrap();
+    let alpn = connection_info
+        .alpn_protocol()
+        .map(|p| String::from_utf8_lossy(p).into_owned());
+    let body = format!(
+        "{}|{}",
+        alpn.unwrap_or_default(),
+        connection_info.server_name().unwrap_or_default()
+    );
+    Response::ok().set_typed_body(body)
+}
+
+/// Generate a self-signed certificate for `localhost`.
+fn self_signed() -> (TlsConfig, reqwest::Certificate) {
+    let certified = rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap();
+    let cert_pem = certified.cert.pem();
+    let tls_config = TlsConfig::from_pem(
+        cert_pem.as_bytes(),
+        certified.key_pair.serialize_pem().as_bytes(),
+    )
+    .unwrap();
+    let root = reqwest::Certificate::from_pem(cert_pem.as_bytes()).unwrap();
+    (tls_config, root)
+}
+
+async fn serve_tls(tls_config: TlsConfig) -> SocketAddr {
+    serve_tls_with_config(tls_config, ServerConfiguration::new().set_n_workers(1)).await
+}
+
+async fn serve_tls_with_config(tls_config: TlsConfig, config: ServerConfiguration) -> SocketAddr {
+    let incoming = IncomingStream::bind_tls("127.0.0.1:0".parse().unwrap(), tls_config)
+        .await
+        .unwrap();
+    let addr = incoming.local_addr().unwrap();
+    Server::new()
+        .set_config(config)
+        .listen(incoming)
+        .serve(tls_info_handler, ());
+    addr
+}
+
+#[tokio::test]
+async fn tls_termination() {
+    let (tls_config, root) = self_signed();
+    let addr = serve_tls(tls_config).await;
+
+    let client = reqwest::Client::builder()
+        .add_root_certificate(root)
+        .build()
+        .unwrap();
+    let body = client
+        .get(format!("https://localhost:{}", addr.port()))
+        .send()
+        .await
+        .unwrap()
+        .error_for_status()
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert_eq!(body, "http/1.1|localhost");
+}
+
+#[tokio::test]
+async fn plaintext_requests_are_rejected() {
+    let (tls_config, _) = self_signed();
+    let addr = serve_tls(tls_config).await;
+
+    let outcome = reqwest::get(format!("http://localhost:{}", addr.port())).await;
+    assert!(outcome.is_err());
+}
+
+#[tokio::test]
+async fn silent_clients_are_disconnected_during_the_handshake() {
+    use tokio::io::AsyncReadExt;
+
+    let (tls_config, _) = self_signed();
+    let config = ServerConfiguration::new()
+        .set_n_workers(1)
+        .set_tls_handshake_timeout(Some(Duration::from_millis(200)));
+    let addr = serve_tls_with_config(tls_config, config).await;
+
+    // Open a TCP connection, but never send a `ClientHello`.
+    let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let mut buf = [0u8; 1];
+    let read = tokio::time::timeout(Duration::from_secs(2), stream.read(&mut buf))
+        .await
+        .expect("The server didn't close the connection when the TLS handshake timed out");
+    // Either a clean EOF or a reset, depending on the platform.
+    assert!(matches!(read, Ok(0) | Err(_)));
+}
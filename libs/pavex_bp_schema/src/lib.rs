@@ -116,6 +116,10 @@ pub struct Route {
     pub request_handler: Callable,
     /// The callable in charge of processing errors returned by the request handler, if any.
     pub error_handler: Option<Callable>,
+    /// The upper limit on the size of incoming request bodies for this route, if any.
+    ///
+    /// If `None`, the limit is inherited from the enclosing `Blueprint`.
+    pub body_size_limit: Option<BodySizeLimit>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -238,6 +242,9 @@ pub struct NestedBlueprint {
     /// If `Some`, only requests whose `Host` header matches this value will be forwarded to the
     /// routes registered against this nested `Blueprint`.
     pub domain: Option<Domain>,
+    /// The upper limit on the size of incoming request bodies for all routes registered against
+    /// the nested `Blueprint`, if any.
+    pub body_size_limit: Option<BodySizeLimit>,
     /// The location where the `Blueprint` was nested under its parent `Blueprint`.
     pub nested_at: Location,
 }
@@ -260,6 +267,17 @@ pub struct Domain {
     pub registered_at: Location,
 }
 
+/// An upper limit on the size of incoming request bodies, set via `body_size_limit`
+/// on a route or on a nested `Blueprint`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BodySizeLimit {
+    /// The maximum size of incoming request bodies, in bytes.
+    /// If `None`, there is no limit.
+    pub max_size: Option<u64>,
+    /// The location where the limit was registered.
+    pub registered_at: Location,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Lifecycle {
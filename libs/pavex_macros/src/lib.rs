@@ -8,6 +8,7 @@ mod from;
 mod middlewares;
 mod path_params;
 mod prebuilt;
+mod typed_header;
 pub(crate) mod utils;
 
 #[allow(non_snake_case)]
@@ -71,6 +72,11 @@ pub fn derive_config_profile(input: TokenStream) -> TokenStream {
     config_profile::derive_config_profile(input)
 }
 
+#[proc_macro_derive(TypedHeader, attributes(pavex))]
+pub fn derive_typed_header(input: TokenStream) -> TokenStream {
+    typed_header::derive_typed_header(input)
+}
+
 #[proc_macro]
 pub fn from(input: TokenStream) -> TokenStream {
     from::from_(input)
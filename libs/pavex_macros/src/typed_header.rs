@@ -0,0 +1,89 @@
+//! A derive macro for implementing the `TypedHeader` trait for newtype structs.
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Lit, parse_macro_input};
+
+pub(super) fn derive_typed_header(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let unsupported_error_msg = "An implementation of the `TypedHeader` trait can only be derived for newtype structs \
+        (i.e. a struct with a single unnamed field). Consider implementing the trait manually.";
+
+    let Data::Struct(data_struct) = &input.data else {
+        return syn::Error::new_spanned(name, unsupported_error_msg)
+            .to_compile_error()
+            .into();
+    };
+    if !matches!(&data_struct.fields, Fields::Unnamed(fields) if fields.unnamed.len() == 1) {
+        return syn::Error::new_spanned(name, unsupported_error_msg)
+            .to_compile_error()
+            .into();
+    }
+
+    let error_msg =
+        "Missing or invalid `pavex` attribute. Expected `#[pavex(name = \"header-name\")]`";
+    let mut header_name = None;
+    for attr in &input.attrs {
+        if !attr.meta.path().is_ident("pavex") {
+            continue;
+        }
+        let Ok(args) = attr.parse_args::<syn::MetaNameValue>() else {
+            return syn::Error::new_spanned(attr, error_msg)
+                .to_compile_error()
+                .into();
+        };
+        if !args.path.is_ident("name") {
+            return syn::Error::new_spanned(&args.path, error_msg)
+                .to_compile_error()
+                .into();
+        }
+        let syn::Expr::Lit(syn::ExprLit {
+            lit: Lit::Str(lit_str),
+            ..
+        }) = &args.value
+        else {
+            return syn::Error::new_spanned(&args.value, error_msg)
+                .to_compile_error()
+                .into();
+        };
+        let value = lit_str.value();
+        if value.is_empty() || !value.bytes().all(is_lowercase_token_char) {
+            return syn::Error::new_spanned(
+                &args.value,
+                "Header names must be non-empty and can only contain lowercase letters, digits, \
+                or one of ``!#$%&'*+-.^_`|~``.",
+            )
+            .to_compile_error()
+            .into();
+        }
+        header_name = Some(value);
+    }
+    let Some(header_name) = header_name else {
+        return syn::Error::new_spanned(name, error_msg)
+            .to_compile_error()
+            .into();
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let expanded = quote! {
+        #[automatically_derived]
+        impl #impl_generics pavex::request::headers::TypedHeader for #name #ty_generics #where_clause {
+            const NAME: pavex::http::HeaderName = pavex::http::HeaderName::from_static(#header_name);
+
+            fn decode(
+                values: pavex::http::header::GetAll<'_, pavex::http::HeaderValue>,
+            ) -> ::std::result::Result<Self, pavex::request::headers::errors::HeaderDecodeError> {
+                pavex::request::headers::decode_from_str(values).map(Self)
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Valid characters for a header name, as defined in RFC 9110 (`tchar`), excluding
+/// uppercase letters.
+fn is_lowercase_token_char(b: u8) -> bool {
+    b.is_ascii_lowercase() || b.is_ascii_digit() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
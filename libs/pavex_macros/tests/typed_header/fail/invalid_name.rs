@@ -0,0 +1,17 @@
+#[derive(pavex_macros::TypedHeader)]
+#[pavex(name = "X-Request-Id")]
+struct Uppercase(String);
+
+#[derive(pavex_macros::TypedHeader)]
+#[pavex(name = "")]
+struct Empty(String);
+
+#[derive(pavex_macros::TypedHeader)]
+#[pavex(name = "x request id")]
+struct Whitespace(String);
+
+#[derive(pavex_macros::TypedHeader)]
+#[pavex(name = 42)]
+struct NotAString(String);
+
+fn main() {}
@@ -0,0 +1,12 @@
+#[derive(pavex_macros::TypedHeader)]
+struct NoAttribute(String);
+
+#[derive(pavex_macros::TypedHeader)]
+#[pavex(id = "x-request-id")]
+struct WrongKey(String);
+
+#[derive(pavex_macros::TypedHeader)]
+#[pavex]
+struct NoArguments(String);
+
+fn main() {}
@@ -0,0 +1,21 @@
+#[derive(pavex_macros::TypedHeader)]
+#[pavex(name = "x-named")]
+struct NamedFields {
+    value: String,
+}
+
+#[derive(pavex_macros::TypedHeader)]
+#[pavex(name = "x-pair")]
+struct TwoFields(String, String);
+
+#[derive(pavex_macros::TypedHeader)]
+#[pavex(name = "x-unit")]
+struct Unit;
+
+#[derive(pavex_macros::TypedHeader)]
+#[pavex(name = "x-enum")]
+enum AnEnum {
+    A(String),
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use pavex::http::{HeaderMap, HeaderValue};
+use pavex::request::headers::TypedHeader;
+
+#[derive(pavex_macros::TypedHeader)]
+#[pavex(name = "x-request-id")]
+struct RequestId(String);
+
+#[derive(pavex_macros::TypedHeader)]
+#[pavex(name = "x-retries")]
+struct Retries(pub u32);
+
+#[derive(pavex_macros::TypedHeader)]
+#[pavex(name = "x-custom_header.v2")]
+struct WithPunctuation(String);
+
+fn main() {
+    assert_eq!(RequestId::NAME.as_str(), "x-request-id");
+    assert_eq!(WithPunctuation::NAME.as_str(), "x-custom_header.v2");
+
+    let mut headers = HeaderMap::new();
+    headers.insert(Retries::NAME, HeaderValue::from_static(" 3 "));
+    let retries = Retries::decode(headers.get_all(Retries::NAME)).unwrap();
+    assert_eq!(retries.0, 3);
+}
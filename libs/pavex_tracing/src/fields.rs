@@ -17,6 +17,7 @@
 //! The module doesn't cover the entirety of OpenTelemetry's semantic convention specification.\
 //! Feel free to open a PR if you need a **stable** field that isn't currently covered!
 
+use pavex::connection::ClientInfo;
 use pavex::http::{Method, Version};
 use pavex::request::RequestHead;
 use pavex::request::path::MatchedPathPattern;
@@ -27,6 +28,12 @@ use tracing::Value;
 // Re-export error-related logging fields and the functions to set them.
 pub use tracing_log_error::fields::*;
 
+/// The field name for the IP address of the client that sent the request,
+/// according to [OpenTelemetry's semantic convention](https://opentelemetry.io/docs/specs/semconv/attributes-registry/client/).
+///
+/// Use [`client_address`] to populate the field.
+pub const CLIENT_ADDRESS: &str = "client.address";
+
 /// The field name for the HTTP method of the incoming request (if canonical),
 /// according to [OpenTelemetry's semantic convention](https://opentelemetry.io/docs/specs/semconv/attributes-registry/http/).
 ///
@@ -75,12 +82,25 @@ pub const URL_PATH: &str = "url.path";
 /// Use [`url_query`] to populate the field.
 pub const URL_QUERY: &str = "url.query";
 
+/// The scheme used by the client to send the request,
+/// according to [OpenTelemetry's semantic convention](https://opentelemetry.io/docs/specs/semconv/attributes-registry/url/).
+///
+/// Use [`url_scheme`] to populate the field.
+pub const URL_SCHEME: &str = "url.scheme";
+
 /// The user agent header for the incoming request,
 /// according to [OpenTelemetry's semantic convention](https://opentelemetry.io/docs/specs/semconv/attributes-registry/user-agent/).
 ///
 /// Use [`user_agent_original`] to populate the field.
 pub const USER_AGENT_ORIGINAL: &str = "user_agent.original";
 
+/// The canonical representation for the value in [`CLIENT_ADDRESS`].
+///
+/// It takes trusted proxies into account—check out [`ClientInfo`] for more details.
+pub fn client_address(client_info: &ClientInfo) -> impl Value + use<> {
+    client_info.ip().map(tracing::field::display)
+}
+
 /// The canonical representation for the value in [`HTTP_REQUEST_METHOD`].
 ///
 /// If the HTTP method is not canonical, it is set to `_OTHER`.
@@ -141,6 +161,13 @@ pub fn url_query(request_head: &RequestHead) -> impl Value + '_ {
     request_head.target.query().unwrap_or_default()
 }
 
+/// The canonical representation for the value in [`URL_SCHEME`].
+///
+/// It takes trusted proxies into account—check out [`ClientInfo`] for more details.
+pub fn url_scheme(client_info: &ClientInfo) -> impl Value + '_ {
+    client_info.scheme().as_str()
+}
+
 /// The canonical representation for the value in [`USER_AGENT_ORIGINAL`].
 pub fn user_agent_original(request_head: &RequestHead) -> impl Value + '_ {
     request_head
@@ -1,4 +1,4 @@
-use crate::compiler::analyses::components::ComponentDb;
+use crate::compiler::analyses::components::{ComponentDb, ComponentId};
 use crate::compiler::analyses::computations::ComputationDb;
 use crate::compiler::analyses::user_components::{UserComponentDb, UserComponentId};
 use crate::compiler::component::{
@@ -9,8 +9,9 @@ use crate::compiler::component::{
 use crate::compiler::resolvers::CallableResolutionError;
 use crate::compiler::traits::MissingTraitImplementationError;
 use crate::diagnostic::{
-    self, AnnotatedSource, CallableDefSource, CompilerDiagnostic, ComponentKind, SourceSpanExt,
-    convert_proc_macro_span, convert_rustdoc_span,
+    self, AnnotatedSource, CallableDefSource, CompilerDiagnostic, ComponentKind,
+    OptionalLabeledSpanExt, OptionalSourceSpanExt, SourceSpanExt, convert_proc_macro_span,
+    convert_rustdoc_span,
 };
 use crate::language::{Callable, ResolvedType};
 use crate::rustdoc::CrateCollection;
@@ -18,6 +19,7 @@ use crate::utils::comma_separated_list;
 use guppy::graph::PackageGraph;
 use indexmap::IndexSet;
 use miette::NamedSource;
+use pavex_bp_schema::Location;
 use rustdoc_types::ItemEnum;
 use syn::spanned::Spanned;
 
@@ -908,6 +910,45 @@ impl ComponentDb {
         diagnostics.push(diagnostic);
     }
 
+    pub(super) fn conflicting_body_size_limit(
+        &self,
+        limit_type: &ResolvedType,
+        location: &Location,
+        conflicting_ids: &[ComponentId],
+        diagnostics: &mut diagnostic::DiagnosticSink,
+    ) {
+        let mut sources = Vec::new();
+        if let Some(source) = diagnostics.source(location).map(|s| {
+            diagnostic::body_size_limit_span(s.source(), location)
+                .labeled("The body size limit was set here".to_string())
+                .attach(s)
+        }) {
+            sources.push(source);
+        }
+        for id in conflicting_ids {
+            if let Some(source) = self.registration_span(
+                *id,
+                diagnostics,
+                "A constructor was registered here".to_string(),
+            ) {
+                sources.push(source);
+            }
+        }
+        let error = anyhow::anyhow!(
+            "You set a body size limit on a nested blueprint, but that blueprint also \
+            registers a constructor for `{limit_type:?}`.\n\
+            I don't know which one should determine the body size limit for its routes!",
+        );
+        let diagnostic = CompilerDiagnostic::builder(error)
+            .sources(sources.into_iter())
+            .help(format!(
+                "Either remove the constructor for `{limit_type:?}` or remove the \
+                `.body_size_limit(...)` call on the nesting conditions."
+            ))
+            .build();
+        diagnostics.push(diagnostic);
+    }
+
     pub(super) fn non_static_reference_in_singleton(
         output_type: &ResolvedType,
         id: UserComponentId,
@@ -22,7 +22,8 @@ use crate::compiler::utils::{
 };
 use crate::diagnostic::{ParsedSourceFile, Registration, TargetSpan};
 use crate::language::{
-    Callable, FQPath, FQPathSegment, FQQualifiedSelf, Lifetime, ResolvedType, TypeReference,
+    Callable, FQPath, FQPathSegment, FQQualifiedSelf, InvocationStyle, Lifetime, ResolvedType,
+    TypeReference,
 };
 use crate::rustdoc::CrateCollection;
 use ahash::{HashMap, HashMapExt, HashSet};
@@ -263,6 +264,9 @@ impl ComponentDb {
 
             self_.process_prebuilt_types(computation_db);
             self_.process_config_types(computation_db);
+            // This **must** be invoked after user-registered constructors have been processed,
+            // since we need to detect conflicts with them.
+            self_.process_body_size_limits(computation_db, krate_collection, diagnostics);
 
             for fallible_id in needs_error_handler {
                 Self::missing_error_handler(fallible_id, &self_.user_db, diagnostics);
@@ -669,6 +673,92 @@ impl ComponentDb {
         }
     }
 
+    /// Register a synthetic constructor for `pavex::request::body::BodySizeLimit` in each
+    /// scope where a body size limit was set—either on a route or on a nested blueprint.
+    ///
+    /// Since constructors are looked up starting from the scope of the request handler,
+    /// the synthetic constructor shadows the ones registered against parent blueprints.
+    ///
+    /// Conflicts are only reported against constructors registered in the very same scope,
+    /// i.e. by the nested blueprint the limit was set on.
+    /// Each route gets a dedicated scope, so a route-level limit never conflicts: it
+    /// shadows the constructors registered by the route's blueprint.
+    /// Constructors registered by blueprints nested further down are in child scopes:
+    /// they shadow the limit for their own routes.
+    fn process_body_size_limits(
+        &mut self,
+        computation_db: &mut ComputationDb,
+        krate_collection: &CrateCollection,
+        diagnostics: &mut crate::diagnostic::DiagnosticSink,
+    ) {
+        let limits = self
+            .user_db
+            .body_size_limits()
+            .map(|(scope_id, limit)| (scope_id, limit.to_owned()))
+            .collect::<Vec<_>>();
+        if limits.is_empty() {
+            return;
+        }
+        let limit_type =
+            process_framework_path("pavex::request::body::BodySizeLimit", krate_collection);
+        let ResolvedType::ResolvedPath(limit_path) = &limit_type else {
+            unreachable!()
+        };
+
+        for (scope_id, limit) in limits {
+            let conflicting_ids = self
+                .constructors(computation_db)
+                .filter(|(id, c)| self.scope_id(*id) == scope_id && c.output_type() == &limit_type)
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>();
+            if !conflicting_ids.is_empty() {
+                self.conflicting_body_size_limit(
+                    &limit_type,
+                    &limit.registered_at,
+                    &conflicting_ids,
+                    diagnostics,
+                );
+                continue;
+            }
+
+            let (variant, extra_field2default_value) = match limit.max_size {
+                Some(max_size) => (
+                    "Enabled",
+                    BTreeMap::from([("max_size".to_owned(), format!("{max_size}u64.into()"))]),
+                ),
+                None => ("Disabled", BTreeMap::new()),
+            };
+            let mut path = limit_path.resolved_path();
+            path.segments.push(FQPathSegment {
+                ident: variant.into(),
+                generic_arguments: vec![],
+            });
+            let callable = Callable {
+                is_async: false,
+                takes_self_as_ref: false,
+                output: Some(limit_type.clone()),
+                path,
+                inputs: vec![],
+                invocation_style: InvocationStyle::StructLiteral {
+                    field_names: BTreeMap::new(),
+                    extra_field2default_value,
+                },
+                source_coordinates: None,
+            };
+            let computation = Computation::Callable(Cow::Owned(callable));
+            self.get_or_intern(
+                UnregisteredComponent::SyntheticConstructor {
+                    computation_id: computation_db.get_or_intern(Constructor(computation)),
+                    scope_id,
+                    lifecycle: Lifecycle::RequestScoped,
+                    cloning_strategy: CloningStrategy::CloneIfNecessary,
+                    derived_from: None,
+                },
+                computation_db,
+            );
+        }
+    }
+
     fn process_request_handlers(
         &mut self,
         needs_error_handler: &mut IndexSet<UserComponentId>,
@@ -12,7 +12,7 @@ use crate::{
 use ahash::HashMap;
 use indexmap::IndexMap;
 use pavex_bp_schema::{
-    CloningStrategy, Import, Lifecycle, Lint, LintSetting, Location, RawIdentifiers,
+    BodySizeLimit, CloningStrategy, Import, Lifecycle, Lint, LintSetting, Location, RawIdentifiers,
 };
 
 use super::{ScopeId, UserComponent, UserComponentId};
@@ -94,6 +94,9 @@ pub(super) struct AuxiliaryData {
     ///
     /// The same guard can be registered at multiple locations, so we use a `Vec` to store them.
     pub(super) domain_guard2locations: IndexMap<DomainGuard, Vec<Location>>,
+    /// Associate a scope with the body size limit that was set on the route or
+    /// the nested `Blueprint` that introduced it, if any.
+    pub(super) scope_id2body_size_limit: IndexMap<ScopeId, BodySizeLimit>,
 }
 
 impl AuxiliaryData {
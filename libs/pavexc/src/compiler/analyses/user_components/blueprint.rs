@@ -66,6 +66,10 @@ pub(super) fn process_blueprint(
         } = item;
         let nested_scope_id =
             scope_graph_builder.add_scope(parent_scope_id, Some(nested_bp.nested_at.clone()));
+        if let Some(limit) = &nested_bp.body_size_limit {
+            aux.scope_id2body_size_limit
+                .insert(nested_scope_id, limit.clone());
+        }
         let Ok((current_prefix, current_domain)) =
             process_nesting_constraints(aux, nested_bp, diagnostics)
         else {
@@ -274,6 +278,10 @@ fn process_route(
         .identifiers_interner
         .get_or_intern(registered_route.request_handler.callable.clone());
     let route_scope_id = scope_graph_builder.add_scope(current_scope_id, None);
+    if let Some(limit) = &registered_route.body_size_limit {
+        aux.scope_id2body_size_limit
+            .insert(route_scope_id, limit.clone());
+    }
     let router_key = {
         let path = match path_prefix {
             Some(prefix) => format!("{}{}", prefix, registered_route.path),
@@ -17,8 +17,8 @@ use crate::diagnostic::{DiagnosticSink, Registration};
 use crate::{language::FQPath, rustdoc::CrateCollection};
 use ahash::HashMap;
 use guppy::PackageId;
-use indexmap::IndexSet;
-use pavex_bp_schema::{Blueprint, CloningStrategy, Lifecycle, Lint, LintSetting};
+use indexmap::{IndexMap, IndexSet};
+use pavex_bp_schema::{Blueprint, BodySizeLimit, CloningStrategy, Lifecycle, Lint, LintSetting};
 use pavex_cli_diagnostic::AnyhowBridge;
 use std::collections::BTreeMap;
 
@@ -79,6 +79,9 @@ pub struct UserComponentDb {
     ///
     /// Invariants: there is an entry for every single request handler.
     handler_id2error_observer_ids: HashMap<UserComponentId, Vec<UserComponentId>>,
+    /// Associate a scope with the body size limit that was set on the route or
+    /// the nested `Blueprint` that introduced it, if any.
+    scope_id2body_size_limit: IndexMap<ScopeId, BodySizeLimit>,
     scope_graph: ScopeGraph,
 }
 
@@ -176,6 +179,7 @@ impl UserComponentDb {
             fallback_id2domain_guard: _,
             fallback_id2path_prefix: _,
             domain_guard2locations: _,
+            scope_id2body_size_limit,
         } = aux;
 
         Ok((
@@ -191,6 +195,7 @@ impl UserComponentDb {
                 config_id2include_if_unused,
                 handler_id2middleware_ids,
                 handler_id2error_observer_ids,
+                scope_id2body_size_limit,
                 scope_graph,
                 id2lints,
             },
@@ -371,6 +376,14 @@ impl UserComponentDb {
     pub fn error_observer_ids(&self, id: UserComponentId) -> &[UserComponentId] {
         &self.handler_id2error_observer_ids[&id]
     }
+
+    /// Iterate over all the body size limits set on routes and nested blueprints,
+    /// alongside the scope they apply to.
+    pub fn body_size_limits(&self) -> impl Iterator<Item = (ScopeId, &BodySizeLimit)> {
+        self.scope_id2body_size_limit
+            .iter()
+            .map(|(scope_id, limit)| (*scope_id, limit))
+    }
 }
 
 /// We try to batch together the computation of the JSON documentation for all the crates that,
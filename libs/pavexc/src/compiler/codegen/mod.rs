@@ -104,6 +104,7 @@ pub(crate) fn codegen_app(
         .collect::<Vec<_>>();
 
     let entrypoint = server_startup(&sdk_deps);
+    let test_client = test_client(&sdk_deps);
     let alloc_extern_import = if package_id2name.contains_right(ALLOC_PACKAGE_ID_REPR) {
         // The fact that an item from `alloc` is used in the generated code does not imply
         // that we need to have an `alloc` import (e.g. it might not appear in function
@@ -141,6 +142,7 @@ pub(crate) fn codegen_app(
         #application_state_init
         #define_application_state_error
         #entrypoint
+        #test_client
         #router
         #(#handler_modules)*
     };
@@ -164,7 +166,7 @@ fn server_startup(sdk_deps: &ServerSdkDeps) -> ItemFn {
                 server_state: std::sync::Arc<ServerState>
             ) -> #pavex::response::Response {
                 let (router, state) = (&server_state.router, &server_state.application_state);
-                router.route(request, connection_info, state).await
+                router.route(request.map(Into::into), connection_info, state).await
             }
 
             let router = Router::new();
@@ -179,6 +181,43 @@ fn server_startup(sdk_deps: &ServerSdkDeps) -> ItemFn {
     .unwrap()
 }
 
+fn test_client(sdk_deps: &ServerSdkDeps) -> TokenStream {
+    let pavex = sdk_deps.pavex_ident();
+    quote! {
+        /// Send requests to your application in-process, without going through the network.
+        ///
+        /// Check out [`pavex::testing`] for more details.
+        pub struct TestClient {
+            router: Router,
+            application_state: ApplicationState
+        }
+
+        impl TestClient {
+            /// Create a new test client for the given application state.
+            pub fn new(application_state: ApplicationState) -> Self {
+                Self {
+                    router: Router::new(),
+                    application_state
+                }
+            }
+
+            /// Process the request as if it had been received by the server,
+            /// and buffer the response.
+            pub async fn send(
+                &self,
+                request: impl Into<#pavex::testing::TestRequest>
+            ) -> #pavex::testing::TestResponse {
+                let (request, connection_info) = request.into().into_parts();
+                let response = self
+                    .router
+                    .route(request, connection_info, &self.application_state)
+                    .await;
+                #pavex::testing::TestResponse::new(response).await
+            }
+        }
+    }
+}
+
 fn define_server_state(application_state_def: &ItemStruct) -> ItemStruct {
     let dead_code = if application_state_def.fields.is_empty() {
         quote! {
@@ -283,7 +283,6 @@ fn domain_router(
     sdk_deps: &ServerSdkDeps,
 ) -> ItemFn {
     let http = sdk_deps.http_ident();
-    let hyper = sdk_deps.hyper_ident();
     let pavex = sdk_deps.pavex_ident();
     let request = format_ident!("request");
     let state = format_ident!("state");
@@ -320,7 +319,7 @@ fn domain_router(
     let generated = quote! {
         pub async fn route(
             &self,
-            #request: #http::Request<#hyper::body::Incoming>,
+            #request: #http::Request<#pavex::request::body::RawIncomingBody>,
             #connection_info: Option<#pavex::connection::ConnectionInfo>,
             #state: &ApplicationState
         ) -> #pavex::response::Response {
@@ -378,7 +377,6 @@ fn path_router(
 
     let pavex = sdk_deps.pavex_ident();
     let http = sdk_deps.http_ident();
-    let hyper = sdk_deps.hyper_ident();
     let mut route_match_arms = Vec::new();
     let server_state_ident = format_ident!("state");
     let connection_info_ident =
@@ -571,7 +569,7 @@ fn path_router(
     let code = quote! {
         async fn route(
             &self,
-            request: #http::Request<#hyper::body::Incoming>,
+            request: #http::Request<#pavex::request::body::RawIncomingBody>,
             #connection_info_ident: Option<#pavex::connection::ConnectionInfo>,
             #[allow(unused)]
             #server_state_ident: &ApplicationState
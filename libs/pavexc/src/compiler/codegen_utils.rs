@@ -227,7 +227,8 @@ pub(crate) fn codegen_call(
                         .iter()
                         .map(|(field_name, default_value)| {
                             let field_name = format_ident!("{}", field_name);
-                            let default_value = format_ident!("{}", default_value);
+                            let default_value: syn::Expr = syn::parse_str(default_value)
+                                .expect("Default field values must be valid Rust expressions");
                             quote! {
                                 #field_name: #default_value
                             }
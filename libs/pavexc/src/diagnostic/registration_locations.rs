@@ -427,6 +427,34 @@ pub(crate) fn domain_span(source: &ParsedSourceFile, location: &Location) -> Opt
     ))
 }
 
+/// Location, obtained via `#[track_caller]` and `std::panic::Location::caller`, points at the
+/// `.` in the method invocation for `body_size_limit`.
+/// E.g.
+///
+/// ```rust,ignore
+/// bp.prefix("/uploads").body_size_limit(BodySizeLimit::Disabled)
+/// //                   ^ `location` points here!
+/// ```
+///
+/// We build a `SourceSpan` that matches the limit argument.
+/// E.g.
+///
+/// ```rust,ignore
+/// bp.prefix("/uploads").body_size_limit(BodySizeLimit::Disabled)
+/// //                                    ^^^^^^^^^^^^^^^^^^^^^^^
+/// //                                    We want a SourceSpan that points at this
+/// ```
+pub(crate) fn body_size_limit_span(
+    source: &ParsedSourceFile,
+    location: &Location,
+) -> Option<SourceSpan> {
+    let arguments = get_inherent_method_arguments("body_size_limit", source, location)?;
+    Some(convert_proc_macro_span(
+        &source.contents,
+        arguments.first()?.span(),
+    ))
+}
+
 /// Location, obtained via `#[track_caller]` and `std::panic::Location::caller`, points at the
 /// `.` in the method invocation for `import`.
 /// E.g.
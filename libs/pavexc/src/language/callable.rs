@@ -239,6 +239,10 @@ pub enum InvocationStyle {
         /// be set to a pre-determined function pointer in order to work around the lack of
         /// TAIT on stable.
         /// TODO: remove when TAIT stabilizes.
+        ///
+        /// It's also used to set the size of per-route body size limits, which are
+        /// known at compile-time.
+        /// Each value must be a valid Rust expression.
         extra_field2default_value: BTreeMap<String, String>,
     },
 }
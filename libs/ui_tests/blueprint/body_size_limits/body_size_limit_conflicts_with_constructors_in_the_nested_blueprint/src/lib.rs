@@ -0,0 +1,32 @@
+use pavex::blueprint::{router::POST, Blueprint};
+use pavex::f;
+use pavex::request::body::{BodySizeLimit, BufferedBody};
+use pavex::response::Response;
+use pavex::unit::ToByteUnit;
+
+pub fn handler(_body: &BufferedBody) -> Response {
+    todo!()
+}
+
+pub fn limit() -> BodySizeLimit {
+    todo!()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.request_scoped(f!(pavex::request::body::BufferedBody::extract))
+        .error_handler(f!(
+            pavex::request::body::errors::ExtractBufferedBodyError::into_response
+        ));
+    bp.prefix("/nested")
+        .body_size_limit(BodySizeLimit::Enabled {
+            max_size: 20.bytes(),
+        })
+        .nest({
+            let mut bp = Blueprint::new();
+            bp.request_scoped(f!(crate::limit));
+            bp.route(POST, "/", f!(crate::handler));
+            bp
+        });
+    bp
+}
@@ -0,0 +1,76 @@
+use std::future::IntoFuture;
+use std::net::TcpListener;
+
+use application::{ApplicationConfig, ApplicationState, run};
+use pavex::http::StatusCode;
+
+async fn spawn_test_server() -> u16 {
+    static TELEMETRY: std::sync::Once = std::sync::Once::new();
+    TELEMETRY.call_once(|| {
+        tracing_subscriber::fmt()
+            .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+            .with_span_events(tracing_subscriber::fmt::format::FmtSpan::FULL)
+            .init();
+    });
+
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to listen on a random port");
+    let port = listener
+        .local_addr()
+        .expect("Failed to get local address")
+        .port();
+    let incoming_stream: pavex::server::IncomingStream =
+        listener.try_into().expect("Failed to convert listener");
+    let server = pavex::server::Server::new().listen(incoming_stream);
+    let application_state = ApplicationState::new(ApplicationConfig {}).await.unwrap();
+    tokio::task::spawn(run(server, application_state).into_future());
+    port
+}
+
+/// Send a body of `size` bytes to `path` and return the status code of the response.
+async fn post(port: u16, path: &str, size: usize) -> u16 {
+    reqwest::Client::new()
+        .post(format!("http://localhost:{port}{path}"))
+        .body(vec![b'a'; size])
+        .send()
+        .await
+        .expect("Failed to make request")
+        .status()
+        .as_u16()
+}
+
+const OK: u16 = StatusCode::OK.as_u16();
+const TOO_LARGE: u16 = StatusCode::PAYLOAD_TOO_LARGE.as_u16();
+
+#[tokio::test]
+async fn the_default_limit_applies_if_no_limit_is_set() {
+    let port = spawn_test_server().await;
+    assert_eq!(post(port, "/default", 1024).await, OK);
+}
+
+#[tokio::test]
+async fn route_level_limits_are_enforced() {
+    let port = spawn_test_server().await;
+    assert_eq!(post(port, "/route", 10).await, OK);
+    assert_eq!(post(port, "/route", 11).await, TOO_LARGE);
+}
+
+#[tokio::test]
+async fn nested_limits_apply_to_all_nested_routes() {
+    let port = spawn_test_server().await;
+    assert_eq!(post(port, "/nested/inherited", 20).await, OK);
+    assert_eq!(post(port, "/nested/inherited", 21).await, TOO_LARGE);
+}
+
+#[tokio::test]
+async fn route_level_limits_take_precedence_over_nested_limits() {
+    let port = spawn_test_server().await;
+    assert_eq!(post(port, "/nested/overridden", 1024).await, OK);
+}
+
+#[tokio::test]
+async fn route_level_limits_take_precedence_over_constructors_in_the_same_blueprint() {
+    let port = spawn_test_server().await;
+    assert_eq!(post(port, "/constructor/inherited", 6).await, TOO_LARGE);
+    assert_eq!(post(port, "/constructor/overridden", 10).await, OK);
+    assert_eq!(post(port, "/constructor/overridden", 11).await, TOO_LARGE);
+}
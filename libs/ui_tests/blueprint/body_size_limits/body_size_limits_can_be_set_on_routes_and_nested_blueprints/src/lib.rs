@@ -0,0 +1,57 @@
+use pavex::blueprint::{router::POST, Blueprint};
+use pavex::f;
+use pavex::request::body::{BodySizeLimit, BufferedBody};
+use pavex::response::Response;
+use pavex::unit::ToByteUnit;
+
+pub fn handler(body: &BufferedBody) -> Response {
+    Response::ok().set_typed_body(format!("{}", body.bytes.len()))
+}
+
+pub fn tiny_limit() -> BodySizeLimit {
+    BodySizeLimit::Enabled {
+        max_size: 5.bytes(),
+    }
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.request_scoped(f!(pavex::request::body::BodySizeLimit::new));
+    bp.request_scoped(f!(pavex::request::body::BufferedBody::extract))
+        .error_handler(f!(
+            pavex::request::body::errors::ExtractBufferedBodyError::into_response
+        ));
+    // The default limit applies.
+    bp.route(POST, "/default", f!(crate::handler));
+    // A route-level limit.
+    bp.route(POST, "/route", f!(crate::handler))
+        .body_size_limit(BodySizeLimit::Enabled {
+            max_size: 10.bytes(),
+        });
+    // A limit for all the routes in the nested blueprint.
+    bp.prefix("/nested")
+        .body_size_limit(BodySizeLimit::Enabled {
+            max_size: 20.bytes(),
+        })
+        .nest({
+            let mut bp = Blueprint::new();
+            bp.route(POST, "/inherited", f!(crate::handler));
+            // A route-level limit takes precedence over the one set on the nested blueprint.
+            bp.route(POST, "/overridden", f!(crate::handler))
+                .body_size_limit(BodySizeLimit::Disabled);
+            bp
+        });
+    // A route-level limit takes precedence over a constructor registered
+    // in the same blueprint.
+    bp.prefix("/constructor").nest({
+        let mut bp = Blueprint::new();
+        bp.request_scoped(f!(crate::tiny_limit));
+        bp.route(POST, "/inherited", f!(crate::handler));
+        bp.route(POST, "/overridden", f!(crate::handler))
+            .body_size_limit(BodySizeLimit::Enabled {
+                max_size: 10.bytes(),
+            });
+        bp
+    });
+    bp
+}
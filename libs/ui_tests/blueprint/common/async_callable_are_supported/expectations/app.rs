@@ -44,7 +44,7 @@ pub fn run(
         server_state: std::sync::Arc<ServerState>,
     ) -> pavex::response::Response {
         let (router, state) = (&server_state.router, &server_state.application_state);
-        router.route(request, connection_info, state).await
+        router.route(request.map(Into::into), connection_info, state).await
     }
     let router = Router::new();
     let server_state = std::sync::Arc::new(ServerState {
@@ -53,6 +53,35 @@ pub fn run(
     });
     server_builder.serve(handler, server_state)
 }
+/// Send requests to your application in-process, without going through the network.
+///
+/// Check out [`pavex::testing`] for more details.
+pub struct TestClient {
+    router: Router,
+    application_state: ApplicationState,
+}
+impl TestClient {
+    /// Create a new test client for the given application state.
+    pub fn new(application_state: ApplicationState) -> Self {
+        Self {
+            router: Router::new(),
+            application_state,
+        }
+    }
+    /// Process the request as if it had been received by the server,
+    /// and buffer the response.
+    pub async fn send(
+        &self,
+        request: impl Into<pavex::testing::TestRequest>,
+    ) -> pavex::testing::TestResponse {
+        let (request, connection_info) = request.into().into_parts();
+        let response = self
+            .router
+            .route(request, connection_info, &self.application_state)
+            .await;
+        pavex::testing::TestResponse::new(response).await
+    }
+}
 struct Router {
     router: matchit::Router<u32>,
 }
@@ -70,7 +99,7 @@ impl Router {
     }
     pub async fn route(
         &self,
-        request: http::Request<hyper::body::Incoming>,
+        request: http::Request<pavex::request::body::RawIncomingBody>,
         _connection_info: Option<pavex::connection::ConnectionInfo>,
         #[allow(unused)]
         state: &ApplicationState,
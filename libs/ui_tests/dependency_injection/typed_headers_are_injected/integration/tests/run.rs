@@ -0,0 +1,84 @@
+use application::{ApplicationConfig, ApplicationState, TestClient};
+use pavex::http::header::AUTHORIZATION;
+use pavex::http::{HeaderName, HeaderValue, StatusCode};
+use pavex::testing::TestRequest;
+
+async fn test_client() -> TestClient {
+    let application_state = ApplicationState::new(ApplicationConfig {}).await.unwrap();
+    TestClient::new(application_state)
+}
+
+fn request_id() -> HeaderName {
+    HeaderName::from_static("x-request-id")
+}
+
+#[tokio::test]
+async fn required_headers_are_extracted() {
+    let client = test_client().await;
+    client
+        .send(
+            TestRequest::get("/required")
+                .insert_header(request_id(), HeaderValue::from_static("abc")),
+        )
+        .await
+        .assert_status(StatusCode::OK)
+        .assert_text("abc");
+}
+
+#[tokio::test]
+async fn missing_required_headers_are_rejected() {
+    let client = test_client().await;
+    client
+        .send(TestRequest::get("/required"))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_text("The `x-request-id` header is missing");
+}
+
+#[tokio::test]
+async fn optional_headers_may_be_missing() {
+    let client = test_client().await;
+    client
+        .send(TestRequest::get("/optional"))
+        .await
+        .assert_status(StatusCode::OK)
+        .assert_text("none");
+    client
+        .send(
+            TestRequest::get("/optional")
+                .insert_header(request_id(), HeaderValue::from_static("abc")),
+        )
+        .await
+        .assert_status(StatusCode::OK)
+        .assert_text("abc");
+}
+
+#[tokio::test]
+async fn optional_headers_must_be_valid_if_present() {
+    let client = test_client().await;
+    client
+        .send(
+            TestRequest::get("/optional")
+                .append_header(request_id(), HeaderValue::from_static("abc"))
+                .append_header(request_id(), HeaderValue::from_static("def")),
+        )
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn built_in_headers_are_extracted() {
+    let client = test_client().await;
+    client
+        .send(
+            TestRequest::get("/authorized")
+                .insert_header(AUTHORIZATION, HeaderValue::from_static("Bearer secret")),
+        )
+        .await
+        .assert_status(StatusCode::OK)
+        .assert_text("secret");
+    client
+        .send(TestRequest::get("/authorized"))
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+}
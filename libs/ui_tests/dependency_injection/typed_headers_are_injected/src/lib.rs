@@ -0,0 +1,43 @@
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::f;
+use pavex::request::headers::{Authorization, Header, OptionalHeader, TypedHeader};
+use pavex::response::Response;
+
+#[derive(TypedHeader)]
+#[pavex(name = "x-request-id")]
+pub struct RequestId(pub String);
+
+pub fn required(request_id: &Header<RequestId>) -> Response {
+    Response::ok().set_typed_body(request_id.0 .0.clone())
+}
+
+pub fn optional(request_id: &OptionalHeader<RequestId>) -> Response {
+    let body = match &request_id.0 {
+        Some(request_id) => request_id.0.clone(),
+        None => "none".to_owned(),
+    };
+    Response::ok().set_typed_body(body)
+}
+
+pub fn authorized(authorization: &Header<Authorization>) -> Response {
+    match &authorization.0 {
+        Authorization::Bearer { token } => Response::ok().set_typed_body(token.clone()),
+        _ => Response::unauthorized(),
+    }
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.request_scoped(f!(pavex::request::headers::Header::extract))
+        .error_handler(f!(
+            pavex::request::headers::errors::ExtractHeaderError::into_response
+        ));
+    bp.request_scoped(f!(pavex::request::headers::OptionalHeader::extract))
+        .error_handler(f!(
+            pavex::request::headers::errors::ExtractHeaderError::into_response
+        ));
+    bp.route(GET, "/required", f!(crate::required));
+    bp.route(GET, "/optional", f!(crate::optional));
+    bp.route(GET, "/authorized", f!(crate::authorized));
+    bp
+}
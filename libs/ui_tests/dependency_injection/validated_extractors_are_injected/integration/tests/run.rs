@@ -0,0 +1,59 @@
+use application::{ApplicationConfig, ApplicationState, TestClient};
+use pavex::http::header::CONTENT_TYPE;
+use pavex::http::{HeaderValue, StatusCode};
+use pavex::testing::TestRequest;
+
+async fn test_client() -> TestClient {
+    let application_state = ApplicationState::new(ApplicationConfig {}).await.unwrap();
+    TestClient::new(application_state)
+}
+
+fn sign_up(body: &'static str) -> TestRequest {
+    TestRequest::post("/users")
+        .insert_header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+        .set_body(body)
+}
+
+#[tokio::test]
+async fn valid_json_bodies_are_accepted() {
+    let client = test_client().await;
+    client
+        .send(sign_up(r#"{"username":"ursula"}"#))
+        .await
+        .assert_status(StatusCode::OK)
+        .assert_text("Welcome, ursula!");
+}
+
+#[tokio::test]
+async fn invalid_json_bodies_are_rejected() {
+    let client = test_client().await;
+    let response = client.send(sign_up(r#"{"username":""}"#)).await;
+    response.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(
+        response.text().contains("must not be empty"),
+        "{}",
+        response.text()
+    );
+}
+
+#[tokio::test]
+async fn valid_query_parameters_are_accepted() {
+    let client = test_client().await;
+    client
+        .send(TestRequest::get("/users?page_size=10"))
+        .await
+        .assert_status(StatusCode::OK)
+        .assert_text("10 items per page");
+}
+
+#[tokio::test]
+async fn invalid_query_parameters_are_rejected() {
+    let client = test_client().await;
+    let response = client.send(TestRequest::get("/users?page_size=0")).await;
+    response.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(
+        response.text().contains("must be between 1 and 100"),
+        "{}",
+        response.text()
+    );
+}
@@ -0,0 +1,71 @@
+use pavex::blueprint::{
+    router::{GET, POST},
+    Blueprint,
+};
+use pavex::f;
+use pavex::request::body::JsonBody;
+use pavex::request::query::QueryParams;
+use pavex::request::validation::{Validate, Validated, ValidationError};
+use pavex::response::Response;
+
+#[derive(serde::Deserialize)]
+pub struct NewUser {
+    pub username: String,
+}
+
+impl Validate for NewUser {
+    fn validate(&self) -> Result<(), ValidationError> {
+        let mut error = ValidationError::new();
+        if self.username.is_empty() {
+            error.push("username", "must not be empty");
+        }
+        error.into_result()
+    }
+}
+
+#[derive(serde::Deserialize)]
+pub struct Pagination {
+    pub page_size: u32,
+}
+
+impl Validate for Pagination {
+    fn validate(&self) -> Result<(), ValidationError> {
+        let mut error = ValidationError::new();
+        if !(1..=100).contains(&self.page_size) {
+            error.push("page_size", "must be between 1 and 100");
+        }
+        error.into_result()
+    }
+}
+
+pub fn sign_up(body: &Validated<JsonBody<NewUser>>) -> Response {
+    Response::ok().set_typed_body(format!("Welcome, {}!", body.0 .0.username))
+}
+
+pub fn list(query: &Validated<QueryParams<Pagination>>) -> Response {
+    Response::ok().set_typed_body(format!("{} items per page", query.0 .0.page_size))
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.request_scoped(f!(pavex::request::body::BodySizeLimit::new));
+    bp.request_scoped(f!(pavex::request::body::BufferedBody::extract))
+        .error_handler(f!(
+            pavex::request::body::errors::ExtractBufferedBodyError::into_response
+        ));
+    bp.request_scoped(f!(pavex::request::body::JsonBody::extract))
+        .error_handler(f!(
+            pavex::request::body::errors::ExtractJsonBodyError::into_response
+        ));
+    bp.request_scoped(f!(pavex::request::query::QueryParams::extract))
+        .error_handler(f!(
+            pavex::request::query::errors::ExtractQueryParamsError::into_response
+        ));
+    bp.request_scoped(f!(pavex::request::validation::Validated::extract))
+        .error_handler(f!(
+            pavex::request::validation::ValidationError::into_response
+        ));
+    bp.route(POST, "/users", f!(crate::sign_up));
+    bp.route(GET, "/users", f!(crate::list));
+    bp
+}
@@ -0,0 +1,63 @@
+use application::{ApplicationConfig, ApplicationState, TestClient};
+use pavex::http::header::{AUTHORIZATION, CONTENT_TYPE};
+use pavex::http::{HeaderName, HeaderValue, StatusCode};
+use pavex::testing::TestRequest;
+
+async fn test_client() -> TestClient {
+    let application_state = ApplicationState::new(ApplicationConfig {}).await.unwrap();
+    TestClient::new(application_state)
+}
+
+#[tokio::test]
+async fn requests_are_routed_to_the_right_handler() {
+    let client = test_client().await;
+    client
+        .send(TestRequest::get("/greet"))
+        .await
+        .assert_status(StatusCode::OK)
+        .assert_header(CONTENT_TYPE, "text/plain; charset=utf-8")
+        .assert_text("Hello!");
+}
+
+#[tokio::test]
+async fn request_bodies_reach_the_handler() {
+    let client = test_client().await;
+    client
+        .send(TestRequest::post("/echo").set_body("ping"))
+        .await
+        .assert_status(StatusCode::OK)
+        .assert_header(CONTENT_TYPE, "application/octet-stream")
+        .assert_text("ping");
+}
+
+#[tokio::test]
+async fn unknown_routes_get_a_404() {
+    let client = test_client().await;
+    client
+        .send(TestRequest::get("/missing"))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn requests_go_through_middlewares() {
+    let client = test_client().await;
+    let x_admin = HeaderName::from_static("x-admin");
+
+    // The pre-processing middleware returns early.
+    client
+        .send(TestRequest::get("/admin/dashboard"))
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    // The request reaches the handler and the post-processing middleware tags the response.
+    client
+        .send(
+            TestRequest::get("/admin/dashboard")
+                .insert_header(AUTHORIZATION, HeaderValue::from_static("Bearer token")),
+        )
+        .await
+        .assert_status(StatusCode::OK)
+        .assert_header(x_admin, "true")
+        .assert_text("Welcome back!");
+}
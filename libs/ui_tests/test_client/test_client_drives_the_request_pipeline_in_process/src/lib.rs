@@ -0,0 +1,56 @@
+use pavex::blueprint::{
+    router::{GET, POST},
+    Blueprint,
+};
+use pavex::f;
+use pavex::http::{header::AUTHORIZATION, HeaderName, HeaderValue};
+use pavex::middleware::Processing;
+use pavex::request::body::BufferedBody;
+use pavex::request::RequestHead;
+use pavex::response::Response;
+
+pub fn greet() -> Response {
+    Response::ok().set_typed_body("Hello!")
+}
+
+pub fn echo(body: &BufferedBody) -> Response {
+    Response::ok().set_typed_body(body.bytes.clone())
+}
+
+pub fn dashboard() -> Response {
+    Response::ok().set_typed_body("Welcome back!")
+}
+
+pub fn require_authorization(request_head: &RequestHead) -> Processing {
+    if request_head.headers.contains_key(AUTHORIZATION) {
+        Processing::Continue
+    } else {
+        Processing::EarlyReturn(Response::unauthorized())
+    }
+}
+
+pub fn tag(response: Response) -> Response {
+    response.insert_header(
+        HeaderName::from_static("x-admin"),
+        HeaderValue::from_static("true"),
+    )
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.request_scoped(f!(pavex::request::body::BodySizeLimit::new));
+    bp.request_scoped(f!(pavex::request::body::BufferedBody::extract))
+        .error_handler(f!(
+            pavex::request::body::errors::ExtractBufferedBodyError::into_response
+        ));
+    bp.route(GET, "/greet", f!(crate::greet));
+    bp.route(POST, "/echo", f!(crate::echo));
+    bp.prefix("/admin").nest({
+        let mut bp = Blueprint::new();
+        bp.pre_process(f!(crate::require_authorization));
+        bp.post_process(f!(crate::tag));
+        bp.route(GET, "/dashboard", f!(crate::dashboard));
+        bp
+    });
+    bp
+}